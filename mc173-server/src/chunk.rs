@@ -43,13 +43,14 @@ impl ChunkTrackers {
         let (cx, cz) = chunk::calc_chunk_pos_unchecked(pos);
         let tracker = self.inner.entry((cx, cz)).or_default();
 
-        let local_pos = ChunkLocalPos {
-            x: (pos.x as u32 & 0b1111) as u8,
-            y: (pos.y as u32 & 0b1111111) as u8,
-            z: (pos.z as u32 & 0b1111) as u8,
-        };
+        tracker.set_block(ChunkLocalPos::new(pos), block, metadata);
+    }
 
-        tracker.set_block(local_pos, block, metadata);
+    /// Notify the tracker of a light change in the given area of a chunk, min and max
+    /// included, the area is sent later to players.
+    pub fn set_light(&mut self, cx: i32, cz: i32, min: IVec3, max: IVec3) {
+        let tracker = self.inner.entry((cx, cz)).or_default();
+        tracker.set_light(ChunkLocalPos::new(min), ChunkLocalPos::new(max));
     }
 
     /// Mark a chunk dirty, to be saved later.
@@ -72,6 +73,15 @@ impl ChunkTrackers {
         self.scheduled_saves.insert(index, (cx, cz, instant));
     }
 
+    /// Remove the tracker of a chunk that is being unloaded, pending block changes are
+    /// discarded and its scheduled save is canceled, the caller should save it.
+    pub fn remove(&mut self, cx: i32, cz: i32) {
+        if self.inner.remove(&(cx, cz)).is_some() {
+            self.scheduled_saves
+                .retain(|&(save_cx, save_cz, _)| (save_cx, save_cz) != (cx, cz));
+        }
+    }
+
     /// Update the given player list to send new block changes into account. The given
     /// world is used to get the chunk if it is full of changes.
     pub fn update_players(&mut self, players: &[ServerPlayer], world: &World) {
@@ -116,6 +126,9 @@ struct ChunkTracker {
     set_blocks_min: ChunkLocalPos,
     /// The maximum position where blocks have been set in the chunk (inclusive).
     set_blocks_max: ChunkLocalPos,
+    /// The minimum and maximum positions (inclusive) where light has changed in the
+    /// chunk, if any.
+    set_light: Option<(ChunkLocalPos, ChunkLocalPos)>,
     /// This represent the number of dirty notifications to this chunk.
     dirty: bool,
     /// Current save interval for this chunk, may increase of decrease.
@@ -132,6 +145,35 @@ struct ChunkLocalPos {
     z: u8,
 }
 
+impl ChunkLocalPos {
+    /// Get the chunk-local position of the given world position.
+    fn new(pos: IVec3) -> Self {
+        Self {
+            x: (pos.x as u32 & 0b1111) as u8,
+            y: (pos.y as u32 & 0b1111111) as u8,
+            z: (pos.z as u32 & 0b1111) as u8,
+        }
+    }
+
+    /// Return the component-wise minimum of the two positions.
+    fn min(self, other: Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z),
+        }
+    }
+
+    /// Return the component-wise maximum of the two positions.
+    fn max(self, other: Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ChunkSetBlock {
     pos: ChunkLocalPos,
@@ -167,44 +209,42 @@ impl ChunkTracker {
             }
         }
 
-        self.set_blocks_min.x = self.set_blocks_min.x.min(pos.x);
-        self.set_blocks_min.y = self.set_blocks_min.y.min(pos.y);
-        self.set_blocks_min.z = self.set_blocks_min.z.min(pos.z);
+        self.set_blocks_min = self.set_blocks_min.min(pos);
+        self.set_blocks_max = self.set_blocks_max.max(pos);
+    }
 
-        self.set_blocks_max.x = self.set_blocks_max.x.max(pos.x);
-        self.set_blocks_max.y = self.set_blocks_max.y.max(pos.y);
-        self.set_blocks_max.z = self.set_blocks_max.z.max(pos.z);
+    /// Internally register a light change in the given area, the changed area is merged
+    /// with the previous one.
+    ///
+    /// PARITY: The Notchian server registers each light change as a block change, so
+    /// the light is resent with the block area once more than 10 blocks have changed.
+    /// Here only the light area is sent as partial chunk data, because block changes
+    /// packets don't contain light and the client may disagree with the server's light.
+    fn set_light(&mut self, min: ChunkLocalPos, max: ChunkLocalPos) {
+        self.set_light = Some(match self.set_light {
+            Some((prev_min, prev_max)) => (prev_min.min(min), prev_max.max(max)),
+            None => (min, max),
+        });
     }
 
     /// Update the given players by sending them the correct packets to update the player
     /// client side. If the chunk is full of set blocks then the whole area is resent,
-    /// else only individual changes are sent to the players loading the chunk.
+    /// else only individual changes are sent to the players loading the chunk. The area
+    /// where light has changed is resent apart, or with the whole area of set blocks.
     ///
     /// Once this function has updated all players, all modifications are removed.
     fn update_players(&mut self, cx: i32, cz: i32, players: &[ServerPlayer], world: &World) {
-        if self.set_blocks_full {
-            let chunk = world.get_chunk(cx, cz).expect("chunk has been removed");
-
-            let from = IVec3 {
-                x: cx * 16 + self.set_blocks_min.x as i32,
-                y: self.set_blocks_min.y as i32,
-                z: cz * 16 + self.set_blocks_min.z as i32,
-            };
-
-            let size = IVec3 {
-                x: (self.set_blocks_max.x - self.set_blocks_min.x + 1) as i32,
-                y: (self.set_blocks_max.y - self.set_blocks_min.y + 1) as i32,
-                z: (self.set_blocks_max.z - self.set_blocks_min.z + 1) as i32,
-            };
-
-            // trace!("sending partial chunk data for {cx}/{cz}, from {from}, size {size}");
+        let mut set_light = self.set_light.take();
 
-            let packet = OutPacket::ChunkData(new_chunk_data_packet(chunk, from, size));
-            for player in players {
-                if player.tracked_chunks.contains(&(cx, cz)) {
-                    player.send(packet.clone());
-                }
+        if self.set_blocks_full {
+            let (mut min, mut max) = (self.set_blocks_min, self.set_blocks_max);
+            if let Some((light_min, light_max)) = set_light.take() {
+                min = min.min(light_min);
+                max = max.max(light_max);
             }
+
+            // trace!("sending partial chunk data for {cx}/{cz}, from {min:?} to {max:?}");
+            send_chunk_area(cx, cz, min, max, players, world);
         } else if self.set_blocks.len() == 1 {
             let set_block = self.set_blocks[0];
             // trace!("sending single block for {cx}/{cz}, at {:?}", set_block.pos);
@@ -248,6 +288,11 @@ impl ChunkTracker {
             }
         }
 
+        if let Some((min, max)) = set_light {
+            // trace!("sending light for {cx}/{cz}, from {min:?} to {max:?}");
+            send_chunk_area(cx, cz, min, max, players, world);
+        }
+
         self.set_blocks_full = false;
         self.set_blocks.clear();
     }
@@ -298,6 +343,46 @@ impl ChunkTracker {
     }
 }
 
+/// Send the given area of a chunk, min and max included, as partial chunk data to the
+/// players loading the chunk.
+fn send_chunk_area(
+    cx: i32,
+    cz: i32,
+    min: ChunkLocalPos,
+    max: ChunkLocalPos,
+    players: &[ServerPlayer],
+    world: &World,
+) {
+    // Avoid compressing the data if no player is loading the chunk.
+    if !players
+        .iter()
+        .any(|player| player.tracked_chunks.contains(&(cx, cz)))
+    {
+        return;
+    }
+
+    let chunk = world.get_chunk(cx, cz).expect("chunk has been removed");
+
+    let from = IVec3 {
+        x: cx * 16 + min.x as i32,
+        y: min.y as i32,
+        z: cz * 16 + min.z as i32,
+    };
+
+    let size = IVec3 {
+        x: (max.x - min.x + 1) as i32,
+        y: (max.y - min.y + 1) as i32,
+        z: (max.z - min.z + 1) as i32,
+    };
+
+    let packet = OutPacket::ChunkData(new_chunk_data_packet(chunk, from, size));
+    for player in players {
+        if player.tracked_chunks.contains(&(cx, cz)) {
+            player.send(packet.clone());
+        }
+    }
+}
+
 /// Create a new chunk data packet for the given chunk. This only works for a single
 /// chunk and the given coordinate should be part of that chunk. The two arguments "from"
 /// and "to" are inclusive but might be modified to include more blocks if ths reduces
@@ -1,17 +1,26 @@
 //! Module for command handlers.
 
+use std::collections::HashMap;
 use std::mem;
 
-use glam::IVec3;
+use glam::{DVec3, IVec3};
 
-use mc173::block;
+use mc173::data;
 use mc173::entity::{BaseKind, Entity, EntityCategory, EntityKind};
+use mc173::gen::locate;
+use mc173::gen::pregen::Pregen;
 use mc173::item::{self, ItemStack};
-use mc173::world::{Event, Weather};
+use mc173::world::observe::ChunkRegion;
+use mc173::world::path::PathOptions;
+use mc173::world::{Difficulty, Dimension, Effect, Event, Weather};
+use mc173::{block, chunk};
 
+use crate::config;
+use crate::message::msg;
 use crate::player::ServerPlayer;
 use crate::proto::{self, OutPacket};
-use crate::world::{ServerWorld, TickMode};
+use crate::selector::Selector;
+use crate::world::{self, ServerWorld, TickMode};
 
 /// Describe all the context when a command is executed by something.
 pub struct CommandContext<'a> {
@@ -26,7 +35,7 @@ pub struct CommandContext<'a> {
 /// Handle a command and execute it.
 pub fn handle_command(ctx: CommandContext) {
     let Some(&cmd_name) = ctx.parts.first() else {
-        ctx.player.send_chat("§eNo command, type help!".to_string());
+        ctx.player.send_chat(msg!("command.none"));
         return;
     };
 
@@ -40,9 +49,11 @@ pub fn handle_command(ctx: CommandContext) {
 
             match res {
                 Err(Some(message)) => ctx.player.send_chat(message),
-                Err(None) => ctx
-                    .player
-                    .send_chat(format!("§eUsage:§r /{} {}", cmd.name, cmd.usage)),
+                Err(None) => ctx.player.send_chat(msg!(
+                    "command.usage",
+                    command = cmd.name,
+                    usage = cmd.usage
+                )),
                 _ => {}
             }
 
@@ -50,8 +61,7 @@ pub fn handle_command(ctx: CommandContext) {
         }
     }
 
-    ctx.player
-        .send_chat("§eUnknown command, type help!".to_string());
+    ctx.player.send_chat(msg!("command.unknown"));
 }
 
 /// The result of a command, if the result is ok, nothing is done, if the result is an
@@ -65,7 +75,7 @@ struct Command {
     name: &'static str,
     /// The command usage.
     usage: &'static str,
-    /// The command description for help message.
+    /// The message key of the command description for help message.
     description: &'static str,
     /// The command handler to call when executing it.
     handler: fn(CommandContext) -> CommandResult,
@@ -76,101 +86,196 @@ const COMMANDS: &[Command] = &[
     Command {
         name: "help",
         usage: "",
-        description: "Print all available commands",
+        description: "help.help",
         handler: cmd_help,
     },
     Command {
         name: "give",
         usage: "<item>[:<damage>] [<size>]",
-        description: "Give item to a player",
+        description: "help.give",
         handler: cmd_give,
     },
+    Command {
+        name: "summon",
+        usage: "<entity_kind> [<x> <y> <z>]",
+        description: "help.summon",
+        handler: cmd_summon,
+    },
     Command {
         name: "spawn",
-        usage: "<entity_kind> [<params>...]",
-        description: "Spawn an entity",
-        handler: cmd_spawn,
+        usage: "<entity_kind> [<x> <y> <z>]",
+        description: "help.spawn",
+        handler: cmd_summon,
+    },
+    Command {
+        name: "kill",
+        usage: "[<selector>]",
+        description: "help.kill",
+        handler: cmd_kill,
+    },
+    Command {
+        name: "clear",
+        usage: "<selector>",
+        description: "help.clear",
+        handler: cmd_clear,
+    },
+    Command {
+        name: "name",
+        usage: "<selector> [<name>]",
+        description: "help.name",
+        handler: cmd_name,
+    },
+    Command {
+        name: "tp",
+        usage: "[<selector>] <x> <y> <z>|[<selector>] <target>",
+        description: "help.tp",
+        handler: cmd_tp,
+    },
+    Command {
+        name: "setblock",
+        usage: "<x> <y> <z> <block>[:<metadata>]",
+        description: "help.setblock",
+        handler: cmd_setblock,
+    },
+    Command {
+        name: "fill",
+        usage: "<x1> <y1> <z1> <x2> <y2> <z2> <block>[:<metadata>]",
+        description: "help.fill",
+        handler: cmd_fill,
     },
     Command {
         name: "time",
         usage: "",
-        description: "Display world and server time",
+        description: "help.time",
         handler: cmd_time,
     },
     Command {
         name: "weather",
         usage: "[clear|rain|thunder]",
-        description: "Display world weather",
+        description: "help.weather",
         handler: cmd_weather,
     },
+    Command {
+        name: "difficulty",
+        usage: "[peaceful|easy|normal|hard]",
+        description: "help.difficulty",
+        handler: cmd_difficulty,
+    },
+    Command {
+        name: "pvp",
+        usage: "[on|off]",
+        description: "help.pvp",
+        handler: cmd_pvp,
+    },
     Command {
         name: "pos",
         usage: "",
-        description: "Display many information about current position",
+        description: "help.pos",
         handler: cmd_pos,
     },
     Command {
         name: "effect",
         usage: "<id> [<data>]",
-        description: "Make some effect in the world",
+        description: "help.effect",
         handler: cmd_effect,
     },
     Command {
         name: "path",
         usage: "<x> <y> <z>",
-        description: "Try to path find to a given position",
+        description: "help.path",
         handler: cmd_path,
     },
     Command {
         name: "tick",
         usage: "freeze|auto|{step [n]}",
-        description: "Control how the world is being ticked",
+        description: "help.tick",
         handler: cmd_tick,
     },
     Command {
         name: "clean",
         usage: "",
-        description: "Remove all entity in the world except the player",
+        description: "help.clean",
         handler: cmd_clean,
     },
+    Command {
+        name: "entities",
+        usage: "[items]",
+        description: "help.entities",
+        handler: cmd_entities,
+    },
     Command {
         name: "explode",
         usage: "",
-        description: "Make an explosion on the player position",
+        description: "help.explode",
         handler: cmd_explode,
     },
     Command {
         name: "perf",
         usage: "",
-        description: "Display performance indicators for the current world",
+        description: "help.perf",
         handler: cmd_perf,
     },
     Command {
         name: "entity",
         usage: "<id>",
-        description: "Display debug information of an entity",
+        description: "help.entity",
         handler: cmd_entity,
     },
+    Command {
+        name: "pregen",
+        usage: "[<radius> [<resume_index>]|stop]",
+        description: "help.pregen",
+        handler: cmd_pregen,
+    },
     Command {
         name: "ib",
         usage: "",
-        description: "Enable or disable instant breaking",
+        description: "help.ib",
         handler: cmd_ib,
     },
+    Command {
+        name: "locate",
+        usage: "dungeon|water_lake|lava_lake|slime [<radius>]",
+        description: "help.locate",
+        handler: cmd_locate,
+    },
+    Command {
+        name: "seed",
+        usage: "",
+        description: "help.seed",
+        handler: cmd_seed,
+    },
+    Command {
+        name: "relight",
+        usage: "[<radius>]",
+        description: "help.relight",
+        handler: cmd_relight,
+    },
+    Command {
+        name: "reload",
+        usage: "",
+        description: "help.reload",
+        handler: cmd_reload,
+    },
 ];
 
 fn cmd_help(ctx: CommandContext) -> CommandResult {
-    ctx.player
-        .send_chat("§8=====================================================".to_string());
+    ctx.player.send_chat(msg!("command.separator"));
 
     for cmd in COMMANDS {
+        let description = msg!(cmd.description);
         if cmd.usage.is_empty() {
-            ctx.player
-                .send_chat(format!("§a/{}:§r {}", cmd.name, cmd.description));
+            ctx.player.send_chat(msg!(
+                "command.help",
+                command = cmd.name,
+                description = description
+            ));
         } else {
-            ctx.player.send_chat(format!(
-                "§a/{} {}:§r {}",
-                cmd.name, cmd.usage, cmd.description
+            ctx.player.send_chat(msg!(
+                "command.help_usage",
+                command = cmd.name,
+                usage = cmd.usage,
+                description = description
             ));
         }
     }
@@ -195,14 +300,12 @@ fn cmd_give(ctx: CommandContext) -> CommandResult {
     } else if let Some(block_id) = block::from_name(id_raw.trim_start_matches("b/")) {
         id = block_id as u16;
     } else {
-        return Err(Some(format!(
-            "§cError: unknown item name or id:§r {id_raw}"
-        )));
+        return Err(Some(msg!("error.unknown_item", item = id_raw)));
     }
 
     let item = item::from_id(id);
     if item.name.is_empty() {
-        return Err(Some(format!("§cError: unknown item id:§r {id_raw}")));
+        return Err(Some(msg!("error.unknown_item_id", item = id_raw)));
     }
 
     let mut stack = ItemStack::new_sized(id, 0, item.max_stack_size);
@@ -210,67 +313,264 @@ fn cmd_give(ctx: CommandContext) -> CommandResult {
     if !metadata_raw.is_empty() {
         stack.damage = metadata_raw
             .parse::<u16>()
-            .map_err(|_| format!("§cError: invalid item damage:§r {metadata_raw}"))?;
+            .map_err(|_| msg!("error.invalid_item_damage", damage = metadata_raw))?;
     }
 
     if let Some(size_raw) = ctx.parts.get(1) {
         stack.size = size_raw
             .parse::<u16>()
-            .map_err(|_| format!("§cError: invalid stack size:§r {size_raw}"))?;
+            .map_err(|_| msg!("error.invalid_stack_size", size = size_raw))?;
     }
 
-    ctx.player.send_chat(format!(
-        "§aGiving §r{}§a (§r{}:{}§a) x§r{}§a to §r{}",
-        item.name, stack.id, stack.damage, stack.size, ctx.player.username
+    ctx.player.send_chat(msg!(
+        "command.give",
+        item = item.name,
+        id = stack.id,
+        damage = stack.damage,
+        size = stack.size,
+        player = ctx.player.username
     ));
     ctx.player.pickup_stack(&mut stack);
     Ok(())
 }
 
-fn cmd_spawn(ctx: CommandContext) -> CommandResult {
-    let [entity_kind_raw] = *ctx.parts else {
+fn cmd_summon(ctx: CommandContext) -> CommandResult {
+    let (entity_kind_raw, pos) = match *ctx.parts {
+        [entity_kind_raw] => (entity_kind_raw, ctx.player.pos),
+        [entity_kind_raw, x_raw, y_raw, z_raw] => (
+            entity_kind_raw,
+            DVec3 {
+                x: parse_coord(x_raw, ctx.player.pos.x, "x")?,
+                y: parse_coord(y_raw, ctx.player.pos.y, "y")?,
+                z: parse_coord(z_raw, ctx.player.pos.z, "z")?,
+            },
+        ),
+        _ => return Err(None),
+    };
+
+    let entity_kind = EntityKind::from_name(entity_kind_raw)
+        .filter(|&kind| kind != EntityKind::Human)
+        .ok_or_else(|| msg!("error.invalid_entity_kind", kind = entity_kind_raw))?;
+
+    let entity_id = ctx.world.world.spawn_entity_of_kind(entity_kind, pos);
+    ctx.player.send_chat(msg!("command.summon", id = entity_id));
+
+    Ok(())
+}
+
+/// Parse a coordinate given the origin that is used for relative coordinates, starting
+/// with a `~`, the axis name is used for the error message.
+fn parse_coord(raw: &str, origin: f64, axis: &str) -> Result<f64, Option<String>> {
+    let value = match raw.strip_prefix('~') {
+        Some("") => Ok(origin),
+        Some(delta) => delta.parse::<f64>().map(|delta| origin + delta),
+        None => raw.parse::<f64>(),
+    };
+
+    value.map_err(|_| Some(msg!("error.invalid_coord", axis = axis, value = raw)))
+}
+
+fn cmd_kill(ctx: CommandContext) -> CommandResult {
+    let ids = match *ctx.parts {
+        [] => vec![ctx.player.entity_id],
+        [selector_raw] => resolve_selector(&ctx, selector_raw)?,
+        _ => return Err(None),
+    };
+
+    let mut killed_count = 0;
+    for id in ids {
+        let Some(Entity(_, base_kind)) = ctx.world.world.get_entity(id) else {
+            continue;
+        };
+
+        // Living entities are hurt to death in order to drop their loot.
+        if let BaseKind::Living(_, _) = base_kind {
+            ctx.world.world.hurt_entity(id, u16::MAX, None);
+        } else {
+            ctx.world.world.remove_entity(id, "server kill command");
+        }
+
+        killed_count += 1;
+    }
+
+    ctx.player
+        .send_chat(msg!("command.kill", count = killed_count));
+    Ok(())
+}
+
+fn cmd_clear(ctx: CommandContext) -> CommandResult {
+    let [selector_raw] = *ctx.parts else {
+        return Err(None);
+    };
+
+    let mut removed_count = 0;
+    for id in resolve_selector(&ctx, selector_raw)? {
+        if !ctx.world.world.is_player_entity(id) {
+            ctx.world.world.remove_entity(id, "server clear command");
+            removed_count += 1;
+        }
+    }
+
+    ctx.player
+        .send_chat(msg!("command.clear", count = removed_count));
+    Ok(())
+}
+
+fn cmd_name(ctx: CommandContext) -> CommandResult {
+    let [selector_raw, ref name_parts @ ..] = *ctx.parts else {
         return Err(None);
     };
 
-    let entity_kind = match entity_kind_raw {
-        "item" => EntityKind::Item,
-        "boat" => EntityKind::Boat,
-        "minecart" => EntityKind::Minecart,
-        "pig" => EntityKind::Pig,
-        "chicken" => EntityKind::Chicken,
-        "cow" => EntityKind::Cow,
-        "sheep" => EntityKind::Sheep,
-        "zombie" => EntityKind::Zombie,
-        "skeleton" => EntityKind::Skeleton,
-        "ghast" => EntityKind::Ghast,
-        "slime" => EntityKind::Slime,
-        "creeper" => EntityKind::Creeper,
-        "squid" => EntityKind::Squid,
-        "lightning_bolt" => EntityKind::LightningBolt,
-        _ => {
-            return Err(Some(format!(
-                "§cError: invalid or unsupported entity kind:§r {entity_kind_raw}"
-            )))
+    let name = (!name_parts.is_empty()).then(|| name_parts.join(" "));
+
+    let mut named_count = 0;
+    for id in resolve_selector(&ctx, selector_raw)? {
+        if ctx.world.world.set_entity_name(id, name.clone()) {
+            named_count += 1;
         }
+    }
+
+    ctx.player
+        .send_chat(msg!("command.name", count = named_count));
+    Ok(())
+}
+
+fn cmd_tp(ctx: CommandContext) -> CommandResult {
+    let (ids, target_parts) = match *ctx.parts {
+        [_, _, _] | [_] => (vec![ctx.player.entity_id], ctx.parts),
+        [selector_raw, ref target_parts @ ..] => {
+            (resolve_selector(&ctx, selector_raw)?, target_parts)
+        }
+        _ => return Err(None),
+    };
+
+    let pos = match *target_parts {
+        [x_raw, y_raw, z_raw] => DVec3 {
+            x: parse_coord(x_raw, ctx.player.pos.x, "x")?,
+            y: parse_coord(y_raw, ctx.player.pos.y, "y")?,
+            z: parse_coord(z_raw, ctx.player.pos.z, "z")?,
+        },
+        [target_raw] => {
+            let target_ids = resolve_selector(&ctx, target_raw)?;
+            let [target_id] = target_ids[..] else {
+                return Err(Some(msg!("error.single_target", target = target_raw)));
+            };
+            ctx.world.world.get_entity(target_id).unwrap().0.pos
+        }
+        _ => return Err(None),
     };
 
-    let mut entity = entity_kind.new_default(ctx.player.pos);
-    entity.0.persistent = true;
+    let mut teleported_count = 0;
+    for id in ids {
+        if id == ctx.player.entity_id {
+            ctx.player.teleport(ctx.world, pos);
+        } else if ctx.world.world.is_player_entity(id) {
+            // Other players would not be notified of their new position.
+            continue;
+        } else if let Some(entity) = ctx.world.world.get_entity_mut(id) {
+            entity.teleport(pos);
+        } else {
+            continue;
+        }
 
-    entity.init_natural_spawn(&mut ctx.world.world);
+        teleported_count += 1;
+    }
 
-    let entity_id = ctx.world.world.spawn_entity(entity);
     ctx.player
-        .send_chat(format!("§aEntity spawned:§r {entity_id}"));
+        .send_chat(msg!("command.tp", count = teleported_count));
+    Ok(())
+}
+
+/// Resolve a selector from the command sender.
+fn resolve_selector(ctx: &CommandContext, raw: &str) -> Result<Vec<u32>, Option<String>> {
+    let selector =
+        Selector::parse(raw).map_err(|message| Some(msg!("error.selector", message = message)))?;
+    Ok(selector.resolve(&ctx.world.world, ctx.player.entity_id, ctx.player.pos))
+}
 
+fn cmd_setblock(ctx: CommandContext) -> CommandResult {
+    let [x_raw, y_raw, z_raw, block_raw] = *ctx.parts else {
+        return Err(None);
+    };
+
+    let pos = parse_block_pos(ctx.player.pos, [x_raw, y_raw, z_raw])?;
+    fill_blocks(ctx, pos, pos, block_raw)
+}
+
+fn cmd_fill(ctx: CommandContext) -> CommandResult {
+    let [x1_raw, y1_raw, z1_raw, x2_raw, y2_raw, z2_raw, block_raw] = *ctx.parts else {
+        return Err(None);
+    };
+
+    let from = parse_block_pos(ctx.player.pos, [x1_raw, y1_raw, z1_raw])?;
+    let to = parse_block_pos(ctx.player.pos, [x2_raw, y2_raw, z2_raw])?;
+    fill_blocks(ctx, from, to, block_raw)
+}
+
+/// Common function to fill blocks for setblock and fill commands.
+fn fill_blocks(ctx: CommandContext, from: IVec3, to: IVec3, block_raw: &str) -> CommandResult {
+    /// Maximum number of blocks that can be filled at once.
+    const MAX_VOLUME: i64 = 32768;
+
+    if !config::is_operator(&ctx.player.username) {
+        return Err(Some(msg!("error.operator_blocks")));
+    }
+
+    let len = |a: i32, b: i32| (a as i64 - b as i64).abs() + 1;
+    let volume = len(from.x, to.x) * len(from.y, to.y) * len(from.z, to.z);
+    if volume > MAX_VOLUME {
+        return Err(Some(msg!(
+            "error.too_many_blocks",
+            volume = volume,
+            max = MAX_VOLUME
+        )));
+    }
+
+    let (id_raw, metadata_raw) = block_raw.split_once(':').unwrap_or((block_raw, "0"));
+    let id = id_raw
+        .parse::<u8>()
+        .ok()
+        .or_else(|| block::from_name(id_raw))
+        .filter(|&id| !block::name(id).is_empty())
+        .ok_or_else(|| msg!("error.unknown_block", block = id_raw))?;
+    let metadata = metadata_raw
+        .parse::<u8>()
+        .ok()
+        .filter(|&metadata| metadata < 16)
+        .ok_or_else(|| msg!("error.invalid_block_metadata", metadata = metadata_raw))?;
+
+    let changes = ctx
+        .world
+        .world
+        .fill_blocks(from, to, id, metadata, Some(ctx.player.entity_id));
+    ctx.world.send_block_changes(&changes);
+
+    ctx.player
+        .send_chat(msg!("command.fill", count = changes.len()));
     Ok(())
 }
 
+/// Parse a block position, coordinates can be relative to the given origin.
+fn parse_block_pos(
+    origin: DVec3,
+    [x_raw, y_raw, z_raw]: [&str; 3],
+) -> Result<IVec3, Option<String>> {
+    Ok(DVec3 {
+        x: parse_coord(x_raw, origin.x, "x")?,
+        y: parse_coord(y_raw, origin.y, "y")?,
+        z: parse_coord(z_raw, origin.z, "z")?,
+    }
+    .floor()
+    .as_ivec3())
+}
+
 fn cmd_time(ctx: CommandContext) -> CommandResult {
+    ctx.player.send_chat(msg!(
+        "command.time.world",
+        time = ctx.world.world.get_time()
+    ));
     ctx.player
-        .send_chat(format!("§aWorld time:§r {}", ctx.world.world.get_time()));
-    ctx.player
-        .send_chat(format!("§aServer time:§r {}", ctx.world.time));
+        .send_chat(msg!("command.time.server", time = ctx.world.time));
     Ok(())
 }
 
@@ -284,43 +584,100 @@ fn cmd_weather(ctx: CommandContext) -> CommandResult {
         };
 
         ctx.world.world.set_weather(weather);
-        ctx.player
-            .send_chat(format!("§aWeather set to:§r {:?}", weather));
+        ctx.player.send_chat(msg!(
+            "command.weather.set",
+            weather = format!("{weather:?}")
+        ));
         Ok(())
     } else if ctx.parts.is_empty() {
+        let weather = ctx.world.world.get_weather();
         ctx.player
-            .send_chat(format!("§aWeather:§r {:?}", ctx.world.world.get_weather()));
+            .send_chat(msg!("command.weather", weather = format!("{weather:?}")));
         Ok(())
     } else {
         Err(None)
     }
 }
 
-fn cmd_pos(ctx: CommandContext) -> CommandResult {
+fn cmd_pvp(ctx: CommandContext) -> CommandResult {
+    let pvp = match *ctx.parts {
+        [] => {
+            let pvp = ctx.world.world.is_pvp();
+            ctx.player.send_chat(msg!("command.pvp", pvp = on_off(pvp)));
+            return Ok(());
+        }
+        ["on"] => true,
+        ["off"] => false,
+        _ => return Err(None),
+    };
+
+    if !config::is_operator(&ctx.player.username) {
+        return Err(Some(msg!("error.operator_pvp")));
+    }
+
+    ctx.world.world.set_pvp(pvp);
     ctx.player
-        .send_chat("§8=====================================================".to_string());
+        .send_chat(msg!("command.pvp.set", pvp = on_off(pvp)));
+    Ok(())
+}
+
+fn cmd_difficulty(ctx: CommandContext) -> CommandResult {
+    if ctx.parts.len() == 1 {
+        let difficulty = match ctx.parts[0] {
+            "peaceful" => Difficulty::Peaceful,
+            "easy" => Difficulty::Easy,
+            "normal" => Difficulty::Normal,
+            "hard" => Difficulty::Hard,
+            _ => return Err(None),
+        };
+
+        ctx.world.world.set_difficulty(difficulty);
+        ctx.player.send_chat(msg!(
+            "command.difficulty.set",
+            difficulty = format!("{difficulty:?}")
+        ));
+        Ok(())
+    } else if ctx.parts.is_empty() {
+        let difficulty = ctx.world.world.get_difficulty();
+        ctx.player.send_chat(msg!(
+            "command.difficulty",
+            difficulty = format!("{difficulty:?}")
+        ));
+        Ok(())
+    } else {
+        Err(None)
+    }
+}
+
+fn cmd_pos(ctx: CommandContext) -> CommandResult {
+    ctx.player.send_chat(msg!("command.separator"));
 
     let block_pos = ctx.player.pos.floor().as_ivec3();
     ctx.player
-        .send_chat(format!("§aReal:§r {}", ctx.player.pos));
-    ctx.player.send_chat(format!("§aBlock:§r {}", block_pos));
+        .send_chat(msg!("command.pos.real", pos = ctx.player.pos));
+    ctx.player
+        .send_chat(msg!("command.pos.block", pos = block_pos));
 
     if let Some(height) = ctx.world.world.get_height(block_pos) {
-        ctx.player.send_chat(format!("§aHeight:§r {}", height));
+        ctx.player
+            .send_chat(msg!("command.pos.height", height = height));
     }
 
     let light = ctx.world.world.get_light(block_pos);
     ctx.player
-        .send_chat(format!("§aBlock light:§r {}", light.block));
-    ctx.player
-        .send_chat(format!("§aSky light:§r {}", light.sky));
+        .send_chat(msg!("command.pos.block_light", light = light.block));
     ctx.player
-        .send_chat(format!("§aSky real light:§r {}", light.sky_real));
+        .send_chat(msg!("command.pos.sky_light", light = light.sky));
     ctx.player
-        .send_chat(format!("§aBrightness:§r {}", light.brightness()));
+        .send_chat(msg!("command.pos.sky_real_light", light = light.sky_real));
+    ctx.player.send_chat(msg!(
+        "command.pos.brightness",
+        brightness = ctx.world.world.get_dimension_info().brightness(light)
+    ));
 
     if let Some(biome) = ctx.world.world.get_biome(block_pos) {
-        ctx.player.send_chat(format!("§aBiome:§r {biome:?}"));
+        ctx.player
+            .send_chat(msg!("command.pos.biome", biome = format!("{biome:?}")));
     }
 
     Ok(())
@@ -332,42 +689,44 @@ fn cmd_effect(ctx: CommandContext) -> CommandResult {
     }
 
     let effect_raw = ctx.parts[0];
-    let (effect_id, mut effect_data) = match effect_raw {
-        "click" => (1000, 0),
-        "click2" => (1001, 0),
-        "bow" => (1002, 0),
-        "door" => (1003, 0),
-        "fizz" => (1004, 0),
-        "record_13" => (1005, 2000),
-        "record_cat" => (1005, 2001),
-        "smoke" => (2000, 0),
-        "break" => (2001, 0),
-        _ => {
-            let id = effect_raw
+    let effect = match effect_raw {
+        "click" => Some(Effect::Click),
+        "click2" => Some(Effect::ClickHigh),
+        "bow" => Some(Effect::Bow),
+        "door" => Some(Effect::Door),
+        "fizz" => Some(Effect::Fizz),
+        "record_13" => Some(Effect::Record(item::RECORD_13 as u32)),
+        "record_cat" => Some(Effect::Record(item::RECORD_CAT as u32)),
+        "smoke" => Some(Effect::Smoke(None)),
+        "break" => Some(Effect::BlockBreak(block::AIR)),
+        _ => None,
+    };
+
+    let pos = ctx.player.pos.floor().as_ivec3();
+    let mut packet = match effect {
+        Some(effect) => proto::EffectPlayPacket::new(pos, effect),
+        None => proto::EffectPlayPacket {
+            x: pos.x,
+            y: pos.y as i8,
+            z: pos.z,
+            effect_id: effect_raw
                 .parse::<u32>()
-                .map_err(|_| format!("§cError: invalid effect id:§r {effect_raw}"))?;
-            (id, 0)
-        }
+                .map_err(|_| msg!("error.invalid_effect_id", id = effect_raw))?,
+            effect_data: 0,
+        },
     };
 
     if let Some(effect_data_raw) = ctx.parts.get(1) {
-        effect_data = effect_data_raw
+        packet.effect_data = effect_data_raw
             .parse::<u32>()
-            .map_err(|_| format!("§cError: invalid effect data:§r {effect_data_raw}"))?;
+            .map_err(|_| msg!("error.invalid_effect_data", data = effect_data_raw))?;
     }
 
-    let pos = ctx.player.pos.floor().as_ivec3();
-    ctx.player
-        .send(OutPacket::EffectPlay(proto::EffectPlayPacket {
-            x: pos.x,
-            y: pos.y as i8,
-            z: pos.z,
-            effect_id,
-            effect_data,
-        }));
+    let (effect_id, effect_data) = (packet.effect_id, packet.effect_data);
+    ctx.player.send(OutPacket::EffectPlay(packet));
 
     ctx.player
-        .send_chat(format!("§aPlayed effect:§r {effect_id}/{effect_data}"));
+        .send_chat(msg!("command.effect", id = effect_id, data = effect_data));
     Ok(())
 }
 
@@ -380,51 +739,54 @@ fn cmd_path(ctx: CommandContext) -> CommandResult {
     let to = IVec3 {
         x: x_raw
             .parse::<i32>()
-            .map_err(|_| format!("§cError: invalid x:§r {x_raw}"))?,
+            .map_err(|_| msg!("error.invalid_coord", axis = "x", value = x_raw))?,
         y: y_raw
             .parse::<i32>()
-            .map_err(|_| format!("§cError: invalid y:§r {y_raw}"))?,
+            .map_err(|_| msg!("error.invalid_coord", axis = "y", value = y_raw))?,
         z: z_raw
             .parse::<i32>()
-            .map_err(|_| format!("§cError: invalid z:§r {z_raw}"))?,
+            .map_err(|_| msg!("error.invalid_coord", axis = "z", value = z_raw))?,
     };
 
-    if let Some(path) = ctx.world.world.find_path(from, to, IVec3::ONE, 20.0) {
+    if let Some(path) =
+        ctx.world
+            .world
+            .find_path(from, to, IVec3::ONE, 20.0, PathOptions::default())
+    {
         for pos in path {
             ctx.world.world.set_block(pos, block::DEAD_BUSH, 0);
         }
 
         Ok(())
     } else {
-        Err(Some("§cError: path not found".to_string()))
+        Err(Some(msg!("error.path_not_found")))
     }
 }
 
 fn cmd_tick(ctx: CommandContext) -> CommandResult {
     match ctx.parts {
         ["freeze"] => {
-            ctx.player
-                .send_chat("§aWorld ticking:§r freeze".to_string());
+            ctx.player.send_chat(msg!("command.tick.freeze"));
             ctx.world.tick_mode = TickMode::Manual(0);
             Ok(())
         }
         ["auto"] => {
-            ctx.player.send_chat("§aWorld ticking:§r auto".to_string());
+            ctx.player.send_chat(msg!("command.tick.auto"));
             ctx.world.tick_mode = TickMode::Auto;
             Ok(())
         }
         ["step"] => {
-            ctx.player.send_chat("§aWorld ticking:§r step".to_string());
+            ctx.player.send_chat(msg!("command.tick.step"));
             ctx.world.tick_mode = TickMode::Manual(1);
             Ok(())
         }
         ["step", step_count] => {
             let step_count = step_count
                 .parse::<u32>()
-                .map_err(|_| format!("§cError: invalid step count:§r {step_count}"))?;
+                .map_err(|_| msg!("error.invalid_step_count", count = step_count))?;
 
             ctx.player
-                .send_chat(format!("§aWorld ticking:§r {step_count} steps"));
+                .send_chat(msg!("command.tick.steps", count = step_count));
             ctx.world.tick_mode = TickMode::Manual(step_count);
             Ok(())
         }
@@ -432,6 +794,79 @@ fn cmd_tick(ctx: CommandContext) -> CommandResult {
     }
 }
 
+fn cmd_pregen(ctx: CommandContext) -> CommandResult {
+    match ctx.parts {
+        [] => {
+            let Some(pregen) = &ctx.world.pregen else {
+                return Err(Some(msg!("error.no_pregen")));
+            };
+
+            let progress = pregen.progress();
+            ctx.player.send_chat(msg!(
+                "command.pregen.progress",
+                percent = format!("{:.1}", progress.percent()),
+                done = progress.done,
+                total = progress.total,
+                failed = progress.failed
+            ));
+            ctx.player.send_chat(msg!(
+                "command.pregen.speed",
+                speed = format!("{:.1}", progress.chunks_per_sec())
+            ));
+            if let Some(eta) = progress.eta() {
+                ctx.player.send_chat(msg!(
+                    "command.pregen.eta",
+                    eta = format!("{:.0}", eta.as_secs_f32())
+                ));
+            }
+            ctx.player.send_chat(msg!(
+                "command.pregen.resume_index",
+                index = progress.resume_index
+            ));
+            Ok(())
+        }
+        ["stop"] => {
+            let Some(pregen) = ctx.world.pregen.take() else {
+                return Err(Some(msg!("error.no_pregen")));
+            };
+
+            ctx.player.send_chat(msg!(
+                "command.pregen.stop",
+                index = pregen.progress().resume_index
+            ));
+            Ok(())
+        }
+        [radius_raw, rest @ ..] if rest.len() <= 1 => {
+            if ctx.world.pregen.is_some() {
+                return Err(Some(msg!("error.pregen_running")));
+            }
+
+            let radius = radius_raw
+                .parse::<u32>()
+                .map_err(|_| msg!("error.invalid_radius", radius = radius_raw))?;
+
+            let resume_index = match rest {
+                [resume_index_raw] => resume_index_raw
+                    .parse::<usize>()
+                    .map_err(|_| msg!("error.invalid_resume_index", index = resume_index_raw))?,
+                _ => 0,
+            };
+
+            let (cx, cz) = chunk::calc_entity_chunk_pos(ctx.player.pos);
+            ctx.world.pregen = Some(Pregen::new(cx, cz, radius).resume(resume_index));
+
+            ctx.player.send_chat(msg!(
+                "command.pregen.start",
+                radius = radius,
+                cx = cx,
+                cz = cz
+            ));
+            Ok(())
+        }
+        _ => Err(None),
+    }
+}
+
 fn cmd_clean(ctx: CommandContext) -> CommandResult {
     let ids = ctx
         .world
@@ -448,7 +883,77 @@ fn cmd_clean(ctx: CommandContext) -> CommandResult {
     }
 
     ctx.player
-        .send_chat(format!("§aCleaned entities:§r {removed_count}"));
+        .send_chat(msg!("command.clean", count = removed_count));
+    Ok(())
+}
+
+fn cmd_entities(ctx: CommandContext) -> CommandResult {
+    /// Maximum number of lines displayed.
+    const MAX_LINES: usize = 10;
+
+    let world = &ctx.world.world;
+    match *ctx.parts {
+        [] => {
+            let mut counts = Vec::<(EntityKind, usize)>::new();
+            for (_, entity) in world.iter_entities() {
+                let kind = entity.kind();
+                match counts.iter_mut().find(|(k, _)| *k == kind) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((kind, 1)),
+                }
+            }
+
+            counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+            ctx.player.send_chat(msg!(
+                "command.entities",
+                count = world.get_entity_count(),
+                kinds = counts.len()
+            ));
+            for (kind, count) in counts.into_iter().take(MAX_LINES) {
+                ctx.player.send_chat(msg!(
+                    "command.entities.kind",
+                    kind = kind.name(),
+                    count = count
+                ));
+            }
+        }
+        ["items"] => {
+            // Item count and oldest item age per chunk.
+            let mut chunks = HashMap::<(i32, i32), (usize, u32)>::new();
+            for (_, Entity(base, kind)) in world.iter_entities() {
+                if let BaseKind::Item(_) = kind {
+                    let chunk = chunks
+                        .entry(chunk::calc_entity_chunk_pos(base.pos))
+                        .or_default();
+                    chunk.0 += 1;
+                    chunk.1 = chunk.1.max(base.lifetime);
+                }
+            }
+
+            let mut chunks = chunks.into_iter().collect::<Vec<_>>();
+            chunks.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+            let total = chunks.iter().map(|(_, (count, _))| count).sum::<usize>();
+            ctx.player.send_chat(msg!(
+                "command.entities.items",
+                total = total,
+                chunks = chunks.len(),
+                lifetime = world.get_item_lifetime()
+            ));
+            for ((cx, cz), (count, oldest)) in chunks.into_iter().take(MAX_LINES) {
+                ctx.player.send_chat(msg!(
+                    "command.entities.items_chunk",
+                    cx = cx,
+                    cz = cz,
+                    count = count,
+                    oldest = oldest
+                ));
+            }
+        }
+        _ => return Err(None),
+    }
+
     Ok(())
 }
 
@@ -457,52 +962,76 @@ fn cmd_explode(ctx: CommandContext) -> CommandResult {
         .world
         .explode(ctx.player.pos, 4.0, false, Some(ctx.player.entity_id));
     ctx.player
-        .send_chat(format!("§aExplode at:§r {}", ctx.player.pos));
+        .send_chat(msg!("command.explode", pos = ctx.player.pos));
     Ok(())
 }
 
 fn cmd_perf(ctx: CommandContext) -> CommandResult {
-    ctx.player
-        .send_chat("§8=====================================================".to_string());
-    ctx.player.send_chat(format!(
-        "§aTick duration:§r {:.1} ms",
-        ctx.world.tick_duration.get() * 1000.0
+    ctx.player.send_chat(msg!("command.separator"));
+    ctx.player.send_chat(msg!(
+        "command.perf.tick_duration",
+        duration = format!("{:.1}", ctx.world.tick_duration.get() * 1000.0)
     ));
-    ctx.player.send_chat(format!(
-        "§aTick interval:§r {:.1} ms",
-        ctx.world.tick_interval.get() * 1000.0
+    ctx.player.send_chat(msg!(
+        "command.perf.tick_interval",
+        interval = format!("{:.1}", ctx.world.tick_interval.get() * 1000.0)
     ));
-    ctx.player.send_chat(format!(
-        "§aEvents:§r {:.1} ({:.1} kB)",
-        ctx.world.events_count.get(),
-        ctx.world.events_count.get() * mem::size_of::<Event>() as f32 / 1000.0
+    ctx.player.send_chat(msg!(
+        "command.perf.events",
+        count = format!("{:.1}", ctx.world.events_count.get()),
+        size = format!(
+            "{:.1}",
+            ctx.world.events_count.get() * mem::size_of::<Event>() as f32 / 1000.0
+        )
     ));
 
-    ctx.player.send_chat(format!(
-        "§aEntities:§r {} ({} players)",
-        ctx.world.world.get_entity_count(),
-        ctx.world.world.get_player_entity_count()
+    ctx.player.send_chat(msg!(
+        "command.perf.entities",
+        count = ctx.world.world.get_entity_count(),
+        players = ctx.world.world.get_player_entity_count()
     ));
 
-    let mut categories_count = [0usize; EntityCategory::ALL.len()];
-    for (_, entity) in ctx.world.world.iter_entities() {
-        categories_count[entity.category() as usize] += 1;
-    }
-
+    ctx.player.send_chat(msg!(
+        "command.perf.mob_caps",
+        spawner = ctx.world.world.is_spawner_mobs_capped()
+    ));
     for category in EntityCategory::ALL {
-        ctx.player.send_chat(format!(
-            "  §a{category:?}s:§r {}",
-            categories_count[category as usize]
+        let cap = ctx.world.world.get_mob_cap(category);
+        ctx.player.send_chat(msg!(
+            "command.perf.category",
+            category = format!("{category:?}"),
+            count = cap.count,
+            max = cap.max
         ));
     }
 
-    ctx.player.send_chat(format!(
-        "§aBlock ticks:§r {}",
-        ctx.world.world.get_block_tick_count()
+    ctx.player.send_chat(msg!(
+        "command.perf.block_entities",
+        count = ctx.world.world.get_block_entity_count(),
+        ticking = ctx.world.world.get_ticking_block_entity_count()
+    ));
+    ctx.player.send_chat(msg!(
+        "command.perf.block_ticks",
+        count = ctx.world.world.get_block_tick_count()
+    ));
+    ctx.player.send_chat(msg!(
+        "command.perf.frozen_chunks",
+        count = ctx.world.world.get_frozen_chunk_count(),
+        wakes = ctx.world.world.get_chunk_wake_count()
     ));
-    ctx.player.send_chat(format!(
-        "§aLight updates:§r {}",
-        ctx.world.world.get_light_update_count()
+    ctx.player.send_chat(msg!(
+        "command.perf.light_updates",
+        count = ctx.world.world.get_light_update_count()
+    ));
+
+    let save_stats = ctx.world.save_stats;
+    ctx.player.send_chat(msg!(
+        "command.perf.saved_chunks",
+        count = save_stats.chunks,
+        size = save_stats.bytes / 1000,
+        duration = format!("{:.1}", save_stats.duration.as_secs_f32() * 1000.0),
+        written = save_stats.entities_written,
+        entities = save_stats.entities
     ));
 
     Ok(())
@@ -516,81 +1045,93 @@ fn cmd_entity(ctx: CommandContext) -> CommandResult {
     let id_raw = ctx.parts[0];
     let id = id_raw
         .parse::<u32>()
-        .map_err(|_| format!("§cError: invalid entity id:§r {id_raw}"))?;
+        .map_err(|_| msg!("error.invalid_entity_id", id = id_raw))?;
 
     let Some(Entity(base, base_kind)) = ctx.world.world.get_entity(id) else {
-        return Err(Some("§cError: unknown entity".to_string()));
+        return Err(Some(msg!("error.unknown_entity")));
     };
 
-    ctx.player
-        .send_chat("§8=====================================================".to_string());
+    ctx.player.send_chat(msg!("command.separator"));
+
+    // Format a vector with 2 decimals, components separated by slashes.
+    let fmt_vec = |v: DVec3| format!("{:.2}/{:.2}/{:.2}", v.x, v.y, v.z);
 
-    ctx.player.send_chat(format!(
-        "§aKind:§r {:?} §8| §aPersistent:§r {} §8| §aLifetime:§r {}",
-        base_kind.entity_kind(),
-        base.persistent,
-        base.lifetime
+    ctx.player.send_chat(msg!(
+        "command.entity.kind",
+        kind = format!("{:?}", base_kind.entity_kind()),
+        persistent = base.persistent,
+        lifetime = base.lifetime
     ));
-    let bb_size = base.bb.size();
-    ctx.player.send_chat(format!(
-        "§aBound:§r {:.2}/{:.2}/{:.2}:{:.2}/{:.2}/{:.2} ({:.2}/{:.2}/{:.2})",
-        base.bb.min.x,
-        base.bb.min.y,
-        base.bb.min.z,
-        base.bb.max.x,
-        base.bb.max.y,
-        base.bb.max.z,
-        bb_size.x,
-        bb_size.y,
-        bb_size.z
+    ctx.player.send_chat(msg!(
+        "command.entity.bound",
+        min = fmt_vec(base.bb.min),
+        max = fmt_vec(base.bb.max),
+        size = fmt_vec(base.bb.size())
     ));
-    ctx.player.send_chat(format!(
-        "§aPos:§r {:.2}/{:.2}/{:.2} §8| §aVel:§r {:.2}/{:.2}/{:.2}",
-        base.pos.x, base.pos.y, base.pos.z, base.vel.x, base.vel.y, base.vel.z
+    ctx.player.send_chat(msg!(
+        "command.entity.pos",
+        pos = fmt_vec(base.pos),
+        vel = fmt_vec(base.vel)
     ));
-    ctx.player.send_chat(format!(
-        "§aLook:§r {:.2}/{:.2} §8| §aCan Pickup:§r {} §8| §aNo Clip:§r {}",
-        base.look.x, base.look.y, base.can_pickup, base.no_clip
+    ctx.player.send_chat(msg!(
+        "command.entity.look",
+        look = format!("{:.2}/{:.2}", base.look.x, base.look.y),
+        can_pickup = base.can_pickup,
+        no_clip = base.no_clip
     ));
-    ctx.player.send_chat(format!(
-        "§aOn Ground:§r {} §aIn Water:§r {} §8| §aIn Lava:§r {}",
-        base.on_ground, base.in_water, base.in_lava
+    ctx.player.send_chat(msg!(
+        "command.entity.state",
+        on_ground = base.on_ground,
+        in_water = base.in_water,
+        in_lava = base.in_lava
     ));
-    ctx.player.send_chat(format!(
-        "§aFall Distance:§r {} §8| §aFire Time:§r {} §8| §aAir Time:§r {}",
-        base.fall_distance, base.fire_time, base.air_time
+    ctx.player.send_chat(msg!(
+        "command.entity.timers",
+        fall_distance = base.fall_distance,
+        fire_time = base.fire_time,
+        air_time = base.air_time
     ));
-    ctx.player.send_chat(format!(
-        "§aRider Id:§r {:?} §8| §aBobber Id:§r {:?}",
-        base.rider_id, base.bobber_id
+    ctx.player.send_chat(msg!(
+        "command.entity.links",
+        rider_id = format!("{:?}", base.rider_id),
+        bobber_id = format!("{:?}", base.bobber_id),
+        name = format!("{:?}", base.name)
     ));
 
     match base_kind {
         BaseKind::Item(item) => {
-            ctx.player.send_chat(format!(
-                "§aItem:§r {} §8| §aDamage:§r {} §8| §aSize:§r {}",
-                item::from_id(item.stack.id).name,
-                item.stack.damage,
-                item.stack.size
+            ctx.player.send_chat(msg!(
+                "command.entity.item",
+                item = item::from_id(item.stack.id).name,
+                damage = item.stack.damage,
+                size = item.stack.size
             ));
-            ctx.player.send_chat(format!(
-                "§aHealth:§r {} §8| §aFrozen Time:§r {}",
-                item.health, item.frozen_time
+            ctx.player.send_chat(msg!(
+                "command.entity.item_state",
+                health = item.health,
+                frozen_time = item.frozen_time,
+                owner_id = format!("{:?}", item.owner_id),
+                owner_time = item.owner_time
             ));
         }
         BaseKind::Painting(painting) => {
-            ctx.player.send_chat(format!(
-                "§aBlock Pos:§r {}/{}/{} §8| §aFace:§r {:?} §8| §aArt:§r {:?}",
-                painting.block_pos.x,
-                painting.block_pos.y,
-                painting.block_pos.z,
-                painting.face,
-                painting.art
+            let block_pos = painting.block_pos;
+            ctx.player.send_chat(msg!(
+                "command.entity.painting",
+                pos = format!("{}/{}/{}", block_pos.x, block_pos.y, block_pos.z),
+                face = format!("{:?}", painting.face),
+                art = format!("{:?}", painting.art)
             ));
         }
         BaseKind::Boat(_) => todo!(),
         BaseKind::Minecart(_) => todo!(),
-        BaseKind::LightningBolt(_) => todo!(),
+        BaseKind::LightningBolt(bolt) => {
+            ctx.player.send_chat(msg!(
+                "command.entity.lightning",
+                state = bolt.state,
+                strikes = bolt.remaining_strikes
+            ));
+        }
         BaseKind::FallingBlock(_) => todo!(),
         BaseKind::Tnt(_) => todo!(),
         BaseKind::Projectile(_, _) => todo!(),
@@ -607,14 +1148,136 @@ fn cmd_ib(ctx: CommandContext) -> CommandResult {
 
     ctx.player.instant_break ^= true;
 
-    ctx.player.send_chat(format!(
-        "§aInstant breaking:§r {}",
-        if ctx.player.instant_break {
-            "enabled"
-        } else {
-            "disabled"
+    let state = if ctx.player.instant_break {
+        msg!("common.enabled")
+    } else {
+        msg!("common.disabled")
+    };
+    ctx.player.send_chat(msg!("command.ib", state = state));
+
+    Ok(())
+}
+
+fn cmd_locate(ctx: CommandContext) -> CommandResult {
+    let (feature, radius) = match *ctx.parts {
+        [feature] => (feature, 16),
+        [feature, radius_raw] => {
+            let radius = radius_raw
+                .parse::<u32>()
+                .ok()
+                .filter(|&radius| radius <= 128)
+                .ok_or_else(|| msg!("error.invalid_radius_max", max = 128, radius = radius_raw))?;
+            (feature, radius)
         }
-    ));
+        _ => return Err(None),
+    };
+
+    if ctx.world.world.get_dimension() != Dimension::Overworld {
+        return Err(Some(msg!("error.locate_dimension")));
+    }
+
+    let seed = ctx.world.seed;
+    let (cx, cz) = chunk::calc_entity_chunk_pos(ctx.player.pos);
+    let region = ChunkRegion::around(cx, cz, radius);
+
+    let positions = match feature {
+        "dungeon" => locate::locate_dungeons(seed, region),
+        "water_lake" => locate::locate_water_lakes(seed, region),
+        "lava_lake" => locate::locate_lava_lakes(seed, region),
+        "slime" => locate::locate_slime_chunks(seed, region)
+            .into_iter()
+            .map(|(cx, cz)| IVec3::new(cx * 16 + 8, 16, cz * 16 + 8))
+            .collect(),
+        _ => return Err(Some(msg!("error.unknown_feature", feature = feature))),
+    };
 
+    let player_pos = ctx.player.pos.floor().as_ivec3();
+    let nearest = positions
+        .into_iter()
+        .min_by_key(|pos| (*pos - player_pos).length_squared());
+
+    match nearest {
+        Some(pos) => {
+            let dist = (pos - player_pos).as_vec3().length();
+            ctx.player.send_chat(msg!(
+                "command.locate",
+                feature = feature,
+                x = pos.x,
+                y = pos.y,
+                z = pos.z,
+                dist = format!("{dist:.0}")
+            ));
+            if feature != "slime" {
+                ctx.player.send_chat(msg!("command.locate.note"));
+            }
+            Ok(())
+        }
+        None => Err(Some(msg!(
+            "error.feature_not_found",
+            feature = feature,
+            radius = radius
+        ))),
+    }
+}
+
+fn cmd_seed(ctx: CommandContext) -> CommandResult {
+    if !config::is_operator(&ctx.player.username) {
+        return Err(Some(msg!("error.operator_seed")));
+    }
+
+    ctx.player
+        .send_chat(msg!("command.seed", seed = ctx.world.seed));
+    Ok(())
+}
+
+fn cmd_relight(ctx: CommandContext) -> CommandResult {
+    let radius = match *ctx.parts {
+        [] => 0,
+        [radius_raw] => radius_raw
+            .parse::<i32>()
+            .ok()
+            .filter(|&radius| (0..=16).contains(&radius))
+            .ok_or_else(|| msg!("error.invalid_radius_max", max = 16, radius = radius_raw))?,
+        _ => return Err(None),
+    };
+
+    let (cx, cz) = chunk::calc_entity_chunk_pos(ctx.player.pos);
+    let mut count = 0;
+    for dx in -radius..=radius {
+        for dz in -radius..=radius {
+            if ctx.world.world.contains_chunk(cx + dx, cz + dz) {
+                ctx.world.world.schedule_chunk_relight(cx + dx, cz + dz);
+                count += 1;
+            }
+        }
+    }
+
+    ctx.player.send_chat(msg!("command.relight", count = count));
+    Ok(())
+}
+
+fn cmd_reload(ctx: CommandContext) -> CommandResult {
+    if !config::is_operator(&ctx.player.username) {
+        return Err(Some(msg!("error.operator_reload")));
+    }
+
+    let dir = config::data_dir().ok_or_else(|| msg!("error.no_data_dir"))?;
+    if !world::load_data_tables(&dir) {
+        return Err(Some(msg!("error.reload_failed")));
+    }
+
+    ctx.player.send_chat(msg!(
+        "command.reload",
+        recipes = data::tables().recipes.len()
+    ));
     Ok(())
 }
+
+/// Return the on/off message for the given boolean.
+fn on_off(value: bool) -> String {
+    if value {
+        msg!("common.on")
+    } else {
+        msg!("common.off")
+    }
+}
@@ -2,9 +2,14 @@
 //! initialized when needed.
 
 use std::env;
+use std::path::PathBuf;
 
 use glam::DVec3;
+
+use mc173::storage::CorruptedChunkFallback;
+use mc173::world::cleanup;
 use once_cell::race::OnceBool;
+use once_cell::sync::OnceCell;
 
 /// Return true if fast entity tracking is enabled on the server.
 ///
@@ -32,6 +37,240 @@ pub fn client_piston() -> bool {
     })
 }
 
+/// Return true if the live map rendering is enabled, when enabled the map tiles are
+/// rendered to the world's map directory while chunks are loaded and modified.
+///
+/// To enable this feature, set `MC173_LIVE_MAP=1`.
+pub fn live_map() -> bool {
+    static ENV: OnceBool = OnceBool::new();
+    ENV.get_or_init(|| {
+        env::var_os("MC173_LIVE_MAP")
+            .map(|s| s.as_encoded_bytes() == b"1")
+            .unwrap_or(false)
+    })
+}
+
+/// Return the world check mode to run on startup, none if disabled. When enabled the
+/// region files are checked before the world is loaded, the check can also repair
+/// light and height map issues and remove unreadable chunks to regenerate them.
+///
+/// To check only, set `MC173_FSCK=1`, to also repair, set `MC173_FSCK=repair`.
+pub fn fsck() -> Option<bool> {
+    static ENV: OnceCell<Option<bool>> = OnceCell::new();
+    *ENV.get_or_init(|| {
+        env::var_os("MC173_FSCK").and_then(|s| match s.as_encoded_bytes() {
+            b"1" => Some(false),
+            b"repair" => Some(true),
+            _ => None,
+        })
+    })
+}
+
+/// Return what the storage should do when a stored chunk is corrupted, by default the
+/// chunk is left unloaded. Corrupted chunks can be regenerated, and optionally their
+/// data can be copied to a sidecar file before being overwritten.
+///
+/// To regenerate, set `MC173_CORRUPTED_CHUNKS=regenerate`, to also keep a copy of the
+/// corrupted data, set `MC173_CORRUPTED_CHUNKS=quarantine`.
+pub fn corrupted_chunk_fallback() -> CorruptedChunkFallback {
+    static ENV: OnceCell<CorruptedChunkFallback> = OnceCell::new();
+    *ENV.get_or_init(|| {
+        match env::var_os("MC173_CORRUPTED_CHUNKS")
+            .as_ref()
+            .map(|s| s.as_encoded_bytes())
+        {
+            Some(b"regenerate") => CorruptedChunkFallback::Regenerate,
+            Some(b"quarantine") => CorruptedChunkFallback::Quarantine,
+            _ => CorruptedChunkFallback::Error,
+        }
+    })
+}
+
+/// Return true if worlds should be hibernated on stop, when enabled the complete
+/// runtime state of each world is frozen to a file on stop and thawed on next start,
+/// this allows fast restarts with the exact same world state.
+///
+/// To enable this feature, set `MC173_HIBERNATE=1`.
+pub fn hibernate() -> bool {
+    static ENV: OnceBool = OnceBool::new();
+    ENV.get_or_init(|| {
+        env::var_os("MC173_HIBERNATE")
+            .map(|s| s.as_encoded_bytes() == b"1")
+            .unwrap_or(false)
+    })
+}
+
+/// Return true if loaded chunks with missing or suspect light data should be relit,
+/// chunks are relit progressively over ticks.
+///
+/// To enable this feature, set `MC173_RELIGHT=1`.
+pub fn lazy_relight() -> bool {
+    static ENV: OnceBool = OnceBool::new();
+    ENV.get_or_init(|| {
+        env::var_os("MC173_RELIGHT")
+            .map(|s| s.as_encoded_bytes() == b"1")
+            .unwrap_or(false)
+    })
+}
+
+/// Return true if mobs are allowed to modify the world, such as creepers destroying
+/// blocks, enabled by default.
+///
+/// To disable this feature, set `MC173_MOB_GRIEFING=0`.
+pub fn mob_griefing() -> bool {
+    static ENV: OnceBool = OnceBool::new();
+    ENV.get_or_init(|| {
+        env::var_os("MC173_MOB_GRIEFING")
+            .map(|s| s.as_encoded_bytes() != b"0")
+            .unwrap_or(true)
+    })
+}
+
+/// Return true if entities spawned by mob spawners are counted in the mob caps, which
+/// prevents natural spawning of their category when too many, enabled by default.
+///
+/// To exclude them from the mob caps, set `MC173_SPAWNER_MOBS_CAPPED=0`.
+pub fn spawner_mobs_capped() -> bool {
+    static ENV: OnceBool = OnceBool::new();
+    ENV.get_or_init(|| {
+        env::var_os("MC173_SPAWNER_MOBS_CAPPED")
+            .map(|s| s.as_encoded_bytes() != b"0")
+            .unwrap_or(true)
+    })
+}
+
+/// Return the distance in chunks from players where chunks are simulated, chunks that
+/// are further away are still loaded and sent to players but frozen, none if unlimited
+/// (default).
+///
+/// To enable this feature, set `MC173_SIMULATION_DISTANCE=<chunks>`.
+pub fn simulation_distance() -> Option<u32> {
+    static ENV: OnceCell<Option<u32>> = OnceCell::new();
+    *ENV.get_or_init(|| {
+        env::var("MC173_SIMULATION_DISTANCE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+    })
+}
+
+/// Return the number of idle ticks before a chunk is frozen, frozen chunks are no longer
+/// random ticked until something happens in them, none if disabled (default).
+///
+/// To enable this feature, set `MC173_CHUNK_FREEZE=<ticks>`.
+pub fn chunk_freeze_delay() -> Option<u64> {
+    static ENV: OnceCell<Option<u64>> = OnceCell::new();
+    *ENV.get_or_init(|| {
+        env::var("MC173_CHUNK_FREEZE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .filter(|&delay| delay != 0)
+    })
+}
+
+/// Return the lifetime of item entities in ticks, items are removed when older, this is
+/// 6000 ticks (5 minutes) by default.
+///
+/// To change it, set `MC173_ITEM_LIFETIME=<ticks>`.
+pub fn item_lifetime() -> u32 {
+    static ENV: OnceCell<u32> = OnceCell::new();
+    *ENV.get_or_init(|| {
+        env::var("MC173_ITEM_LIFETIME")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(cleanup::DEFAULT_ITEM_LIFETIME)
+    })
+}
+
+/// Return the interval in ticks between two cleanup sweeps removing all item entities,
+/// players are warned before each sweep, none if disabled (default).
+///
+/// To enable this feature, set `MC173_ITEM_CLEANUP=<ticks>`.
+pub fn item_cleanup_interval() -> Option<u64> {
+    static ENV: OnceCell<Option<u64>> = OnceCell::new();
+    *ENV.get_or_init(|| {
+        env::var("MC173_ITEM_CLEANUP")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .filter(|&interval| interval != 0)
+    })
+}
+
+/// Return the path of the block and item ids remapping table applied to loaded chunks,
+/// see [`IdRemap::parse`](mc173::serde::remap::IdRemap::parse) for its format.
+///
+/// To enable this feature, set `MC173_REMAP=<path>`.
+pub fn remap_path() -> Option<PathBuf> {
+    env::var_os("MC173_REMAP").map(PathBuf::from)
+}
+
+/// Return the path of the messages catalog overriding the player-facing messages of the
+/// server, see [`Catalog::parse`](crate::message::Catalog::parse) for its format.
+///
+/// To enable this feature, set `MC173_MESSAGES=<path>`.
+pub fn messages_path() -> Option<PathBuf> {
+    env::var_os("MC173_MESSAGES").map(PathBuf::from)
+}
+
+/// Return the directory of the data tables tweaking the game balance, loaded at startup
+/// and with the `/reload` command. It may contain `recipes.txt`, `dungeon_loot.txt`,
+/// `bonus_chest_loot.txt` and `spawns.txt`, missing files keep the default tables, see
+/// [`mc173::data`] for formats.
+///
+/// To enable this feature, set `MC173_DATA=<path>`.
+pub fn data_dir() -> Option<PathBuf> {
+    env::var_os("MC173_DATA").map(PathBuf::from)
+}
+
+/// Return true if a bonus chest should be placed near the spawn point when the overworld
+/// spawn chunk is first generated, enabling it on an existing world has no effect.
+///
+/// To enable this feature, set `MC173_BONUS_CHEST=1`.
+pub fn bonus_chest() -> bool {
+    static ENV: OnceBool = OnceBool::new();
+    ENV.get_or_init(|| {
+        env::var_os("MC173_BONUS_CHEST")
+            .map(|s| s.as_encoded_bytes() == b"1")
+            .unwrap_or(false)
+    })
+}
+
+/// Return true if the overworld should be generated empty, for skyblock-like servers.
+/// An island schematic can be stamped at spawn, see [`island_path`].
+///
+/// To enable this feature, set `MC173_VOID=1`.
+pub fn void_world() -> bool {
+    static ENV: OnceBool = OnceBool::new();
+    ENV.get_or_init(|| {
+        env::var_os("MC173_VOID")
+            .map(|s| s.as_encoded_bytes() == b"1")
+            .unwrap_or(false)
+    })
+}
+
+/// Return the path of the island schematic, in MCEdit format, stamped at spawn when the
+/// overworld is generated empty.
+///
+/// To enable this feature, set `MC173_ISLAND=<path>`.
+pub fn island_path() -> Option<PathBuf> {
+    env::var_os("MC173_ISLAND").map(PathBuf::from)
+}
+
+/// Return true if the given player is a server operator, operators are given by their
+/// comma-separated usernames. If not set, all players are operators.
+///
+/// To restrict operators, set `MC173_OPS=<username>[,<username>...]`.
+pub fn is_operator(username: &str) -> bool {
+    static ENV: OnceCell<Option<Vec<String>>> = OnceCell::new();
+    ENV.get_or_init(|| {
+        env::var("MC173_OPS")
+            .ok()
+            .map(|s| s.split(',').map(|s| s.trim().to_string()).collect())
+    })
+    .as_ref()
+    .map(|ops| ops.iter().any(|op| op == username))
+    .unwrap_or(true)
+}
+
 /// Server world seed is currently hardcoded.
 pub const SEED: i64 = 9999;
 
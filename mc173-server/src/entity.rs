@@ -65,7 +65,6 @@ impl EntityTracker {
             EntityKind::Tnt => (160, 10, true),
             EntityKind::FallingBlock => (160, 20, true),
             EntityKind::Painting => (160, 0, false),
-            EntityKind::LightningBolt => (512, 0, false), // Specific to this impl
             // All remaining animals and mobs.
             _ => (160, 3, true),
         };
@@ -307,7 +306,8 @@ impl EntityTracker {
             BaseKind::Minecart(e::Minecart::Furnace { .. }) => {
                 self.spawn_entity_object(player, 12, false)
             }
-            BaseKind::LightningBolt(_) => self.spawn_entity_lightning_bolt(player),
+            // Lightning bolts are not tracked, see the lightning world event.
+            BaseKind::LightningBolt(_) => {}
             BaseKind::FallingBlock(falling_block) => {
                 // NOTE: We use sand for any block id that is unsupported.
                 match falling_block.block_id {
@@ -324,7 +324,7 @@ impl EntityTracker {
                 ProjectileKind::Bobber(_) => self.spawn_entity_object(player, 90, true),
             },
             BaseKind::Living(_, living_kind) => match living_kind {
-                LivingKind::Human(pl) => self.spawn_entity_human(player, pl, metadata),
+                LivingKind::Human(pl) => self.spawn_entity_human(player, base, pl, metadata),
                 LivingKind::Ghast(_) => self.spawn_entity_mob(player, 56, metadata),
                 LivingKind::Slime(_) => self.spawn_entity_mob(player, 55, metadata),
                 LivingKind::Pig(_) => self.spawn_entity_mob(player, 90, metadata),
@@ -341,17 +341,32 @@ impl EntityTracker {
                 LivingKind::Zombie(_) => self.spawn_entity_mob(player, 54, metadata),
             },
         }
+
+        if let Some(vehicle_id) = base.vehicle_id {
+            player.send(OutPacket::EntityRide(proto::EntityRidePacket {
+                entity_id: self.id,
+                vehicle_entity_id: vehicle_id,
+            }));
+        }
     }
 
     fn spawn_entity_human(
         &self,
         player: &ServerPlayer,
+        base: &e::Base,
         human: &e::Human,
         metadata: Vec<proto::Metadata>,
     ) {
+        // The nameplate shows the display name if any, the client rejects names longer
+        // than 16 characters.
+        let username = match &base.name {
+            Some(name) => name.chars().take(16).collect(),
+            None => human.username.clone(),
+        };
+
         player.send(OutPacket::HumanSpawn(proto::HumanSpawnPacket {
             entity_id: self.id,
-            username: human.username.clone(),
+            username,
             x: self.sent_pos.0,
             y: self.sent_pos.1,
             z: self.sent_pos.2,
@@ -380,15 +395,6 @@ impl EntityTracker {
         }));
     }
 
-    fn spawn_entity_lightning_bolt(&self, player: &ServerPlayer) {
-        player.send(OutPacket::LightningBolt(proto::LightningBoltPacket {
-            entity_id: self.id,
-            x: self.sent_pos.0,
-            y: self.sent_pos.1,
-            z: self.sent_pos.2,
-        }));
-    }
-
     fn spawn_entity_painting(&self, player: &ServerPlayer, painting: &e::Painting) {
         player.send(OutPacket::PaintingSpawn(proto::PaintingSpawnPacket {
             entity_id: self.id,
@@ -452,12 +458,17 @@ impl EntityTracker {
 
     /// Internal method to generate an entity metadata vector.
     #[inline(always)]
-    fn make_entity_metadata(&self, Entity(_, base_kind): &Entity) -> Vec<proto::Metadata> {
-        match base_kind {
+    fn make_entity_metadata(&self, Entity(base, base_kind): &Entity) -> Vec<proto::Metadata> {
+        // Common flags of all entities, bit 0 is set if the entity is burning and bit 1
+        // is set when the entity is sneaking.
+        let mut flags = (base.fire_time > 0) as i8;
+        if let BaseKind::Living(_, LivingKind::Human(human)) = base_kind {
+            flags |= (human.sneaking as i8) << 1;
+        }
+
+        let mut metadata = vec![proto::Metadata::new_byte(0, flags)];
+        metadata.extend(match base_kind {
             BaseKind::Living(living, living_kind) => match living_kind {
-                LivingKind::Human(human) => {
-                    vec![proto::Metadata::new_byte(0, (human.sneaking as i8) << 1)]
-                }
                 LivingKind::Ghast(_) => vec![proto::Metadata::new_byte(
                     16,
                     (living.attack_time > 50) as _,
@@ -471,26 +482,25 @@ impl EntityTracker {
                     16,
                     ((sheep.sheared as i8) << 4) | ((sheep.color as i8) & 15),
                 )],
-                LivingKind::Wolf(wolf) => vec![proto::Metadata::new_byte(
-                    16,
-                    (wolf.sitting as i8)
-                        | ((wolf.angry as i8) << 1)
-                        | ((wolf.owner.is_some() as i8) << 2),
-                )],
-                LivingKind::Creeper(creeper) => vec![
+                LivingKind::Wolf(wolf) => vec![
                     proto::Metadata::new_byte(
                         16,
-                        if creeper.ignited_time.is_some() {
-                            1
-                        } else {
-                            -1
-                        },
+                        (wolf.sitting as i8)
+                            | ((wolf.angry as i8) << 1)
+                            | ((wolf.owner.is_some() as i8) << 2),
                     ),
+                    proto::Metadata::new_string(17, wolf.owner.clone().unwrap_or_default()),
+                    proto::Metadata::new_int(18, living.health as i32),
+                ],
+                LivingKind::Creeper(creeper) => vec![
+                    proto::Metadata::new_byte(16, if creeper.ignited { 1 } else { -1 }),
                     proto::Metadata::new_byte(17, creeper.powered as _),
                 ],
                 _ => vec![],
             },
             _ => vec![],
-        }
+        });
+
+        metadata
     }
 }
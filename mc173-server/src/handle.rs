@@ -0,0 +1,137 @@
+//! A thread-safe handle to the server, used by external integrations running in other
+//! threads, such as the console, web dashboards or chat bridges. Requests are queued and
+//! executed by the server at the beginning of its next tick, between the network and the
+//! worlds ticking, results are delivered through one-shot channels.
+//!
+//! Handles can also subscribe to the stream of [`ServerEvent`] emitted by the server,
+//! each subscriber has a bounded channel and events are dropped for subscribers that
+//! are not consuming them fast enough, so the server never blocks on a slow bridge.
+
+use crossbeam_channel::{bounded, Receiver, Sender};
+use glam::DVec3;
+
+/// A cloneable handle to enqueue requests to the server from any thread, see
+/// [`Server::handle`](crate::server::Server::handle). If the server is stopped, requests
+/// are silently dropped and their result channels are disconnected.
+#[derive(Debug, Clone)]
+pub struct ServerHandle {
+    /// The sender of requests to the server.
+    requests: Sender<ServerRequest>,
+}
+
+/// A request sent to the server through a [`ServerHandle`].
+#[derive(Debug)]
+pub(crate) enum ServerRequest {
+    /// Send a chat message to all players.
+    Broadcast { message: String },
+    /// Run a command as an online player, its chat messages are returned instead of
+    /// being sent to the player, none if the player is not online.
+    Command {
+        username: String,
+        command: String,
+        reply: Sender<Option<Vec<String>>>,
+    },
+    /// Query all online players.
+    Players { reply: Sender<Vec<PlayerInfo>> },
+    /// Query all worlds of the server.
+    Worlds { reply: Sender<Vec<WorldInfo>> },
+    /// Subscribe to all events emitted by the server from now on.
+    Subscribe { events: Sender<ServerEvent> },
+}
+
+/// An event emitted by the server to subscribers, see [`ServerHandle::subscribe`].
+///
+/// PARITY: Achievements are only tracked by the client in the Notchian beta 1.7.3, the
+/// server is never notified of them, so they cannot be streamed.
+#[derive(Debug, Clone)]
+pub enum ServerEvent {
+    /// A player joined the server.
+    Join { username: String },
+    /// A player left the server.
+    Leave { username: String },
+    /// A player sent a chat message, commands are not included.
+    Chat { username: String, message: String },
+    /// A player died, the killer is the player that recently damaged it, if any.
+    Death {
+        username: String,
+        killer: Option<String>,
+    },
+}
+
+/// Information about an online player, returned by [`ServerHandle::query_players`].
+#[derive(Debug, Clone)]
+pub struct PlayerInfo {
+    /// The username of the player.
+    pub username: String,
+    /// The name of the world the player is in.
+    pub world: String,
+    /// The last position of the player.
+    pub pos: DVec3,
+}
+
+/// Information about a world, returned by [`ServerHandle::query_worlds`].
+#[derive(Debug, Clone)]
+pub struct WorldInfo {
+    /// The name of the world.
+    pub name: String,
+    /// The current world time, in ticks.
+    pub time: u64,
+    /// Number of players in the world.
+    pub player_count: usize,
+    /// Number of entities in the world, including players.
+    pub entity_count: usize,
+}
+
+impl ServerHandle {
+    pub(crate) fn new(requests: Sender<ServerRequest>) -> Self {
+        Self { requests }
+    }
+
+    /// Send a chat message to all online players.
+    pub fn broadcast(&self, message: impl Into<String>) {
+        self.send(ServerRequest::Broadcast {
+            message: message.into(),
+        });
+    }
+
+    /// Run a command (without the leading slash) as the given online player, the chat
+    /// messages of the command are received instead of being sent to the player, none
+    /// is received if the player is not online.
+    pub fn run_command(&self, username: &str, command: &str) -> Receiver<Option<Vec<String>>> {
+        let (reply, ret) = bounded(1);
+        self.send(ServerRequest::Command {
+            username: username.to_string(),
+            command: command.to_string(),
+            reply,
+        });
+        ret
+    }
+
+    /// Query all online players.
+    pub fn query_players(&self) -> Receiver<Vec<PlayerInfo>> {
+        let (reply, ret) = bounded(1);
+        self.send(ServerRequest::Players { reply });
+        ret
+    }
+
+    /// Query all worlds of the server.
+    pub fn query_worlds(&self) -> Receiver<Vec<WorldInfo>> {
+        let (reply, ret) = bounded(1);
+        self.send(ServerRequest::Worlds { reply });
+        ret
+    }
+
+    /// Subscribe to the events emitted by the server, starting from its next tick. The
+    /// channel can hold the given number of events, further events are dropped until the
+    /// receiver catches up. The subscription ends when the receiver is dropped.
+    pub fn subscribe(&self, capacity: usize) -> Receiver<ServerEvent> {
+        let (events, ret) = bounded(capacity);
+        self.send(ServerRequest::Subscribe { events });
+        ret
+    }
+
+    fn send(&self, request: ServerRequest) {
+        // NOTE: We ignore if the server is stopped, the reply channel is then dropped.
+        let _ = self.requests.send(request);
+    }
+}
@@ -1,11 +1,16 @@
 //! A Minecraft beta 1.7.3 server in Rust.
 
+use std::io::BufRead;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use mc173::world::Dimension;
+use mc173::gen::{OverworldGenerator, OverworldGeneratorOptions};
+use mc173::world::dimension::DimensionRegistry;
+use mc173::world::{Dimension, DimensionInfo};
+use tracing::info;
 
-// The common configuration of the server.
+// The common configuration of the server, and its player-facing messages.
 pub mod config;
+pub mod message;
 
 // The network modules, net is generic and proto is the implementation for b1.7.3.
 pub mod net;
@@ -18,22 +23,57 @@ pub mod command;
 pub mod entity;
 pub mod offline;
 pub mod player;
+pub mod selector;
 pub mod world;
 
 // This module link the previous ones to make a fully functional, multi-world server.
+pub mod handle;
 pub mod server;
 
 /// Storing true while the server should run.
 static RUNNING: AtomicBool = AtomicBool::new(true);
 
+/// Number of events buffered by the event log before dropping them.
+const EVENT_LOG_CAPACITY: usize = 256;
+
 /// Entrypoint!
 pub fn main() {
     init_tracing();
 
     ctrlc::set_handler(|| RUNNING.store(false, Ordering::Relaxed)).unwrap();
 
+    if let Some(dir) = config::data_dir() {
+        world::load_data_tables(&dir);
+    }
+
     let mut server = server::Server::bind("127.0.0.1:25565".parse().unwrap()).unwrap();
-    server.register_world("overworld".to_string(), Dimension::Overworld);
+    let mut registry = DimensionRegistry::new();
+    if config::void_world() {
+        registry.register(DimensionInfo::OVERWORLD, world::new_void_generator());
+    } else {
+        let options = OverworldGeneratorOptions {
+            bonus_chest: config::bonus_chest().then(|| config::SPAWN_POS.as_ivec3()),
+            ..Default::default()
+        };
+        registry.register(
+            DimensionInfo::OVERWORLD,
+            OverworldGenerator::with_options(config::SEED, options),
+        );
+    }
+
+    server.register_world("overworld".to_string(), &registry, Dimension::Overworld);
+
+    let handle = server.handle();
+    std::thread::Builder::new()
+        .name("console".to_string())
+        .spawn(move || run_console(handle))
+        .unwrap();
+
+    let events = server.handle().subscribe(EVENT_LOG_CAPACITY);
+    std::thread::Builder::new()
+        .name("events".to_string())
+        .spawn(move || run_event_log(events))
+        .unwrap();
 
     while RUNNING.load(Ordering::Relaxed) {
         server.tick_padded().unwrap();
@@ -42,6 +82,86 @@ pub fn main() {
     server.stop();
 }
 
+/// Read console commands from the standard input until closed, the commands are run on
+/// the server through the given handle:
+///
+/// - `list` to list online players;
+/// - `worlds` to list worlds;
+/// - `say <message>` to broadcast a message to all players;
+/// - `as <player> <command>` to run a command as an online player.
+fn run_console(handle: handle::ServerHandle) {
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        let line = line.trim();
+        let (name, args) = line.split_once(' ').unwrap_or((line, ""));
+
+        match (name, args.trim()) {
+            ("", _) => {}
+            ("list", "") => {
+                let Ok(players) = handle.query_players().recv() else {
+                    break;
+                };
+                info!("{} players online", players.len());
+                for player in players {
+                    let pos = player.pos;
+                    info!(
+                        "- {} in {} at {:.1}/{:.1}/{:.1}",
+                        player.username, player.world, pos.x, pos.y, pos.z
+                    );
+                }
+            }
+            ("worlds", "") => {
+                let Ok(worlds) = handle.query_worlds().recv() else {
+                    break;
+                };
+                for world in worlds {
+                    info!(
+                        "- {}: time {}, {} players, {} entities",
+                        world.name, world.time, world.player_count, world.entity_count
+                    );
+                }
+            }
+            ("say", message) if !message.is_empty() => {
+                handle.broadcast(message::msg!("server.broadcast", message = message));
+            }
+            ("as", args) => {
+                let Some((username, command)) = args.split_once(' ') else {
+                    info!("usage: as <player> <command>");
+                    continue;
+                };
+                let command = command.trim().trim_start_matches('/');
+                match handle.run_command(username, command).recv() {
+                    Ok(Some(messages)) => messages.iter().for_each(|m| info!("{m}")),
+                    Ok(None) => info!("player {username} is not online"),
+                    Err(_) => break,
+                }
+            }
+            _ => info!("unknown console command, available: list, worlds, say, as"),
+        }
+    }
+}
+
+/// Log all events emitted by the server until it stops, this is the simplest consumer of
+/// the event stream, chat bridges to other platforms would be implemented the same way.
+fn run_event_log(events: crossbeam_channel::Receiver<handle::ServerEvent>) {
+    use handle::ServerEvent;
+    for event in events {
+        match event {
+            ServerEvent::Join { username } => info!("event: {username} joined"),
+            ServerEvent::Leave { username } => info!("event: {username} left"),
+            ServerEvent::Chat { username, message } => info!("event: <{username}> {message}"),
+            ServerEvent::Death {
+                username,
+                killer: Some(killer),
+            } => info!("event: {username} was killed by {killer}"),
+            ServerEvent::Death {
+                username,
+                killer: None,
+            } => info!("event: {username} died"),
+        }
+    }
+}
+
 /// Initialize tracing to output into the console.
 fn init_tracing() {
     use tracing_subscriber::layer::SubscriberExt;
@@ -0,0 +1,329 @@
+//! Catalog of all player-facing messages of the server, such as kick reasons, join and
+//! leave messages or command feedback. Each message is identified by a key and has a
+//! default text that can be overridden by operators from a catalog file, in order to
+//! customize or translate the server, see [`Catalog::parse`] for its format.
+//!
+//! Message texts can contain `{name}` placeholders, substituted when formatting, use
+//! the [`msg`] macro to format a message with named arguments.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display, Write};
+use std::fs;
+
+use once_cell::sync::OnceCell;
+use tracing::{info, warn};
+
+use crate::config;
+
+/// Format the message of the given key from the server catalog, each named argument is
+/// substituted to the placeholder of the same name, for example:
+/// `msg!("server.join", player = username)`.
+macro_rules! msg {
+    ( $key:expr $( , $name:ident = $value:expr )* $(,)? ) => {
+        $crate::message::format(
+            $key,
+            &[ $( (stringify!($name), &$value as &dyn ::std::fmt::Display) ),* ],
+        )
+    };
+}
+
+pub(crate) use msg;
+
+/// Default texts of all messages, this also defines all valid message keys.
+const DEFAULTS: &[(&str, &str)] = &[
+    // Server and world.
+    ("kick.invalid_packet", "Invalid packet: {packet}"),
+    ("kick.protocol_mismatch", "Protocol version mismatch!"),
+    ("server.join", "{player} joined the server."),
+    ("server.leave", "{player} left the server."),
+    ("server.chat", "<{player}> {message}"),
+    ("server.broadcast", "§d[Server] {message}"),
+    ("world.pvp_disabled", "§cPvP is disabled here"),
+    ("world.item_cleanup", "§eItems on the ground will be removed in {seconds} seconds"),
+    ("entity.name", "§7{name}"),
+    // Common words.
+    ("common.on", "on"),
+    ("common.off", "off"),
+    ("common.enabled", "enabled"),
+    ("common.disabled", "disabled"),
+    // Commands.
+    ("command.none", "§eNo command, type help!"),
+    ("command.unknown", "§eUnknown command, type help!"),
+    ("command.usage", "§eUsage:§r /{command} {usage}"),
+    ("command.separator", "§8====================================================="),
+    ("command.help", "§a/{command}:§r {description}"),
+    ("command.help_usage", "§a/{command} {usage}:§r {description}"),
+    ("command.give", "§aGiving §r{item}§a (§r{id}:{damage}§a) x§r{size}§a to §r{player}"),
+    ("command.summon", "§aEntity spawned:§r {id}"),
+    ("command.kill", "§aKilled entities:§r {count}"),
+    ("command.clear", "§aRemoved entities:§r {count}"),
+    ("command.name", "§aNamed entities:§r {count}"),
+    ("command.tp", "§aTeleported entities:§r {count}"),
+    ("command.fill", "§aChanged blocks:§r {count}"),
+    ("command.time.world", "§aWorld time:§r {time}"),
+    ("command.time.server", "§aServer time:§r {time}"),
+    ("command.weather", "§aWeather:§r {weather}"),
+    ("command.weather.set", "§aWeather set to:§r {weather}"),
+    ("command.pvp", "§aPvP:§r {pvp}"),
+    ("command.pvp.set", "§aPvP set to:§r {pvp}"),
+    ("command.difficulty", "§aDifficulty:§r {difficulty}"),
+    ("command.difficulty.set", "§aDifficulty set to:§r {difficulty}"),
+    ("command.pos.real", "§aReal:§r {pos}"),
+    ("command.pos.block", "§aBlock:§r {pos}"),
+    ("command.pos.height", "§aHeight:§r {height}"),
+    ("command.pos.block_light", "§aBlock light:§r {light}"),
+    ("command.pos.sky_light", "§aSky light:§r {light}"),
+    ("command.pos.sky_real_light", "§aSky real light:§r {light}"),
+    ("command.pos.brightness", "§aBrightness:§r {brightness}"),
+    ("command.pos.biome", "§aBiome:§r {biome}"),
+    ("command.effect", "§aPlayed effect:§r {id}/{data}"),
+    ("command.tick.freeze", "§aWorld ticking:§r freeze"),
+    ("command.tick.auto", "§aWorld ticking:§r auto"),
+    ("command.tick.step", "§aWorld ticking:§r step"),
+    ("command.tick.steps", "§aWorld ticking:§r {count} steps"),
+    ("command.pregen.progress", "§aPre-generation:§r {percent}% ({done}/{total}, {failed} failed)"),
+    ("command.pregen.speed", "§aSpeed:§r {speed} chunks/s"),
+    ("command.pregen.eta", "§aETA:§r {eta} s"),
+    ("command.pregen.resume_index", "§aResume index:§r {index}"),
+    ("command.pregen.stop", "§aPre-generation stopped, resume index:§r {index}"),
+    ("command.pregen.start", "§aPre-generating chunks:§r radius {radius} around {cx}/{cz}"),
+    ("command.clean", "§aCleaned entities:§r {count}"),
+    ("command.entities", "§aEntities:§r {count} ({kinds} kinds)"),
+    ("command.entities.kind", "§a- {kind}:§r {count}"),
+    ("command.entities.items", "§aItems:§r {total} in {chunks} chunks §8| §aLifetime:§r {lifetime} ticks"),
+    ("command.entities.items_chunk", "§a- {cx}/{cz}:§r {count} §8| §aOldest:§r {oldest} ticks"),
+    ("command.explode", "§aExplode at:§r {pos}"),
+    ("command.perf.tick_duration", "§aTick duration:§r {duration} ms"),
+    ("command.perf.tick_interval", "§aTick interval:§r {interval} ms"),
+    ("command.perf.events", "§aEvents:§r {count} ({size} kB)"),
+    ("command.perf.entities", "§aEntities:§r {count} ({players} players)"),
+    ("command.perf.mob_caps", "§aMob caps:§r §8(spawner mobs counted: {spawner})"),
+    ("command.perf.category", "  §a{category}s:§r {count}/{max}"),
+    ("command.perf.block_entities", "§aBlock entities:§r {count} ({ticking} ticking)"),
+    ("command.perf.block_ticks", "§aBlock ticks:§r {count}"),
+    ("command.perf.frozen_chunks", "§aFrozen chunks:§r {count} ({wakes} wake-ups)"),
+    ("command.perf.light_updates", "§aLight updates:§r {count}"),
+    ("command.perf.saved_chunks", "§aSaved chunks:§r {count} ({size} kB, {duration} ms) §8| §aEntities written:§r {written}/{entities}"),
+    ("command.entity.kind", "§aKind:§r {kind} §8| §aPersistent:§r {persistent} §8| §aLifetime:§r {lifetime}"),
+    ("command.entity.bound", "§aBound:§r {min}:{max} ({size})"),
+    ("command.entity.pos", "§aPos:§r {pos} §8| §aVel:§r {vel}"),
+    ("command.entity.look", "§aLook:§r {look} §8| §aCan Pickup:§r {can_pickup} §8| §aNo Clip:§r {no_clip}"),
+    ("command.entity.state", "§aOn Ground:§r {on_ground} §aIn Water:§r {in_water} §8| §aIn Lava:§r {in_lava}"),
+    ("command.entity.timers", "§aFall Distance:§r {fall_distance} §8| §aFire Time:§r {fire_time} §8| §aAir Time:§r {air_time}"),
+    ("command.entity.links", "§aRider Id:§r {rider_id} §8| §aBobber Id:§r {bobber_id} §8| §aName:§r {name}"),
+    ("command.entity.item", "§aItem:§r {item} §8| §aDamage:§r {damage} §8| §aSize:§r {size}"),
+    ("command.entity.item_state", "§aHealth:§r {health} §8| §aFrozen Time:§r {frozen_time} §8| §aOwner:§r {owner_id} ({owner_time})"),
+    ("command.entity.painting", "§aBlock Pos:§r {pos} §8| §aFace:§r {face} §8| §aArt:§r {art}"),
+    ("command.entity.lightning", "§aState:§r {state} §8| §aRemaining Strikes:§r {strikes}"),
+    ("command.ib", "§aInstant breaking:§r {state}"),
+    ("command.locate", "§aNearest {feature}:§r {x}/{y}/{z} §a({dist} blocks)"),
+    ("command.locate.note", "§7Note: this is a generation attempt, it may not exist"),
+    ("command.seed", "§aSeed:§r {seed}"),
+    ("command.relight", "§aScheduled relight of {count} chunks"),
+    ("command.reload", "§aReloaded data tables:§r {recipes} recipes"),
+    // Command errors.
+    ("error.selector", "§cError:§r {message}"),
+    ("error.unknown_item", "§cError: unknown item name or id:§r {item}"),
+    ("error.unknown_item_id", "§cError: unknown item id:§r {item}"),
+    ("error.invalid_item_damage", "§cError: invalid item damage:§r {damage}"),
+    ("error.invalid_stack_size", "§cError: invalid stack size:§r {size}"),
+    ("error.invalid_entity_kind", "§cError: invalid or unsupported entity kind:§r {kind}"),
+    ("error.invalid_coord", "§cError: invalid {axis}:§r {value}"),
+    ("error.single_target", "§cError: target must select exactly one entity:§r {target}"),
+    ("error.operator_blocks", "§cError: only operators can change blocks"),
+    ("error.too_many_blocks", "§cError: too many blocks:§r {volume} > {max}"),
+    ("error.unknown_block", "§cError: unknown block name or id:§r {block}"),
+    ("error.invalid_block_metadata", "§cError: invalid block metadata:§r {metadata}"),
+    ("error.operator_pvp", "§cError: only operators can change PvP"),
+    ("error.invalid_effect_id", "§cError: invalid effect id:§r {id}"),
+    ("error.invalid_effect_data", "§cError: invalid effect data:§r {data}"),
+    ("error.path_not_found", "§cError: path not found"),
+    ("error.invalid_step_count", "§cError: invalid step count:§r {count}"),
+    ("error.no_pregen", "§cError: no pre-generation running"),
+    ("error.pregen_running", "§cError: a pre-generation is already running"),
+    ("error.invalid_radius", "§cError: invalid radius:§r {radius}"),
+    ("error.invalid_radius_max", "§cError: invalid radius (max {max}):§r {radius}"),
+    ("error.invalid_resume_index", "§cError: invalid resume index:§r {index}"),
+    ("error.invalid_entity_id", "§cError: invalid entity id:§r {id}"),
+    ("error.unknown_entity", "§cError: unknown entity"),
+    ("error.locate_dimension", "§cError: features can only be located in the overworld"),
+    ("error.unknown_feature", "§cError: unknown feature:§r {feature}"),
+    ("error.feature_not_found", "§cError: no {feature} found in radius {radius}"),
+    ("error.operator_seed", "§cError: only operators can see the seed"),
+    ("error.operator_reload", "§cError: only operators can reload data tables"),
+    ("error.no_data_dir", "§cError: no data directory configured"),
+    ("error.reload_failed", "§cError: failed to reload data tables, see server logs"),
+    // Selector errors.
+    ("selector.missing_bracket", "missing closing bracket: {selector}"),
+    ("selector.invalid", "invalid selector: {selector}"),
+    ("selector.player_arguments", "player selector has no arguments: {selector}"),
+    ("selector.invalid_argument", "invalid selector argument: {argument}"),
+    ("selector.invalid_kind", "invalid entity kind: {kind}"),
+    ("selector.invalid_radius", "invalid radius: {radius}"),
+    ("selector.invalid_count", "invalid count: {count}"),
+    ("selector.unknown_argument", "unknown selector argument: {argument}"),
+    // Commands description for help.
+    ("help.help", "Print all available commands"),
+    ("help.give", "Give item to a player"),
+    ("help.summon", "Spawn an entity, coordinates can be relative with ~"),
+    ("help.spawn", "Alias of summon"),
+    ("help.kill", "Kill the selected entities, or yourself"),
+    ("help.clear", "Remove the selected entities without loot, except players"),
+    ("help.name", "Set or clear the display name of the selected entities"),
+    ("help.tp", "Teleport yourself or entities to a position or target"),
+    ("help.setblock", "Set a block (operators only)"),
+    ("help.fill", "Fill a cuboid with a block (operators only)"),
+    ("help.time", "Display world and server time"),
+    ("help.weather", "Display world weather"),
+    ("help.difficulty", "Display or change world difficulty"),
+    ("help.pvp", "Display or change if players can damage each other"),
+    ("help.pos", "Display many information about current position"),
+    ("help.effect", "Make some effect in the world"),
+    ("help.path", "Try to path find to a given position"),
+    ("help.tick", "Control how the world is being ticked"),
+    ("help.clean", "Remove all entity in the world except the player"),
+    ("help.entities", "Display entity counts per kind, or item counts per chunk"),
+    ("help.explode", "Make an explosion on the player position"),
+    ("help.perf", "Display performance indicators and mob caps for the current world"),
+    ("help.entity", "Display debug information of an entity"),
+    ("help.pregen", "Pre-generate chunks around the player or display progress"),
+    ("help.ib", "Enable or disable instant breaking"),
+    ("help.locate", "Locate the nearest feature from the world seed"),
+    ("help.seed", "Display the world seed (operators only)"),
+    ("help.relight", "Rebuild the light of loaded chunks around the player"),
+    ("help.reload", "Reload recipes, loot and spawn tables (operators only)"),
+];
+
+/// A catalog of message texts, indexed by their key.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    /// All message texts, there is a text for each key of the defaults.
+    messages: HashMap<&'static str, String>,
+}
+
+/// Error returned when parsing a message catalog.
+#[derive(Debug, Clone)]
+pub struct CatalogError {
+    /// The line number, starting at 1.
+    pub line: usize,
+    /// Description of the error.
+    pub message: &'static str,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Catalog {
+    /// Create a new catalog with the default texts of all messages.
+    pub fn new() -> Self {
+        Self {
+            messages: DEFAULTS
+                .iter()
+                .map(|&(key, text)| (key, text.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Parse a catalog from its text format, messages that are not given keep their
+    /// default text. Each non-empty line defines a message text with `<key> = <text>`,
+    /// the text is trimmed, lines starting with `#` are comments.
+    pub fn parse(text: &str) -> Result<Self, CatalogError> {
+        let mut catalog = Self::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let err = |message| CatalogError {
+                line: index + 1,
+                message,
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, text) = line.split_once('=').ok_or_else(|| err("missing '='"))?;
+            let message = catalog
+                .messages
+                .get_mut(key.trim())
+                .ok_or_else(|| err("unknown message key"))?;
+
+            *message = text.trim().to_string();
+        }
+
+        Ok(catalog)
+    }
+
+    /// Get the text of a message from its key, the key itself is returned if unknown.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages.get(key).map_or(key, String::as_str)
+    }
+
+    /// Format the message of the given key, each `{name}` placeholder is substituted
+    /// with the argument of the same name, unknown placeholders are left as-is.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut rest = self.get(key);
+        let mut ret = String::with_capacity(rest.len());
+
+        while let Some(start) = rest.find('{') {
+            ret.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+
+            let arg = rest.find('}').and_then(|end| {
+                let name = &rest[..end];
+                args.iter()
+                    .find(|&&(arg_name, _)| arg_name == name)
+                    .map(|&(_, value)| (end, value))
+            });
+
+            if let Some((end, value)) = arg {
+                write!(ret, "{value}").unwrap();
+                rest = &rest[end + 1..];
+            } else {
+                ret.push('{');
+            }
+        }
+
+        ret.push_str(rest);
+        ret
+    }
+}
+
+/// Format the message of the given key from the server catalog, the catalog is loaded
+/// on first use, see [`Catalog::format`]. The [`msg`] macro is more convenient.
+pub fn format(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    static CATALOG: OnceCell<Catalog> = OnceCell::new();
+    CATALOG.get_or_init(load_catalog).format(key, args)
+}
+
+/// Load the server catalog from the configured file, or the default one.
+fn load_catalog() -> Catalog {
+    let Some(path) = config::messages_path() else {
+        return Catalog::new();
+    };
+
+    let res = fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| Catalog::parse(&text).map_err(|e| e.to_string()));
+
+    match res {
+        Ok(catalog) => {
+            info!("loaded messages catalog from {}", path.display());
+            catalog
+        }
+        Err(e) => {
+            warn!("failed to load messages catalog {}: {e}", path.display());
+            Catalog::new()
+        }
+    }
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for CatalogError {}
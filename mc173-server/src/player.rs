@@ -1,5 +1,6 @@
 //! Server player tracker.
 
+use std::cell::RefCell;
 use std::collections::HashSet;
 
 use glam::{DVec3, IVec3, Vec2};
@@ -8,23 +9,40 @@ use mc173::util::split_at_utf8_boundary;
 use tracing::warn;
 
 use mc173::world::interact::Interaction;
-use mc173::world::{BlockEntityEvent, BlockEntityProgress, BlockEntityStorage, EntityEvent, Event};
+use mc173::world::observe::{ChunkRegion, ObserverHandle};
+use mc173::world::{
+    BlockEntityEvent, BlockEntityProgress, BlockEntityStorage, Effect, EntityEvent, Event, World,
+};
 
 use mc173::block_entity::BlockEntity;
-use mc173::entity::{self as e, BaseKind, Entity, Hurt, LivingKind};
+use mc173::entity::{self as e, Entity, Human, Hurt};
 use mc173::item::{self, ItemStack};
 use mc173::{block, chunk};
 
 use mc173::craft::CraftTracker;
-use mc173::geom::Face;
+use mc173::geom::{BoundingBox, Face};
 use mc173::inventory::InventoryHandle;
 
 use crate::chunk::new_chunk_data_packet;
 use crate::command::{self, CommandContext};
+use crate::config;
+use crate::handle::ServerEvent;
+use crate::message::msg;
 use crate::offline::OfflinePlayer;
 use crate::proto::{self, InPacket, Network, NetworkClient, OutPacket};
 use crate::world::ServerWorld;
 
+/// Maximum distance between the player's eyes and a block or an entity it targets.
+const REACH_DISTANCE: f64 = 5.0;
+/// Minimum cosine of the angle between the player's look and a block or an entity it
+/// targets, this is a cone of 60 degrees around the look.
+const REACH_MIN_COS: f64 = 0.5;
+/// Targets closer than this distance to the player's eyes are not checked against the
+/// look, because the angle is not meaningful at such distance.
+const REACH_ANY_ANGLE_DISTANCE: f64 = 2.0;
+/// Range of chunks sent to the player around the chunk it is in.
+const VIEW_RANGE: i32 = 3;
+
 /// A server player is an actual
 pub struct ServerPlayer {
     /// The network handle for the network server.
@@ -43,6 +61,9 @@ pub struct ServerPlayer {
     pub instant_break: bool,
     /// Set of chunks that are already sent to the player.
     pub tracked_chunks: HashSet<(i32, i32)>,
+    /// The chunk observer for the view region of this player, used to send chunks that
+    /// are loaded after the player entered its region.
+    pub chunk_observer: Option<ObserverHandle>,
     /// Set of tracked entities by this player, all entity ids in this set are considered
     /// known and rendered by the client, when the entity will disappear, a kill packet
     /// should be sent.
@@ -70,6 +91,9 @@ pub struct ServerPlayer {
     craft_tracker: CraftTracker,
     /// If the player is breaking a block, this record the breaking state.
     breaking_block: Option<BreakingBlock>,
+    /// While running a command from a server handle, the chat messages are captured here
+    /// instead of being sent to the player.
+    chat_capture: RefCell<Option<Vec<String>>>,
 }
 
 /// Describe an opened window and how to handle clicks into it.
@@ -128,6 +152,7 @@ impl ServerPlayer {
             look: offline.look,
             instant_break: false,
             tracked_chunks: HashSet::new(),
+            chunk_observer: None,
             tracked_entities: HashSet::new(),
             main_inv: offline.main_inv.clone(),
             armor_inv: offline.armor_inv.clone(),
@@ -138,6 +163,7 @@ impl ServerPlayer {
             window: Window::default(),
             craft_tracker: CraftTracker::default(),
             breaking_block: None,
+            chat_capture: RefCell::new(None),
         }
     }
 
@@ -150,6 +176,11 @@ impl ServerPlayer {
     /// Send a chat message to this player. This function will split the message in
     /// multiple chat packets if needed.
     pub fn send_chat(&self, mut message: String) {
+        if let Some(capture) = &mut *self.chat_capture.borrow_mut() {
+            capture.push(message);
+            return;
+        }
+
         let mut slice = &message[..];
         while slice.len() > 199 {
             let split = split_at_utf8_boundary(slice, 119);
@@ -163,6 +194,19 @@ impl ServerPlayer {
         self.send(OutPacket::Chat(proto::ChatPacket { message }));
     }
 
+    /// Play the given effect at a block position for this player only.
+    pub fn play_effect(&self, pos: IVec3, effect: Effect) {
+        self.send(OutPacket::EffectPlay(proto::EffectPlayPacket::new(
+            pos, effect,
+        )));
+    }
+
+    /// Play the given effect for this player only, at the block containing the given
+    /// position, this is typically used for sounds emitted by entities.
+    pub fn play_sound_at(&self, pos: DVec3, effect: Effect) {
+        self.play_effect(pos.floor().as_ivec3(), effect);
+    }
+
     pub fn send_disconnect(&self, reason: String) {
         self.send(OutPacket::Disconnect(proto::DisconnectPacket { reason }));
         self.net.disconnect(self.client);
@@ -174,6 +218,7 @@ impl ServerPlayer {
             InPacket::KeepAlive => {}
             InPacket::Flying(_) => {} // Ignore because it doesn't update anything.
             InPacket::Disconnect(_) => self.handle_disconnect(),
+            InPacket::Respawn(_) => self.handle_respawn(world),
             InPacket::Chat(packet) => self.handle_chat(world, packet.message),
             InPacket::Position(packet) => self.handle_position(world, packet),
             InPacket::Look(packet) => self.handle_look(world, packet),
@@ -196,6 +241,37 @@ impl ServerPlayer {
         self.net.disconnect(self.client);
     }
 
+    /// Handle a respawn request from the client, the player is sent back to the spawn.
+    fn handle_respawn(&mut self, sw: &mut ServerWorld) {
+        // PARITY: Players should respawn in the overworld when their dimension cannot be
+        // respawned in, but worlds are not linked together for now.
+        let dimension = sw.world.get_dimension_info().id;
+        self.send(OutPacket::Respawn(proto::RespawnPacket { dimension }));
+        self.teleport(sw, config::SPAWN_POS);
+
+        if let Some(Entity(_, e::BaseKind::Living(_, e::LivingKind::Human(human)))) =
+            sw.world.get_entity_mut(self.entity_id)
+        {
+            human.invulnerable_time = Human::SPAWN_INVULNERABLE_TIME;
+        }
+    }
+
+    /// Teleport the player and its entity to the given position in its current world,
+    /// the new position is sent to the client.
+    pub fn teleport(&mut self, sw: &mut ServerWorld, pos: DVec3) {
+        self.pos = pos;
+        if let Some(entity) = sw.world.get_entity_mut(self.entity_id) {
+            entity.teleport(self.pos);
+        }
+
+        self.send(OutPacket::PositionLook(proto::PositionLookPacket {
+            pos: self.pos,
+            stance: self.pos.y + 1.62,
+            look: self.look * (360.0 / core::f32::consts::TAU),
+            on_ground: false,
+        }));
+    }
+
     /// Handle a chat message packet.
     fn handle_chat(&mut self, sw: &mut ServerWorld, message: String) {
         if let Some(command_str) = message.strip_prefix('/') {
@@ -205,9 +281,30 @@ impl ServerPlayer {
                 world: sw,
                 player: self,
             });
+        } else {
+            let message = message.trim();
+            if !message.is_empty() {
+                sw.server_events.push(ServerEvent::Chat {
+                    username: self.username.clone(),
+                    message: message.to_string(),
+                });
+            }
         }
     }
 
+    /// Run a command as this player and return its chat messages instead of sending them
+    /// to the player, this is used by server handles.
+    pub fn run_captured_command(&mut self, sw: &mut ServerWorld, command: &str) -> Vec<String> {
+        self.chat_capture.replace(Some(Vec::new()));
+        let parts = command.split_whitespace().collect::<Vec<_>>();
+        command::handle_command(CommandContext {
+            parts: &parts,
+            world: sw,
+            player: self,
+        });
+        self.chat_capture.take().unwrap_or_default()
+    }
+
     /// Handle a position packet.
     fn handle_position(&mut self, sw: &mut ServerWorld, packet: proto::PositionPacket) {
         self.handle_position_look_inner(sw, Some(packet.pos), None, packet.on_ground);
@@ -236,7 +333,31 @@ impl ServerPlayer {
             .expect("incoherent player entity");
         entity.0.on_ground = on_ground;
 
+        // While riding, the client doesn't send its real position and the position of the
+        // player entity follows its vehicle.
+        let pos = match entity.0.vehicle_id {
+            Some(_) => pos.map(|_| entity.0.pos),
+            None => pos,
+        };
+
         if let Some(pos) = pos {
+            // Track the fall distance from the client movement, this is used to know
+            // if the player is falling when attacking, for critical hits, and to damage
+            // the player when landing.
+            // REF: EntityPlayerMP::handleFalling
+            if on_ground {
+                let damage = e::common::calc_fall_damage(entity.0.fall_distance);
+                if damage != 0 {
+                    entity.0.hurt.push(Hurt {
+                        damage,
+                        origin_id: None,
+                    });
+                }
+                entity.0.fall_distance = 0.0;
+            } else if pos.y < self.pos.y {
+                entity.0.fall_distance += (self.pos.y - pos.y) as f32;
+            }
+
             self.pos = pos;
             entity.teleport(pos);
         }
@@ -274,10 +395,15 @@ impl ServerPlayer {
             _ => return,
         };
 
+        let pos = IVec3::new(packet.x, packet.y as i32, packet.z);
+        if matches!(packet.status, 0 | 2) && !self.check_block_reach(sw, pos, Some(face)) {
+            self.breaking_block = None;
+            return;
+        }
+
         let Some(entity) = sw.world.get_entity_mut(self.entity_id) else {
             return;
         };
-        let pos = IVec3::new(packet.x, packet.y as i32, packet.z);
 
         // TODO: Use server time for breaking blocks.
 
@@ -308,7 +434,7 @@ impl ServerPlayer {
                 if break_duration.is_infinite() {
                     // Do nothing, the block is unbreakable.
                 } else if break_duration == 0.0 {
-                    sw.world.break_block(pos);
+                    sw.world.break_block_by(pos, Some(self.entity_id));
                 } else {
                     self.breaking_block = Some(BreakingBlock {
                         start_time: sw.world.get_time(), // + (break_duration * 0.7) as u64,
@@ -326,7 +452,7 @@ impl ServerPlayer {
                         .get_break_duration(stack.id, state.id, in_water, on_ground);
                     let min_time = state.start_time + (break_duration * 0.7) as u64;
                     if sw.world.get_time() >= min_time {
-                        sw.world.break_block(pos);
+                        sw.world.break_block_by(pos, Some(self.entity_id));
                     } else {
                         warn!(
                             "from {}, incoherent break time, expected {min_time} but got {}",
@@ -378,36 +504,40 @@ impl ServerPlayer {
             z: packet.z,
         };
 
+        // Check if the player can reach the block, the client may have placed a block
+        // or used its item, so its hand slot is also sent again.
+        if face.is_some() && !self.check_block_reach(sw, pos, face) {
+            self.send_main_inv_item(self.hand_slot as usize);
+            return;
+        }
+
         let mut inv = InventoryHandle::new(&mut self.main_inv[..]);
         let inv_index = self.hand_slot as usize;
 
-        // Check if the player is reasonably near the block.
-        if face.is_none() || self.pos.distance_squared(pos.as_dvec3() + 0.5) < 64.0 {
-            // The real action depends on
-            if let Some(face) = face {
-                match sw.world.interact_block(pos, false) {
-                    Interaction::None => {
-                        // No interaction, use the item at that block.
-                        sw.world
-                            .use_stack(&mut inv, inv_index, pos, face, self.entity_id);
-                    }
-                    Interaction::CraftingTable { pos } => {
-                        return self.open_window(sw, WindowKind::CraftingTable { pos });
-                    }
-                    Interaction::Chest { pos } => {
-                        return self.open_window(sw, WindowKind::Chest { pos });
-                    }
-                    Interaction::Furnace { pos } => {
-                        return self.open_window(sw, WindowKind::Furnace { pos });
-                    }
-                    Interaction::Dispenser { pos } => {
-                        return self.open_window(sw, WindowKind::Dispenser { pos });
-                    }
-                    Interaction::Handled => {}
+        // The real action depends on
+        if let Some(face) = face {
+            match sw.world.interact_block(pos, false) {
+                Interaction::None => {
+                    // No interaction, use the item at that block.
+                    sw.world
+                        .use_stack(&mut inv, inv_index, pos, face, self.entity_id);
                 }
-            } else {
-                sw.world.use_raw_stack(&mut inv, inv_index, self.entity_id);
+                Interaction::CraftingTable { pos } => {
+                    return self.open_window(sw, WindowKind::CraftingTable { pos });
+                }
+                Interaction::Chest { pos } => {
+                    return self.open_window(sw, WindowKind::Chest { pos });
+                }
+                Interaction::Furnace { pos } => {
+                    return self.open_window(sw, WindowKind::Furnace { pos });
+                }
+                Interaction::Dispenser { pos } => {
+                    return self.open_window(sw, WindowKind::Dispenser { pos });
+                }
+                Interaction::Handled => {}
             }
+        } else {
+            sw.world.use_raw_stack(&mut inv, inv_index, self.entity_id);
         }
 
         for index in inv.iter_changes() {
@@ -747,7 +877,7 @@ impl ServerPlayer {
                 // if the slot would accept that drop by checking validity.
                 cursor_stack = slot_stack;
                 if packet.right_click && slot_access.can_drop(cursor_stack) {
-                    cursor_stack.size = (cursor_stack.size + 1) / 2;
+                    cursor_stack.size = cursor_stack.size.div_ceil(2);
                 }
 
                 let mut new_slot_stack = slot_stack;
@@ -907,7 +1037,12 @@ impl ServerPlayer {
             );
         }
 
-        let Some(Entity(target_base, _)) = sw.world.get_entity_mut(packet.target_entity_id) else {
+        if packet.target_entity_id == self.entity_id {
+            warn!("from {}, incoherent interact with itself", self.username);
+            return;
+        }
+
+        let Some(Entity(target_base, _)) = sw.world.get_entity(packet.target_entity_id) else {
             warn!(
                 "from {}, incoherent interact entity target: {}",
                 self.username, packet.target_entity_id
@@ -915,9 +1050,12 @@ impl ServerPlayer {
             return;
         };
 
-        if self.pos.distance_squared(target_base.pos) >= 36.0 {
+        // Both attacks and interactions are checked for reach.
+        if !self.tracked_entities.contains(&packet.target_entity_id)
+            || !self.can_reach(target_base.bb)
+        {
             warn!(
-                "from {}, incoherent interact entity distance",
+                "from {}, incoherent interact entity distance or angle",
                 self.username
             );
             return;
@@ -926,13 +1064,50 @@ impl ServerPlayer {
         let hand_stack = self.main_inv[self.hand_slot as usize];
 
         if packet.left_click {
-            // TODO: Critical damage if vel.y < 0
+            // Compute the attack damage from the player's state, critical hits are only
+            // possible when the player is falling and not in water.
+            let Some(Entity(player_base, _)) = sw.world.get_entity_mut(self.entity_id) else {
+                return;
+            };
 
-            let damage = item::attack::get_base_damage(hand_stack.id);
-            target_base.hurt.push(Hurt {
-                damage,
-                origin_id: Some(self.entity_id),
-            });
+            let mut damage = item::attack::get_base_damage(hand_stack.id);
+            if player_base.fall_distance > 0.0 && !player_base.on_ground && !player_base.in_water {
+                // PARITY: Critical hits are not part of the Notchian beta server, we use
+                //  the same random bonus as later releases.
+                damage += player_base.rand.next_int_bounded(damage as i32 / 2 + 2) as u16;
+            }
+
+            // The knock back direction is computed from our entity position when the
+            // hurt is processed, and the hurt animation is sent with the damage event.
+            sw.world
+                .hurt_entity(packet.target_entity_id, damage, Some(self.entity_id));
+        } else {
+            let mut inv = InventoryHandle::new(&mut self.main_inv[..]);
+            let inv_index = self.hand_slot as usize;
+            let handled = sw.world.interact_entity(
+                &mut inv,
+                inv_index,
+                packet.target_entity_id,
+                self.entity_id,
+            );
+
+            for index in inv.iter_changes() {
+                self.send_main_inv_item(index);
+            }
+
+            if handled {
+                return;
+            }
+
+            // Only player entities have a nameplate in this version, the name of other
+            // entities is told in chat when interacting with them.
+            let Some(target) = sw.world.get_entity(packet.target_entity_id) else {
+                return;
+            };
+
+            if let (Some(name), None) = (&target.0.name, target.get::<Human>()) {
+                self.send_chat(msg!("entity.name", name = name));
+            }
         }
     }
 
@@ -947,8 +1122,10 @@ impl ServerPlayer {
 
         // A player action is only relevant on human entities, ignore if the player is
         // bound to any other entity kind.
-        let Some(Entity(_, BaseKind::Living(_, LivingKind::Human(human)))) =
-            sw.world.get_entity_mut(self.entity_id)
+        let Some(human) = sw
+            .world
+            .get_entity_mut(self.entity_id)
+            .and_then(|entity| entity.get_mut::<Human>())
         else {
             return;
         };
@@ -1026,7 +1203,7 @@ impl ServerPlayer {
                     if let Some(BlockEntity::Chest(chest)) = sw.world.get_block_entity(pos) {
                         stacks.extend(chest.inv.iter().map(|stack| stack.to_non_empty()));
                     } else {
-                        stacks.extend(std::iter::repeat(None).take(27));
+                        stacks.extend(std::iter::repeat_n(None, 27));
                     }
                 }
 
@@ -1342,6 +1519,63 @@ impl ServerPlayer {
         })
     }
 
+    /// Return true if the given bounding box can be reached by the player, it must be
+    /// near enough from its eyes and in front of its look.
+    fn can_reach(&self, bb: BoundingBox) -> bool {
+        let eye_pos = self.pos + DVec3::new(0.0, 1.62, 0.0);
+        let closest_pos = eye_pos.clamp(bb.min, bb.max);
+        if eye_pos.distance_squared(closest_pos) > REACH_DISTANCE * REACH_DISTANCE {
+            return false;
+        }
+
+        let delta = bb.center() - eye_pos;
+        if delta.length_squared() < REACH_ANY_ANGLE_DISTANCE * REACH_ANY_ANGLE_DISTANCE {
+            return true;
+        }
+
+        let (yaw_sin, yaw_cos) = (self.look.x as f64).sin_cos();
+        let (pitch_sin, pitch_cos) = (self.look.y as f64).sin_cos();
+        let look_dir = DVec3::new(-yaw_sin * pitch_cos, -pitch_sin, yaw_cos * pitch_cos);
+        look_dir.dot(delta.normalize()) >= REACH_MIN_COS
+    }
+
+    /// Check that the given block can be reached by the player and is in a chunk already
+    /// sent to it, if not the block and its neighbor on the given face are sent again to
+    /// the client in order to cancel its prediction.
+    fn check_block_reach(&self, sw: &ServerWorld, pos: IVec3, face: Option<Face>) -> bool {
+        let (cx, cz) = chunk::calc_chunk_pos_unchecked(pos);
+        if self.tracked_chunks.contains(&(cx, cz))
+            && self.can_reach(BoundingBox::CUBE.offset(pos.as_dvec3()))
+        {
+            return true;
+        }
+
+        warn!(
+            "from {}, incoherent block distance or angle: {pos}",
+            self.username
+        );
+
+        let face_pos = face.map(|face| pos + face.delta());
+        for pos in std::iter::once(pos).chain(face_pos) {
+            let (cx, cz) = chunk::calc_chunk_pos_unchecked(pos);
+            if !self.tracked_chunks.contains(&(cx, cz)) {
+                continue;
+            }
+
+            if let Some((block, metadata)) = sw.world.get_block(pos) {
+                self.send(OutPacket::BlockSet(proto::BlockSetPacket {
+                    x: pos.x,
+                    y: pos.y as i8,
+                    z: pos.z,
+                    block,
+                    metadata,
+                }));
+            }
+        }
+
+        false
+    }
+
     /// Send the main inventory item at given index to the client.
     fn send_main_inv_item(&self, index: usize) {
         let slot = match index {
@@ -1392,57 +1626,128 @@ impl ServerPlayer {
                 base.vel.y += rand_vel_y as f64;
             }
 
-            item.frozen_time = 40;
+            // Thrown items cannot be picked up by the player for some time.
+            item.frozen_time = e::Item::THROWN_FROZEN_TIME;
+            if !on_ground {
+                item.owner_id = Some(self.entity_id);
+                item.owner_time = e::Item::THROWN_OWNER_TIME;
+            }
+
             item.stack = stack;
         });
 
         sw.world.spawn_entity(entity);
     }
 
-    /// Update the chunks sent to this player.
-    pub fn update_chunks(&mut self, sw: &ServerWorld) {
+    /// Drop all stacks of the main and armor inventories around the player, this is
+    /// used when the player dies.
+    /// REF: InventoryPlayer::dropAllItems
+    pub fn drop_inventory(&mut self, sw: &mut ServerWorld) {
+        for index in 0..self.main_inv.len() {
+            if let Some(stack) = self.main_inv[index].take_non_empty() {
+                self.send_main_inv_item(index);
+                self.drop_stack(sw, stack, true);
+            }
+        }
+
+        for index in 0..self.armor_inv.len() {
+            if let Some(stack) = self.armor_inv[index].take_non_empty() {
+                self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
+                    window_id: 0,
+                    slot: 5 + index as i16,
+                    stack: None,
+                }));
+                self.drop_stack(sw, stack, true);
+            }
+        }
+    }
+
+    /// Update the chunks sent to this player, and the region of its chunk observer.
+    /// Chunks of the view region that are not yet loaded are requested to the storage.
+    pub fn update_chunks(&mut self, sw: &mut ServerWorld) {
         let (ocx, ocz) = chunk::calc_entity_chunk_pos(self.pos);
-        let view_range = 3;
-
-        for cx in (ocx - view_range)..(ocx + view_range) {
-            for cz in (ocz - view_range)..(ocz + view_range) {
-                if let Some(chunk) = sw.world.get_chunk(cx, cz) {
-                    if self.tracked_chunks.insert((cx, cz)) {
-                        self.send(OutPacket::ChunkState(proto::ChunkStatePacket {
-                            cx,
-                            cz,
-                            init: true,
-                        }));
 
-                        let from = IVec3 {
-                            x: cx * 16,
-                            y: 0,
-                            z: cz * 16,
-                        };
+        let region = ChunkRegion::new(
+            ocx - VIEW_RANGE,
+            ocz - VIEW_RANGE,
+            ocx + VIEW_RANGE - 1,
+            ocz + VIEW_RANGE - 1,
+        );
 
-                        let size = IVec3 {
-                            x: 16,
-                            y: 128,
-                            z: 16,
-                        };
+        match self.chunk_observer {
+            Some(handle) => {
+                sw.world.set_observed_region(handle, region);
+            }
+            None => self.chunk_observer = Some(sw.world.observe_chunks(region)),
+        }
 
-                        self.send(OutPacket::ChunkData(new_chunk_data_packet(
-                            chunk, from, size,
-                        )));
-                    }
-                }
+        for cx in region.min_cx..=region.max_cx {
+            for cz in region.min_cz..=region.max_cz {
+                sw.request_chunk_load(cx, cz);
+                self.send_chunk(&sw.world, cx, cz);
+                self.send_chunk_signs(&sw.world, cx, cz);
+            }
+        }
+    }
 
-                // Search signs block entities in chunk.
-                for (pos, block_entity) in sw.world.iter_block_entities_in_chunk(cx, cz) {
-                    if let BlockEntity::Sign(sign) = block_entity {
-                        self.send(OutPacket::UpdateSign(proto::UpdateSignPacket {
-                            x: pos.x,
-                            y: pos.y as i16,
-                            z: pos.z,
-                            lines: sign.lines.clone(),
-                        }));
-                    }
-                }
+    /// Return the region of chunks kept loaded by this player, this is its view region
+    /// extended by one chunk in order to avoid unloading chunks on the view border.
+    pub fn get_chunk_ticket(&self) -> ChunkRegion {
+        let (ocx, ocz) = chunk::calc_entity_chunk_pos(self.pos);
+        ChunkRegion::around(ocx, ocz, VIEW_RANGE as u32 + 1)
+    }
+
+    /// Stop tracking the given chunk if tracked, the client is told to unload it.
+    pub fn untrack_chunk(&mut self, cx: i32, cz: i32) {
+        if self.tracked_chunks.remove(&(cx, cz)) {
+            self.send(OutPacket::ChunkState(proto::ChunkStatePacket {
+                cx,
+                cz,
+                init: false,
+            }));
+        }
+    }
+
+    /// Send the given chunk to this player if loaded and not already tracked.
+    pub fn send_chunk(&mut self, world: &World, cx: i32, cz: i32) {
+        if let Some(chunk) = world.get_chunk(cx, cz) {
+            if self.tracked_chunks.insert((cx, cz)) {
+                self.send(OutPacket::ChunkState(proto::ChunkStatePacket {
+                    cx,
+                    cz,
+                    init: true,
+                }));
+
+                let from = IVec3 {
+                    x: cx * 16,
+                    y: 0,
+                    z: cz * 16,
+                };
+
+                let size = IVec3 {
+                    x: 16,
+                    y: 128,
+                    z: 16,
+                };
+
+                self.send(OutPacket::ChunkData(new_chunk_data_packet(
+                    chunk, from, size,
+                )));
+            }
+        }
+    }
+
+    /// Send all signs in the given chunk to this player.
+    pub fn send_chunk_signs(&self, world: &World, cx: i32, cz: i32) {
+        // Search signs block entities in chunk.
+        for (pos, block_entity) in world.iter_block_entities_in_chunk(cx, cz) {
+            if let BlockEntity::Sign(sign) = block_entity {
+                self.send(OutPacket::UpdateSign(proto::UpdateSignPacket {
+                    x: pos.x,
+                    y: pos.y as i16,
+                    z: pos.z,
+                    lines: sign.lines.clone(),
+                }));
             }
         }
     }
@@ -1467,7 +1772,7 @@ impl ServerPlayer {
             WindowKind::Furnace { pos }
             | WindowKind::Dispenser { pos }
             | WindowKind::CraftingTable { pos } => pos == target_pos,
-            WindowKind::Chest { ref pos } => pos.iter().any(|&pos| pos == target_pos),
+            WindowKind::Chest { ref pos } => pos.contains(&target_pos),
         };
 
         if contains {
@@ -1495,33 +1800,29 @@ impl ServerPlayer {
                     }
                 }
             }
-            WindowKind::Furnace { pos } => {
-                if pos == target_pos {
-                    let slot = match storage {
-                        BlockEntityStorage::FurnaceInput => 0,
-                        BlockEntityStorage::FurnaceFuel => 1,
-                        BlockEntityStorage::FurnaceOutput => 2,
-                        _ => return,
-                    };
+            WindowKind::Furnace { pos } if pos == target_pos => {
+                let slot = match storage {
+                    BlockEntityStorage::FurnaceInput => 0,
+                    BlockEntityStorage::FurnaceFuel => 1,
+                    BlockEntityStorage::FurnaceOutput => 2,
+                    _ => return,
+                };
 
+                self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
+                    window_id: self.window.id,
+                    slot,
+                    stack: stack.to_non_empty(),
+                }));
+            }
+            WindowKind::Dispenser { pos } if pos == target_pos => {
+                if let BlockEntityStorage::Standard(index) = storage {
                     self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
                         window_id: self.window.id,
-                        slot,
+                        slot: index as i16,
                         stack: stack.to_non_empty(),
                     }));
                 }
             }
-            WindowKind::Dispenser { pos } => {
-                if pos == target_pos {
-                    if let BlockEntityStorage::Standard(index) = storage {
-                        self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-                            window_id: self.window.id,
-                            slot: index as i16,
-                            stack: stack.to_non_empty(),
-                        }));
-                    }
-                }
-            }
             _ => {} // Not handled.
         }
     }
@@ -11,6 +11,7 @@ use byteorder::{ReadBytesExt, WriteBytesExt};
 use mc173::io::{ReadJavaExt, WriteJavaExt};
 use mc173::item::ItemStack;
 use mc173::util::split_at_utf8_boundary;
+use mc173::world::Effect;
 
 use crate::net;
 
@@ -603,17 +604,45 @@ pub struct EffectPlayPacket {
     /// - 1001: Play sound 'random.click' with pitch 1.2
     /// - 1002: Play sound 'random.bow' with pitch 1.2
     /// - 1003: Play sound randomly between 'random.door_open' and 'random.door_close'
-    ///         with random uniform pitch between 0.9 and 1.0
+    ///   with random uniform pitch between 0.9 and 1.0
     /// - 1004: Play sound 'random.fizz' with volume 0.5 and random pitch
     /// - 1005: Play record sound, the record item id is given in effect data
-    /// - 2000: Spawn smoke particles, the radius is given in effect data with two bits
-    ///         for X and Z axis, like this: `0bZZXX`
+    /// - 2000: Spawn smoke particles, the direction is given in effect data as
+    ///   `(dx + 1) + (dz + 1) * 3`, 4 being centered on the block
     /// - 2001: Play and show block break sound and particles, the block id is given in
-    ///         effect data.
+    ///   effect data.
     pub effect_id: u32,
     pub effect_data: u32,
 }
 
+impl EffectPlayPacket {
+    /// Create a packet playing the given effect at the given block position.
+    pub fn new(pos: IVec3, effect: Effect) -> Self {
+        let (effect_id, effect_data) = match effect {
+            Effect::Click => (1000, 0),
+            Effect::ClickHigh => (1001, 0),
+            Effect::Bow => (1002, 0),
+            Effect::Door => (1003, 0),
+            Effect::Fizz => (1004, 0),
+            Effect::Record(item) => (1005, item),
+            Effect::Smoke(face) => {
+                // The client decodes the direction as 'x + z * 3' with both in 0..3.
+                let delta = face.map(|face| face.delta()).unwrap_or_default();
+                (2000, ((delta.x + 1) + (delta.z + 1) * 3) as u32)
+            }
+            Effect::BlockBreak(id) => (2001, id as u32),
+        };
+
+        Self {
+            x: pos.x,
+            y: pos.y as i8,
+            z: pos.z,
+            effect_id,
+            effect_data,
+        }
+    }
+}
+
 /// Packet 70
 #[derive(Debug, Clone)]
 pub struct NotificationPacket {
@@ -785,6 +814,14 @@ impl Metadata {
         }
     }
 
+    #[inline]
+    pub fn new_string(id: u8, value: String) -> Self {
+        Self {
+            id,
+            kind: MetadataKind::String(value),
+        }
+    }
+
     #[inline]
     pub fn new_item_stack(id: u8, value: ItemStack) -> Self {
         Self {
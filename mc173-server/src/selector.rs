@@ -0,0 +1,153 @@
+//! Target selectors used by commands to designate entities.
+//!
+//! A selector is either a player username or a base selector optionally followed by
+//! arguments in brackets, for example `@e[kind=zombie,r=10,c=2]`. Supported bases are
+//! `@s` for the sender, `@p` for the nearest player, `@a` for all players and `@e` for
+//! all entities. Supported arguments are `kind` (or `type`) for the entity kind, `r`
+//! for the maximum distance to the sender and `c` for the maximum number of entities,
+//! the nearest being selected first.
+
+use glam::DVec3;
+
+use mc173::entity::{BaseKind, Entity, EntityKind, LivingKind};
+use mc173::world::World;
+
+use crate::message::msg;
+
+/// A parsed target selector.
+#[derive(Debug, Clone)]
+pub struct Selector {
+    /// The base of the selector.
+    base: SelectorBase,
+    /// Only select entities of this kind.
+    kind: Option<EntityKind>,
+    /// Only select entities within this distance of the sender.
+    radius: Option<f64>,
+    /// Maximum number of entities to select.
+    count: Option<usize>,
+}
+
+/// The base of a selector, before its arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SelectorBase {
+    /// The sender itself, `@s`.
+    Sender,
+    /// The nearest player to the sender, `@p`.
+    NearestPlayer,
+    /// All players, `@a`.
+    AllPlayers,
+    /// All entities, `@e`.
+    AllEntities,
+    /// The player with the given username.
+    Player(String),
+}
+
+impl Selector {
+    /// Parse a selector, an error message is returned if invalid.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let (base_raw, args_raw) = match raw.split_once('[') {
+            Some((base_raw, args_raw)) => {
+                let args_raw = args_raw
+                    .strip_suffix(']')
+                    .ok_or_else(|| msg!("selector.missing_bracket", selector = raw))?;
+                (base_raw, Some(args_raw))
+            }
+            None => (raw, None),
+        };
+
+        let base = match base_raw {
+            "@s" => SelectorBase::Sender,
+            "@p" => SelectorBase::NearestPlayer,
+            "@a" => SelectorBase::AllPlayers,
+            "@e" => SelectorBase::AllEntities,
+            _ if base_raw.starts_with('@') => {
+                return Err(msg!("selector.invalid", selector = base_raw));
+            }
+            _ if args_raw.is_some() => {
+                return Err(msg!("selector.player_arguments", selector = raw));
+            }
+            username => SelectorBase::Player(username.to_string()),
+        };
+
+        let mut selector = Self {
+            base,
+            kind: None,
+            radius: None,
+            count: None,
+        };
+
+        for arg in args_raw.into_iter().flat_map(|args| args.split(',')) {
+            let (key, value) = arg
+                .split_once('=')
+                .ok_or_else(|| msg!("selector.invalid_argument", argument = arg))?;
+
+            match key {
+                "kind" | "type" => {
+                    selector.kind = Some(
+                        EntityKind::from_name(value)
+                            .ok_or_else(|| msg!("selector.invalid_kind", kind = value))?,
+                    );
+                }
+                "r" => {
+                    selector.radius = Some(
+                        value
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|r| *r >= 0.0)
+                            .ok_or_else(|| msg!("selector.invalid_radius", radius = value))?,
+                    );
+                }
+                "c" => {
+                    selector.count = Some(
+                        value
+                            .parse::<usize>()
+                            .map_err(|_| msg!("selector.invalid_count", count = value))?,
+                    );
+                }
+                _ => return Err(msg!("selector.unknown_argument", argument = key)),
+            }
+        }
+
+        Ok(selector)
+    }
+
+    /// Resolve this selector in the given world, the sender is given by its entity id
+    /// and position. Selected entity ids are returned, nearest first.
+    pub fn resolve(&self, world: &World, sender_id: u32, sender_pos: DVec3) -> Vec<u32> {
+        let mut selected = world
+            .iter_entities()
+            .filter(|&(id, entity)| self.matches(id, entity, sender_id, sender_pos))
+            .map(|(id, Entity(base, _))| (id, base.pos.distance_squared(sender_pos)))
+            .collect::<Vec<_>>();
+
+        selected.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let mut count = self.count.unwrap_or(usize::MAX);
+        if self.base == SelectorBase::NearestPlayer {
+            count = 1;
+        }
+
+        selected.into_iter().take(count).map(|(id, _)| id).collect()
+    }
+
+    /// Internal function to check if an entity matches this selector.
+    fn matches(&self, id: u32, entity: &Entity, sender_id: u32, sender_pos: DVec3) -> bool {
+        let username = match entity {
+            Entity(_, BaseKind::Living(_, LivingKind::Human(human))) => Some(&human.username),
+            _ => None,
+        };
+
+        let base_match = match &self.base {
+            SelectorBase::Sender => id == sender_id,
+            SelectorBase::NearestPlayer | SelectorBase::AllPlayers => username.is_some(),
+            SelectorBase::AllEntities => true,
+            SelectorBase::Player(name) => username == Some(name),
+        };
+
+        base_match
+            && self.kind.is_none_or(|kind| entity.kind() == kind)
+            && self
+                .radius
+                .is_none_or(|radius| entity.0.pos.distance_squared(sender_pos) <= radius * radius)
+    }
+}
@@ -5,12 +5,16 @@ use std::io;
 use std::net::SocketAddr;
 use std::time::{Duration, Instant};
 
+use crossbeam_channel::{unbounded, Receiver, Sender, TrySendError};
 use tracing::{info, warn};
 
 use mc173::entity::{self as e};
+use mc173::world::dimension::DimensionRegistry;
 use mc173::world::{Dimension, Weather};
 
 use crate::config;
+use crate::handle::{PlayerInfo, ServerEvent, ServerHandle, ServerRequest, WorldInfo};
+use crate::message::msg;
 use crate::offline::OfflinePlayer;
 use crate::player::ServerPlayer;
 use crate::proto::{self, InPacket, Network, NetworkClient, NetworkEvent, OutPacket};
@@ -30,6 +34,12 @@ pub struct Server {
     worlds: Vec<WorldState>,
     /// Offline players database.
     offline_players: HashMap<String, OfflinePlayer>,
+    /// Sender of requests, cloned into each server handle.
+    requests_sender: Sender<ServerRequest>,
+    /// Requests received from server handles, processed on each tick.
+    requests: Receiver<ServerRequest>,
+    /// Subscribers to the events emitted by this server.
+    subscribers: Vec<EventSubscriber>,
 }
 
 impl Server {
@@ -37,18 +47,44 @@ impl Server {
     pub fn bind(addr: SocketAddr) -> io::Result<Self> {
         info!("server bound to {addr}");
 
+        let (requests_sender, requests) = unbounded();
         Ok(Self {
             net: Network::bind(addr)?,
             clients: HashMap::new(),
             worlds: vec![],
             offline_players: HashMap::new(),
+            requests_sender,
+            requests,
+            subscribers: Vec::new(),
         })
     }
 
-    /// Register a world in this server.
-    pub fn register_world(&mut self, name: String, dimension: Dimension) {
+    /// Create a new handle that can be sent to other threads in order to run commands,
+    /// broadcast messages and query this server, requests are executed on each tick.
+    pub fn handle(&self) -> ServerHandle {
+        ServerHandle::new(self.requests_sender.clone())
+    }
+
+    /// Register a world in this server, in a dimension of the given registry.
+    pub fn register_world(
+        &mut self,
+        name: String,
+        registry: &DimensionRegistry,
+        dimension: Dimension,
+    ) {
+        let entry = registry
+            .get(dimension)
+            .expect("world dimension is not registered");
+
+        match registry.get_portal_target(dimension) {
+            Some(target) => {
+                info!("registering world {name} in {dimension:?}, portals to {target:?}")
+            }
+            None => info!("registering world {name} in {dimension:?}, without portals"),
+        }
+
         self.worlds.push(WorldState {
-            world: ServerWorld::new(name, dimension),
+            world: ServerWorld::new(name, entry),
             players: Vec::new(),
         });
     }
@@ -83,11 +119,17 @@ impl Server {
         // All client-world interactions happens here.
         self.tick_net()?;
 
+        // Requests from server handles are executed between network and worlds ticks.
+        self.tick_requests();
+
         // Then we tick each world.
         for state in &mut self.worlds {
             state.world.tick(&mut state.players);
         }
 
+        // Finally dispatch the events queued by worlds while ticking network and worlds.
+        self.tick_events();
+
         Ok(())
     }
 
@@ -105,6 +147,105 @@ impl Server {
         Ok(())
     }
 
+    /// Execute all pending requests from server handles, results are ignored if their
+    /// receiver has been dropped.
+    fn tick_requests(&mut self) {
+        while let Ok(request) = self.requests.try_recv() {
+            match request {
+                ServerRequest::Broadcast { message } => {
+                    info!("broadcast: {message}");
+                    self.broadcast_chat(message);
+                }
+                ServerRequest::Command {
+                    username,
+                    command,
+                    reply,
+                } => {
+                    info!("run command as {username}: /{command}");
+                    let ret = self.worlds.iter_mut().find_map(|state| {
+                        let player = state
+                            .players
+                            .iter_mut()
+                            .find(|player| player.username == username)?;
+                        Some(player.run_captured_command(&mut state.world, &command))
+                    });
+                    let _ = reply.send(ret);
+                }
+                ServerRequest::Players { reply } => {
+                    let players = self
+                        .worlds
+                        .iter()
+                        .flat_map(|state| {
+                            state.players.iter().map(|player| PlayerInfo {
+                                username: player.username.clone(),
+                                world: state.world.name.clone(),
+                                pos: player.pos,
+                            })
+                        })
+                        .collect();
+                    let _ = reply.send(players);
+                }
+                ServerRequest::Worlds { reply } => {
+                    let worlds = self
+                        .worlds
+                        .iter()
+                        .map(|state| WorldInfo {
+                            name: state.world.name.clone(),
+                            time: state.world.world.get_time(),
+                            player_count: state.players.len(),
+                            entity_count: state.world.world.get_entity_count(),
+                        })
+                        .collect();
+                    let _ = reply.send(worlds);
+                }
+                ServerRequest::Subscribe { events } => {
+                    self.subscribers
+                        .push(EventSubscriber { events, dropped: 0 });
+                }
+            }
+        }
+    }
+
+    /// Dispatch the server events queued by all worlds, chat messages are also broadcast
+    /// to all players.
+    fn tick_events(&mut self) {
+        let mut events = Vec::new();
+        for state in &mut self.worlds {
+            events.append(&mut state.world.server_events);
+        }
+
+        for event in events {
+            if let ServerEvent::Chat { username, message } = &event {
+                self.broadcast_chat(msg!("server.chat", player = username, message = message));
+            }
+            self.emit_event(event);
+        }
+    }
+
+    /// Send an event to all subscribers without blocking, the event is dropped for
+    /// subscribers with a full channel, and disconnected subscribers are removed.
+    fn emit_event(&mut self, event: ServerEvent) {
+        self.subscribers.retain_mut(
+            |subscriber| match subscriber.events.try_send(event.clone()) {
+                Ok(()) => {
+                    if subscriber.dropped != 0 {
+                        warn!(
+                            "event subscriber lagging, {} events dropped",
+                            subscriber.dropped
+                        );
+                        subscriber.dropped = 0;
+                    }
+                    true
+                }
+                Err(TrySendError::Full(_)) => {
+                    subscriber.dropped += 1;
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            },
+        );
+    }
+
     /// Handle new client accepted by the network.
     fn handle_accept(&mut self, client: NetworkClient) {
         info!("accept client #{}", client.id());
@@ -127,8 +268,13 @@ impl Server {
             // If the client was playing, remove it from its world.
             // Swap remove the player and tell the world.
             let mut player = self.worlds[world_index].players.swap_remove(player_index);
-            self.worlds[world_index].world.handle_player_leave(&mut player, true);
-            self.broadcast_chat(format!("{} left the server.", player.username));
+            self.worlds[world_index]
+                .world
+                .handle_player_leave(&mut player, true);
+            self.broadcast_chat(msg!("server.leave", player = player.username));
+            self.emit_event(ServerEvent::Leave {
+                username: player.username.clone(),
+            });
             // If a player has been swapped in place of this new one, redefine its state.
             if let Some(swapped_player) = self.worlds[world_index].players.get(player_index) {
                 self.clients
@@ -168,7 +314,10 @@ impl Server {
             InPacket::KeepAlive => {}
             InPacket::Handshake(_) => self.handle_handshake(client),
             InPacket::Login(packet) => self.handle_login(client, packet),
-            _ => self.send_disconnect(client, format!("Invalid packet: {packet:?}")),
+            _ => self.send_disconnect(
+                client,
+                msg!("kick.invalid_packet", packet = format!("{packet:?}")),
+            ),
         }
     }
 
@@ -186,7 +335,7 @@ impl Server {
     /// Handle a login after handshake.
     fn handle_login(&mut self, client: NetworkClient, packet: proto::InLoginPacket) {
         if packet.protocol_version != 14 {
-            self.send_disconnect(client, "Protocol version mismatch!".to_string());
+            self.send_disconnect(client, msg!("kick.protocol_mismatch"));
             return;
         }
 
@@ -230,10 +379,7 @@ impl Server {
             OutPacket::Login(proto::OutLoginPacket {
                 entity_id,
                 random_seed: self.worlds[world_index].world.seed,
-                dimension: match self.worlds[world_index].world.world.get_dimension() {
-                    Dimension::Overworld => 0,
-                    Dimension::Nether => -1,
-                },
+                dimension: self.worlds[world_index].world.world.get_dimension_info().id,
             }),
         );
 
@@ -273,9 +419,13 @@ impl Server {
             .world
             .handle_player_join(&mut player);
         let player_index = self.worlds[world_index].players.len();
-        let player_join_message = format!("{} joined the server.", player.username);
+        let player_join_message = msg!("server.join", player = player.username);
+        let player_join_event = ServerEvent::Join {
+            username: player.username.clone(),
+        };
         self.worlds[world_index].players.push(player);
         self.broadcast_chat(player_join_message);
+        self.emit_event(player_join_event);
 
         // Replace the previous state with a playing state containing the world and
         // player indices, used to get to the player instance.
@@ -406,3 +556,12 @@ struct WorldState {
     /// The players currently in this world.
     players: Vec<ServerPlayer>,
 }
+
+/// A subscriber to the server events, registered from a server handle.
+struct EventSubscriber {
+    /// The bounded channel of events.
+    events: Sender<ServerEvent>,
+    /// Number of events dropped since the last one successfully sent, because the
+    /// channel was full.
+    dropped: u64,
+}
@@ -1,31 +1,53 @@
 //! Server world structure.
 
 use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use glam::{DVec3, IVec3, Vec2};
 
+use mc173::biome::SpawnTable;
 use mc173::block_entity::BlockEntity;
-use tracing::{debug, info};
+use mc173::craft::RecipeRegistry;
+use mc173::data::{self, DataError, DataTables};
+use tracing::{debug, info, warn};
 
-use mc173::entity::{BaseKind, Entity, ProjectileKind};
-use mc173::gen::OverworldGenerator;
+use mc173::entity::{BaseKind, Entity, Human, Living, ProjectileKind};
+use mc173::gen::pregen::Pregen;
+use mc173::gen::VoidGenerator;
 use mc173::item::{self, ItemStack};
-use mc173::storage::{ChunkStorage, ChunkStorageReply};
+use mc173::loot::LootTable;
+use mc173::map::MapRenderer;
+use mc173::serde::freeze;
+use mc173::serde::meta::{self, WorldMeta};
+use mc173::serde::remap::IdRemap;
+use mc173::serde::schematic;
+use mc173::storage::fsck::{self, FsckOptions};
+use mc173::storage::{ChunkStorage, ChunkStorageReply, SaveStats};
 use mc173::util::FadingAverage;
 use mc173::{block, chunk};
 
+use mc173::world::dimension::{DimensionEntry, DimensionInfo};
+use mc173::world::observe::{ChunkRegion, ObserverHandle};
+use mc173::world::silent::BlockChanges;
 use mc173::world::{
     BlockEntityEvent, BlockEntityProgress, BlockEntityStorage, BlockEvent, ChunkEvent, Dimension,
-    EntityEvent, Event, Weather, World,
+    Effect, EntityEvent, Event, Weather, World,
 };
 
 use crate::chunk::ChunkTrackers;
 use crate::config;
 use crate::entity::EntityTracker;
+use crate::handle::ServerEvent;
+use crate::message::msg;
 use crate::player::ServerPlayer;
 use crate::proto::{self, OutPacket};
 
+/// Maximum number of retries of a chunk load failing with a temporary error.
+const MAX_LOAD_RETRIES: u8 = 3;
+
 /// A single world in the server, this structure extends the basic [`World`] structure for
 /// server-specific behaviors, such as name, tick mode or entity tracking.
 pub struct ServerWorld {
@@ -43,6 +65,11 @@ pub struct ServerWorld {
     pub tick_mode: TickMode,
     /// The chunk source used to load and save the world's chunk.
     storage: ChunkStorage,
+    /// The running pre-generation, if any. Chunks loaded by the pre-generation are not
+    /// added to the world.
+    pub pregen: Option<Pregen>,
+    /// The live map renderer and its observer of all chunks, if enabled.
+    live_map: Option<(MapRenderer, ObserverHandle)>,
     /// Chunks trackers used to send proper block changes packets.
     chunk_trackers: ChunkTrackers,
     /// Entity tracker, each is associated to the entity id.
@@ -55,6 +82,13 @@ pub struct ServerWorld {
     pub tick_interval: FadingAverage,
     /// Fading average of events count on each tick.
     pub events_count: FadingAverage,
+    /// Statistics of all chunks saved since the world has been loaded.
+    pub save_stats: SaveStats,
+    /// Number of retries of chunk loads that failed with a temporary error.
+    load_retries: HashMap<(i32, i32), u8>,
+    /// Events queued for the server handles subscribers, dispatched by the server after
+    /// each tick.
+    pub server_events: Vec<ServerEvent>,
 }
 
 /// Indicate the current mode for ticking the world.
@@ -67,28 +101,65 @@ pub enum TickMode {
 }
 
 impl ServerWorld {
-    /// Internal function to create a server world.
-    pub fn new(name: String, dimension: Dimension) -> Self {
-        let mut world = World::new(dimension);
+    /// Internal function to create a server world in the given registered dimension.
+    pub fn new(name: String, dimension: &DimensionEntry) -> Self {
+        // A thawed world already knows its next entity id.
+        let mut world = config::hibernate()
+            .then(|| thaw_world(&name))
+            .flatten()
+            .unwrap_or_else(|| {
+                let mut world = dimension.new_world();
+                load_meta(&name).apply(&mut world);
+                world
+            });
+
+        // Frozen worlds only know their dimension, not the registered properties.
+        world.set_dimension_info(*dimension.get_info());
 
         // Make sure that the world initially have an empty events queue.
         world.swap_events(Some(Vec::new()));
+        world.set_lazy_relight(config::lazy_relight());
+        world.set_mob_griefing(config::mob_griefing());
+        world.set_spawner_mobs_capped(config::spawner_mobs_capped());
+        world.set_simulation_distance(config::simulation_distance());
+        world.set_chunk_freeze_delay(config::chunk_freeze_delay());
+        world.set_item_lifetime(config::item_lifetime());
+        world.set_item_cleanup_interval(config::item_cleanup_interval());
 
         let seed = config::SEED;
 
+        let region_dir = region_dir(dimension.get_dimension());
+        if let Some(repair) = config::fsck() {
+            check_storage(&region_dir, dimension.get_info(), repair);
+        }
+
+        let mut storage = dimension.new_storage(region_dir, 4, load_remap());
+        storage.set_corrupted_fallback(config::corrupted_chunk_fallback());
+
+        let live_map = config::live_map().then(|| {
+            let handle =
+                world.observe_chunks(ChunkRegion::new(i32::MIN, i32::MIN, i32::MAX, i32::MAX));
+            (MapRenderer::new(map_dir(dimension.get_dimension())), handle)
+        });
+
         Self {
             name,
             world,
             seed,
             time: 0,
             tick_mode: TickMode::Auto,
-            storage: ChunkStorage::new("test_world/region/", OverworldGenerator::new(seed), 4),
+            storage,
+            pregen: None,
+            live_map,
             chunk_trackers: ChunkTrackers::new(),
             entity_trackers: HashMap::new(),
             tick_last: Instant::now(),
             tick_duration: FadingAverage::default(),
             tick_interval: FadingAverage::default(),
             events_count: FadingAverage::default(),
+            save_stats: SaveStats::default(),
+            load_retries: HashMap::new(),
+            server_events: Vec::new(),
         }
     }
 
@@ -100,8 +171,9 @@ impl ServerWorld {
         //     player.send_disconnect(format!("Server stopping..."));
         // }
 
+        let mut stop_stats = SaveStats::default();
         for (cx, cz) in self.chunk_trackers.drain_save() {
-            if let Some(snapshot) = self.world.take_chunk_snapshot(cx, cz) {
+            if let Some(snapshot) = self.world.take_chunk_save_snapshot(cx, cz) {
                 debug!("saving {} chunk: {cx}/{cz}", self.name);
                 self.storage.request_save(snapshot);
             }
@@ -113,9 +185,10 @@ impl ServerWorld {
                     ChunkStorageReply::Save {
                         cx,
                         cz,
-                        res: Ok(()),
+                        res: Ok(stats),
                     } => {
-                        debug!("saved chunk in storage: {cx}/{cz}");
+                        debug!("saved chunk in storage: {cx}/{cz}: {stats:?}");
+                        stop_stats += stats;
                     }
                     ChunkStorageReply::Save {
                         cx,
@@ -128,6 +201,39 @@ impl ServerWorld {
                 }
             }
         }
+
+        self.save_stats += stop_stats;
+        info!(
+            "saved {} chunks of {}: {}/{} entities written, {} kB in {:.1} ms",
+            stop_stats.chunks,
+            self.name,
+            stop_stats.entities_written,
+            stop_stats.entities,
+            stop_stats.bytes / 1000,
+            stop_stats.duration.as_secs_f32() * 1000.0
+        );
+
+        self.save_meta();
+
+        if config::hibernate() {
+            let path = hibernate_path(&self.name);
+            info!("hibernating {} to {}...", self.name, path.display());
+            if let Err(e) = freeze::save(&path, &self.world.freeze()) {
+                warn!("failed to hibernate {}: {e}", self.name);
+            }
+        }
+    }
+
+    /// Save the metadata of this world, such as the next entity id, see [`WorldMeta`].
+    fn save_meta(&self) {
+        let path = meta_path(&self.name);
+        if let Err(e) = meta::save(&path, &WorldMeta::from_world(&self.world)) {
+            warn!(
+                "failed to save metadata of {} to {}: {e}",
+                self.name,
+                path.display()
+            );
+        }
     }
 
     /// Tick this world.
@@ -143,30 +249,59 @@ impl ServerWorld {
             self.init(players);
         }
 
+        // Request chunks of the running pre-generation.
+        if let Some(pregen) = &mut self.pregen {
+            pregen.request(&mut self.storage);
+        }
+
         // Poll all chunks to load in the world.
         while let Some(reply) = self.storage.poll() {
             match reply {
+                // Chunks requested by the pre-generation are dropped, unless also needed
+                // by the world, in which case they are handled like any other.
+                ChunkStorageReply::Load { cx, cz, ref res }
+                    if self
+                        .pregen
+                        .as_mut()
+                        .is_some_and(|pregen| pregen.handle_load(cx, cz, res.is_ok()))
+                        && !is_chunk_ticketed(players, cx, cz) =>
+                {
+                    debug!("pre-generated chunk: {cx}/{cz}");
+                }
                 ChunkStorageReply::Load {
                     cx,
                     cz,
                     res: Ok(snapshot),
                 } => {
-                    debug!("loaded chunk from storage: {cx}/{cz}");
-                    self.world.insert_chunk_snapshot(snapshot);
+                    self.load_retries.remove(&(cx, cz));
+                    // The chunk may already be present if the world has been thawed.
+                    if !self.world.contains_chunk(cx, cz) {
+                        debug!("loaded chunk from storage: {cx}/{cz}");
+                        self.world.insert_chunk_snapshot(snapshot);
+                    }
                 }
                 ChunkStorageReply::Load {
                     cx,
                     cz,
                     res: Err(err),
                 } => {
-                    debug!("failed to load chunk from storage: {cx}/{cz}: {err}");
+                    let retries = self.load_retries.entry((cx, cz)).or_default();
+                    if err.is_retryable() && *retries < MAX_LOAD_RETRIES {
+                        *retries += 1;
+                        warn!("failed to load chunk {cx}/{cz}, retrying ({retries}): {err}");
+                        self.storage.request_load(cx, cz);
+                    } else {
+                        self.load_retries.remove(&(cx, cz));
+                        warn!("failed to load chunk {cx}/{cz}, left unloaded: {err}");
+                    }
                 }
                 ChunkStorageReply::Save {
                     cx,
                     cz,
-                    res: Ok(()),
+                    res: Ok(stats),
                 } => {
-                    debug!("saved chunk in storage: {cx}/{cz}");
+                    debug!("saved chunk in storage: {cx}/{cz}: {stats:?}");
+                    self.save_stats += stats;
                 }
                 ChunkStorageReply::Save {
                     cx,
@@ -178,6 +313,43 @@ impl ServerWorld {
             }
         }
 
+        // Log the pre-generation progress every 10 seconds, and stop it when done.
+        if let Some(pregen) = &self.pregen {
+            let progress = pregen.progress();
+            if pregen.is_done() {
+                info!(
+                    "pre-generation of {} done: {} chunks ({} failed) in {:.0} s",
+                    self.name,
+                    progress.total,
+                    progress.failed,
+                    progress.elapsed.as_secs_f32()
+                );
+                self.pregen = None;
+            } else if time.is_multiple_of(200) {
+                info!(
+                    "pre-generation of {}: {:.1}% ({}/{}), {:.1} chunks/s, resume index: {}",
+                    self.name,
+                    progress.percent(),
+                    progress.done,
+                    progress.total,
+                    progress.chunks_per_sec(),
+                    progress.resume_index
+                );
+            }
+        }
+
+        // The armor is kept in the players inventory, it's copied to their entities so
+        // that their damages are reduced.
+        for player in players.iter() {
+            if let Some(human) = self
+                .world
+                .get_entity_mut(player.entity_id)
+                .and_then(|entity| entity.get_mut::<Human>())
+            {
+                human.armor = *player.armor_inv;
+            }
+        }
+
         // Only run if no tick freeze.
         match self.tick_mode {
             TickMode::Auto => self.world.tick(),
@@ -204,9 +376,10 @@ impl ServerWorld {
                         prev_id,
                         prev_metadata,
                     } => self.handle_block_set(players, pos, id, metadata, prev_id, prev_metadata),
-                    BlockEvent::Sound { id, metadata } => {
-                        self.handle_block_sound(players, pos, id, metadata)
+                    BlockEvent::Break { id, origin_id, .. } => {
+                        self.handle_block_break(players, pos, id, origin_id)
                     }
+                    BlockEvent::Effect { effect } => self.handle_block_effect(players, pos, effect),
                     BlockEvent::Piston { face, extending } => {
                         if config::client_piston() {
                             self.handle_block_action(players, pos, (!extending) as i8, face as i8);
@@ -218,6 +391,11 @@ impl ServerWorld {
                 },
                 Event::Entity { id, inner } => match inner {
                     EntityEvent::Spawn => self.handle_entity_spawn(players, id),
+                    // Entities are tracked from the spawn event, whatever the reason.
+                    EntityEvent::Spawned { .. } => {}
+                    EntityEvent::Ride { vehicle_id } => {
+                        self.handle_entity_ride(players, id, vehicle_id)
+                    }
                     EntityEvent::Remove => self.handle_entity_remove(players, id),
                     EntityEvent::Position { pos } => self.handle_entity_position(id, pos),
                     EntityEvent::Look { look } => self.handle_entity_look(id, look),
@@ -228,6 +406,23 @@ impl ServerWorld {
                     EntityEvent::Damage => self.handle_entity_damage(players, id),
                     EntityEvent::Dead => self.handle_entity_dead(players, id),
                     EntityEvent::Metadata => self.handle_entity_metadata(players, id),
+                    EntityEvent::Name => self.handle_entity_name(players, id),
+                    EntityEvent::Heal => self.handle_entity_health(players, id),
+                    EntityEvent::Armor => self.handle_entity_armor(players, id),
+                    EntityEvent::Tame { tamed } => {
+                        self.handle_entity_status(players, id, if tamed { 7 } else { 6 })
+                    }
+                    EntityEvent::PvpDenied { attacker_id } => {
+                        self.handle_entity_pvp_denied(players, attacker_id)
+                    }
+                    // The Notchian client plays ambient sounds by itself, and no packet
+                    // exists in this version to play a named sound.
+                    EntityEvent::AmbientSound { .. } => {}
+                    // The Notchian client spawns impact particles by itself.
+                    EntityEvent::Impact { .. } => {}
+                    // No packet exists in this version for the splash, the client only
+                    // sees the bobber sinking through its velocity.
+                    EntityEvent::Bite => {}
                 },
                 Event::BlockEntity { pos, inner } => match inner {
                     BlockEntityEvent::Set => self.handle_block_entity_set(players, pos),
@@ -242,11 +437,22 @@ impl ServerWorld {
                 },
                 Event::Chunk { cx, cz, inner } => match inner {
                     ChunkEvent::Set => {}
-                    ChunkEvent::Remove => {}
+                    ChunkEvent::Remove => {
+                        for player in &mut players[..] {
+                            player.untrack_chunk(cx, cz);
+                        }
+                    }
                     ChunkEvent::Dirty => self.chunk_trackers.set_dirty(cx, cz),
+                    ChunkEvent::Light { min, max } => {
+                        self.chunk_trackers.set_light(cx, cz, min, max)
+                    }
                 },
                 Event::Weather { new, .. } => self.handle_weather_change(players, new),
                 Event::Explode { center, radius } => self.handle_explode(players, center, radius),
+                Event::Lightning { id, pos } => self.handle_lightning(players, id, pos),
+                Event::ItemCleanup { delay } => self.handle_item_cleanup(players, delay),
+                // The Notchian client plays cave sounds by itself.
+                Event::CaveSound { .. } => {}
                 Event::DebugParticle { pos, block } => {
                     self.handle_debug_particle(players, pos, block)
                 }
@@ -256,8 +462,36 @@ impl ServerWorld {
         // Reinsert events after processing.
         self.world.swap_events(Some(events));
 
+        // Send chunks loaded in the view region of players.
+        for player in &mut players[..] {
+            let Some(handle) = player.chunk_observer else {
+                continue;
+            };
+            for (cx, cz, event) in self.world.take_observed_chunks(handle).unwrap_or_default() {
+                if event == ChunkEvent::Set {
+                    player.send_chunk(&self.world, cx, cz);
+                    player.send_chunk_signs(&self.world, cx, cz);
+                }
+            }
+        }
+
+        // Render loaded and modified chunks to the live map every second.
+        if time.is_multiple_of(20) {
+            if let Some((renderer, handle)) = &self.live_map {
+                for (cx, cz, event) in self.world.take_observed_chunks(*handle).unwrap_or_default()
+                {
+                    if event != ChunkEvent::Remove {
+                        if let Some(snapshot) = self.world.take_chunk_snapshot(cx, cz) {
+                            let north = self.world.take_chunk_snapshot(cx, cz - 1).map(|s| s.chunk);
+                            renderer.request_render(cx, cz, snapshot.chunk, north);
+                        }
+                    }
+                }
+            }
+        }
+
         // Send time to every playing clients every second.
-        if time % 20 == 0 {
+        if time.is_multiple_of(20) {
             let world_time = self.world.get_time();
             for player in &mut players[..] {
                 player.send(OutPacket::UpdateTime(proto::UpdateTimePacket {
@@ -271,7 +505,7 @@ impl ServerWorld {
 
         // After world events are processed, tick entity trackers.
         for tracker in self.entity_trackers.values_mut() {
-            if time % 60 == 0 {
+            if time.is_multiple_of(60) {
                 tracker.update_tracking_players(players, &self.world);
             }
             tracker.tick_and_update_players(players);
@@ -279,11 +513,21 @@ impl ServerWorld {
 
         // Drain dirty chunks coordinates and save them.
         while let Some((cx, cz)) = self.chunk_trackers.next_save() {
-            if let Some(snapshot) = self.world.take_chunk_snapshot(cx, cz) {
+            if let Some(snapshot) = self.world.take_chunk_save_snapshot(cx, cz) {
                 self.storage.request_save(snapshot);
             }
         }
 
+        // Unload chunks no longer kept loaded every second.
+        if time.is_multiple_of(20) {
+            self.unload_chunks(players);
+        }
+
+        // Save the world metadata every minute, along with saved chunks.
+        if time.is_multiple_of(1200) {
+            self.save_meta();
+        }
+
         // Update tick duration metric.
         let tick_duration = start.elapsed();
         self.tick_duration.push(tick_duration.as_secs_f32(), 0.02);
@@ -304,15 +548,67 @@ impl ServerWorld {
             });
         }
 
-        // NOTE: Temporary code.
-        let (center_cx, center_cz) = chunk::calc_entity_chunk_pos(config::SPAWN_POS);
-        for cx in center_cx - 10..=center_cx + 10 {
-            for cz in center_cz - 10..=center_cz + 10 {
-                self.storage.request_load(cx, cz);
+        let region = spawn_chunk_ticket();
+        for cx in region.min_cx..=region.max_cx {
+            for cz in region.min_cz..=region.max_cz {
+                self.request_chunk_load(cx, cz);
             }
         }
     }
 
+    /// Request the loading of a chunk from the storage, if not already loaded or
+    /// requested. The chunk is added to the world once loaded.
+    pub fn request_chunk_load(&mut self, cx: i32, cz: i32) {
+        if !self.world.contains_chunk(cx, cz) && !self.storage.is_load_requested(cx, cz) {
+            self.storage.request_load(cx, cz);
+        }
+    }
+
+    /// Unload chunks that are no longer referenced by any ticket, chunks are kept loaded
+    /// by the spawn ticket and the tickets of players, see
+    /// [`ServerPlayer::get_chunk_ticket`]. Unloaded chunks are saved along with their
+    /// entities and block entities, that are removed from the world, a chunk remove
+    /// event is pushed for each of them.
+    ///
+    /// REF: ChunkProviderServer::unload100OldestChunks
+    fn unload_chunks(&mut self, players: &[ServerPlayer]) {
+        /// Maximum number of chunks unloaded at once.
+        const MAX_UNLOAD: usize = 100;
+
+        let spawn_ticket = spawn_chunk_ticket();
+        let player_tickets = players
+            .iter()
+            .map(ServerPlayer::get_chunk_ticket)
+            .collect::<Vec<_>>();
+
+        let unload = self
+            .world
+            .iter_chunks()
+            .filter(|&(cx, cz)| {
+                !spawn_ticket.contains(cx, cz)
+                    && !player_tickets.iter().any(|ticket| ticket.contains(cx, cz))
+            })
+            .take(MAX_UNLOAD)
+            .collect::<Vec<_>>();
+
+        for (cx, cz) in unload {
+            self.chunk_trackers.remove(cx, cz);
+            if let Some(snapshot) = self.world.remove_chunk_snapshot(cx, cz) {
+                debug!("unloading {} chunk: {cx}/{cz}", self.name);
+                self.storage.request_save(snapshot);
+            }
+        }
+    }
+
+    /// Send block changes made in a silent scope to the players tracking their chunks,
+    /// see [`World::with_silent_changes`].
+    pub fn send_block_changes(&mut self, changes: &BlockChanges) {
+        for change in changes.iter() {
+            self.chunk_trackers
+                .set_block(change.pos, change.id, change.metadata);
+        }
+    }
+
     /// Handle a player joining this world.
     pub fn handle_player_join(&mut self, player: &mut ServerPlayer) {
         // Initial tracked entities.
@@ -345,6 +641,10 @@ impl ServerWorld {
         self.world
             .remove_entity(player.entity_id, "server player leave");
 
+        if let Some(handle) = player.chunk_observer.take() {
+            self.world.unobserve_chunks(handle);
+        }
+
         // If player has not lost connection but it's just leaving the world, we just
         // send it untrack packets.
         if !lost {
@@ -387,23 +687,28 @@ impl ServerWorld {
         }
     }
 
-    fn handle_block_sound(
+    /// Handle a block break, the break effect is played to all players tracking the chunk,
+    /// except the one that broke it because its client already plays it.
+    fn handle_block_break(
         &mut self,
         players: &mut [ServerPlayer],
         pos: IVec3,
-        _block: u8,
-        _metadata: u8,
+        id: u8,
+        origin_id: Option<u32>,
     ) {
+        let (cx, cz) = chunk::calc_chunk_pos_unchecked(pos);
+        for player in players {
+            if player.tracked_chunks.contains(&(cx, cz)) && origin_id != Some(player.entity_id) {
+                player.play_effect(pos, Effect::BlockBreak(id));
+            }
+        }
+    }
+
+    fn handle_block_effect(&mut self, players: &mut [ServerPlayer], pos: IVec3, effect: Effect) {
         let (cx, cz) = chunk::calc_chunk_pos_unchecked(pos);
         for player in players {
             if player.tracked_chunks.contains(&(cx, cz)) {
-                player.send(OutPacket::EffectPlay(proto::EffectPlayPacket {
-                    effect_id: 1003,
-                    x: pos.x,
-                    y: pos.y as i8,
-                    z: pos.z,
-                    effect_data: 0,
-                }));
+                player.play_effect(pos, effect);
             }
         }
     }
@@ -444,10 +749,31 @@ impl ServerWorld {
         }
     }
 
+    /// Handle a lightning strike world event, the Notchian server sends it to all players
+    /// in a 512 blocks radius.
+    fn handle_lightning(&mut self, players: &mut [ServerPlayer], id: u32, pos: DVec3) {
+        let scaled = (pos * 32.0).floor().as_ivec3();
+        for player in players {
+            if player.pos.distance_squared(pos) < 512.0 * 512.0 {
+                player.send(OutPacket::LightningBolt(proto::LightningBoltPacket {
+                    entity_id: id,
+                    x: scaled.x,
+                    y: scaled.y,
+                    z: scaled.z,
+                }));
+            }
+        }
+    }
+
     /// Handle an entity spawn world event.
     fn handle_entity_spawn(&mut self, players: &mut [ServerPlayer], id: u32) {
-        // The entity may have already been removed.
-        if let Some(entity) = self.world.get_entity(id) {
+        // The entity may have already been removed, lightning bolts are not tracked but
+        // sent once when striking.
+        if let Some(entity) = self
+            .world
+            .get_entity(id)
+            .filter(|entity| !matches!(entity.1, BaseKind::LightningBolt(_)))
+        {
             self.entity_trackers.entry(id).or_insert_with(|| {
                 let tracker = EntityTracker::new(id, entity);
                 tracker.update_tracking_players(players, &self.world);
@@ -527,11 +853,14 @@ impl ServerWorld {
     /// Handle an entity damage event.
     fn handle_entity_damage(&mut self, players: &mut [ServerPlayer], id: u32) {
         self.handle_entity_status(players, id, 2);
+        self.handle_entity_health(players, id);
+    }
 
-        // TODO: This is temporary code, we need to make a common method to update health.
+    /// Handle a change of an entity health, sent to the player if this is its entity.
+    fn handle_entity_health(&mut self, players: &mut [ServerPlayer], id: u32) {
         for player in players {
             if player.entity_id == id {
-                if let Entity(_, BaseKind::Living(living, _)) = self.world.get_entity(id).unwrap() {
+                if let Some(living) = self.world.get_entity(id).unwrap().get::<Living>() {
                     player.send(OutPacket::UpdateHealth(proto::UpdateHealthPacket {
                         health: living.health.min(i16::MAX as _) as i16,
                     }));
@@ -540,9 +869,90 @@ impl ServerWorld {
         }
     }
 
+    /// Handle a player entity armor damaged, copied back to the player inventory.
+    fn handle_entity_armor(&mut self, players: &mut [ServerPlayer], id: u32) {
+        let Some(human) = self.world.get_entity(id).and_then(|e| e.get::<Human>()) else {
+            return;
+        };
+
+        for player in players {
+            if player.entity_id == id {
+                for (index, &stack) in human.armor.iter().enumerate() {
+                    if player.armor_inv[index] != stack {
+                        player.armor_inv[index] = stack;
+                        player.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
+                            window_id: 0,
+                            slot: index as i16 + 5,
+                            stack: stack.to_non_empty(),
+                        }));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handle a change of an entity display name, only player entities have a nameplate
+    /// so they are spawned again to players tracking them.
+    fn handle_entity_name(&mut self, players: &mut [ServerPlayer], id: u32) {
+        if !self.world.is_player_entity(id) {
+            return;
+        }
+
+        if let Some(tracker) = self.entity_trackers.get(&id) {
+            tracker.untrack_players(players);
+            tracker.update_tracking_players(players, &self.world);
+        }
+    }
+
+    /// Handle an entity mounting or leaving a vehicle.
+    fn handle_entity_ride(
+        &mut self,
+        players: &mut [ServerPlayer],
+        id: u32,
+        vehicle_id: Option<u32>,
+    ) {
+        for player in players {
+            if player.tracked_entities.contains(&id) || player.entity_id == id {
+                player.send(OutPacket::EntityRide(proto::EntityRidePacket {
+                    entity_id: id,
+                    vehicle_entity_id: vehicle_id.unwrap_or(u32::MAX),
+                }));
+            }
+        }
+    }
+
+    /// Handle a denied player damage, the attacker is told that PvP is disabled.
+    fn handle_entity_pvp_denied(&mut self, players: &mut [ServerPlayer], attacker_id: u32) {
+        for player in players {
+            if player.entity_id == attacker_id {
+                player.send_chat(msg!("world.pvp_disabled"));
+            }
+        }
+    }
+
     /// Handle an entity dead event (the entity is not yet removed).
     fn handle_entity_dead(&mut self, players: &mut [ServerPlayer], id: u32) {
         self.handle_entity_status(players, id, 3);
+
+        let killer = self
+            .world
+            .get_entity(id)
+            .and_then(|e| e.get::<Human>())
+            .and_then(Human::get_combat_attacker)
+            .and_then(|attacker_id| {
+                players
+                    .iter()
+                    .find(|player| player.entity_id == attacker_id)
+            })
+            .map(|attacker| attacker.username.clone());
+
+        if let Some(player) = players.iter_mut().find(|player| player.entity_id == id) {
+            self.server_events.push(ServerEvent::Death {
+                username: player.username.clone(),
+                killer,
+            });
+            player.drop_inventory(self);
+        }
     }
 
     /// Handle an entity damage/dead or other status for an entity.
@@ -634,18 +1044,216 @@ impl ServerWorld {
         }
     }
 
+    fn handle_item_cleanup(&mut self, players: &mut [ServerPlayer], delay: u64) {
+        for player in players {
+            player.send_chat(msg!("world.item_cleanup", seconds = delay / 20));
+        }
+    }
+
     fn handle_debug_particle(&mut self, players: &mut [ServerPlayer], pos: IVec3, block: u8) {
         let (cx, cz) = chunk::calc_chunk_pos_unchecked(pos);
         for player in players {
             if player.tracked_chunks.contains(&(cx, cz)) {
-                player.send(OutPacket::EffectPlay(proto::EffectPlayPacket {
-                    effect_id: 2001,
-                    x: pos.x,
-                    y: pos.y as i8,
-                    z: pos.z,
-                    effect_data: block as u32,
-                }));
+                player.play_effect(pos, Effect::BlockBreak(block));
             }
         }
     }
 }
+
+/// Get the region directory of the given dimension, like the Notchian server, only the
+/// overworld is stored at the root of the world directory.
+fn region_dir(dimension: Dimension) -> PathBuf {
+    match dimension {
+        Dimension::Overworld => PathBuf::from("test_world/region/"),
+        _ => PathBuf::from(format!("test_world/DIM{}/region/", dimension.id())),
+    }
+}
+
+/// Return the directory where live map tiles of the given dimension are rendered.
+fn map_dir(dimension: Dimension) -> PathBuf {
+    match dimension {
+        Dimension::Overworld => PathBuf::from("test_world/map/"),
+        _ => PathBuf::from(format!("test_world/DIM{}/map/", dimension.id())),
+    }
+}
+
+/// Return the path of the hibernation file of the given world.
+fn hibernate_path(name: &str) -> PathBuf {
+    PathBuf::from(format!("test_world/{name}.hibernate"))
+}
+
+/// Return the path of the metadata file of the given world.
+fn meta_path(name: &str) -> PathBuf {
+    PathBuf::from(format!("test_world/{name}.meta"))
+}
+
+/// Load the metadata of the given world, default metadata is returned if the world has
+/// no metadata file yet or if it fails to load.
+fn load_meta(name: &str) -> WorldMeta {
+    let path = meta_path(name);
+    if !path.is_file() {
+        return WorldMeta::default();
+    }
+
+    match meta::load(&path) {
+        Ok(meta) => meta,
+        Err(e) => {
+            warn!(
+                "failed to load metadata of {name} from {}: {e}",
+                path.display()
+            );
+            WorldMeta::default()
+        }
+    }
+}
+
+/// Thaw a world from its hibernation file, if existing, the file is removed so the
+/// world is only thawed once.
+fn thaw_world(name: &str) -> Option<World> {
+    let path = hibernate_path(name);
+    if !path.is_file() {
+        return None;
+    }
+
+    info!("thawing {name} from {}...", path.display());
+    let res = freeze::load(&path);
+    if let Err(e) = fs::remove_file(&path) {
+        warn!("failed to remove hibernation file {}: {e}", path.display());
+    }
+
+    match res {
+        Ok(freeze) => Some(World::thaw(freeze)),
+        Err(e) => {
+            warn!("failed to thaw {name}: {e}");
+            None
+        }
+    }
+}
+
+/// Load the ids remapping table from the configured path, if any.
+/// Create the generator of an empty overworld, with the configured island schematic
+/// stamped at spawn, if any.
+pub fn new_void_generator() -> VoidGenerator {
+    let gen = VoidGenerator::new();
+    let Some(path) = config::island_path() else {
+        return gen;
+    };
+
+    match schematic::load(&path) {
+        Ok(island) => {
+            info!("loaded island schematic from {}", path.display());
+            gen.with_island_at_spawn(config::SPAWN_POS.as_ivec3(), island)
+        }
+        Err(e) => {
+            warn!("failed to load island schematic {}: {e}", path.display());
+            gen
+        }
+    }
+}
+
+fn load_remap() -> Option<IdRemap> {
+    let path = config::remap_path()?;
+    let res = fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| IdRemap::parse(&text).map_err(|e| e.to_string()));
+
+    match res {
+        Ok(remap) => {
+            info!("loaded ids remapping table from {}", path.display());
+            Some(remap)
+        }
+        Err(e) => {
+            warn!("failed to load ids remapping table {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Load all the data tables from the given directory and replace the current ones, each
+/// table file is optional and defaults to the Notchian table. Current tables are kept
+/// if any table fails to load, return true if tables have been replaced.
+pub fn load_data_tables(dir: &Path) -> bool {
+    let res = (|| {
+        let mut tables = DataTables::default();
+        if let Some(recipes) = load_data_table(dir, "recipes.txt", RecipeRegistry::parse)? {
+            tables.recipes = recipes;
+        }
+        if let Some(loot) = load_data_table(dir, "dungeon_loot.txt", LootTable::parse)? {
+            tables.dungeon_loot = loot;
+        }
+        if let Some(loot) = load_data_table(dir, "bonus_chest_loot.txt", LootTable::parse)? {
+            tables.bonus_chest_loot = loot;
+        }
+        if let Some(spawns) = load_data_table(dir, "spawns.txt", SpawnTable::parse)? {
+            tables.spawns = spawns;
+        }
+        Ok::<_, String>(tables)
+    })();
+
+    match res {
+        Ok(tables) => {
+            info!(
+                "loaded data tables from {} ({} recipes)",
+                dir.display(),
+                tables.recipes.len()
+            );
+            data::set_tables(tables);
+            true
+        }
+        Err(e) => {
+            warn!("failed to load data tables from {}: {e}", dir.display());
+            false
+        }
+    }
+}
+
+/// Load a single data table file, none if the file doesn't exist.
+fn load_data_table<T>(
+    dir: &Path,
+    file_name: &str,
+    parse: fn(&str) -> Result<T, DataError>,
+) -> Result<Option<T>, String> {
+    let path = dir.join(file_name);
+    match fs::read_to_string(&path) {
+        Ok(text) => parse(&text)
+            .map(Some)
+            .map_err(|e| format!("{file_name}: {e}")),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{file_name}: {e}")),
+    }
+}
+
+/// Return the region of chunks around the spawn that are always kept loaded.
+fn spawn_chunk_ticket() -> ChunkRegion {
+    /// Radius of the spawn region, in chunks.
+    const SPAWN_CHUNK_RADIUS: u32 = 10;
+    let (cx, cz) = chunk::calc_entity_chunk_pos(config::SPAWN_POS);
+    ChunkRegion::around(cx, cz, SPAWN_CHUNK_RADIUS)
+}
+
+/// Return true if the given chunk is kept loaded by the spawn ticket or the ticket of
+/// any player.
+fn is_chunk_ticketed(players: &[ServerPlayer], cx: i32, cz: i32) -> bool {
+    spawn_chunk_ticket().contains(cx, cz)
+        || players
+            .iter()
+            .any(|player| player.get_chunk_ticket().contains(cx, cz))
+}
+
+/// Check the region files of a world before loading it, and optionally repair them.
+fn check_storage(region_dir: &Path, info: &DimensionInfo, repair: bool) {
+    info!("checking {}...", region_dir.display());
+
+    let options = FsckOptions {
+        repair,
+        regenerate: repair,
+        check_sky_light: info.has_sky,
+    };
+
+    match fsck::fsck(region_dir, &options) {
+        Ok(report) if report.is_clean() => info!("{report}"),
+        Ok(report) => warn!("{report}"),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => warn!("failed to check {}: {e}", region_dir.display()),
+    }
+}
@@ -0,0 +1,27 @@
+//! Common utilities shared by benchmarks.
+
+use mc173::chunk::Chunk;
+use mc173::gen::{ChunkGenerator, OverworldGenerator};
+use mc173::world::{Dimension, World};
+
+/// Seed of the worlds generated for benchmarks.
+pub const SEED: i64 = 9999;
+
+/// Create a world with the overworld terrain of the given seed, generated in all
+/// chunks within the given radius around the origin chunk. Features are not generated.
+pub fn new_terrain_world(seed: i64, radius: i32) -> World {
+    let generator = OverworldGenerator::new(seed);
+    let mut state = Default::default();
+    let mut world = World::new(Dimension::Overworld);
+
+    for cx in -radius..=radius {
+        for cz in -radius..=radius {
+            let mut chunk = Chunk::new();
+            let chunk_mut = std::sync::Arc::get_mut(&mut chunk).unwrap();
+            generator.gen_terrain(cx, cz, chunk_mut, &mut state);
+            world.set_chunk(cx, cz, chunk);
+        }
+    }
+
+    world
+}
@@ -0,0 +1,39 @@
+//! Benchmarks of chunk generation.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use mc173::chunk::Chunk;
+use mc173::gen::{ChunkGenerator, OverworldGenerator};
+
+/// Generate the terrain of a single chunk, for a few seeds because the cost of the
+/// terrain depends on the biomes and caves.
+fn gen_terrain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gen_terrain");
+
+    for seed in [9999, -5584946235460478062, 42] {
+        let generator = OverworldGenerator::new(seed);
+        let mut state = Default::default();
+        let mut chunk = Chunk::new();
+        let chunk_mut = std::sync::Arc::get_mut(&mut chunk).unwrap();
+        let mut cx = 0;
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(seed),
+            &generator,
+            |b, generator| {
+                b.iter(|| {
+                    // Use a different chunk each time to avoid biasing the generator caches.
+                    cx += 1;
+                    generator.gen_terrain(black_box(cx), 0, chunk_mut, &mut state);
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, gen_terrain);
+criterion_main!(benches);
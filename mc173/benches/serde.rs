@@ -0,0 +1,39 @@
+//! Benchmarks of chunk serialization to and from NBT.
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use mc173::serde::{chunk, nbt};
+
+use common::{new_terrain_world, SEED};
+
+/// Serialize a generated chunk to NBT and then to bytes, and the reverse.
+fn chunk_nbt(c: &mut Criterion) {
+    let world = new_terrain_world(SEED, 0);
+    let snapshot = world.take_chunk_snapshot(0, 0).unwrap();
+
+    c.bench_function("chunk_to_nbt", |b| {
+        let mut buf = Vec::new();
+        b.iter(|| {
+            buf.clear();
+            let root = chunk::to_nbt(black_box(&snapshot));
+            nbt::to_writer(&mut buf, &root).unwrap();
+        });
+    });
+
+    let mut buf = Vec::new();
+    nbt::to_writer(&mut buf, &chunk::to_nbt(&snapshot)).unwrap();
+
+    c.bench_function("chunk_from_nbt", |b| {
+        b.iter(|| {
+            let root = nbt::from_reader(black_box(&buf[..])).unwrap();
+            chunk::from_nbt(&root).unwrap()
+        });
+    });
+}
+
+criterion_group!(benches, chunk_nbt);
+criterion_main!(benches);
@@ -0,0 +1,109 @@
+//! Benchmarks of world ticking, light propagation and collision queries.
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use glam::{DVec3, IVec3};
+
+use mc173::block;
+use mc173::entity::{Item, Pig};
+use mc173::geom::BoundingBox;
+
+use common::{new_terrain_world, SEED};
+
+/// Tick a world with an increasing number of pigs and items on its surface.
+fn tick_entities(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tick_entities");
+
+    for count in [0, 100, 1000] {
+        let mut world = new_terrain_world(SEED, 3);
+
+        for i in 0..count {
+            let x = (i % 64) as f64 - 32.0 + 0.5;
+            let z = (i / 64 % 64) as f64 - 32.0 + 0.5;
+            let y = world.get_height(IVec3::new(x as i32, 0, z as i32)).unwrap() as f64;
+            let pos = DVec3::new(x, y, z);
+            if i % 2 == 0 {
+                world.spawn_entity(Pig::new_default(pos));
+            } else {
+                world.spawn_entity(Item::new_default(pos));
+            }
+        }
+
+        // Settle entities and light before measuring.
+        for _ in 0..20 {
+            world.tick();
+        }
+
+        group.bench_function(BenchmarkId::from_parameter(count), |b| {
+            b.iter(|| world.tick());
+        });
+    }
+
+    group.finish();
+}
+
+/// Place and remove a grid of glowstone blocks in the air, each causing a light
+/// propagation storm, and tick light until all updates have been processed.
+fn light_storm(c: &mut Criterion) {
+    let mut world = new_terrain_world(SEED, 2);
+    while world.get_light_update_count() != 0 {
+        world.tick_light(usize::MAX);
+    }
+
+    let positions = (0..16)
+        .map(|i| IVec3::new(i % 4 * 8 - 16, 100, i / 4 * 8 - 16))
+        .collect::<Vec<_>>();
+
+    c.bench_function("light_storm", |b| {
+        b.iter(|| {
+            for &pos in &positions {
+                world.set_block(pos, block::GLOWSTONE, 0);
+            }
+            while world.get_light_update_count() != 0 {
+                world.tick_light(usize::MAX);
+            }
+            for &pos in &positions {
+                world.set_block(pos, block::AIR, 0);
+            }
+            while world.get_light_update_count() != 0 {
+                world.tick_light(usize::MAX);
+            }
+        });
+    });
+}
+
+/// Query block boxes and entities colliding with bounding boxes of various sizes.
+fn collision(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collision");
+
+    let mut world = new_terrain_world(SEED, 2);
+    for i in 0..200 {
+        let pos = DVec3::new((i % 20) as f64 - 10.0, 80.0, (i / 20) as f64 - 10.0);
+        world.spawn_entity(Pig::new_default(pos));
+    }
+
+    for size in [1.0, 4.0, 16.0] {
+        let center = DVec3::new(0.5, 64.0, 0.5);
+        let bb = BoundingBox {
+            min: center - size / 2.0,
+            max: center + size / 2.0,
+        };
+
+        group.bench_with_input(BenchmarkId::new("blocks", size), &bb, |b, &bb| {
+            b.iter(|| world.iter_blocks_boxes_colliding(black_box(bb)).count());
+        });
+
+        let bb = bb + DVec3::new(0.0, 16.0, 0.0);
+        group.bench_with_input(BenchmarkId::new("entities", size), &bb, |b, &bb| {
+            b.iter(|| world.iter_entities_colliding(black_box(bb)).count());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, tick_entities, light_storm, collision);
+criterion_main!(benches);
@@ -1,12 +1,16 @@
 //! This modules provide the biome enumeration, it is stored in each chunk on the 2D grid.
 //! The Notchian implementation doesn't store the biomes, so they are generated on each
-//! chunk load, biomes are also not sent to the client, so it is also recomputed
-//! client-side in order to have the proper foliage color.
+//! chunk load, this implementation saves them with the chunk and only generate them if
+//! missing. Biomes are also not sent to the client, so it is also recomputed client-side
+//! in order to have the proper foliage color.
 
+use std::collections::HashMap;
+
+use crate::data::{self, DataError};
 use crate::entity::{EntityCategory, EntityKind};
 
 /// Possible biomes, only used server-side for natural mob spawning.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Biome {
     #[default]
     Void,
@@ -26,6 +30,52 @@ pub enum Biome {
 }
 
 impl Biome {
+    /// Array of all biomes.
+    pub const ALL: [Self; 14] = [
+        Biome::Void,
+        Biome::RainForest,
+        Biome::Swampland,
+        Biome::SeasonalForest,
+        Biome::Forest,
+        Biome::Savanna,
+        Biome::ShrubLand,
+        Biome::Taiga,
+        Biome::Desert,
+        Biome::Plains,
+        Biome::IceDesert,
+        Biome::Tundra,
+        Biome::Nether,
+        Biome::Sky,
+    ];
+
+    /// Get the name of this biome, in snake case, such as `seasonal_forest`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Biome::Void => "void",
+            Biome::RainForest => "rain_forest",
+            Biome::Swampland => "swampland",
+            Biome::SeasonalForest => "seasonal_forest",
+            Biome::Forest => "forest",
+            Biome::Savanna => "savanna",
+            Biome::ShrubLand => "shrub_land",
+            Biome::Taiga => "taiga",
+            Biome::Desert => "desert",
+            Biome::Plains => "plains",
+            Biome::IceDesert => "ice_desert",
+            Biome::Tundra => "tundra",
+            Biome::Nether => "nether",
+            Biome::Sky => "sky",
+        }
+    }
+
+    /// Get a biome from its name, see [`name`](Self::name), the name is case
+    /// insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|biome| biome.name().eq_ignore_ascii_case(name))
+    }
+
     /// Return true if it is possible to rain in a chunk.
     #[inline]
     pub fn has_rain(self) -> bool {
@@ -41,7 +91,9 @@ impl Biome {
         matches!(self, Biome::Taiga | Biome::IceDesert | Biome::Tundra)
     }
 
-    /// Get the natural entity kinds for the given category and this current biome.
+    /// Get the Notchian natural entity kinds for the given category and this current
+    /// biome, the kinds actually spawning are defined by the [`SpawnTable`] of the
+    /// [data tables](crate::data).
     pub fn natural_entity_kinds(self, category: EntityCategory) -> &'static [NaturalEntityKind] {
         const ANIMALS: &[NaturalEntityKind] = &[
             NaturalEntityKind::new(EntityKind::Sheep, 12),
@@ -109,7 +161,7 @@ impl Biome {
     }
 }
 
-/// Describe a natural
+/// Describe a natural entity kind that can spawn in a biome.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct NaturalEntityKind {
     /// The entity kind.
@@ -124,3 +176,101 @@ impl NaturalEntityKind {
         Self { kind, chance }
     }
 }
+
+/// A table of the entity kinds naturally spawning in each biome, for each category. The
+/// default table is the Notchian one, the current table is part of the
+/// [data tables](crate::data).
+#[derive(Debug, Clone)]
+pub struct SpawnTable {
+    /// The natural entity kinds of each biome and category, none if empty.
+    kinds: HashMap<(Biome, EntityCategory), Vec<NaturalEntityKind>>,
+}
+
+impl Default for SpawnTable {
+    fn default() -> Self {
+        let mut kinds = HashMap::new();
+        for biome in Biome::ALL {
+            for category in EntityCategory::ALL {
+                let biome_kinds = biome.natural_entity_kinds(category);
+                if !biome_kinds.is_empty() {
+                    kinds.insert((biome, category), biome_kinds.to_vec());
+                }
+            }
+        }
+        Self { kinds }
+    }
+}
+
+impl SpawnTable {
+    /// Remove all the natural entity kinds of all biomes.
+    pub fn clear(&mut self) {
+        self.kinds.clear();
+    }
+
+    /// Get the natural entity kinds of the given category in the given biome.
+    pub fn get(&self, biome: Biome, category: EntityCategory) -> &[NaturalEntityKind] {
+        self.kinds
+            .get(&(biome, category))
+            .map_or(&[], |kinds| &kinds[..])
+    }
+
+    /// Set the spawn chance of the given entity kind in the given biome, spawning with
+    /// the given category, the kind is removed from the biome if the chance is zero.
+    pub fn set_chance(
+        &mut self,
+        biome: Biome,
+        category: EntityCategory,
+        kind: EntityKind,
+        chance: u16,
+    ) {
+        let kinds = self.kinds.entry((biome, category)).or_default();
+        if let Some(index) = kinds.iter().position(|natural| natural.kind == kind) {
+            if chance == 0 {
+                kinds.remove(index);
+            } else {
+                kinds[index].chance = chance;
+            }
+        } else if chance != 0 {
+            kinds.push(NaturalEntityKind::new(kind, chance));
+        }
+    }
+
+    /// Parse a spawn table from its text format, starting from the default table. Each
+    /// non-empty line is a directive, `#` starts a comment:
+    ///
+    /// - `clear` to remove all the entity kinds of all biomes;
+    /// - `spawn <biome>|* <category> <entity> <chance>` to set the spawn chance of an
+    ///   entity kind in a biome, or in all biomes, a zero chance prevents the entity from
+    ///   spawning. The category is one of `animal`, `water_animal` or `mob`.
+    pub fn parse(text: &str) -> Result<Self, DataError> {
+        let mut table = Self::default();
+
+        data::parse_lines(text, |parts| {
+            match *parts {
+                ["clear"] => table.clear(),
+                ["spawn", biome, category, kind, chance] => {
+                    let category = match category {
+                        "animal" => EntityCategory::Animal,
+                        "water_animal" => EntityCategory::WaterAnimal,
+                        "mob" => EntityCategory::Mob,
+                        _ => return Err("unknown category"),
+                    };
+                    let kind = EntityKind::from_name(kind).ok_or("unknown entity kind")?;
+                    let chance = chance.parse::<u16>().map_err(|_| "invalid chance")?;
+                    if biome == "*" {
+                        for biome in Biome::ALL {
+                            table.set_chance(biome, category, kind, chance);
+                        }
+                    } else {
+                        let biome = Biome::from_name(biome).ok_or("unknown biome")?;
+                        table.set_chance(biome, category, kind, chance);
+                    }
+                }
+                _ => return Err("invalid directive"),
+            }
+            Ok(())
+        })?;
+
+        Ok(table)
+    }
+}
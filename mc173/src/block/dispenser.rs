@@ -13,8 +13,9 @@ pub fn get_face(metadata: u8) -> Option<Face> {
     })
 }
 
-/// Set facing of the dispenser.
+/// Set facing of the dispenser, the face must be horizontal.
 pub fn set_face(metadata: &mut u8, face: Face) {
+    debug_assert!(!face.is_y());
     *metadata = match face {
         Face::NegY => 0,
         Face::PosY => 1,
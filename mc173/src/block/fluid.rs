@@ -1,5 +1,12 @@
 //! Fluid block special functions (mostly for water).
 
+use glam::{DVec3, IVec3};
+
+use crate::block;
+use crate::block::material::Material;
+use crate::geom::Face;
+use crate::world::World;
+
 /// Return true if this still/moving fluid block acts like a source.
 #[inline]
 pub fn is_source(metadata: u8) -> bool {
@@ -20,6 +27,7 @@ pub fn get_distance(metadata: u8) -> u8 {
     metadata & 7
 }
 
+/// Set the distance to source of a fluid block, from 0 to 7.
 #[inline]
 pub fn set_distance(metadata: &mut u8, distance: u8) {
     debug_assert!(distance <= 7);
@@ -27,12 +35,20 @@ pub fn set_distance(metadata: &mut u8, distance: u8) {
     *metadata |= distance;
 }
 
+/// Get the metadata of a non-falling fluid block at the given distance to its source,
+/// none if the distance is too far for the fluid to exist.
+#[inline]
+pub fn from_distance(distance: u8) -> Option<u8> {
+    (distance <= 7).then_some(distance)
+}
+
 /// Get if this fluid block is falling and therefore should not spread on sides.
 #[inline]
 pub fn is_falling(metadata: u8) -> bool {
     metadata & 8 != 0
 }
 
+/// Set if this fluid block is falling.
 #[inline]
 pub fn set_falling(metadata: &mut u8, falling: bool) {
     *metadata &= !8;
@@ -56,3 +72,64 @@ pub fn get_actual_distance(metadata: u8) -> u8 {
 pub fn get_actual_height(metadata: u8) -> f32 {
     1.0 - (get_actual_distance(metadata) + 1) as f32 / 9.0
 }
+
+/// Calculate the normalized flow vector of the fluid block at the given position, this
+/// depends on the distance of neighbor blocks of the same fluid material. A zero vector
+/// is returned if the block is not a fluid or if its fluid is not flowing.
+///
+/// REF: BlockFluid::getFlowVector
+pub fn calc_flow(world: &World, pos: IVec3) -> DVec3 {
+    let (id, metadata) = world.get_block(pos).unwrap_or_default();
+    let material = block::material::get_material(id);
+    if !material.is_fluid() {
+        return DVec3::ZERO;
+    }
+
+    let distance = get_actual_distance(metadata);
+    let mut flow = DVec3::ZERO;
+
+    for face in Face::HORIZONTAL {
+        let face_delta = face.delta();
+        let face_pos = pos + face_delta;
+        let (face_id, face_metadata) = world.get_block(face_pos).unwrap_or_default();
+        let face_material = block::material::get_material(face_id);
+
+        if face_material == material {
+            let face_distance = get_actual_distance(face_metadata);
+            let delta = face_distance as i32 - distance as i32;
+            flow += (face_delta * delta).as_dvec3();
+        } else if !face_material.is_solid() {
+            let below_pos = face_pos - IVec3::Y;
+            let (below_id, below_metadata) = world.get_block(below_pos).unwrap_or_default();
+            if block::material::get_material(below_id) == material {
+                let below_distance = get_actual_distance(below_metadata);
+                let delta = below_distance as i32 - (distance as i32 - 8);
+                flow += (face_delta * delta).as_dvec3();
+            }
+        }
+    }
+
+    // Falling fluid next to a solid side is pulled down.
+    if is_falling(metadata) {
+        let blocked = Face::HORIZONTAL.into_iter().any(|face| {
+            let face_pos = pos + face.delta();
+            is_flow_blocking(world, face_pos, material)
+                || is_flow_blocking(world, face_pos + IVec3::Y, material)
+        });
+        if blocked {
+            flow = flow.normalize_or_zero() + DVec3::new(0.0, -6.0, 0.0);
+        }
+    }
+
+    flow.normalize_or_zero()
+}
+
+/// Return true if the block at the given position blocks the side flow of the given
+/// fluid material, ice never blocks.
+///
+/// REF: BlockFluid::isBlockSolid
+fn is_flow_blocking(world: &World, pos: IVec3, material: Material) -> bool {
+    let (id, _) = world.get_block(pos).unwrap_or_default();
+    let block_material = block::material::get_material(id);
+    block_material != material && block_material != Material::Ice && block_material.is_solid()
+}
@@ -0,0 +1,23 @@
+//! Leaves block metadata functions.
+
+use super::sapling::TreeKind;
+
+/// Get the kind of tree for this leaves block.
+#[inline]
+pub fn get_kind(metadata: u8) -> TreeKind {
+    super::sapling::get_kind(metadata)
+}
+
+/// Return true if the leaves block should check for a log around on the next random
+/// tick, and decay if no log is found.
+#[inline]
+pub fn is_check_decay(metadata: u8) -> bool {
+    metadata & 8 != 0
+}
+
+/// Set if the leaves block should check for decay on its next random tick.
+#[inline]
+pub fn set_check_decay(metadata: &mut u8, check_decay: bool) {
+    *metadata &= !8;
+    *metadata |= (check_decay as u8) << 3;
+}
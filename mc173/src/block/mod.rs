@@ -12,9 +12,11 @@ pub mod dispenser;
 pub mod door;
 pub mod fluid;
 pub mod ladder;
+pub mod leaves;
 pub mod lever;
 pub mod piston;
 pub mod pumpkin;
+pub mod rail;
 pub mod repeater;
 pub mod sapling;
 pub mod sign;
@@ -14,9 +14,10 @@ pub fn get_face(metadata: u8) -> Face {
     }
 }
 
-/// Set the face where the pumpkin is carved.
+/// Set the face where the pumpkin is carved, the face must be horizontal.
 #[inline]
 pub fn set_face(metadata: &mut u8, face: Face) {
+    debug_assert!(!face.is_y());
     *metadata &= !3;
     *metadata |= match face {
         Face::PosZ => 0,
@@ -0,0 +1,169 @@
+//! Rail block metadata functions, for regular, powered and detector rails.
+
+use crate::geom::Face;
+
+/// The shape of a rail, regular rails can take any shape while powered and detector
+/// rails cannot be curved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RailShape {
+    /// Flat rail along the Z axis.
+    FlatZ = 0,
+    /// Flat rail along the X axis.
+    FlatX = 1,
+    /// Rail along the X axis, ascending toward positive X.
+    AscendingPosX = 2,
+    /// Rail along the X axis, ascending toward negative X.
+    AscendingNegX = 3,
+    /// Rail along the Z axis, ascending toward negative Z.
+    AscendingNegZ = 4,
+    /// Rail along the Z axis, ascending toward positive Z.
+    AscendingPosZ = 5,
+    /// Curved rail connecting positive Z and positive X.
+    CurvePosZPosX = 6,
+    /// Curved rail connecting positive Z and negative X.
+    CurvePosZNegX = 7,
+    /// Curved rail connecting negative Z and negative X.
+    CurveNegZNegX = 8,
+    /// Curved rail connecting negative Z and positive X.
+    CurveNegZPosX = 9,
+}
+
+impl RailShape {
+    /// Return true if this rail shape is ascending.
+    #[inline]
+    pub fn is_ascending(self) -> bool {
+        matches!(
+            self,
+            Self::AscendingPosX | Self::AscendingNegX | Self::AscendingNegZ | Self::AscendingPosZ
+        )
+    }
+
+    /// Return true if this rail shape is curved, only regular rails can be curved.
+    #[inline]
+    pub fn is_curved(self) -> bool {
+        (self as u8) >= 6
+    }
+
+    /// Get the two horizontal faces this rail shape connects to. For ascending shapes,
+    /// the first face is the one where the rail ascends to.
+    pub fn get_faces(self) -> (Face, Face) {
+        match self {
+            Self::FlatZ => (Face::NegZ, Face::PosZ),
+            Self::FlatX => (Face::NegX, Face::PosX),
+            Self::AscendingPosX => (Face::PosX, Face::NegX),
+            Self::AscendingNegX => (Face::NegX, Face::PosX),
+            Self::AscendingNegZ => (Face::NegZ, Face::PosZ),
+            Self::AscendingPosZ => (Face::PosZ, Face::NegZ),
+            Self::CurvePosZPosX => (Face::PosZ, Face::PosX),
+            Self::CurvePosZNegX => (Face::PosZ, Face::NegX),
+            Self::CurveNegZNegX => (Face::NegZ, Face::NegX),
+            Self::CurveNegZPosX => (Face::NegZ, Face::PosX),
+        }
+    }
+
+    /// Get the flat or ascending rail shape going along the axis of the given
+    /// horizontal face, none if the face is vertical.
+    pub fn from_face(face: Face, ascending: bool) -> Option<Self> {
+        Some(match (face, ascending) {
+            (Face::NegZ | Face::PosZ, false) => Self::FlatZ,
+            (Face::NegX | Face::PosX, false) => Self::FlatX,
+            (Face::PosX, true) => Self::AscendingPosX,
+            (Face::NegX, true) => Self::AscendingNegX,
+            (Face::NegZ, true) => Self::AscendingNegZ,
+            (Face::PosZ, true) => Self::AscendingPosZ,
+            _ => return None,
+        })
+    }
+
+    /// Internal function to get a shape from its raw metadata value.
+    fn from_raw(raw: u8) -> Option<Self> {
+        Some(match raw {
+            0 => Self::FlatZ,
+            1 => Self::FlatX,
+            2 => Self::AscendingPosX,
+            3 => Self::AscendingNegX,
+            4 => Self::AscendingNegZ,
+            5 => Self::AscendingPosZ,
+            6 => Self::CurvePosZPosX,
+            7 => Self::CurvePosZNegX,
+            8 => Self::CurveNegZNegX,
+            9 => Self::CurveNegZPosX,
+            _ => return None,
+        })
+    }
+}
+
+/// Get the shape of a regular rail, none if the metadata is invalid.
+#[inline]
+pub fn get_shape(metadata: u8) -> Option<RailShape> {
+    RailShape::from_raw(metadata & 15)
+}
+
+/// Set the shape of a regular rail.
+#[inline]
+pub fn set_shape(metadata: &mut u8, shape: RailShape) {
+    *metadata &= !15;
+    *metadata |= shape as u8;
+}
+
+/// Get the shape of a powered or detector rail, none if the metadata is invalid, these
+/// rails are never curved.
+#[inline]
+pub fn get_straight_shape(metadata: u8) -> Option<RailShape> {
+    RailShape::from_raw(metadata & 7).filter(|shape| !shape.is_curved())
+}
+
+/// Set the shape of a powered or detector rail, the shape must not be curved.
+#[inline]
+pub fn set_straight_shape(metadata: &mut u8, shape: RailShape) {
+    debug_assert!(!shape.is_curved());
+    *metadata &= !7;
+    *metadata |= shape as u8 & 7;
+}
+
+/// Get the shape of any kind of rail given its block id, none if the block is not a
+/// rail or if the metadata is invalid.
+#[inline]
+pub fn get_any_shape(id: u8, metadata: u8) -> Option<RailShape> {
+    match id {
+        super::RAIL => get_shape(metadata),
+        super::POWERED_RAIL | super::DETECTOR_RAIL => get_straight_shape(metadata),
+        _ => None,
+    }
+}
+
+/// Return true if the powered rail is powered, or if the detector rail is active.
+#[inline]
+pub fn is_powered(metadata: u8) -> bool {
+    metadata & 8 != 0
+}
+
+/// Set if the powered rail is powered, or if the detector rail is active.
+#[inline]
+pub fn set_powered(metadata: &mut u8, powered: bool) {
+    *metadata &= !8;
+    *metadata |= (powered as u8) << 3;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn rail_shape() {
+        let mut metadata = 0;
+        set_shape(&mut metadata, RailShape::CurveNegZPosX);
+        assert_eq!(get_shape(metadata), Some(RailShape::CurveNegZPosX));
+        assert_eq!(get_shape(12), None);
+
+        // Powered rails keep their powered bit apart from their straight shape.
+        let mut metadata = 0;
+        set_straight_shape(&mut metadata, RailShape::AscendingPosZ);
+        set_powered(&mut metadata, true);
+        assert_eq!(metadata, 13);
+        assert_eq!(get_straight_shape(metadata), Some(RailShape::AscendingPosZ));
+        assert!(get_straight_shape(6).is_none());
+        assert!(is_powered(metadata));
+    }
+}
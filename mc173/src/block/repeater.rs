@@ -14,9 +14,10 @@ pub fn get_face(metadata: u8) -> Face {
     }
 }
 
-/// Set the face where the repeater send power.
+/// Set the face where the repeater send power, the face must be horizontal.
 #[inline]
 pub fn set_face(metadata: &mut u8, face: Face) {
+    debug_assert!(!face.is_y());
     *metadata &= !3;
     *metadata |= match face {
         Face::NegZ => 0,
@@ -33,9 +34,10 @@ pub fn get_delay(metadata: u8) -> u8 {
     (metadata & 0b1100) >> 2
 }
 
-/// Set the delay of the repeater.
+/// Set the delay of the repeater, from 0 to 3.
 #[inline]
 pub fn set_delay(metadata: &mut u8, delay: u8) {
+    debug_assert!(delay <= 3);
     *metadata &= !0b1100;
     *metadata |= (delay & 0b11) << 2;
 }
@@ -14,10 +14,12 @@ pub fn get_face(metadata: u8) -> Face {
     }
 }
 
+/// Set the face where the stair leads to, the face must be horizontal.
 #[inline]
 pub fn set_face(metadata: &mut u8, face: Face) {
+    debug_assert!(!face.is_y());
     *metadata &= !3;
-    *metadata = match face {
+    *metadata |= match face {
         Face::PosX => 0,
         Face::NegX => 1,
         Face::PosZ => 2,
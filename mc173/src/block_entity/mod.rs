@@ -2,6 +2,7 @@
 
 use glam::IVec3;
 
+use crate::block;
 use crate::world::World;
 
 pub mod chest;
@@ -27,6 +28,29 @@ pub enum BlockEntity {
 }
 
 impl BlockEntity {
+    /// Return true if this block entity needs to be ticked by the world, other block
+    /// entities are only modified by interactions and block changes.
+    pub fn needs_tick(&self) -> bool {
+        matches!(
+            self,
+            BlockEntity::Furnace(_) | BlockEntity::Spawner(_) | BlockEntity::Piston(_)
+        )
+    }
+
+    /// Return true if this block entity can be attached to the given block id.
+    pub fn is_valid_for(&self, id: u8) -> bool {
+        match self {
+            BlockEntity::Chest(_) => id == block::CHEST,
+            BlockEntity::Furnace(_) => matches!(id, block::FURNACE | block::FURNACE_LIT),
+            BlockEntity::Dispenser(_) => id == block::DISPENSER,
+            BlockEntity::Spawner(_) => id == block::SPAWNER,
+            BlockEntity::NoteBlock(_) => id == block::NOTE_BLOCK,
+            BlockEntity::Piston(_) => id == block::PISTON_MOVING,
+            BlockEntity::Sign(_) => matches!(id, block::SIGN | block::WALL_SIGN),
+            BlockEntity::Jukebox(_) => id == block::JUKEBOX,
+        }
+    }
+
     /// Tick the block entity at its position in the world.
     pub fn tick(&mut self, world: &mut World, pos: IVec3) {
         match self {
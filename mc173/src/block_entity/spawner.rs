@@ -4,9 +4,9 @@ use glam::{DVec3, IVec3};
 
 use tracing::trace;
 
-use crate::entity::{Entity, EntityKind};
+use crate::entity::{BaseKind, Entity, EntityKind};
 use crate::geom::BoundingBox;
-use crate::world::World;
+use crate::world::{SpawnReason, World};
 
 #[derive(Debug, Clone)]
 pub struct SpawnerBlockEntity {
@@ -78,9 +78,15 @@ impl SpawnerBlockEntity {
 
             let mut entity = self.entity_kind.new_default(pos);
             entity.0.look.x = rand.next_float();
+            if let Entity(_, BaseKind::Living(living, _)) = &mut *entity {
+                living.from_spawner = true;
+            }
 
-            if entity.can_natural_spawn(world) {
-                world.spawn_entity(entity);
+            if entity.can_natural_spawn(world)
+                && world
+                    .spawn_entity_with_reason(entity, SpawnReason::Spawner)
+                    .is_some()
+            {
                 same_count += 1;
             }
         }
@@ -83,13 +83,18 @@ pub struct Chunk {
     /// and therefore all blocks above also have sky light 15. The height must be in
     /// range 0..=128.
     pub height: ChunkArray2<u8>,
-    /// The biome map, this map is not actually saved nor sent to the client. It is
-    /// internally used by this implementation to really split the chunk generation from
-    /// the running world. The Notchian server is different because the mob spawning
-    /// algorithms requires the biome map to be generated at runtime. This also explains
-    /// why we can use a Rust enumeration for this one, and not raw value, because we
-    /// don't need to deserialize it and therefore don't risk any unwanted value.
+    /// The biome map, this map is not sent to the client. It is internally used by this
+    /// implementation to really split the chunk generation from the running world. The
+    /// Notchian server is different because the mob spawning algorithms requires the
+    /// biome map to be generated at runtime. This map is saved with the chunk by this
+    /// implementation, unknown biome ids are deserialized as void biome.
     pub biome: ChunkArray2<Biome>,
+    /// True when the chunk has been populated by features (trees, ores, plants...), this
+    /// is saved with the chunk data, chunks imported from other tools may not have been
+    /// populated, in which case [`World::populate_existing`] can be used.
+    ///
+    /// [`World::populate_existing`]: crate::world::World::populate_existing
+    pub populated: bool,
 }
 
 impl Chunk {
@@ -110,6 +115,7 @@ impl Chunk {
             sky_light: ChunkNibbleArray3::new(15),
             height: [0; CHUNK_2D_SIZE],
             biome: [Biome::Void; CHUNK_2D_SIZE],
+            populated: false,
         })
     }
 
@@ -183,6 +189,13 @@ impl Chunk {
         self.biome[calc_2d_index(pos)]
     }
 
+    /// Get the biome at the given X/Z position, this is equivalent to
+    /// [`get_biome`](Self::get_biome) without the Y component.
+    #[inline]
+    pub fn biome(&self, x: i32, z: i32) -> Biome {
+        self.get_biome(IVec3::new(x, 0, z))
+    }
+
     /// Set the biome at the given position, the Y component is ignored.
     #[inline]
     pub fn set_biome(&mut self, pos: IVec3, biome: Biome) {
@@ -284,6 +297,51 @@ impl Chunk {
         }
     }
 
+    /// Apply a batch of block changes to this chunk through the given accessor function.
+    /// Unlike [`set_block`](Self::set_block) followed by
+    /// [`recompute_height`](Self::recompute_height), the height of each modified column
+    /// is only recomputed once, when the batch ends.
+    pub fn apply<R>(&mut self, func: impl FnOnce(&mut ChunkAccessor) -> R) -> R {
+        self.apply_inner(None, func)
+    }
+
+    /// Internal function to apply a batch of block changes, if given, all changes are
+    /// recorded in the given vector.
+    pub(crate) fn apply_inner<R>(
+        &mut self,
+        changes: Option<&mut Vec<ChunkChange>>,
+        func: impl FnOnce(&mut ChunkAccessor) -> R,
+    ) -> R {
+        let mut accessor = ChunkAccessor {
+            chunk: self,
+            column_top: [0; CHUNK_2D_SIZE],
+            changes,
+        };
+
+        let ret = func(&mut accessor);
+
+        let ChunkAccessor { column_top, .. } = accessor;
+        for (index, top) in column_top.into_iter().enumerate() {
+            if top != 0 {
+                let pos = IVec3::new(index as i32 & 15, top as i32 - 1, index as i32 >> 4);
+                self.recompute_height(pos);
+            }
+        }
+
+        ret
+    }
+
+    /// Reset all light data of this chunk from its blocks: block light is set to zero,
+    /// the height map and the sky light of each column are recomputed. Like
+    /// [`recompute_all_height`](Self::recompute_all_height), light is not propagated.
+    pub fn reset_light(&mut self) {
+        self.block_light = ChunkNibbleArray3::new(0);
+        // Sky light is cleared and height is set to the top to force full recompute.
+        self.sky_light = ChunkNibbleArray3::new(0);
+        self.height = [CHUNK_HEIGHT as u8; CHUNK_2D_SIZE];
+        self.recompute_all_height();
+    }
+
     /// Write this chunk's data to the given writer, the data is copied from the start
     /// point for the given size. Note that this function may change the start and size
     /// of the area to be more efficient while while writing data.
@@ -353,6 +411,74 @@ impl Chunk {
     }
 }
 
+/// A batch accessor to the blocks of a chunk, see [`Chunk::apply`]. Like the chunk
+/// methods, positions are global but rebased to chunk-local.
+pub struct ChunkAccessor<'a> {
+    /// The chunk being modified.
+    chunk: &'a mut Chunk,
+    /// For each column, the Y position above the highest modified block, zero if the
+    /// column has not been modified.
+    column_top: ChunkArray2<u8>,
+    /// If recording, the block changes made by this accessor.
+    changes: Option<&'a mut Vec<ChunkChange>>,
+}
+
+impl ChunkAccessor<'_> {
+    /// Get block id and metadata at the given position.
+    /// Panics if Y component of the position is not between 0 and 128 (excluded).
+    #[inline]
+    pub fn get_block(&self, pos: IVec3) -> (u8, u8) {
+        self.chunk.get_block(pos)
+    }
+
+    /// Set block id and metadata at the given position, the previous block id and
+    /// metadata are returned.
+    /// Panics if Y component of the position is not between 0 and 128 (excluded).
+    #[inline]
+    pub fn set_block(&mut self, pos: IVec3, id: u8, metadata: u8) -> (u8, u8) {
+        let (prev_id, prev_metadata) = self.chunk.get_block(pos);
+        if id != prev_id || metadata != prev_metadata {
+            self.chunk.set_block(pos, id, metadata);
+
+            let top = &mut self.column_top[calc_2d_index(pos)];
+            *top = (*top).max(pos.y as u8 + 1);
+
+            if let Some(changes) = &mut self.changes {
+                changes.push(ChunkChange {
+                    pos: IVec3::new(pos.x & 15, pos.y, pos.z & 15),
+                    prev_id,
+                    prev_metadata,
+                    id,
+                    metadata,
+                });
+            }
+        }
+
+        (prev_id, prev_metadata)
+    }
+
+    /// Get the biome at the given position, the Y component is ignored.
+    #[inline]
+    pub fn get_biome(&self, pos: IVec3) -> Biome {
+        self.chunk.get_biome(pos)
+    }
+}
+
+/// A block change recorded by a [`ChunkAccessor`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ChunkChange {
+    /// The chunk-local position of the changed block.
+    pub pos: IVec3,
+    /// Block id before the change.
+    pub prev_id: u8,
+    /// Block metadata before the change.
+    pub prev_metadata: u8,
+    /// Block id after the change.
+    pub id: u8,
+    /// Block metadata after the change.
+    pub metadata: u8,
+}
+
 /// Type alias for a chunk array that stores `u8 * CHUNK_2D_SIZE` values.
 pub type ChunkArray2<T> = [T; CHUNK_2D_SIZE];
 
@@ -1,6 +1,9 @@
 //! Item crafting management.
 
+use std::borrow::Cow;
+
 use crate::block;
+use crate::data::{self, DataError};
 use crate::item::{self, ItemStack};
 
 /// This structure keeps track of the current crafting recipe selected and allows lazy
@@ -21,17 +24,7 @@ impl CraftTracker {
             return;
         }
 
-        for (recipe_index, recipe) in RECIPES.iter().enumerate() {
-            let item = match recipe {
-                Recipe::Shaped(shaped) => shaped.check(grid),
-                Recipe::Shapeless(shapeless) => shapeless.check(grid),
-            };
-
-            if let Some(item) = item {
-                self.current_recipe = Some((recipe_index, item));
-                break;
-            }
-        }
+        self.current_recipe = data::tables().recipes.find(grid);
     }
 
     /// If there is a selected recipe, consume the recipe items from the given inventory,
@@ -576,9 +569,141 @@ const RECIPES: &[Recipe] = &[
     dye_mix!(13 * 4, [4, 1, 1, 15]),
 ];
 
+/// A registry of crafting recipes, checked in registration order. The default registry
+/// contains all the Notchian recipes, the current registry is part of the
+/// [data tables](crate::data).
+#[derive(Debug, Clone)]
+pub struct RecipeRegistry {
+    /// All registered recipes, in registration order.
+    recipes: Vec<Recipe>,
+}
+
+impl Default for RecipeRegistry {
+    fn default() -> Self {
+        Self {
+            recipes: RECIPES.to_vec(),
+        }
+    }
+}
+
+impl RecipeRegistry {
+    /// Remove all the registered recipes.
+    pub fn clear(&mut self) {
+        self.recipes.clear();
+    }
+
+    /// Return the number of registered recipes.
+    pub fn len(&self) -> usize {
+        self.recipes.len()
+    }
+
+    /// Return true if there is no registered recipe.
+    pub fn is_empty(&self) -> bool {
+        self.recipes.is_empty()
+    }
+
+    /// Register a shaped recipe, the pattern is made of rows of the given width that can
+    /// be placed anywhere in the grid, and can be horizontally flipped. Empty stacks are
+    /// slots that should be empty. The pattern should fit in the 3x3 grid.
+    pub fn register_shaped(&mut self, result: ItemStack, pattern: Vec<ItemStack>, width: u8) {
+        debug_assert!((1..=3).contains(&width) && pattern.len() <= width as usize * 3);
+        self.recipes.push(Recipe::Shaped(ShapedRecipe {
+            result,
+            pattern: Cow::Owned(pattern),
+            width,
+        }));
+    }
+
+    /// Register a shapeless recipe, each stack of the pattern should be present once in
+    /// the grid, in any slot. The pattern should have at most 9 stacks.
+    pub fn register_shapeless(&mut self, result: ItemStack, pattern: Vec<ItemStack>) {
+        debug_assert!(pattern.len() <= 9);
+        self.recipes.push(Recipe::Shapeless(ShapelessRecipe {
+            result,
+            pattern: Cow::Owned(pattern),
+        }));
+    }
+
+    /// Find the first recipe that can be crafted with the given 3x3 grid of items,
+    /// returning its index and result item.
+    pub fn find(&self, grid: &[ItemStack; 9]) -> Option<(usize, ItemStack)> {
+        self.recipes
+            .iter()
+            .enumerate()
+            .find_map(|(recipe_index, recipe)| {
+                let item = match recipe {
+                    Recipe::Shaped(shaped) => shaped.check(grid),
+                    Recipe::Shapeless(shapeless) => shapeless.check(grid),
+                };
+                item.map(|item| (recipe_index, item))
+            })
+    }
+
+    /// Parse a recipe registry from its text format, starting from the default recipes.
+    /// Each non-empty line is a directive, `#` starts a comment:
+    ///
+    /// - `clear` to remove all the recipes registered so far;
+    /// - `shaped <result> <count> <width> <slot>...` to register a shaped recipe, `-`
+    ///   being an empty slot;
+    /// - `shapeless <result> <count> <item>...` to register a shapeless recipe.
+    ///
+    /// Items are written `<item>[:<damage>]`, with a numeric id, an item or block name.
+    pub fn parse(text: &str) -> Result<Self, DataError> {
+        let mut registry = Self::default();
+
+        data::parse_lines(text, |parts| {
+            match *parts {
+                ["clear"] => registry.clear(),
+                ["shaped", result, count, width, ref slots @ ..] if !slots.is_empty() => {
+                    let result = parse_result(result, count)?;
+                    let width = width
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|width| (1..=3).contains(width))
+                        .ok_or("invalid pattern width")?;
+                    if slots.len() % width as usize != 0 || slots.len() > width as usize * 3 {
+                        return Err("pattern doesn't fit the width");
+                    }
+                    let pattern = slots
+                        .iter()
+                        .map(|&slot| match slot {
+                            "-" => Ok(EMPTY),
+                            _ => data::parse_stack(slot),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    registry.register_shaped(result, pattern, width);
+                }
+                ["shapeless", result, count, ref items @ ..] if (1..=9).contains(&items.len()) => {
+                    let result = parse_result(result, count)?;
+                    let pattern = items
+                        .iter()
+                        .map(|&item| data::parse_stack(item))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    registry.register_shapeless(result, pattern);
+                }
+                _ => return Err("invalid directive"),
+            }
+            Ok(())
+        })?;
+
+        Ok(registry)
+    }
+}
+
+/// Parse the result stack of a recipe and its count.
+fn parse_result(result: &str, count: &str) -> Result<ItemStack, &'static str> {
+    let count = count
+        .parse::<u16>()
+        .ok()
+        .filter(|&count| count != 0)
+        .ok_or("invalid result count")?;
+    Ok(data::parse_stack(result)?.with_size(count))
+}
+
 /// The recipe enumeration stores different types of recipes.
 ///
 /// **Note that crafting recipes currently ignore the stack size in of patterns.**
+#[derive(Debug, Clone)]
 enum Recipe {
     /// A shaped crafting recipe requires the items to be in a specific pattern, the
     /// pattern has a size and if smaller than 3x3 it can be moved everywhere in the
@@ -589,28 +714,33 @@ enum Recipe {
     Shapeless(ShapelessRecipe),
 }
 
+#[derive(Debug, Clone)]
 struct ShapedRecipe {
     result: ItemStack,
-    pattern: &'static [ItemStack],
+    pattern: Cow<'static, [ItemStack]>,
     width: u8,
 }
 
+#[derive(Debug, Clone)]
 struct ShapelessRecipe {
     result: ItemStack,
-    pattern: &'static [ItemStack],
+    pattern: Cow<'static, [ItemStack]>,
 }
 
 impl Recipe {
     const fn new_shaped(result: ItemStack, pattern: &'static [ItemStack], width: u8) -> Self {
         Self::Shaped(ShapedRecipe {
             result,
-            pattern,
+            pattern: Cow::Borrowed(pattern),
             width,
         })
     }
 
     const fn new_shapeless(result: ItemStack, pattern: &'static [ItemStack]) -> Self {
-        Self::Shapeless(ShapelessRecipe { result, pattern })
+        Self::Shapeless(ShapelessRecipe {
+            result,
+            pattern: Cow::Borrowed(pattern),
+        })
     }
 }
 
@@ -621,7 +751,7 @@ impl ShapedRecipe {
         // Compute recipe size based on pattern length and width.
         // NOTE: We compute the height in which the pattern fit.
         let recipe_width = self.width as usize;
-        let recipe_height = (self.pattern.len() + recipe_width - 1) / recipe_width;
+        let recipe_height = self.pattern.len().div_ceil(recipe_width);
 
         // Recipe size cannot fit in the given inventory shape: discard immediately.
         // NOTE: This also avoids arithmetics underflow just below.
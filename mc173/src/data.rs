@@ -0,0 +1,189 @@
+//! Data tables of the game balance: crafting recipes, dungeon and bonus chest loot and
+//! natural spawn weights.
+//!
+//! The default tables reproduce the Notchian behavior. The current tables are shared by
+//! all worlds and can be replaced at any time with [`set_tables`], typically by a server
+//! that reloads tables tweaked by its owners. Each table can be parsed from a simple
+//! line-based text format, see [`RecipeRegistry::parse`], [`LootTable::parse`] and
+//! [`SpawnTable::parse`].
+
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use crate::biome::SpawnTable;
+use crate::craft::RecipeRegistry;
+use crate::gen::bonus::BonusChestGenerator;
+use crate::gen::dungeon::DungeonGenerator;
+use crate::item::ItemStack;
+use crate::loot::LootTable;
+use crate::{block, item};
+
+/// The current data tables, none until first accessed or set.
+static TABLES: RwLock<Option<Arc<DataTables>>> = RwLock::new(None);
+
+/// All the data tables used by the game logic.
+#[derive(Debug, Clone)]
+pub struct DataTables {
+    /// Crafting recipes.
+    pub recipes: RecipeRegistry,
+    /// Loot of the chests generated in dungeons.
+    pub dungeon_loot: LootTable,
+    /// Loot of the bonus chest generated near the spawn point.
+    pub bonus_chest_loot: LootTable,
+    /// Weights of entities naturally spawning in each biome.
+    pub spawns: SpawnTable,
+}
+
+impl Default for DataTables {
+    fn default() -> Self {
+        Self {
+            recipes: RecipeRegistry::default(),
+            dungeon_loot: DungeonGenerator::default_loot(),
+            bonus_chest_loot: BonusChestGenerator::default_loot(),
+            spawns: SpawnTable::default(),
+        }
+    }
+}
+
+/// Error while parsing a data table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataError {
+    /// The line number, starting at 1.
+    pub line: usize,
+    /// Description of the error.
+    pub message: &'static str,
+}
+
+/// Get the current data tables, the default ones if they have never been set.
+pub fn tables() -> Arc<DataTables> {
+    if let Some(tables) = &*TABLES.read().unwrap() {
+        return Arc::clone(tables);
+    }
+
+    let mut tables = TABLES.write().unwrap();
+    Arc::clone(tables.get_or_insert_with(Default::default))
+}
+
+/// Replace the current data tables, the previous tables are kept alive by users that
+/// are still holding them.
+pub fn set_tables(tables: DataTables) {
+    *TABLES.write().unwrap() = Some(Arc::new(tables));
+}
+
+/// Parse each directive line of a data table, `#` starts a comment and empty lines are
+/// ignored, the given function receives the whitespace-separated parts of each line.
+pub(crate) fn parse_lines(
+    text: &str,
+    mut func: impl FnMut(&[&str]) -> Result<(), &'static str>,
+) -> Result<(), DataError> {
+    for (index, line) in text.lines().enumerate() {
+        let line = line.split_once('#').map_or(line, |(line, _)| line);
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        if !parts.is_empty() {
+            func(&parts).map_err(|message| DataError {
+                line: index + 1,
+                message,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Parse a single item stack from its `<item>[:<damage>]` text format, the item being
+/// its numeric id, its item name or its block name.
+pub(crate) fn parse_stack(raw: &str) -> Result<ItemStack, &'static str> {
+    let (id_raw, damage_raw) = raw.split_once(':').unwrap_or((raw, "0"));
+
+    let id = if let Ok(id) = id_raw.parse::<u16>() {
+        id
+    } else if let Some(id) = item::from_name(id_raw) {
+        id
+    } else if let Some(id) = block::from_name(id_raw) {
+        id as u16
+    } else {
+        return Err("unknown item");
+    };
+
+    if item::try_from_id(id).is_none() {
+        return Err("unknown item");
+    }
+
+    let damage = damage_raw.parse().map_err(|_| "invalid item damage")?;
+    Ok(ItemStack::new_single(id, damage))
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for DataError {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::biome::Biome;
+    use crate::entity::{EntityCategory, EntityKind};
+    use crate::rand::JavaRandom;
+
+    #[test]
+    fn parse_tables() {
+        let recipes = RecipeRegistry::parse(
+            "clear # Only custom recipes.
+            shaped diamond 2 1 dirt dirt
+            shapeless stick 4 dirt:1",
+        )
+        .unwrap();
+        assert_eq!(recipes.len(), 2);
+
+        let mut grid = [ItemStack::EMPTY; 9];
+        grid[2] = ItemStack::new_block(block::DIRT, 0);
+        grid[5] = ItemStack::new_block(block::DIRT, 0);
+        assert_eq!(
+            recipes.find(&grid),
+            Some((0, ItemStack::new_sized(item::DIAMOND, 0, 2)))
+        );
+        grid[5] = ItemStack::EMPTY;
+        assert_eq!(recipes.find(&grid), None);
+        grid[2] = ItemStack::new_block(block::DIRT, 1);
+        assert_eq!(
+            recipes.find(&grid),
+            Some((1, ItemStack::new_sized(item::STICK, 0, 4)))
+        );
+
+        let loot = LootTable::parse("loot 1 1 2 2 diamond").unwrap();
+        assert_eq!(
+            loot.pick(&mut JavaRandom::new(0)),
+            ItemStack::new_sized(item::DIAMOND, 0, 2)
+        );
+
+        let spawns = SpawnTable::parse("spawn * mob zombie 0\nspawn forest mob ghast 5").unwrap();
+        let forest = spawns.get(Biome::Forest, EntityCategory::Mob);
+        assert!(forest
+            .iter()
+            .all(|natural| natural.kind != EntityKind::Zombie));
+        assert!(forest
+            .iter()
+            .any(|natural| natural.kind == EntityKind::Ghast && natural.chance == 5));
+        assert_eq!(spawns.get(Biome::Plains, EntityCategory::Mob).len(), 4);
+
+        assert_eq!(
+            RecipeRegistry::parse("shaped stick 1 2 dirt")
+                .unwrap_err()
+                .line,
+            1
+        );
+        assert_eq!(
+            LootTable::parse("\nloot 1 1 1 1 nothing").unwrap_err().line,
+            2
+        );
+        assert_eq!(
+            SpawnTable::parse("spawn moon animal pig 1")
+                .unwrap_err()
+                .line,
+            1
+        );
+    }
+}
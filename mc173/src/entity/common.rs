@@ -7,7 +7,7 @@ use glam::{DVec3, IVec3, Vec2, Vec3Swizzles};
 
 use crate::block;
 use crate::block::material::Material;
-use crate::geom::{BoundingBox, Face};
+use crate::geom::BoundingBox;
 use crate::world::bound::RayTraceKind;
 use crate::world::{Light, World};
 
@@ -41,6 +41,25 @@ pub fn calc_eye_pos(base: &Base) -> DVec3 {
     pos
 }
 
+/// Return true if the eye of the given entity is inside the given fluid material.
+///
+/// REF: Entity::isInsideOfMaterial
+pub fn is_eye_in_fluid(world: &World, base: &Base, material: Material) -> bool {
+    debug_assert!(material.is_fluid());
+    let eye_pos = calc_eye_pos(base);
+    let block_pos = eye_pos.floor().as_ivec3();
+    let Some((block, metadata)) = world.get_block(block_pos) else {
+        return false;
+    };
+
+    if block::material::get_material(block) != material {
+        return false;
+    }
+
+    let height = block::fluid::get_actual_height(metadata) as f64 + 1.0 / 9.0;
+    eye_pos.y < block_pos.y as f64 + height
+}
+
 /// Return true if the given bounding box is colliding with any fluid (given material).
 pub fn has_fluids_colliding(world: &World, bb: BoundingBox, material: Material) -> bool {
     debug_assert!(material.is_fluid());
@@ -54,43 +73,6 @@ pub fn has_fluids_colliding(world: &World, bb: BoundingBox, material: Material)
         })
 }
 
-/// Calculate the velocity of a fluid at given position, this depends on neighbor blocks.
-/// This calculation will only take the given material into account, this material should
-/// be a fluid material (water/lava), and the given metadata should be the one of the
-/// current block the the position.
-pub fn calc_fluid_vel(world: &World, pos: IVec3, material: Material, metadata: u8) -> DVec3 {
-    debug_assert!(material.is_fluid());
-
-    let distance = block::fluid::get_actual_distance(metadata);
-    let mut vel = DVec3::ZERO;
-
-    for face in Face::HORIZONTAL {
-        let face_delta = face.delta();
-        let face_pos = pos + face_delta;
-        let (face_block, face_metadata) = world.get_block(face_pos).unwrap_or_default();
-        let face_material = block::material::get_material(face_block);
-
-        if face_material == material {
-            let face_distance = block::fluid::get_actual_distance(face_metadata);
-            let delta = face_distance as i32 - distance as i32;
-            vel += (face_delta * delta).as_dvec3();
-        } else if !face_material.is_solid() {
-            let below_pos = face_pos - IVec3::Y;
-            let (below_block, below_metadata) = world.get_block(below_pos).unwrap_or_default();
-            let below_material = block::material::get_material(below_block);
-            if below_material == material {
-                let below_distance = block::fluid::get_actual_distance(below_metadata);
-                let delta = below_distance as i32 - (distance as i32 - 8);
-                vel += (face_delta * delta).as_dvec3();
-            }
-        }
-    }
-
-    // TODO: Things with falling water.
-
-    vel.normalize()
-}
-
 /// Calculate the light levels for an entity given its base component.
 pub fn get_entity_light(world: &World, base: &Base) -> Light {
     let mut check_pos = base.bb.min;
@@ -151,6 +133,14 @@ pub fn update_knock_back(base: &mut Base, dir: DVec3) {
     base.vel.y = base.vel.y.min(0.4);
 }
 
+/// Return the damage dealt to a living entity landing after falling the given distance,
+/// the first 3 blocks are free.
+///
+/// REF: EntityLiving::fall
+pub fn calc_fall_damage(fall_distance: f32) -> u16 {
+    (fall_distance - 3.0).ceil().max(0.0) as u16
+}
+
 /// Return true if the entity can eye track the target entity, this use ray tracing.
 pub fn can_eye_track(world: &World, base: &Base, target_base: &Base) -> bool {
     let origin = calc_eye_pos(base);
@@ -184,18 +174,18 @@ fn path_weight_animal(world: &World, pos: IVec3) -> f32 {
     if world.is_block(pos - IVec3::Y, block::GRASS) {
         10.0
     } else {
-        world.get_light(pos).brightness() - 0.5
+        world.get_brightness(pos) - 0.5
     }
 }
 
 /// Path weight function for mobs.
 fn path_weight_mob(world: &World, pos: IVec3) -> f32 {
-    0.5 - world.get_light(pos).brightness()
+    0.5 - world.get_brightness(pos)
 }
 
 /// Path weight function for Giant.
 fn path_weight_giant(world: &World, pos: IVec3) -> f32 {
-    world.get_light(pos).brightness() - 0.5
+    world.get_brightness(pos) - 0.5
 }
 
 /// Path weight function by default.
@@ -0,0 +1,107 @@
+//! Component-style access to entity data.
+//!
+//! Entities are stored as nested enumerations, which is efficient but requires matching
+//! the whole hierarchy to access a specific data. The [`Component`] trait is implemented
+//! for every data structure of the hierarchy (except the [`Base`] that is always present)
+//! and allows accessing it directly from an entity, for example `entity.get::<Living>()`
+//! or `world.iter_entities_with::<Living>()`.
+
+use super::*;
+
+/// A data structure that can be found in an entity's kind.
+pub trait Component: Sized {
+    /// Get a reference to this component from an entity's base kind, if present.
+    fn from_kind(kind: &BaseKind) -> Option<&Self>;
+
+    /// Get a mutable reference to this component from an entity's base kind, if present.
+    fn from_kind_mut(kind: &mut BaseKind) -> Option<&mut Self>;
+}
+
+/// Implement the component trait from a pattern binding the component to the given name.
+macro_rules! impl_component {
+    ( $( $ty:ty => |$c:ident| $pat:pat ),* $(,)? ) => {
+        $(
+            impl Component for $ty {
+                #[inline]
+                fn from_kind(kind: &BaseKind) -> Option<&Self> {
+                    match kind {
+                        $pat => Some($c),
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+
+                #[inline]
+                fn from_kind_mut(kind: &mut BaseKind) -> Option<&mut Self> {
+                    match kind {
+                        $pat => Some($c),
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_component! {
+    Item => |c| BaseKind::Item(c),
+    Painting => |c| BaseKind::Painting(c),
+    Boat => |c| BaseKind::Boat(c),
+    Minecart => |c| BaseKind::Minecart(c),
+    LightningBolt => |c| BaseKind::LightningBolt(c),
+    FallingBlock => |c| BaseKind::FallingBlock(c),
+    Tnt => |c| BaseKind::Tnt(c),
+    Projectile => |c| BaseKind::Projectile(c, _),
+    ProjectileKind => |c| BaseKind::Projectile(_, c),
+    Arrow => |c| BaseKind::Projectile(_, ProjectileKind::Arrow(c)),
+    Egg => |c| BaseKind::Projectile(_, ProjectileKind::Egg(c)),
+    Fireball => |c| BaseKind::Projectile(_, ProjectileKind::Fireball(c)),
+    Snowball => |c| BaseKind::Projectile(_, ProjectileKind::Snowball(c)),
+    Bobber => |c| BaseKind::Projectile(_, ProjectileKind::Bobber(c)),
+    Living => |c| BaseKind::Living(c, _),
+    LivingKind => |c| BaseKind::Living(_, c),
+    Human => |c| BaseKind::Living(_, LivingKind::Human(c)),
+    Ghast => |c| BaseKind::Living(_, LivingKind::Ghast(c)),
+    Slime => |c| BaseKind::Living(_, LivingKind::Slime(c)),
+    Pig => |c| BaseKind::Living(_, LivingKind::Pig(c)),
+    Chicken => |c| BaseKind::Living(_, LivingKind::Chicken(c)),
+    Cow => |c| BaseKind::Living(_, LivingKind::Cow(c)),
+    Sheep => |c| BaseKind::Living(_, LivingKind::Sheep(c)),
+    Squid => |c| BaseKind::Living(_, LivingKind::Squid(c)),
+    Wolf => |c| BaseKind::Living(_, LivingKind::Wolf(c)),
+    Creeper => |c| BaseKind::Living(_, LivingKind::Creeper(c)),
+    Giant => |c| BaseKind::Living(_, LivingKind::Giant(c)),
+    PigZombie => |c| BaseKind::Living(_, LivingKind::PigZombie(c)),
+    Skeleton => |c| BaseKind::Living(_, LivingKind::Skeleton(c)),
+    Spider => |c| BaseKind::Living(_, LivingKind::Spider(c)),
+    Zombie => |c| BaseKind::Living(_, LivingKind::Zombie(c)),
+}
+
+impl Entity {
+    /// Get a reference to the given component of this entity, if present.
+    #[inline]
+    pub fn get<C: Component>(&self) -> Option<&C> {
+        C::from_kind(&self.1)
+    }
+
+    /// Get a mutable reference to the given component of this entity, if present.
+    #[inline]
+    pub fn get_mut<C: Component>(&mut self) -> Option<&mut C> {
+        C::from_kind_mut(&mut self.1)
+    }
+
+    /// Get a mutable reference to the entity base and to the given component of this
+    /// entity, if present.
+    #[inline]
+    pub fn get_with_base_mut<C: Component>(&mut self) -> Option<(&mut Base, &mut C)> {
+        let Entity(base, base_kind) = self;
+        Some((base, C::from_kind_mut(base_kind)?))
+    }
+
+    /// Return true if this entity has the given component.
+    #[inline]
+    pub fn has<C: Component>(&self) -> bool {
+        self.get::<C>().is_some()
+    }
+}
@@ -11,12 +11,19 @@ use crate::util::default as def;
 use crate::world::World;
 
 pub mod common;
+mod component;
+pub mod sound;
+
+pub use component::Component;
 
 mod tick;
 mod tick_ai;
 mod tick_attack;
 mod tick_state;
 
+#[cfg(test)]
+mod trajectory;
+
 use tick_ai::tick_ai;
 use tick_attack::tick_attack;
 use tick_state::tick_state;
@@ -56,7 +63,7 @@ pub enum EntityKind {
 
 /// Category of entity enumeration, this defines various common properties for groups of
 /// entities, such as natural spawning properties.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EntityCategory {
     /// All animal entities.
     Animal = 0,
@@ -161,6 +168,8 @@ pub struct Base {
     pub no_clip: bool,
     /// Is this entity currently on ground.
     pub on_ground: bool,
+    /// Set to true if the entity collided on X or Z axis on its last move.
+    pub collided_horizontally: bool,
     /// Is this entity in water.
     pub in_water: bool,
     /// Is this entity in lava.
@@ -175,8 +184,13 @@ pub struct Base {
     pub hurt: Vec<Hurt>,
     /// If this entity is ridden, this contains its entity id.
     pub rider_id: Option<u32>,
+    /// If this entity is riding another entity, this contains the vehicle entity id.
+    pub vehicle_id: Option<u32>,
     /// If this entity has thrown a bobber for fishing, this contains its entity id.
     pub bobber_id: Option<u32>,
+    /// The display name of this entity, if named. This is distinct from the username
+    /// of player entities, and is used to name pets or label non-player characters.
+    pub name: Option<String>,
     /// The random number generator used for this entity.
     pub rand: JavaRandom,
 }
@@ -198,6 +212,10 @@ pub struct Living {
     /// an entity is despawned when too far from the closest player (maximum distance of
     /// 128.0 blocks).
     pub artificial: bool,
+    /// Set to true if the entity has been spawned by a mob spawner, such entities are
+    /// not counted in the mob caps if [disabled](crate::world::World::set_spawner_mobs_capped).
+    /// This is not persisted, so spawner entities are counted again after reloading.
+    pub from_spawner: bool,
     /// The health.
     pub health: u16,
     /// The last damage inflicted to the entity during `hurt_time`, this is used to only
@@ -210,6 +228,9 @@ pub struct Living {
     /// The death timer, increasing each tick when no health, after 20 ticks the entity
     /// is definitely removed from the world.
     pub death_time: u16,
+    /// The ambient sound timer, increasing each tick, the higher it is the more likely
+    /// an ambient sound is played, see [`sound::get_ambient_sound`].
+    pub ambient_time: i16,
     /// The strafing acceleration.
     pub accel_strafing: f32,
     /// The forward acceleration.
@@ -263,6 +284,28 @@ pub struct Item {
     pub health: u16,
     /// Remaining time for this item to be picked up by entities that have `can_pickup`.
     pub frozen_time: u32,
+    /// The entity id of the entity that has thrown this item, if any.
+    pub owner_id: Option<u32>,
+    /// Remaining time for this item to be picked up by its owner, this is counted
+    /// after the frozen time.
+    pub owner_time: u32,
+}
+
+impl Item {
+    /// The frozen time of items thrown by players.
+    /// REF: EntityPlayer::dropPlayerItemWithRandomChoice
+    pub const THROWN_FROZEN_TIME: u32 = 40;
+    /// The time during which a thrown item cannot be picked up by its owner, after its
+    /// frozen time.
+    /// PARITY: The Notchian server has no owner exclusion, the thrower can pick up the
+    ///  item as soon as it is no longer frozen.
+    pub const THROWN_OWNER_TIME: u32 = 60;
+
+    /// Return true if this item can be picked up by the given entity id.
+    #[inline]
+    pub fn can_pickup(&self, entity_id: u32) -> bool {
+        self.frozen_time == 0 && (self.owner_time == 0 || self.owner_id != Some(entity_id))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -289,7 +332,11 @@ impl Default for Painting {
 }
 
 #[derive(Debug, Clone, Default)]
-pub struct Boat {}
+pub struct Boat {
+    /// The damage taken by the boat, the boat breaks when it goes above 40, each hit
+    /// adds 10 times its damage and it decreases by one every tick.
+    pub damage: u16,
+}
 
 #[derive(Debug, Clone, Default)]
 pub enum Minecart {
@@ -320,8 +367,23 @@ pub struct Bobber {
     pub catch_time: u16,
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct LightningBolt {}
+#[derive(Debug, Clone)]
+pub struct LightningBolt {
+    /// The strike state, entities around are struck while it is positive or zero, it
+    /// decrements on each tick and the bolt may strike again when it becomes negative.
+    pub state: i8,
+    /// Number of remaining strikes after the first one, randomized on the first tick.
+    pub remaining_strikes: u8,
+}
+
+impl Default for LightningBolt {
+    fn default() -> Self {
+        Self {
+            state: 2,
+            remaining_strikes: 0,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Default)]
 pub struct FallingBlock {
@@ -362,6 +424,39 @@ pub struct Human {
     pub sleeping: bool,
     /// True when the player is sneaking.
     pub sneaking: bool,
+    /// Remaining ticks of invulnerability, damages dealt by other entities are ignored
+    /// while not zero, this is typically set after respawning.
+    pub invulnerable_time: u16,
+    /// Remaining ticks of the combat tag, set when damaged by another player.
+    pub combat_time: u16,
+    /// The entity id of the last player that damaged this player, while tagged.
+    pub combat_attacker: Option<u32>,
+    /// The armor worn by the player, from helmet to boots, reducing the damages.
+    pub armor: [ItemStack; 4],
+    /// The damage that has not been dealt because of the armor reduction, accumulated
+    /// with the next damage.
+    pub damage_remainder: u16,
+}
+
+impl Human {
+    /// The invulnerability time given to players after respawning, in ticks.
+    pub const SPAWN_INVULNERABLE_TIME: u16 = 60;
+    /// The time a player stays tagged in combat after being damaged by another player,
+    /// in ticks.
+    pub const COMBAT_TAG_TIME: u16 = 300;
+
+    /// Return true if the player is currently invulnerable to other entities.
+    #[inline]
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_time != 0
+    }
+
+    /// Return the entity id of the player that recently damaged this player, if the
+    /// player is still tagged in combat.
+    #[inline]
+    pub fn get_combat_attacker(&self) -> Option<u32> {
+        self.combat_attacker.filter(|_| self.combat_time != 0)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -383,6 +478,15 @@ pub struct Slime {
     pub jump_remaining_time: u32,
 }
 
+impl Slime {
+    /// Get the actual size of the slime, as in the Notchian implementation, this is also
+    /// its attack damage and the square root of its initial health.
+    #[inline]
+    pub fn real_size(&self) -> u16 {
+        self.size as u16 + 1
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Pig {
     /// True when the pig has a saddle.
@@ -391,7 +495,7 @@ pub struct Pig {
 
 #[derive(Debug, Clone, Default)]
 pub struct Chicken {
-    /// Ticks remaining until this chicken lays an egg.
+    /// Ticks remaining until this chicken lays an egg, zero if not yet initialized.
     pub next_egg_ticks: u32,
 }
 
@@ -402,6 +506,8 @@ pub struct Cow {}
 pub struct Sheep {
     pub sheared: bool,
     pub color: u8, // TODO: Color enumeration.
+    /// Ticks remaining until the sheep has finished grazing, zero if not grazing.
+    pub graze_time: u8,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -414,8 +520,11 @@ pub struct Squid {
 
 #[derive(Debug, Clone, Default)]
 pub struct Wolf {
+    /// True when the wolf has been hurt by a player, it then attacks nearby players.
     pub angry: bool,
+    /// True when the wolf is sitting, it no longer searches for paths.
     pub sitting: bool,
+    /// The username of the owner player, when tamed.
     pub owner: Option<String>,
 }
 
@@ -423,8 +532,16 @@ pub struct Wolf {
 pub struct Creeper {
     /// True when the creeper is powered.
     pub powered: bool,
-    /// Set to some time when the creeper is ignited.
-    pub ignited_time: Option<u16>,
+    /// True when the creeper is ignited and swelling toward its explosion.
+    pub ignited: bool,
+    /// Ticks of swelling, the creeper explodes when it reaches
+    /// [`FUSE_TIME`](Self::FUSE_TIME), it shrinks back when no longer ignited.
+    pub fuse_time: u16,
+}
+
+impl Creeper {
+    /// The swelling time for a creeper to explode, in ticks.
+    pub const FUSE_TIME: u16 = 30;
 }
 
 #[derive(Debug, Clone, Default)]
@@ -432,7 +549,22 @@ pub struct Giant {}
 
 #[derive(Debug, Clone, Default)]
 pub struct PigZombie {
-    pub anger: bool,
+    /// Remaining time in ticks of the pig zombie anger, it only attacks players while
+    /// this is non-zero.
+    pub anger: u16,
+    /// Countdown before playing the angry sound, zero when no sound is pending.
+    pub sound_delay: u8,
+}
+
+impl PigZombie {
+    /// Make this pig zombie angry against the given target entity, for a random time.
+    ///
+    /// REF: EntityPigZombie::becomeAngryAt
+    pub fn become_angry(&mut self, base: &mut Base, living: &mut Living, target_id: u32) {
+        living.attack_target = Some(target_id);
+        self.anger = 400 + base.rand.next_int_bounded(400) as u16;
+        self.sound_delay = base.rand.next_int_bounded(40) as u8;
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -782,7 +914,7 @@ impl Entity {
     /// Initialize this entity for natural spawn, for example this randomize the slime
     /// size or sheep color or make a spider with jokey.
     pub fn init_natural_spawn(&mut self, _world: &mut World) {
-        let Entity(base, BaseKind::Living(_, living_kind)) = self else {
+        let Entity(base, BaseKind::Living(living, living_kind)) = self else {
             // Non-living entities cannot naturally spawn.
             return;
         };
@@ -791,9 +923,10 @@ impl Entity {
 
         match living_kind {
             LivingKind::Slime(slime) => {
-                slime.size = 1 << base.rand.next_int_bounded(3) as u8;
+                // REF: EntitySlime::setSlimeSize
+                slime.size = (1 << base.rand.next_int_bounded(3) as u8) - 1;
+                living.health = slime.real_size().pow(2);
                 self.sync();
-                // TODO: Set health depending on size
             }
             LivingKind::Sheep(sheep) => {
                 let rand = base.rand.next_int_bounded(100) as u8;
@@ -865,6 +998,78 @@ impl ProjectileKind {
 }
 
 impl EntityKind {
+    /// Array of all entity kinds.
+    pub const ALL: [Self; 27] = [
+        EntityKind::Item,
+        EntityKind::Painting,
+        EntityKind::Boat,
+        EntityKind::Minecart,
+        EntityKind::Bobber,
+        EntityKind::LightningBolt,
+        EntityKind::FallingBlock,
+        EntityKind::Tnt,
+        EntityKind::Arrow,
+        EntityKind::Egg,
+        EntityKind::Fireball,
+        EntityKind::Snowball,
+        EntityKind::Human,
+        EntityKind::Ghast,
+        EntityKind::Slime,
+        EntityKind::Pig,
+        EntityKind::Chicken,
+        EntityKind::Cow,
+        EntityKind::Sheep,
+        EntityKind::Squid,
+        EntityKind::Wolf,
+        EntityKind::Creeper,
+        EntityKind::Giant,
+        EntityKind::PigZombie,
+        EntityKind::Skeleton,
+        EntityKind::Spider,
+        EntityKind::Zombie,
+    ];
+
+    /// Get the name of this entity kind, in snake case, such as `pig_zombie`.
+    pub fn name(self) -> &'static str {
+        match self {
+            EntityKind::Item => "item",
+            EntityKind::Painting => "painting",
+            EntityKind::Boat => "boat",
+            EntityKind::Minecart => "minecart",
+            EntityKind::Bobber => "bobber",
+            EntityKind::LightningBolt => "lightning_bolt",
+            EntityKind::FallingBlock => "falling_block",
+            EntityKind::Tnt => "tnt",
+            EntityKind::Arrow => "arrow",
+            EntityKind::Egg => "egg",
+            EntityKind::Fireball => "fireball",
+            EntityKind::Snowball => "snowball",
+            EntityKind::Human => "human",
+            EntityKind::Ghast => "ghast",
+            EntityKind::Slime => "slime",
+            EntityKind::Pig => "pig",
+            EntityKind::Chicken => "chicken",
+            EntityKind::Cow => "cow",
+            EntityKind::Sheep => "sheep",
+            EntityKind::Squid => "squid",
+            EntityKind::Wolf => "wolf",
+            EntityKind::Creeper => "creeper",
+            EntityKind::Giant => "giant",
+            EntityKind::PigZombie => "pig_zombie",
+            EntityKind::Skeleton => "skeleton",
+            EntityKind::Spider => "spider",
+            EntityKind::Zombie => "zombie",
+        }
+    }
+
+    /// Get an entity kind from its name, see [`name`](Self::name), the name is case
+    /// insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+
     /// Create a new default entity instance from the given type.
     pub fn new_default(self, pos: DVec3) -> Box<Entity> {
         match self {
@@ -0,0 +1,145 @@
+//! Sounds emitted by entities, such as hurt, death and ambient sounds.
+//!
+//! PARITY: With the Notchian client, hurt and death sounds are played by the client
+//! itself when receiving the entity status, and ambient sounds are also played by its
+//! own client-side timer, there is no packet in this version to play a named sound.
+
+use super::{BaseKind, Entity, EntityKind, LivingKind};
+
+/// Interval in ticks between two ambient sounds, the timer is reset to the opposite
+/// value when an ambient sound has been played.
+pub const AMBIENT_INTERVAL: i16 = 80;
+
+/// The table of sounds emitted by an entity kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SoundTable {
+    /// The sound played randomly while the entity is living, none if silent. Wolves
+    /// have variants of this sound, see [`get_ambient_sound`].
+    pub ambient: Option<&'static str>,
+    /// The sound played when the entity is hurt.
+    pub hurt: Option<&'static str>,
+    /// The sound played when the entity dies.
+    pub death: Option<&'static str>,
+    /// True if the entity plays the step sound of the block it is walking on.
+    pub step: bool,
+}
+
+impl SoundTable {
+    /// Internal constructor for living entities.
+    const fn living(
+        ambient: Option<&'static str>,
+        hurt: Option<&'static str>,
+        death: Option<&'static str>,
+    ) -> Self {
+        Self {
+            ambient,
+            hurt,
+            death,
+            step: true,
+        }
+    }
+}
+
+/// Get the sound table of the given entity kind.
+///
+/// REF:
+/// - EntityLiving::getLivingSound
+/// - EntityLiving::getHurtSound
+/// - EntityLiving::getDeathSound
+/// - Entity::canTriggerWalking
+pub fn get_table(kind: EntityKind) -> SoundTable {
+    const HURT: Option<&str> = Some("random.hurt");
+    match kind {
+        EntityKind::Human | EntityKind::Giant => SoundTable::living(None, HURT, HURT),
+        EntityKind::Ghast => SoundTable::living(
+            Some("mob.ghast.moan"),
+            Some("mob.ghast.scream"),
+            Some("mob.ghast.death"),
+        ),
+        EntityKind::Slime => SoundTable::living(None, Some("mob.slime"), Some("mob.slime")),
+        EntityKind::Pig => {
+            SoundTable::living(Some("mob.pig"), Some("mob.pig"), Some("mob.pigdeath"))
+        }
+        EntityKind::Chicken => SoundTable::living(
+            Some("mob.chicken"),
+            Some("mob.chickenhurt"),
+            Some("mob.chickenhurt"),
+        ),
+        EntityKind::Cow => {
+            SoundTable::living(Some("mob.cow"), Some("mob.cowhurt"), Some("mob.cowhurt"))
+        }
+        EntityKind::Sheep => {
+            SoundTable::living(Some("mob.sheep"), Some("mob.sheep"), Some("mob.sheep"))
+        }
+        EntityKind::Squid => SoundTable::living(None, None, None),
+        EntityKind::Wolf => SoundTable::living(
+            Some("mob.wolf.bark"),
+            Some("mob.wolf.hurt"),
+            Some("mob.wolf.death"),
+        ),
+        EntityKind::Creeper => {
+            SoundTable::living(None, Some("mob.creeper"), Some("mob.creeperdeath"))
+        }
+        EntityKind::PigZombie => SoundTable::living(
+            Some("mob.zombiepig.zpig"),
+            Some("mob.zombiepig.zpighurt"),
+            Some("mob.zombiepig.zpigdeath"),
+        ),
+        EntityKind::Skeleton => SoundTable::living(
+            Some("mob.skeleton"),
+            Some("mob.skeletonhurt"),
+            Some("mob.skeletonhurt"),
+        ),
+        EntityKind::Spider => SoundTable::living(
+            Some("mob.spider"),
+            Some("mob.spider"),
+            Some("mob.spiderdeath"),
+        ),
+        EntityKind::Zombie => SoundTable::living(
+            Some("mob.zombie"),
+            Some("mob.zombiehurt"),
+            Some("mob.zombiedeath"),
+        ),
+        _ => SoundTable::default(),
+    }
+}
+
+/// Get the ambient sound to play for the given entity, this is the ambient sound of its
+/// kind's table, except for wolves that may growl, whine or pant depending on their
+/// state, the entity's random generator is used for that choice.
+///
+/// REF: EntityWolf::getLivingSound
+pub fn get_ambient_sound(entity: &mut Entity) -> Option<&'static str> {
+    match entity {
+        Entity(base, BaseKind::Living(living, LivingKind::Wolf(wolf))) => Some(if wolf.angry {
+            "mob.wolf.growl"
+        } else if base.rand.next_int_bounded(3) == 0 {
+            if wolf.owner.is_some() && living.health < 10 {
+                "mob.wolf.whine"
+            } else {
+                "mob.wolf.panting"
+            }
+        } else {
+            "mob.wolf.bark"
+        }),
+        _ => get_table(entity.kind()).ambient,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn sound_table() {
+        let zombie = get_table(EntityKind::Zombie);
+        assert_eq!(zombie.ambient, Some("mob.zombie"));
+        assert_eq!(zombie.death, Some("mob.zombiedeath"));
+        assert!(zombie.step);
+
+        assert_eq!(get_table(EntityKind::Creeper).ambient, None);
+        assert_eq!(get_table(EntityKind::Human).hurt, Some("random.hurt"));
+        assert_eq!(get_table(EntityKind::Arrow), SoundTable::default());
+    }
+}
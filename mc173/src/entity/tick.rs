@@ -14,11 +14,11 @@ use tracing::trace;
 
 use crate::block;
 use crate::block::material::Material;
-use crate::entity::Chicken;
+use crate::entity::{Chicken, PigZombie};
 use crate::geom::{BoundingBox, Face};
 use crate::item::{self, ItemStack};
 use crate::world::bound::RayTraceKind;
-use crate::world::{EntityEvent, Event, World};
+use crate::world::{Difficulty, Effect, EntityEvent, Event, LocalWeather, SpawnReason, World};
 
 use super::{Base, BaseKind, Entity, Hurt, Living, LivingKind, ProjectileHit, ProjectileKind};
 
@@ -48,12 +48,15 @@ pub(super) fn tick(world: &mut World, id: u32, entity: &mut Entity) {
         Entity(_, BaseKind::Painting(_)) => tick_painting(world, id, entity),
         Entity(_, BaseKind::FallingBlock(_)) => tick_falling_block(world, id, entity),
         Entity(_, BaseKind::Tnt(_)) => tick_tnt(world, id, entity),
+        Entity(_, BaseKind::Boat(_)) => tick_boat(world, id, entity),
         Entity(_, BaseKind::Living(_, _)) => tick_living(world, id, entity),
         Entity(_, BaseKind::Projectile(_, _)) => tick_projectile(world, id, entity),
         Entity(_, BaseKind::LightningBolt(_)) => tick_lightning_bolt(world, id, entity),
         Entity(_, _) => tick_base(world, id, entity),
     }
 
+    tick_riding(world, id, entity);
+
     // Finally check all major changes and push events if needed.
     let Entity(base, _) = entity;
 
@@ -79,6 +82,49 @@ pub(super) fn tick(world: &mut World, id: u32, entity: &mut Entity) {
     }
 }
 
+/// Update the links between this entity and its rider or vehicle, riders are moved on
+/// top of their vehicle and links are removed if the other entity no longer exists.
+///
+/// REF: Entity::updateRidden, Entity::updateRiderPosition
+fn tick_riding(world: &mut World, id: u32, entity: &mut Entity) {
+    if let Some(vehicle_id) = entity.0.vehicle_id {
+        match world.get_entity(vehicle_id) {
+            Some(Entity(vehicle_base, _)) if vehicle_base.rider_id == Some(id) => {
+                let pos = vehicle_base.pos + DVec3::new(0.0, vehicle_base.bb.size_y() * 0.75, 0.0);
+                entity.0.vel = DVec3::ZERO;
+                entity.0.fall_distance = 0.0;
+                entity.teleport(pos);
+            }
+            _ => {
+                entity.0.vehicle_id = None;
+                world.push_event(Event::Entity {
+                    id,
+                    inner: EntityEvent::Ride { vehicle_id: None },
+                });
+            }
+        }
+    }
+
+    let Entity(base, _) = entity;
+    if let Some(rider_id) = base.rider_id {
+        let rider_pos = base.pos + DVec3::new(0.0, base.bb.size_y() * 0.75, 0.0);
+        match world.get_entity_mut(rider_id) {
+            Some(rider) if rider.0.vehicle_id == Some(id) => {
+                rider.0.vel = DVec3::ZERO;
+                rider.0.fall_distance = 0.0;
+                if rider.0.pos != rider_pos {
+                    rider.teleport(rider_pos);
+                    world.push_event(Event::Entity {
+                        id: rider_id,
+                        inner: EntityEvent::Position { pos: rider_pos },
+                    });
+                }
+            }
+            _ => base.rider_id = None,
+        }
+    }
+}
+
 /// REF: Entity::onUpdate
 fn tick_base(world: &mut World, id: u32, entity: &mut Entity) {
     tick_state(world, id, entity);
@@ -91,6 +137,8 @@ fn tick_item(world: &mut World, id: u32, entity: &mut Entity) {
 
     if item.frozen_time > 0 {
         item.frozen_time -= 1;
+    } else if item.owner_time > 0 {
+        item.owner_time -= 1;
     }
 
     // Update item velocity.
@@ -150,7 +198,7 @@ fn tick_item(world: &mut World, id: u32, entity: &mut Entity) {
 
         if let Some((ground_id, _)) = world.get_block(ground_pos) {
             if ground_id != block::AIR {
-                slipperiness = block::material::get_slipperiness(ground_id);
+                slipperiness = block::material::get_slipperiness(ground_id) * 0.98;
             }
         }
     }
@@ -164,8 +212,27 @@ fn tick_item(world: &mut World, id: u32, entity: &mut Entity) {
         base.vel.y *= -0.5;
     }
 
-    // Kill the item self after 5 minutes (5 * 60 * 20).
-    if base.lifetime >= 6000 {
+    // Apply damages to the item, fire and lava are destroying items in a few ticks.
+    // REF: EntityItem::attackEntityFrom
+    // PARITY: No item is immune to fire in this version, only items in water will
+    //  not catch fire.
+    while let Some(hurt) = base.hurt.pop() {
+        item.health = item.health.saturating_sub(hurt.damage);
+    }
+
+    if item.health == 0 {
+        if base.in_lava || base.fire_time > 0 {
+            // Fizz sound followed by smoke particles centered on the block.
+            let pos = base.pos.floor().as_ivec3();
+            world.play_effect(pos, Effect::Fizz);
+            world.play_effect(pos, Effect::Smoke(None));
+        }
+        world.remove_entity(id, "item burned");
+        return;
+    }
+
+    // Kill the item self after its lifetime, 5 minutes (5 * 60 * 20) by default.
+    if base.lifetime >= world.get_item_lifetime() {
         world.remove_entity(id, "item too old");
     }
 }
@@ -223,6 +290,7 @@ fn tick_falling_block(world: &mut World, id: u32, entity: &mut Entity) {
     base.vel.y -= 0.04;
 
     apply_base_vel(world, id, base, base.vel, 0.0, true);
+    base.vel *= 0.98;
 
     if base.on_ground {
         base.vel *= DVec3::new(0.7, -0.5, 0.7);
@@ -255,16 +323,143 @@ fn tick_tnt(world: &mut World, id: u32, entity: &mut Entity) {
 
     base.vel.y -= 0.04;
     apply_base_vel(world, id, base, base.vel, 0.0, true);
-    base.vel.y *= 0.98;
+    base.vel *= 0.98;
 
     if base.on_ground {
         base.vel *= DVec3::new(0.7, -0.5, 0.7);
     }
 
-    tnt.fuse_time = tnt.fuse_time.saturating_sub(1);
+    // The TNT explodes on the tick after its fuse time reached zero.
     if tnt.fuse_time == 0 {
         world.remove_entity(id, "tnt explode");
         world.explode(base.pos, 4.0, false, None);
+    } else {
+        tnt.fuse_time -= 1;
+    }
+}
+
+/// REF: EntityBoat::onUpdate
+fn tick_boat(world: &mut World, id: u32, entity: &mut Entity) {
+    tick_base(world, id, entity);
+    let_expect!(Entity(base, BaseKind::Boat(boat)) = entity);
+
+    boat.damage = boat.damage.saturating_sub(1);
+
+    // Each hit damages the boat, that breaks if too damaged.
+    // REF: EntityBoat::attackEntityFrom
+    while let Some(hurt) = base.hurt.pop() {
+        boat.damage = boat.damage.saturating_add(hurt.damage.saturating_mul(10));
+    }
+
+    if boat.damage > 40 {
+        world.remove_entity(id, "boat destroyed");
+        spawn_boat_loot(world, base.pos);
+        return;
+    }
+
+    // Compute the ratio of the boat's height that is in water, using 5 slices.
+    let mut water_ratio = 0.0;
+    for i in 0u8..5 {
+        let check_bb = BoundingBox {
+            min: DVec3 {
+                x: base.bb.min.x,
+                y: base.bb.min.y + base.bb.size_y() * i as f64 / 5.0 - 0.125,
+                z: base.bb.min.z,
+            },
+            max: DVec3 {
+                x: base.bb.max.x,
+                y: base.bb.min.y + base.bb.size_y() * (i + 1) as f64 / 5.0 - 0.125,
+                z: base.bb.max.z,
+            },
+        };
+
+        if common::has_fluids_colliding(world, check_bb, Material::Water) {
+            water_ratio += 1.0 / 5.0;
+        }
+    }
+
+    // The boat floats up when more than half in water, and falls otherwise.
+    base.vel.y += 0.04 * (water_ratio * 2.0 - 1.0);
+
+    // The rider accelerates the boat with its own velocity.
+    if let Some(rider_id) = base.rider_id {
+        if let Some(Entity(rider_base, _)) = world.get_entity(rider_id) {
+            base.vel.x += rider_base.vel.x * 0.2;
+            base.vel.z += rider_base.vel.z * 0.2;
+        } else {
+            base.rider_id = None;
+        }
+    }
+
+    base.vel.x = base.vel.x.clamp(-0.4, 0.4);
+    base.vel.z = base.vel.z.clamp(-0.4, 0.4);
+
+    if base.on_ground {
+        base.vel *= 0.5;
+    }
+
+    let prev_pos = base.pos;
+    let prev_vel = base.vel;
+    apply_base_vel(world, id, base, base.vel, 0.0, true);
+
+    // The velocity is reset on the axis where the boat has collided, the boat breaks
+    // if it collided horizontally while too fast.
+    let collided_horizontally = prev_vel.x != base.vel.x || prev_vel.z != base.vel.z;
+    if collided_horizontally && base.vel.xz().length() > 0.15 {
+        world.remove_entity(id, "boat crashed");
+        spawn_boat_loot(world, base.pos);
+        return;
+    }
+
+    base.vel *= DVec3::new(0.99, 0.95, 0.99);
+
+    // Progressively turn the boat toward its moving direction.
+    base.look.y = 0.0;
+    let delta = prev_pos.xz() - base.pos.xz();
+    if delta.length_squared() > 0.001 {
+        let target_yaw = f64::atan2(delta.y, delta.x) as f32;
+        let max_yaw_delta = 20f32.to_radians();
+        let mut yaw_delta = (target_yaw - base.look.x) % std::f32::consts::TAU;
+        if yaw_delta >= std::f32::consts::PI {
+            yaw_delta -= std::f32::consts::TAU;
+        } else if yaw_delta < -std::f32::consts::PI {
+            yaw_delta += std::f32::consts::TAU;
+        }
+        base.look.x += yaw_delta.clamp(-max_yaw_delta, max_yaw_delta);
+    }
+
+    // Push other boats colliding with this one.
+    for (push_id, push_entity) in
+        world.iter_entities_colliding_mut(base.bb.inflate(DVec3::new(0.2, 0.0, 0.2)))
+    {
+        if let Entity(push_base, BaseKind::Boat(_)) = push_entity {
+            if Some(push_id) != base.rider_id {
+                push_base_apart(base, push_base);
+            }
+        }
+    }
+
+    // Snow layers under the corners of the boat are destroyed.
+    for i in 0..4 {
+        let pos = IVec3 {
+            x: (base.pos.x + ((i % 2) as f64 - 0.5) * 0.8).floor() as i32,
+            y: base.pos.y.floor() as i32,
+            z: (base.pos.z + ((i / 2) as f64 - 0.5) * 0.8).floor() as i32,
+        };
+
+        if world.is_block(pos, block::SNOW) {
+            world.set_block_notify(pos, block::AIR, 0);
+        }
+    }
+}
+
+/// Spawn the loot of a broken boat, 3 planks and 2 sticks.
+fn spawn_boat_loot(world: &mut World, pos: DVec3) {
+    for _ in 0..3 {
+        world.spawn_loot(pos, ItemStack::new_block(block::WOOD, 0), 0.0);
+    }
+    for _ in 0..2 {
+        world.spawn_loot(pos, ItemStack::new_single(item::STICK, 0), 0.0);
     }
 }
 
@@ -291,6 +486,32 @@ fn tick_living(world: &mut World, id: u32, entity: &mut Entity) {
 
     tick_living_pos(world, id, base, living, living_kind);
     tick_living_push(world, id, base);
+
+    if let LivingKind::Chicken(chicken) = living_kind {
+        tick_chicken(world, base, chicken);
+    }
+}
+
+/// Chickens flap their wings to slowly fall and never take fall damage, they also lay an
+/// egg every 5 to 10 minutes.
+///
+/// REF: EntityChicken::onLivingUpdate, EntityChicken::fall
+fn tick_chicken(world: &mut World, base: &mut Base, chicken: &mut Chicken) {
+    if !base.on_ground && base.vel.y < 0.0 {
+        base.vel.y *= 0.6;
+    }
+
+    base.fall_distance = 0.0;
+
+    if chicken.next_egg_ticks == 0 {
+        chicken.next_egg_ticks = base.rand.next_int_bounded(6000) as u32 + 6000;
+    }
+
+    chicken.next_egg_ticks -= 1;
+    if chicken.next_egg_ticks == 0 {
+        world.spawn_loot(base.pos, ItemStack::new_single(item::EGG, 0), 0.0);
+        chicken.next_egg_ticks = base.rand.next_int_bounded(6000) as u32 + 6000;
+    }
 }
 
 /// REF:
@@ -307,16 +528,40 @@ fn tick_projectile(world: &mut World, id: u32, entity: &mut Entity) {
     projectile.shake = projectile.shake.saturating_sub(1);
     projectile.state_time = projectile.state_time.saturating_add(1);
 
+    // Only fireballs can be deflected when hit, toward the look of the attacker, other
+    // projectiles just ignore hurts.
+    // REF: EntityFireball::attackEntityFrom
+    while let Some(hurt) = base.hurt.pop() {
+        let ProjectileKind::Fireball(fireball) = projectile_kind else {
+            continue;
+        };
+
+        let Some(Entity(origin_base, _)) = hurt.origin_id.and_then(|id| world.get_entity(id))
+        else {
+            continue;
+        };
+
+        let (yaw_sin, yaw_cos) = origin_base.look.x.sin_cos();
+        let (pitch_sin, pitch_cos) = origin_base.look.y.sin_cos();
+
+        base.vel.x = (-yaw_sin * pitch_cos) as f64;
+        base.vel.y = (-pitch_sin) as f64;
+        base.vel.z = (yaw_cos * pitch_cos) as f64;
+        fireball.accel = base.vel * 0.1;
+    }
+
     // If this is a fishing rod bobber, we force its position, but we also prevent it to
     // enter the block hit state.
     if let ProjectileKind::Bobber(bobber) = projectile_kind {
-        // Kill the bobber by default, keep it if the owner is still alive and still
-        // declare it has the its bobber.
+        // Kill the bobber by default, keep it if the owner is still alive, still
+        // declare it has the its bobber and is not too far away.
         let mut remove_bobber = true;
 
         if let Some(owner_id) = projectile.owner_id {
             if let Some(Entity(owner_base, _)) = world.get_entity(owner_id) {
-                if owner_base.bobber_id == Some(id) {
+                if owner_base.bobber_id == Some(id)
+                    && owner_base.pos.distance_squared(base.pos) <= 32.0 * 32.0
+                {
                     remove_bobber = false;
                 }
             }
@@ -446,14 +691,21 @@ fn tick_projectile(world: &mut World, id: u32, entity: &mut Entity) {
             }
         }
         ProjectileKind::Snowball(_) | ProjectileKind::Egg(_) => {
-            if let Some((_, Entity(hit_base, _))) = hit_entity {
+            // Only knock back the entity.
+            let mut target_id = None;
+            if let Some((hit_id, Entity(hit_base, _))) = hit_entity {
                 hit_base.hurt.push(Hurt {
                     damage: 0,
                     origin_id: projectile.owner_id,
                 });
+                target_id = Some(hit_id);
             }
 
-            if hit_entity.is_some() || hit_block.is_some() {
+            if target_id.is_some() || hit_block.is_some() {
+                world.push_event(Event::Entity {
+                    id,
+                    inner: EntityEvent::Impact { target_id },
+                });
                 world.remove_entity(id, "projectile hit");
 
                 // For egg we try to spawn a chicken.
@@ -465,21 +717,31 @@ fn tick_projectile(world: &mut World, id: u32, entity: &mut Entity) {
                         }
 
                         for _ in 0..count {
-                            world.spawn_entity(Chicken::new_with(|new_base, new_living, _| {
+                            let chicken = Chicken::new_with(|new_base, new_living, _| {
                                 new_base.persistent = true;
                                 new_base.pos = base.pos;
-                                new_base.look.x = base.rand.next_float() * std::f32::consts::TAU;
+                                new_base.look.x = base.look.x;
                                 new_living.health = 4;
-                            }));
+                            });
+                            world.spawn_entity_with_reason(chicken, SpawnReason::Egg);
                         }
                     }
                 }
             }
         }
         ProjectileKind::Fireball(_) => {
-            if hit_entity.is_some() || hit_block.is_some() {
+            let mut hit = hit_block.is_some();
+            if let Some((_, Entity(hit_base, _))) = hit_entity {
+                hit_base.hurt.push(Hurt {
+                    damage: 0,
+                    origin_id: projectile.owner_id,
+                });
+                hit = true;
+            }
+
+            if hit {
                 world.remove_entity(id, "fireball hit");
-                world.explode(base.pos, 1.0, true, projectile.owner_id);
+                world.explode_by_mob(base.pos, 1.0, true, projectile.owner_id);
             }
         }
         ProjectileKind::Bobber(bobber) => {
@@ -507,13 +769,13 @@ fn tick_projectile(world: &mut World, id: u32, entity: &mut Entity) {
 
     // The velocity update depends on projectile kind.
     if let ProjectileKind::Fireball(fireball) = projectile_kind {
+        base.vel += fireball.accel;
+
         if base.in_water {
             base.vel *= 0.8;
         } else {
             base.vel *= 0.95;
         }
-
-        base.vel += fireball.accel;
     } else if let ProjectileKind::Bobber(bobber) = projectile_kind {
         // PARITY: The bobber in Notchian implementation is really weird, so I just
         // tried here to make a better logic that do not use the block collision
@@ -546,12 +808,20 @@ fn tick_projectile(world: &mut World, id: u32, entity: &mut Entity) {
             if bobber.catch_time > 0 {
                 bobber.catch_time -= 1;
             } else {
-                let chance = 500;
-                // TODO: If canLightningStrikeAt, it's 300
+                // Fish bite more often under the rain.
+                let rain_pos = base.pos.floor().as_ivec3() + IVec3::Y;
+                let chance = match world.get_local_weather(rain_pos) {
+                    LocalWeather::Rain => 300,
+                    _ => 500,
+                };
 
                 if base.rand.next_int_bounded(chance) == 0 {
                     bobber.catch_time = base.rand.next_int_bounded(30) as u16 + 10;
                     base.vel.y -= 0.2;
+                    world.push_event(Event::Entity {
+                        id,
+                        inner: EntityEvent::Bite,
+                    });
                 }
             }
         }
@@ -587,37 +857,133 @@ fn tick_projectile(world: &mut World, id: u32, entity: &mut Entity) {
 }
 
 /// REF:
+/// - EntityLightningBolt::EntityLightningBolt
 /// - EntityLightningBolt::onUpdate
 fn tick_lightning_bolt(world: &mut World, id: u32, entity: &mut Entity) {
-    let_expect!(Entity(base, _) = entity);
+    let_expect!(Entity(base, BaseKind::LightningBolt(bolt)) = entity);
 
+    // PARITY: The Notchian implementation does this in the constructor.
     if base.lifetime == 1 {
-        // FIXME: Set fire only if difficulty >= 2
+        bolt.remaining_strikes = base.rand.next_int_bounded(3) as u8 + 1;
+
+        // Fire is only set from normal difficulty.
+        if world.get_difficulty() >= Difficulty::Normal {
+            let fire_pos = base.pos.floor().as_ivec3();
+            place_lightning_fire(world, fire_pos);
+
+            for _ in 0..4 {
+                let fire_pos = fire_pos
+                    + IVec3 {
+                        x: base.rand.next_int_bounded(3) - 1,
+                        y: base.rand.next_int_bounded(3) - 1,
+                        z: base.rand.next_int_bounded(3) - 1,
+                    };
+                place_lightning_fire(world, fire_pos);
+            }
+        }
 
-        // PARITY: We don't check if fire can be placed.
+        world.push_event(Event::Lightning { id, pos: base.pos });
+    }
 
-        let fire_pos = base.pos.floor().as_ivec3();
-        if world.is_block_air(fire_pos) {
-            world.set_block_notify(fire_pos, block::FIRE, 0);
+    bolt.state -= 1;
+    if bolt.state < 0 {
+        if bolt.remaining_strikes == 0 {
+            world.remove_entity(id, "lightning bolt");
+            return;
+        } else if bolt.state < -base.rand.next_int_bounded(10) as i8 {
+            bolt.remaining_strikes -= 1;
+            bolt.state = 1;
+            place_lightning_fire(world, base.pos.floor().as_ivec3());
         }
+    }
 
-        for _ in 0..4 {
-            let fire_pos = fire_pos
-                + IVec3 {
-                    x: base.rand.next_int_bounded(3) - 1,
-                    y: base.rand.next_int_bounded(3) - 1,
-                    z: base.rand.next_int_bounded(3) - 1,
-                };
+    if bolt.state >= 0 {
+        strike_lightning(world, base.pos);
+    }
+}
+
+/// Place a fire block at the given position if there is air and fire can be placed.
+fn place_lightning_fire(world: &mut World, pos: IVec3) {
+    if world.is_block_air(pos) && world.can_place_block(pos, Face::NegY, block::FIRE) {
+        world.set_block_notify(pos, block::FIRE, 0);
+    }
+}
+
+/// Strike all entities around a lightning bolt at the given position, entities are
+/// damaged and set on fire, pigs are turned into pig zombies and creepers are powered.
+///
+/// REF: Entity::onStruckByLightning
+fn strike_lightning(world: &mut World, pos: DVec3) {
+    let bb = BoundingBox {
+        min: pos - 3.0,
+        max: pos + DVec3::new(3.0, 9.0, 3.0),
+    };
+
+    // Temporarily owned vector to avoid allocation.
+    common::ENTITY_ID.with_borrow_mut(|pig_ids| {
+        debug_assert!(pig_ids.is_empty());
 
-            if world.is_block_air(fire_pos) {
-                world.set_block_notify(fire_pos, block::FIRE, 0);
+        let mut powered_ids = Vec::new();
+
+        for (struck_id, struck_entity) in world.iter_entities_colliding_mut(bb) {
+            let Entity(struck_base, struck_base_kind) = struck_entity;
+
+            // REF: EntityPig::onStruckByLightning
+            if let BaseKind::Living(_, LivingKind::Pig(_)) = struck_base_kind {
+                pig_ids.push(struck_id);
+                continue;
+            }
+
+            // REF: Entity::dealFireDamage
+            match struck_base_kind {
+                BaseKind::Living(_, living_kind) if tick_state::is_immune_to_fire(living_kind) => {}
+                _ => struck_base.hurt.push(Hurt {
+                    damage: 5,
+                    origin_id: None,
+                }),
+            }
+
+            if struck_base.fire_time == 0 {
+                struck_base.fire_time = 300;
+            } else {
+                struck_base.fire_time += 1;
+            }
+
+            // REF: EntityCreeper::onStruckByLightning
+            if let BaseKind::Living(_, LivingKind::Creeper(creeper)) = struck_base_kind {
+                if !creeper.powered {
+                    creeper.powered = true;
+                    powered_ids.push(struck_id);
+                }
             }
         }
 
-        // TODO: Strike entities.
-    } else {
-        world.remove_entity(id, "lightning bolt");
-    }
+        for struck_id in powered_ids {
+            world.push_event(Event::Entity {
+                id: struck_id,
+                inner: EntityEvent::Metadata,
+            });
+        }
+
+        for pig_id in pig_ids.drain(..) {
+            let Some(Entity(pig_base, _)) = world.get_entity(pig_id) else {
+                continue;
+            };
+
+            let zombie = PigZombie::new_with(|base, _, _| {
+                base.pos = pig_base.pos;
+                base.look = pig_base.look;
+                base.persistent = pig_base.persistent;
+            });
+
+            if world
+                .spawn_entity_with_reason(zombie, SpawnReason::Lightning)
+                .is_some()
+            {
+                world.remove_entity(pig_id, "struck by lightning");
+            }
+        }
+    });
 }
 
 /// Tick a living entity to push/being pushed an entity.
@@ -625,9 +991,14 @@ fn tick_living_push(world: &mut World, _id: u32, base: &mut Base) {
     // TODO: pushing minecart
 
     // For each colliding entity, precalculate the velocity to add to both entities.
-    for (_, push_entity) in
+    for (push_id, push_entity) in
         world.iter_entities_colliding_mut(base.bb.inflate(DVec3::new(0.2, 0.0, 0.2)))
     {
+        // An entity doesn't push its rider or vehicle.
+        if base.rider_id == Some(push_id) || base.vehicle_id == Some(push_id) {
+            continue;
+        }
+
         let Entity(push_base, push_base_kind) = push_entity;
 
         match push_base_kind {
@@ -635,26 +1006,33 @@ fn tick_living_push(world: &mut World, _id: u32, base: &mut Base) {
             _ => continue, // Other entities cannot be pushed.
         }
 
-        let mut dx = base.pos.x - push_base.pos.x;
-        let mut dz = base.pos.z - push_base.pos.z;
-        let mut delta = f64::max(dx.abs(), dz.abs());
-
-        if delta >= 0.01 {
-            delta = delta.sqrt();
-            dx /= delta;
-            dz /= delta;
-
-            let delta_inv = 1.0 / delta;
-            dx *= delta_inv;
-            dz *= delta_inv;
-            dx *= 0.05;
-            dz *= 0.05;
-
-            let delta = DVec3::new(dx, 0.0, dz);
+        push_base_apart(base, push_base);
+    }
+}
 
-            push_base.vel -= delta;
-            base.vel += delta;
-        }
+/// Push two colliding entities apart by adding opposite velocities to both.
+///
+/// REF: Entity::applyEntityCollision
+fn push_base_apart(base: &mut Base, push_base: &mut Base) {
+    let mut dx = base.pos.x - push_base.pos.x;
+    let mut dz = base.pos.z - push_base.pos.z;
+    let mut delta = f64::max(dx.abs(), dz.abs());
+
+    if delta >= 0.01 {
+        delta = delta.sqrt();
+        dx /= delta;
+        dz /= delta;
+
+        let delta_inv = 1.0 / delta;
+        dx *= delta_inv;
+        dz *= delta_inv;
+        dx *= 0.05;
+        dz *= 0.05;
+
+        let delta = DVec3::new(dx, 0.0, dz);
+
+        push_base.vel -= delta;
+        base.vel += delta;
     }
 }
 
@@ -716,6 +1094,8 @@ fn tick_living_pos(
 
     // All living entities have step height 0.5;
     let step_height = 0.5;
+    // The fall distance before moving, used to damage the entity if it lands.
+    let mut fall_distance = base.fall_distance;
 
     // REF: EntityFlying::moveEntityWithHeading
     let flying = matches!(living_kind, LivingKind::Ghast(_));
@@ -757,11 +1137,38 @@ fn tick_living_pos(
 
         apply_living_accel(base, living, vel_factor);
 
-        // TODO: Is on ladder
+        // Spiders climb on any wall they collided with, other entities only on ladders.
+        // REF: EntityLiving::isOnLadder, EntitySpider::isOnLadder
+        let on_ladder = match living_kind {
+            LivingKind::Spider(_) => base.collided_horizontally,
+            _ => {
+                let ladder_pos = IVec3 {
+                    x: base.pos.x.floor() as i32,
+                    y: base.bb.min.y.floor() as i32,
+                    z: base.pos.z.floor() as i32,
+                };
+                world.is_block(ladder_pos, block::LADDER)
+            }
+        };
+
+        if on_ladder {
+            base.vel.x = base.vel.x.clamp(-0.15, 0.15);
+            base.vel.z = base.vel.z.clamp(-0.15, 0.15);
+            base.vel.y = base.vel.y.max(-0.15);
+            base.fall_distance = 0.0;
+            fall_distance = 0.0;
+            if let LivingKind::Human(human) = living_kind {
+                if human.sneaking && base.vel.y < 0.0 {
+                    base.vel.y = 0.0;
+                }
+            }
+        }
 
         apply_base_vel(world, id, base, base.vel, step_height, false);
 
-        // TODO: Collided horizontally and on ladder
+        if on_ladder && base.collided_horizontally {
+            base.vel.y = 0.2;
+        }
 
         if flying {
             base.vel *= slipperiness as f64;
@@ -772,6 +1179,21 @@ fn tick_living_pos(
             base.vel.z *= slipperiness as f64;
         }
     }
+
+    // Living entities are damaged when landing after a fall.
+    // REF: EntityLiving::fall
+    // PARITY: Players are excluded because EntityPlayerMP::updateFallState does nothing,
+    // the server applies their fall damage from the client's movement packets.
+    let is_human = matches!(living_kind, LivingKind::Human(_));
+    if !is_human && base.on_ground && base.fall_distance == 0.0 {
+        let damage = common::calc_fall_damage(fall_distance);
+        if damage != 0 {
+            base.hurt.push(Hurt {
+                damage,
+                origin_id: None,
+            });
+        }
+    }
 }
 
 /// Update a living entity velocity according to its strafing/forward accel.
@@ -863,6 +1285,7 @@ pub fn apply_base_vel(
         let collided_y = delta.y != new_delta.y;
         let collided_z = delta.z != new_delta.z;
         let on_ground = collided_y && delta.y < 0.0; // || self.on_ground
+        base.collided_horizontally = collided_x || collided_z;
 
         // Apply step if relevant.
         if step_height > 0.0 && on_ground && (collided_x || collided_z) {
@@ -873,7 +1296,21 @@ pub fn apply_base_vel(
 
         if on_ground {
             if base.fall_distance > 0.0 {
-                // TODO: Damage?
+                // Living entities are damaged by the fall in `tick_living_pos`.
+
+                // Entities falling on farmland have a chance to trample it.
+                // REF: BlockFarmland::onFallenUpon
+                let ground_pos = IVec3 {
+                    x: base.bb.center_x().floor() as i32,
+                    y: (base.bb.min.y - 0.2).floor() as i32,
+                    z: base.bb.center_z().floor() as i32,
+                };
+
+                if world.is_block(ground_pos, block::FARMLAND)
+                    && world.get_rand_mut().next_int_bounded(4) == 0
+                {
+                    world.set_block_notify(ground_pos, block::DIRT, 0);
+                }
             }
             base.fall_distance = 0.0;
         } else if new_delta.y < 0.0 {
@@ -903,3 +1340,268 @@ pub fn apply_base_vel(
         z: base.bb.center_z(),
     };
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn chicken_eggs() {
+        use crate::entity::{Chicken, LivingKind};
+        use crate::item;
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .entity(Chicken::new_default(DVec3::new(8.5, 40.0, 8.5)))
+            .build_with_entities();
+        let [chicken_id] = ids[..] else {
+            unreachable!()
+        };
+
+        // Chickens slowly fall, without accumulating fall distance.
+        for _ in 0..20 {
+            world.tick();
+            let Entity(base, _) = world.get_entity(chicken_id).unwrap();
+            assert!(base.vel.y > -0.15);
+            assert_eq!(base.fall_distance, 0.0);
+        }
+
+        let Some(Entity(_, BaseKind::Living(_, LivingKind::Chicken(chicken)))) =
+            world.get_entity_mut(chicken_id)
+        else {
+            panic!("chicken should exists");
+        };
+        assert!((5980..12000).contains(&chicken.next_egg_ticks));
+        chicken.next_egg_ticks = 2;
+
+        let has_egg = |world: &World| {
+            world.iter_entities().any(|(_, entity)| {
+                matches!(entity, Entity(_, BaseKind::Item(item)) if item.stack.id == item::EGG)
+            })
+        };
+        world.tick();
+        assert!(!has_egg(&world));
+        world.tick();
+        assert!(has_egg(&world));
+    }
+
+    #[test]
+    fn spider_climb() {
+        use crate::entity::{Pig, Spider};
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .wall(
+                IVec3::new(10, 10, 0),
+                IVec3::new(10, 10, 15),
+                6,
+                block::STONE,
+                0,
+            )
+            .entity(Spider::new_default(DVec3::new(9.25, 10.0, 4.5)))
+            .entity(Pig::new_default(DVec3::new(9.5, 10.0, 12.5)))
+            .build_with_entities();
+
+        // Both entities are pushed against the wall, but only the spider climbs. The
+        // highest positions are kept because wandering may move them off the wall.
+        let mut max_y = [0.0f64; 2];
+        for _ in 0..30 {
+            for &id in &ids {
+                world.get_entity_mut(id).unwrap().0.vel.x = 0.1;
+            }
+            world.tick();
+            for (max_y, &id) in max_y.iter_mut().zip(&ids) {
+                *max_y = max_y.max(world.get_entity(id).unwrap().0.pos.y);
+            }
+        }
+
+        assert!(max_y[0] > 11.5);
+        assert!(max_y[1] < 11.0);
+    }
+
+    #[test]
+    fn fall_damage() {
+        use crate::entity::{Human, Living, Pig};
+
+        let health = |world: &World, id| {
+            world
+                .get_entity(id)
+                .unwrap()
+                .get::<Living>()
+                .unwrap()
+                .health
+        };
+
+        let mut world = World::builder().platform(10, block::STONE, 0).build();
+        world.swap_events(Some(Vec::new()));
+        let high_id = world.spawn_entity(Pig::new_default(DVec3::new(4.5, 20.0, 4.5)));
+        let low_id = world.spawn_entity(Pig::new_default(DVec3::new(12.5, 12.0, 12.5)));
+        let player_id = world.spawn_entity(Human::new_default(DVec3::new(4.5, 20.0, 12.5)));
+        let full_health = health(&world, high_id);
+        let player_health = health(&world, player_id);
+
+        for _ in 0..40 {
+            world.tick();
+        }
+        assert_eq!(health(&world, high_id), full_health - 7);
+        assert_eq!(health(&world, low_id), full_health);
+        assert!(world.get_entity(player_id).unwrap().0.on_ground);
+        assert_eq!(health(&world, player_id), player_health);
+
+        assert!(world.hurt_entity(low_id, 3, Some(high_id)));
+        assert!(!world.hurt_entity(u32::MAX, 3, None));
+        world.tick();
+        assert_eq!(health(&world, low_id), full_health - 3);
+
+        let events = world.swap_events(None).unwrap();
+        assert!(events.iter().any(|event| matches!(event, Event::Entity {
+            id,
+            inner: EntityEvent::Damage,
+        } if *id == low_id)));
+    }
+
+    #[test]
+    fn fishing() {
+        use crate::entity::{Human, Pig, ProjectileKind};
+        use crate::inventory::InventoryHandle;
+        use crate::item;
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .fill(
+                IVec3::new(4, 9, 4),
+                IVec3::new(12, 9, 12),
+                block::WATER_STILL,
+                0,
+            )
+            .player(Human::new_default(DVec3::new(8.5, 10.0, 1.5)))
+            .entity(Pig::new_default(DVec3::new(8.5, 10.0, 14.5)))
+            .build_with_entities();
+        let [player_id, pig_id] = ids[..] else {
+            unreachable!()
+        };
+
+        let mut inv = [ItemStack::new_single(item::FISHING_ROD, 0)];
+        let cast = |world: &mut World, inv: &mut [ItemStack]| {
+            world.use_raw_stack(&mut InventoryHandle::new(inv), 0, player_id);
+            world.get_entity(player_id).unwrap().0.bobber_id
+        };
+
+        // A bitten bobber catches a fish thrown toward the player.
+        let bobber_id = cast(&mut world, &mut inv).unwrap();
+        let Some(Entity(_, BaseKind::Projectile(_, ProjectileKind::Bobber(bobber)))) =
+            world.get_entity_mut(bobber_id)
+        else {
+            panic!("bobber should be spawned");
+        };
+        bobber.catch_time = 20;
+        assert_eq!(cast(&mut world, &mut inv), None);
+        assert!(!world.contains_entity(bobber_id));
+        let fish = world.iter_entities().find_map(|(_, entity)| match entity {
+            Entity(base, BaseKind::Item(fish)) if fish.stack.id == item::RAW_FISH => Some(base),
+            _ => None,
+        });
+        assert!(fish.unwrap().vel.z < 0.0);
+        assert_eq!(inv[0].damage, 1);
+
+        // A bobber hooked to an entity yanks it toward the player.
+        let bobber_id = cast(&mut world, &mut inv).unwrap();
+        let Some(Entity(_, BaseKind::Projectile(_, ProjectileKind::Bobber(bobber)))) =
+            world.get_entity_mut(bobber_id)
+        else {
+            panic!("bobber should be spawned");
+        };
+        bobber.attached_id = Some(pig_id);
+        cast(&mut world, &mut inv);
+        assert!(world.get_entity(pig_id).unwrap().0.vel.z < 0.0);
+        assert_eq!(inv[0].damage, 4);
+
+        // A bobber too far from its owner is removed.
+        let bobber_id = cast(&mut world, &mut inv).unwrap();
+        world
+            .get_entity_mut(bobber_id)
+            .unwrap()
+            .teleport(DVec3::new(8.5, 10.0, 40.5));
+        world.tick();
+        assert!(!world.contains_entity(bobber_id));
+    }
+
+    #[test]
+    fn lightning_strike() {
+        use crate::entity::{Creeper, EntityKind, LightningBolt, LivingKind, Pig};
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .entity(LightningBolt::new_default(DVec3::new(8.5, 10.0, 8.5)))
+            .entity(Pig::new_default(DVec3::new(9.5, 10.0, 8.5)))
+            .entity(Creeper::new_default(DVec3::new(7.5, 10.0, 8.5)))
+            .events()
+            .build_with_entities();
+        let [bolt_id, pig_id, creeper_id] = ids[..] else {
+            unreachable!()
+        };
+        world.set_difficulty(Difficulty::Normal);
+
+        world.tick();
+        assert!(world.is_block(IVec3::new(8, 10, 8), block::FIRE));
+        assert!(!world.contains_entity(pig_id));
+        assert!(world
+            .iter_entities()
+            .any(|(_, entity)| entity.kind() == EntityKind::PigZombie));
+        assert!(matches!(
+            world.get_entity(creeper_id),
+            Some(Entity(base, BaseKind::Living(_, LivingKind::Creeper(Creeper { powered: true, .. }))))
+                if base.fire_time > 0
+        ));
+
+        let events = world.swap_events(Some(Vec::new())).unwrap();
+        assert!(events.contains(&Event::Lightning {
+            id: bolt_id,
+            pos: DVec3::new(8.5, 10.0, 8.5)
+        }));
+
+        for _ in 0..40 {
+            world.tick();
+        }
+        assert!(!world.contains_entity(bolt_id));
+    }
+
+    #[test]
+    fn snowball_impact() {
+        use crate::entity::{Living, Pig, Snowball};
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .entity(Pig::new_default(DVec3::new(12.5, 10.0, 8.5)))
+            .entity(Snowball::new_with(|base, _, _| {
+                base.pos = DVec3::new(4.5, 11.0, 8.5);
+                base.vel = DVec3::new(1.0, 0.0, 0.0);
+            }))
+            .events()
+            .build_with_entities();
+        let [pig_id, snowball_id] = ids[..] else {
+            unreachable!()
+        };
+
+        for _ in 0..20 {
+            world.tick();
+        }
+
+        assert!(!world.contains_entity(snowball_id));
+        let events = world.swap_events(Some(Vec::new())).unwrap();
+        assert!(events.contains(&Event::Entity {
+            id: snowball_id,
+            inner: EntityEvent::Impact {
+                target_id: Some(pig_id)
+            }
+        }));
+
+        // Snowballs deal no damage.
+        let Some(Entity(_, BaseKind::Living(Living { health, .. }, _))) = world.get_entity(pig_id)
+        else {
+            panic!("pig should be alive");
+        };
+        assert_eq!(*health, 10);
+    }
+}
@@ -5,12 +5,14 @@ use std::ops::Add;
 use glam::{DVec3, IVec3, Vec2};
 use tracing::trace;
 
-use crate::entity::{Fireball, LookTarget, Path};
-use crate::world::{EntityEvent, Event, World};
+use crate::block;
+use crate::entity::{Fireball, Hurt, LookTarget, Path};
+use crate::world::path::PathOptions;
+use crate::world::{Difficulty, EntityEvent, Event, World};
 
 use super::common::{self, let_expect};
 use super::tick_attack;
-use super::{BaseKind, Entity, EntityCategory, LivingKind};
+use super::{BaseKind, Entity, EntityCategory, LivingKind, Sheep};
 
 const RAD_10_DEG: f32 = core::f32::consts::FRAC_PI_6 / 3.0;
 const RAD_20_DEG: f32 = core::f32::consts::FRAC_PI_3 / 3.0;
@@ -24,6 +26,22 @@ pub(super) fn tick_ai(world: &mut World, id: u32, entity: &mut Entity) {
         Entity(_, BaseKind::Living(_, LivingKind::Ghast(_))) => tick_ghast_ai(world, id, entity),
         Entity(_, BaseKind::Living(_, LivingKind::Squid(_))) => tick_squid_ai(world, id, entity),
         Entity(_, BaseKind::Living(_, LivingKind::Slime(_))) => tick_slime_ai(world, id, entity),
+        Entity(_, BaseKind::Living(_, LivingKind::Sheep(_))) => {
+            tick_ground_ai(world, id, entity);
+            tick_sheep_graze(world, id, entity);
+        }
+        Entity(_, BaseKind::Living(_, LivingKind::Creeper(_))) => {
+            tick_ground_ai(world, id, entity);
+            tick_creeper_ai(world, id, entity);
+        }
+        Entity(_, BaseKind::Living(_, LivingKind::Wolf(_))) => {
+            tick_ground_ai(world, id, entity);
+            tick_wolf_ai(world, id, entity);
+        }
+        Entity(_, BaseKind::Living(_, LivingKind::PigZombie(_))) => {
+            tick_ground_ai(world, id, entity);
+            tick_pig_zombie_ai(world, id, entity);
+        }
         Entity(_, BaseKind::Living(_, _)) => tick_ground_ai(world, id, entity),
         _ => unreachable!("invalid argument for this function"),
     }
@@ -101,6 +119,60 @@ fn tick_living_ai(world: &mut World, _id: u32, entity: &mut Entity) {
     }
 }
 
+/// Sheared sheep may eat the tall grass they stand in, or the grass block below them, in
+/// order to regrow their wool. Grazing takes 2 seconds, during which the sheep stands
+/// still, the grass is eaten near the end.
+///
+/// PARITY: Sheep don't eat grass in the Notchian beta 1.7.3 server, this later behavior
+/// is only done by sheared sheep and only if mob griefing is enabled.
+///
+/// REF (1.2.5): EntityAIEatGrass
+fn tick_sheep_graze(world: &mut World, id: u32, entity: &mut Entity) {
+    /// Total ticks of grazing.
+    const GRAZE_TIME: u8 = 40;
+    /// Remaining grazing ticks when the grass is eaten.
+    const EAT_TIME: u8 = 4;
+
+    let_expect!(Entity(base, BaseKind::Living(living, LivingKind::Sheep(sheep))) = entity);
+
+    let pos = base.pos.floor().as_ivec3();
+
+    if sheep.graze_time == 0 {
+        if !sheep.sheared || !world.is_mob_griefing() || base.rand.next_int_bounded(1000) != 0 {
+            return;
+        }
+        if !world.is_block(pos, block::TALL_GRASS) && !world.is_block(pos - IVec3::Y, block::GRASS)
+        {
+            return;
+        }
+        sheep.graze_time = GRAZE_TIME;
+    }
+
+    sheep.graze_time -= 1;
+    living.path = None;
+    living.accel_forward = 0.0;
+    living.accel_strafing = 0.0;
+    living.jumping = false;
+
+    if sheep.graze_time != EAT_TIME {
+        return;
+    }
+
+    if world.is_block(pos, block::TALL_GRASS) {
+        world.set_block_notify(pos, block::AIR, 0);
+    } else if world.is_block(pos - IVec3::Y, block::GRASS) {
+        world.set_block_notify(pos - IVec3::Y, block::DIRT, 0);
+    } else {
+        return;
+    }
+
+    sheep.sheared = false;
+    world.push_event(Event::Entity {
+        id,
+        inner: EntityEvent::Metadata,
+    });
+}
+
 /// Tick an ground creature (animal/mob) entity AI.
 ///
 /// REF: EntityCreature::updatePlayerActionState
@@ -129,6 +201,9 @@ fn tick_ground_ai(world: &mut World, id: u32, entity: &mut Entity) {
     let mut target_pos = None;
     // Set to true when the entity should strafe while following its path.
     let mut should_strafe = false;
+    // Sitting wolves don't search for new paths, but they keep following the current one.
+    // REF: EntityWolf::isMovementCeased
+    let movement_ceased = matches!(living_kind, LivingKind::Wolf(wolf) if wolf.sitting);
 
     // Start by finding an attack target, or attack the existing one.
     if let Some(target_id) = living.attack_target {
@@ -162,9 +237,14 @@ fn tick_ground_ai(world: &mut World, id: u32, entity: &mut Entity) {
             LivingKind::Giant(_) => true,
             LivingKind::Skeleton(_) => true,
             LivingKind::Zombie(_) => true,
-            LivingKind::PigZombie(pig_zombie) => pig_zombie.anger,
+            LivingKind::PigZombie(pig_zombie) => pig_zombie.anger != 0,
             LivingKind::Wolf(wolf) => wolf.angry,
-            LivingKind::Spider(_) => common::get_entity_light(world, base).brightness() < 0.5,
+            LivingKind::Spider(_) => {
+                world
+                    .get_dimension_info()
+                    .brightness(common::get_entity_light(world, base))
+                    < 0.5
+            }
             _ => false,
         };
 
@@ -195,6 +275,7 @@ fn tick_ground_ai(world: &mut World, id: u32, entity: &mut Entity) {
         // If the entity has not attacked and if the path is not none, there is 1.25%
         // chance to recompute the path, if the path is none there is 2.484375% chance.
         if !should_strafe
+            && !movement_ceased
             && ((living.path.is_none() && base.rand.next_int_bounded(80) == 0)
                 || base.rand.next_int_bounded(80) == 0)
         {
@@ -235,7 +316,12 @@ fn tick_ground_ai(world: &mut World, id: u32, entity: &mut Entity) {
         // trace!("entity #{id}, path finding: {}", target.pos);
 
         let path = world
-            .find_path_from_bounding_box(base.bb, target.pos, PATH_FINDER_MAX_DIST)
+            .find_path_from_bounding_box(
+                base.bb,
+                target.pos,
+                PATH_FINDER_MAX_DIST,
+                PathOptions::default(),
+            )
             .map(Path::from);
 
         if target.overwrite || path.is_some() {
@@ -279,6 +365,7 @@ fn tick_ground_ai(world: &mut World, id: u32, entity: &mut Entity) {
                 let dz = next_pos.z - base.pos.z;
 
                 let move_speed = match living_kind {
+                    LivingKind::PigZombie(_) if living.attack_target.is_some() => 0.95,
                     LivingKind::Giant(_) | LivingKind::Zombie(_) | LivingKind::PigZombie(_) => 0.5,
                     LivingKind::Spider(_) => 0.8,
                     _ => 0.5,
@@ -328,6 +415,161 @@ fn tick_ground_ai(world: &mut World, id: u32, entity: &mut Entity) {
     tick_living_ai(world, id, entity);
 }
 
+/// Tick the creeper-specific AI, run after the ground AI. A creeper without target
+/// stops being ignited and shrinks back.
+///
+/// REF: EntityCreeper::updatePlayerActionState
+fn tick_creeper_ai(world: &mut World, id: u32, entity: &mut Entity) {
+    let_expect!(Entity(_, BaseKind::Living(living, LivingKind::Creeper(creeper))) = entity);
+
+    if living.attack_target.is_none() && creeper.fuse_time > 0 {
+        creeper.fuse_time -= 1;
+        if creeper.ignited {
+            creeper.ignited = false;
+            world.push_event(Event::Entity {
+                id,
+                inner: EntityEvent::Metadata,
+            });
+        }
+    }
+}
+
+/// Tick the pig zombie-specific AI, run after the ground AI. The pig zombie calms down
+/// and forget its target when its anger runs out, and plays its angry sound after being
+/// provoked.
+///
+/// REF: EntityPigZombie::onUpdate
+fn tick_pig_zombie_ai(world: &mut World, id: u32, entity: &mut Entity) {
+    let_expect!(
+        Entity(
+            _,
+            BaseKind::Living(living, LivingKind::PigZombie(pig_zombie))
+        ) = entity
+    );
+
+    if pig_zombie.sound_delay > 0 {
+        pig_zombie.sound_delay -= 1;
+        if pig_zombie.sound_delay == 0 {
+            world.push_event(Event::Entity {
+                id,
+                inner: EntityEvent::AmbientSound {
+                    sound: "mob.zombiepig.zpigangry",
+                },
+            });
+        }
+    }
+
+    if pig_zombie.anger > 0 {
+        pig_zombie.anger -= 1;
+        if pig_zombie.anger == 0 {
+            trace!("entity #{id}, pig zombie calmed down");
+            living.attack_target = None;
+        }
+    }
+}
+
+/// Tick the wolf-specific AI, run after the ground AI. Tamed wolves follow their owner
+/// and teleport to it when too far, and wild wolves randomly hunt sheep around.
+///
+/// REF: EntityWolf::updatePlayerActionState
+fn tick_wolf_ai(world: &mut World, id: u32, entity: &mut Entity) {
+    /// Maximum distance for the path finder when following the owner.
+    const PATH_FINDER_MAX_DIST: f32 = 16.0;
+
+    let_expect!(Entity(base, BaseKind::Living(living, LivingKind::Wolf(wolf))) = entity);
+
+    let was_sitting = wolf.sitting;
+    let mut teleport_pos = None;
+
+    if let Some(owner) = &wolf.owner {
+        if !wolf.sitting && living.path.is_none() {
+            // Copy the owner position and feet because we need the world for path finding.
+            let owner_pos = world
+                .iter_player_entities()
+                .find_map(|(_, player)| match player {
+                    Entity(player_base, BaseKind::Living(_, LivingKind::Human(human)))
+                        if human.username.eq_ignore_ascii_case(owner) =>
+                    {
+                        Some((player_base.pos, player_base.bb.min.y))
+                    }
+                    _ => None,
+                });
+
+            if let Some((owner_pos, owner_feet)) = owner_pos {
+                let dist = owner_pos.distance(base.pos);
+                if dist > 5.0 {
+                    living.path = world
+                        .find_path_from_bounding_box(
+                            base.bb,
+                            owner_pos,
+                            PATH_FINDER_MAX_DIST,
+                            PathOptions::default(),
+                        )
+                        .map(Path::from);
+
+                    // If the owner can't be reached and is too far, teleport on a free
+                    // solid block around it, avoiding the owner's column.
+                    if living.path.is_none() && dist > 12.0 {
+                        let start = IVec3 {
+                            x: owner_pos.x.floor() as i32 - 2,
+                            y: owner_feet.floor() as i32,
+                            z: owner_pos.z.floor() as i32 - 2,
+                        };
+                        teleport_pos = (0..=4)
+                            .flat_map(|dx| (0..=4).map(move |dz| (dx, dz)))
+                            .filter(|&(dx, dz)| dx < 1 || dz < 1 || dx > 3 || dz > 3)
+                            .map(|(dx, dz)| start + IVec3::new(dx, 0, dz))
+                            .find(|&pos| {
+                                world.is_block_opaque_cube(pos - IVec3::Y)
+                                    && !world.is_block_opaque_cube(pos)
+                                    && !world.is_block_opaque_cube(pos + IVec3::Y)
+                            })
+                            .map(|pos| pos.as_dvec3() + DVec3::new(0.5, 0.0, 0.5));
+                    }
+                }
+            } else if !base.in_water {
+                // The owner is not online, wait for it.
+                wolf.sitting = true;
+            }
+        }
+    } else if living.attack_target.is_none()
+        && living.path.is_none()
+        && base.rand.next_int_bounded(100) == 0
+    {
+        common::ENTITY_ID.with_borrow_mut(|sheep_ids| {
+            debug_assert!(sheep_ids.is_empty());
+
+            sheep_ids.extend(
+                world
+                    .iter_entities_colliding(base.bb.inflate(DVec3::new(16.0, 4.0, 16.0)))
+                    .filter(|(_, entity)| entity.has::<Sheep>())
+                    .map(|(sheep_id, _)| sheep_id),
+            );
+
+            if !sheep_ids.is_empty() {
+                living.attack_target = Some(base.rand.next_choice(sheep_ids));
+            }
+
+            sheep_ids.clear();
+        });
+    }
+
+    if base.in_water {
+        wolf.sitting = false;
+    }
+
+    if was_sitting != wolf.sitting {
+        world.push_event(Event::Entity {
+            id,
+            inner: EntityEvent::Metadata,
+        });
+    }
+
+    if let Some(pos) = teleport_pos {
+        entity.teleport(pos);
+    }
+}
+
 /// Tick a slime entity AI.
 ///
 /// REF: EntitySlime::updatePlayerActionState
@@ -372,6 +614,28 @@ fn tick_slime_ai(world: &mut World, id: u32, entity: &mut Entity) {
             living.accel_forward = 0.0;
         }
     }
+
+    // Slimes bigger than the smallest ones damage the closest player they touch.
+    // REF: EntitySlime::onCollideWithPlayer
+    let size = slime.real_size();
+    if size > 1 {
+        let attacked_id = closest_player.and_then(|(player_id, Entity(player_base, _), dist)| {
+            (dist < 0.6 * size as f64
+                && player_base
+                    .bb
+                    .inflate(DVec3::new(1.0, 0.0, 1.0))
+                    .intersects(base.bb)
+                && common::can_eye_track(world, base, player_base))
+            .then_some(player_id)
+        });
+
+        if let Some(Entity(player_base, _)) = attacked_id.and_then(|id| world.get_entity_mut(id)) {
+            player_base.hurt.push(Hurt {
+                damage: size,
+                origin_id: Some(id),
+            });
+        }
+    }
 }
 
 /// Tick a ghast entity AI.
@@ -534,6 +798,15 @@ fn tick_natural_despawn(world: &mut World, id: u32, entity: &mut Entity) -> bool
         return false;
     };
 
+    // Hostile entities are removed on peaceful difficulty, even persistent ones.
+    // REF: EntityMob::onUpdate
+    if living_kind.entity_kind().category() == EntityCategory::Mob
+        && world.get_difficulty() == Difficulty::Peaceful
+    {
+        world.remove_entity(id, "peaceful difficulty");
+        return true;
+    }
+
     // Can't despawn persistent entities.
     if living.artificial {
         return false;
@@ -550,7 +823,10 @@ fn tick_natural_despawn(world: &mut World, id: u32, entity: &mut Entity) -> bool
     // faster increment.
     living.wander_time = living.wander_time.saturating_add(1);
     if living_kind.entity_kind().category() == EntityCategory::Mob
-        && common::get_entity_light(world, base).brightness() > 0.5
+        && world
+            .get_dimension_info()
+            .brightness(common::get_entity_light(world, base))
+            > 0.5
     {
         living.wander_time = living.wander_time.saturating_add(2);
     }
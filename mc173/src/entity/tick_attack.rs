@@ -2,7 +2,7 @@
 
 use glam::{DVec3, Vec3Swizzles};
 
-use crate::entity::{Arrow, Hurt};
+use crate::entity::{Arrow, Creeper, Hurt};
 use crate::world::{EntityEvent, Event, World};
 
 use super::common::{self, let_expect};
@@ -31,6 +31,15 @@ pub(super) fn tick_attack(
             eye_track,
             should_strafe,
         ),
+        Entity(_, BaseKind::Living(_, LivingKind::Wolf(_))) => tick_wolf_attack(
+            world,
+            id,
+            entity,
+            target_id,
+            dist_squared,
+            eye_track,
+            should_strafe,
+        ),
         Entity(_, BaseKind::Living(_, LivingKind::Creeper(_))) => tick_creeper_attack(
             world,
             id,
@@ -89,6 +98,7 @@ fn tick_mob_attack(
                 LivingKind::Giant(_) => 50,
                 LivingKind::PigZombie(_) => 5,
                 LivingKind::Zombie(_) => 5,
+                LivingKind::Wolf(wolf) if wolf.owner.is_some() => 4,
                 _ => 2,
             };
 
@@ -98,6 +108,18 @@ fn tick_mob_attack(
                 damage: attack_damage,
                 origin_id: Some(id),
             });
+
+            // Burning zombies propagate their fire to the entity they hit.
+            // PARITY: This is not part of the Notchian beta server.
+            if let LivingKind::Zombie(_) = living_kind {
+                if base.fire_time > 0 && target_base.fire_time == 0 {
+                    target_base.fire_time = 100;
+                    world.push_event(Event::Entity {
+                        id: target_id,
+                        inner: EntityEvent::Metadata,
+                    });
+                }
+            }
         }
     }
 }
@@ -120,7 +142,10 @@ fn tick_spider_attack(
     let_expect!(Entity(base, BaseKind::Living(living, LivingKind::Spider(_))) = entity);
 
     // If the brightness has changed, there if 1% chance to loose target.
-    if common::get_entity_light(world, base).brightness() > 0.5
+    if world
+        .get_dimension_info()
+        .brightness(common::get_entity_light(world, base))
+        > 0.5
         && base.rand.next_int_bounded(100) == 0
     {
         // Loose target because it's too bright.
@@ -153,6 +178,51 @@ fn tick_spider_attack(
     }
 }
 
+/// REF: EntityWolf::attackEntity
+fn tick_wolf_attack(
+    world: &mut World,
+    id: u32,
+    entity: &mut Entity,
+    target_id: u32,
+    dist_squared: f64,
+    eye_track: bool,
+    should_strafe: &mut bool,
+) {
+    /// Minimum distance from the target to trigger a leap of the wolf.
+    const MIN_DIST_SQUARED: f64 = 2.0 * 2.0;
+    /// Maximum distance from the target to trigger a leap of the wolf.
+    const MAX_DIST_SQUARED: f64 = 6.0 * 6.0;
+
+    let_expect!(Entity(base, BaseKind::Living(_, LivingKind::Wolf(_))) = entity);
+
+    if dist_squared > MIN_DIST_SQUARED
+        && dist_squared < MAX_DIST_SQUARED
+        && base.rand.next_int_bounded(10) == 0
+    {
+        // If the target is in certain range, there is 10% chance of leaping.
+        if base.on_ground {
+            let Entity(target_base, _) = world.get_entity(target_id).unwrap();
+
+            let delta = target_base.pos.xz() - base.pos.xz();
+            let h_dist = delta.length();
+            let h_vel = delta / h_dist * 0.5 * 0.8 + base.vel.xz() * 0.2;
+            base.vel = DVec3::new(h_vel.x, 0.4, h_vel.y);
+        }
+    } else {
+        // PARITY: The Notchian wolf bites at 1.5 blocks regardless of the attack time,
+        //  we reuse the common mob attack logic instead.
+        tick_mob_attack(
+            world,
+            id,
+            entity,
+            target_id,
+            dist_squared,
+            eye_track,
+            should_strafe,
+        )
+    }
+}
+
 /// REF: EntityCreeper::attackEntity
 fn tick_creeper_attack(
     world: &mut World,
@@ -161,47 +231,47 @@ fn tick_creeper_attack(
     _target_id: u32,
     dist_squared: f64,
     eye_track: bool,
-    _should_strafe: &mut bool,
+    should_strafe: &mut bool,
 ) {
-    /// Minimum distance from a player to trigger a climb of the spider.
+    /// Maximum distance from the target for an idle creeper to ignite.
     const IDLE_MAX_DIST_SQUARED: f64 = 3.0 * 3.0;
-    /// Maximum distance from a player to trigger a climb of the spider.
+    /// Maximum distance from the target for an ignited creeper to keep swelling.
     const IGNITED_MAX_DIST_SQUARED: f64 = 7.0 * 7.0;
 
     let_expect!(Entity(base, BaseKind::Living(_, LivingKind::Creeper(creeper))) = entity);
 
     // Check if the creeper should be ignited depending on its current state.
-    let ignited = eye_track
-        && (creeper.ignited_time.is_none() && dist_squared < IDLE_MAX_DIST_SQUARED)
-        || (creeper.ignited_time.is_some() && dist_squared < IGNITED_MAX_DIST_SQUARED);
+    let max_dist_squared = match creeper.ignited {
+        true => IGNITED_MAX_DIST_SQUARED,
+        false => IDLE_MAX_DIST_SQUARED,
+    };
 
-    if ignited {
-        if creeper.ignited_time.is_none() {
-            world.push_event(Event::Entity {
-                id,
-                inner: EntityEvent::Metadata,
-            });
-        }
+    let ignited = eye_track && dist_squared < max_dist_squared;
+    if ignited != creeper.ignited {
+        creeper.ignited = ignited;
+        world.push_event(Event::Entity {
+            id,
+            inner: EntityEvent::Metadata,
+        });
+    }
 
-        let ignited_time = creeper.ignited_time.unwrap_or(0) + 1;
-        creeper.ignited_time = Some(ignited_time);
+    if ignited {
+        // The creeper no longer wanders while swelling.
+        *should_strafe = true;
 
-        if ignited_time >= 30 {
+        creeper.fuse_time += 1;
+        if creeper.fuse_time >= Creeper::FUSE_TIME {
             // Kill the creeper and return none in order to loose focus on the entity.
             world.remove_entity(id, "creeper explode");
 
             if creeper.powered {
-                world.explode(base.pos, 6.0, false, Some(id));
+                world.explode_by_mob(base.pos, 6.0, false, Some(id));
             } else {
-                world.explode(base.pos, 3.0, false, Some(id));
+                world.explode_by_mob(base.pos, 3.0, false, Some(id));
             }
         }
-    } else if creeper.ignited_time.is_some() {
-        world.push_event(Event::Entity {
-            id,
-            inner: EntityEvent::Metadata,
-        });
-        creeper.ignited_time = None;
+    } else {
+        creeper.fuse_time = creeper.fuse_time.saturating_sub(1);
     }
 }
 
@@ -217,11 +287,15 @@ fn tick_skeleton_attack(
 ) {
     const MAX_DIST_SQUARED: f64 = 10.0 * 10.0;
 
+    let_expect!(Entity(base, BaseKind::Living(living, LivingKind::Skeleton(_))) = entity);
+
+    // The attack cooldown keeps decreasing even when the target is out of range.
+    living.attack_time = living.attack_time.saturating_sub(1);
+
     if eye_track && dist_squared < MAX_DIST_SQUARED {
-        let_expect!(Entity(base, BaseKind::Living(living, LivingKind::Skeleton(_))) = entity);
         let Entity(target_base, _) = world.get_entity(target_id).unwrap();
+        let target_pos = target_base.pos;
 
-        living.attack_time = living.attack_time.saturating_sub(1);
         if living.attack_time == 0 {
             living.attack_time = 30;
 
@@ -247,7 +321,48 @@ fn tick_skeleton_attack(
             world.spawn_entity(arrow);
         }
 
-        // TODO: Look toward target
+        // Face the target while strafing around it.
+        let dx = target_pos.x - base.pos.x;
+        let dz = target_pos.z - base.pos.z;
+        base.look.x = f64::atan2(dz, dx) as f32 - std::f32::consts::FRAC_PI_2;
         *should_strafe = true;
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::block;
+
+    #[test]
+    fn skeleton_shoot() {
+        use crate::entity::{Human, ProjectileKind, Skeleton};
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .player(Human::new_default(DVec3::new(4.5, 10.0, 8.5)))
+            .entity(Skeleton::new_default(DVec3::new(10.5, 10.0, 8.5)))
+            .build_with_entities();
+
+        for _ in 0..3 {
+            world.tick();
+        }
+
+        // The skeleton faces the player and shot an arrow toward it.
+        let Entity(base, _) = world.get_entity(ids[1]).unwrap();
+        assert!((base.look.x - std::f32::consts::FRAC_PI_2).abs() < 0.1);
+        let arrows = world
+            .iter_entities()
+            .filter_map(|(_, entity)| match entity {
+                Entity(base, BaseKind::Projectile(projectile, ProjectileKind::Arrow(_))) => {
+                    Some((base.vel.x, projectile.owner_id))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(arrows.len(), 1);
+        assert!(arrows[0].0 < 0.0);
+        assert_eq!(arrows[0].1, Some(ids[1]));
+    }
+}
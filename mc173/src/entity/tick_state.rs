@@ -6,25 +6,47 @@ use glam::DVec3;
 
 use crate::block;
 use crate::block::material::Material;
-use crate::entity::{Hurt, LivingKind, ProjectileKind};
-use crate::item::{self, ItemStack};
-use crate::world::{EntityEvent, Event, World};
+use crate::entity::{EntityKind, Human, Hurt, LivingKind, ProjectileKind};
+use crate::geom::BoundingBox;
+use crate::item::{self, armor, ItemStack};
+use crate::world::{Difficulty, EntityEvent, Event, LocalWeather, SpawnReason, World};
 
 use super::common::{self, let_expect};
-use super::{Base, BaseKind, Entity, Living};
+use super::sound;
+use super::{Base, BaseKind, Entity, EntityCategory, Living, PigZombie, Skeleton, Slime, Wolf};
+
+/// Maximum air time of entities, in ticks.
+const MAX_AIR_TIME: u32 = 300;
 
 /// Tick base method that is common to every entity kind, this is split in Notchian impl
 /// so we split it here.
 pub(super) fn tick_state(world: &mut World, id: u32, entity: &mut Entity) {
+    let was_burning = entity.0.fire_time > 0;
+
     match entity {
         Entity(_, BaseKind::Living(_, _)) => tick_state_living(world, id, entity),
         Entity(_, _) => tick_state_base(world, id, entity),
     }
+
+    // The burning flag is part of the entity metadata, so we notify when it changes.
+    if was_burning != (entity.0.fire_time > 0) {
+        world.push_event(Event::Entity {
+            id,
+            inner: EntityEvent::Metadata,
+        });
+    }
+}
+
+/// Return true if the given living entity kind is immune to fire and lava damages.
+pub(super) fn is_immune_to_fire(living_kind: &LivingKind) -> bool {
+    matches!(living_kind, LivingKind::Ghast(_) | LivingKind::PigZombie(_))
 }
 
 /// REF: Entity::onEntityUpdate
 fn tick_state_base(world: &mut World, id: u32, entity: &mut Entity) {
     let Entity(base, base_kind) = entity;
+    let immune_to_fire =
+        matches!(base_kind, BaseKind::Living(_, living_kind) if is_immune_to_fire(living_kind));
 
     // Compute the bounding box used for water collision, it depends on the entity kind.
     let water_bb = match base_kind {
@@ -41,7 +63,7 @@ fn tick_state_base(world: &mut World, id: u32, entity: &mut Entity) {
             let height = block::fluid::get_actual_height(metadata);
             if water_bb.max.y.add(1.0).floor() >= pos.y as f64 + height as f64 {
                 base.in_water = true;
-                water_vel += common::calc_fluid_vel(world, pos, material, metadata);
+                water_vel += block::fluid::calc_flow(world, pos);
             }
         }
     }
@@ -56,10 +78,7 @@ fn tick_state_base(world: &mut World, id: u32, entity: &mut Entity) {
     if base.in_water {
         base.fire_time = 0;
         base.fall_distance = 0.0;
-    } else if matches!(
-        base_kind,
-        BaseKind::Living(_, LivingKind::Ghast(_) | LivingKind::PigZombie(_))
-    ) {
+    } else if immune_to_fire {
         base.fire_time = 0;
     }
 
@@ -79,6 +98,69 @@ fn tick_state_base(world: &mut World, id: u32, entity: &mut Entity) {
         .iter_blocks_in_box(lava_bb)
         .any(|(_, block, _)| block::material::get_material(block) == Material::Lava);
 
+    // Lava damage and fire time, this also destroys item entities.
+    if base.in_lava && !immune_to_fire {
+        base.hurt.push(Hurt {
+            damage: 4,
+            origin_id: None,
+        });
+        base.fire_time = 600;
+    }
+
+    // Check blocks colliding with the entity, for fire and cactus.
+    // PARITY: The Notchian impl does this check when moving the entity, we do it here
+    //  so that it's applied to all entities every tick.
+    let collide_bb = base.bb.inflate(DVec3::splat(-0.001));
+    let mut in_fire = false;
+    let mut in_cactus = false;
+    for (_, block, _) in world.iter_blocks_in_box(collide_bb) {
+        match block {
+            block::FIRE => in_fire = true,
+            block::CACTUS => in_cactus = true,
+            _ => {}
+        }
+    }
+
+    // Entities touching cactus are damaged.
+    // REF: BlockCactus::onEntityCollidedWithBlock
+    if in_cactus {
+        base.hurt.push(Hurt {
+            damage: 1,
+            origin_id: None,
+        });
+    }
+
+    // Entities are wet when in water or under the rain.
+    // REF: Entity::isWet
+    let wet = base.in_water
+        || ((in_fire || base.fire_time > 0)
+            && world.get_local_weather(base.pos.floor().as_ivec3()) == LocalWeather::Rain);
+
+    // Entities touching fire blocks are damaged and set on fire, unless wet.
+    if in_fire {
+        if !immune_to_fire {
+            base.hurt.push(Hurt {
+                damage: 1,
+                origin_id: None,
+            });
+        }
+
+        if !wet {
+            if base.fire_time == 0 {
+                base.fire_time = 300;
+            } else {
+                // Compensate the decrement done above, so that fire time is kept.
+                base.fire_time += 1;
+            }
+        }
+    }
+
+    // Wet entities are extinguished, this also cancels the fire time given by lava.
+    // REF: Entity::moveEntity
+    if wet {
+        base.fire_time = 0;
+    }
+
     // If this entity can pickup other ones, trigger an event.
     if base.can_pickup {
         // Temporarily owned vector to avoid allocation.
@@ -89,15 +171,13 @@ fn tick_state_base(world: &mut World, id: u32, entity: &mut Entity) {
                 world.iter_entities_colliding(base.bb.inflate(DVec3::new(1.0, 0.0, 1.0)))
             {
                 match &entity.1 {
-                    BaseKind::Item(item) => {
-                        if item.frozen_time == 0 {
-                            picked_up_entities.push(entity_id);
-                        }
+                    BaseKind::Item(item) if item.can_pickup(id) => {
+                        picked_up_entities.push(entity_id);
                     }
-                    BaseKind::Projectile(projectile, ProjectileKind::Arrow(arrow)) => {
-                        if projectile.state.is_some() && arrow.from_player {
-                            picked_up_entities.push(entity_id);
-                        }
+                    BaseKind::Projectile(projectile, ProjectileKind::Arrow(arrow))
+                        if projectile.state.is_some() && arrow.from_player =>
+                    {
+                        picked_up_entities.push(entity_id);
                     }
                     _ => {}
                 }
@@ -120,6 +200,21 @@ fn tick_state_living(world: &mut World, id: u32, entity: &mut Entity) {
     // Super call.
     tick_state_base(world, id, entity);
 
+    // Randomly play the ambient sound, the chance increases with the time since the
+    // last one.
+    let_expect!(Entity(base, BaseKind::Living(living, _)) = entity);
+    let ambient_time = living.ambient_time;
+    living.ambient_time = ambient_time.saturating_add(1);
+    if base.rand.next_int_bounded(1000) < ambient_time as i32 {
+        living.ambient_time = -sound::AMBIENT_INTERVAL;
+        if let Some(sound) = sound::get_ambient_sound(entity) {
+            world.push_event(Event::Entity {
+                id,
+                inner: EntityEvent::AmbientSound { sound },
+            });
+        }
+    }
+
     let_expect!(Entity(base, BaseKind::Living(living, living_kind)) = entity);
 
     // Suffocate entities if inside opaque cubes (except for sleeping players).
@@ -151,31 +246,69 @@ fn tick_state_living(world: &mut World, id: u32, entity: &mut Entity) {
 
     // TODO: Air time underwater
 
-    // If the zombie/skeleton see the sky light, set it on fire.
+    // Squids breathe in water and suffocate out of it, two damages every second once
+    // their air is exhausted.
+    // REF: EntityWaterMob::onEntityUpdate
+    if let LivingKind::Squid(_) = living_kind {
+        if common::is_eye_in_fluid(world, base, Material::Water) {
+            base.air_time = MAX_AIR_TIME;
+        } else if base.air_time == 0 {
+            // PARITY: The Notchian impl decrements air down to -20, we can't go below
+            // zero so we reset to 20 instead, which gives the same damage interval.
+            base.air_time = 20;
+            base.hurt.push(Hurt {
+                damage: 2,
+                origin_id: None,
+            });
+        } else {
+            base.air_time -= 1;
+        }
+    }
+
+    // If the zombie/skeleton see the sky light during day, set it on fire.
+    // REF: EntityZombie::onLivingUpdate
     if matches!(living_kind, LivingKind::Zombie(_) | LivingKind::Skeleton(_)) {
         let block_pos = base.pos.floor().as_ivec3();
         let height = world.get_height(block_pos).unwrap_or(0);
         if block_pos.y >= height {
             let light = common::get_entity_light(world, base);
+            let brightness = world.get_dimension_info().brightness(light);
             if light.sky_real >= 12
-                && base.rand.next_float() * 30.0 < (light.brightness() - 0.4) * 2.0
+                && brightness > 0.5
+                && base.rand.next_float() * 30.0 < (brightness - 0.4) * 2.0
             {
                 base.fire_time = 300;
             }
         }
     }
 
-    // Lava damage and fire time.
-    if base.in_lava {
-        base.hurt.push(Hurt {
-            damage: 4,
-            origin_id: None,
-        });
-        base.fire_time = 600;
+    // Players regenerate their health on peaceful difficulty.
+    // PARITY: The Notchian condition is `ticksExisted % 20 * 12 == 0`, which heals every
+    //  20 ticks because of operators precedence, we keep this behavior.
+    // REF: EntityPlayer::onLivingUpdate
+    if let LivingKind::Human(_) = living_kind {
+        if world.get_difficulty() == Difficulty::Peaceful
+            && living.health != 0
+            && living.health < 20
+            && base.lifetime % 20 == 0
+        {
+            living.health += 1;
+            world.push_event(Event::Entity {
+                id,
+                inner: EntityEvent::Heal,
+            });
+        }
     }
 
     // Decrease countdowns.
     living.hurt_time = living.hurt_time.saturating_sub(1);
+    if let LivingKind::Human(human) = living_kind {
+        human.invulnerable_time = human.invulnerable_time.saturating_sub(1);
+        human.combat_time = human.combat_time.saturating_sub(1);
+        if human.combat_time == 0 {
+            human.combat_attacker = None;
+        }
+    }
 
     /// The hurt time when hit for the first time.
     /// PARITY: The Notchian impl doesn't actually use hurt time but another variable
@@ -185,13 +318,70 @@ fn tick_state_living(world: &mut World, id: u32, entity: &mut Entity) {
 
     // We keep the entity that killed it.
     let mut killer_id = None;
+    // The health is part of the wolf metadata, so we notify when it changes.
+    let prev_health = living.health;
 
-    while let Some(hurt) = base.hurt.pop() {
+    while let Some(mut hurt) = base.hurt.pop() {
         // Don't go further if entity is already dead.
         if living.health == 0 {
             break;
         }
 
+        if let (LivingKind::Human(human), Some(origin_id)) = (&mut *living_kind, hurt.origin_id) {
+            // Players are invulnerable to other entities for a short time after respawn.
+            if human.is_invulnerable() {
+                continue;
+            }
+
+            let origin_kind = world.get_entity(origin_id).map(Entity::kind);
+
+            // Damage dealt by hostile entities to players depends on difficulty.
+            // REF: EntityPlayer::attackEntityFrom
+            if origin_kind.is_some_and(|kind| kind.category() == EntityCategory::Mob) {
+                hurt.damage = world.get_difficulty().scale_player_damage(hurt.damage);
+                if hurt.damage == 0 {
+                    continue;
+                }
+            }
+
+            // Players damaged by another player are tagged in combat, if PvP is allowed.
+            if origin_kind == Some(EntityKind::Human) && origin_id != id {
+                if !world.can_pvp(origin_id, base.pos) {
+                    world.push_event(Event::Entity {
+                        id,
+                        inner: EntityEvent::PvpDenied {
+                            attacker_id: origin_id,
+                        },
+                    });
+                    continue;
+                }
+
+                human.combat_time = Human::COMBAT_TAG_TIME;
+                human.combat_attacker = Some(origin_id);
+            }
+        }
+
+        // Wolves stand up when hurt, and may get angry at their attacker.
+        if let LivingKind::Wolf(wolf) = &mut *living_kind {
+            if wolf.sitting {
+                wolf.sitting = false;
+                world.push_event(Event::Entity {
+                    id,
+                    inner: EntityEvent::Metadata,
+                });
+            }
+
+            if let Some(origin_id) = hurt.origin_id {
+                hurt_wolf(world, id, base, living, wolf, origin_id);
+            }
+        }
+
+        if let LivingKind::PigZombie(pig_zombie) = &mut *living_kind {
+            if let Some(origin_id) = hurt.origin_id {
+                hurt_pig_zombie(world, base, living, pig_zombie, origin_id);
+            }
+        }
+
         // Reset the interaction time of the entity when it get hurt.
         living.wander_time = 0;
 
@@ -225,6 +415,23 @@ fn tick_state_living(world: &mut World, id: u32, entity: &mut Entity) {
             living.hurt_last_damage = hurt.damage;
         }
 
+        // Players damage are reduced by their armor, which is damaged.
+        // REF: EntityPlayer::damageEntity
+        if let LivingKind::Human(human) = &mut *living_kind {
+            if actual_damage != 0 {
+                let armor = 25 - armor::get_total_armor(&human.armor) as u32;
+                let total = actual_damage as u32 * armor + human.damage_remainder as u32;
+                if armor::damage_armor(&mut human.armor, actual_damage) {
+                    world.push_event(Event::Entity {
+                        id,
+                        inner: EntityEvent::Armor,
+                    });
+                }
+                actual_damage = (total / 25).min(u16::MAX as u32) as u16;
+                human.damage_remainder = (total % 25) as u16;
+            }
+        }
+
         // Apply damage.
         if actual_damage != 0 {
             living.health = living.health.saturating_sub(actual_damage);
@@ -233,8 +440,15 @@ fn tick_state_living(world: &mut World, id: u32, entity: &mut Entity) {
             if living.health == 0 {
                 killer_id = hurt.origin_id;
             }
+        }
+    }
 
-            // TODO: For players, take armor into account.
+    if let LivingKind::Wolf(_) = living_kind {
+        if living.health != prev_health {
+            world.push_event(Event::Entity {
+                id,
+                inner: EntityEvent::Metadata,
+            });
         }
     }
 
@@ -247,12 +461,22 @@ fn tick_state_living(world: &mut World, id: u32, entity: &mut Entity) {
             });
             spawn_living_loot(world, base, living, living_kind);
 
+            // PARITY: Saddled pigs don't drop their saddle in the Notchian beta 1.7.3
+            // server, this is a later behavior.
+            if let LivingKind::Pig(pig) = living_kind {
+                if pig.saddle {
+                    pig.saddle = false;
+                    world.spawn_loot(base.pos, ItemStack::new_single(item::SADDLE, 0), 0.0);
+                }
+            }
+
             // If we know the killer id and we are a creeper, check if this the killer
             // is a skeleton, in which case we drop a music disk.
             if let LivingKind::Creeper(_) = living_kind {
                 if let Some(killer_id) = killer_id {
-                    if let Some(Entity(_, BaseKind::Living(_, LivingKind::Skeleton(_)))) =
-                        world.get_entity(killer_id)
+                    if world
+                        .get_entity(killer_id)
+                        .is_some_and(|killer| killer.has::<Skeleton>())
                     {
                         let item = base.rand.next_choice(&[item::RECORD_13, item::RECORD_CAT]);
                         let stack = ItemStack::new_single(item, 0);
@@ -264,11 +488,167 @@ fn tick_state_living(world: &mut World, id: u32, entity: &mut Entity) {
 
         living.death_time += 1;
         if living.death_time > 20 {
+            if let LivingKind::Slime(slime) = living_kind {
+                split_slime(world, base, slime);
+            }
             world.remove_entity(id, "health dead");
         }
     }
 }
 
+/// Handle a wolf hurt by another living entity. Wild wolves get angry against players and
+/// call the wild wolves around for help, angry or tamed wolves fight back, unless hurt by
+/// their owner.
+///
+/// REF: EntityWolf::attackEntityFrom
+fn hurt_wolf(
+    world: &mut World,
+    id: u32,
+    base: &Base,
+    living: &mut Living,
+    wolf: &mut Wolf,
+    origin_id: u32,
+) {
+    if origin_id == id {
+        return;
+    }
+
+    let (origin_player, origin_owner) = match world.get_entity(origin_id) {
+        Some(Entity(_, BaseKind::Living(_, LivingKind::Human(human)))) => (
+            true,
+            wolf.owner
+                .as_deref()
+                .is_some_and(|owner| owner.eq_ignore_ascii_case(&human.username)),
+        ),
+        Some(Entity(_, BaseKind::Living(_, _))) => (false, false),
+        _ => return,
+    };
+
+    if wolf.owner.is_some() || wolf.angry {
+        if !origin_owner {
+            living.attack_target = Some(origin_id);
+        }
+        return;
+    }
+
+    if origin_player {
+        wolf.angry = true;
+        living.attack_target = Some(origin_id);
+        world.push_event(Event::Entity {
+            id,
+            inner: EntityEvent::Metadata,
+        });
+    } else if living.attack_target.is_none() {
+        living.attack_target = Some(origin_id);
+    }
+
+    // Wild wolves of the pack without target join the fight.
+    common::ENTITY_ID.with_borrow_mut(|wolf_ids| {
+        debug_assert!(wolf_ids.is_empty());
+
+        let pack_bb = (BoundingBox::CUBE + base.pos).inflate(DVec3::new(16.0, 4.0, 16.0));
+        wolf_ids.extend(
+            world
+                .iter_entities_colliding(pack_bb)
+                .filter(|(_, entity)| entity.has::<Wolf>())
+                .map(|(wolf_id, _)| wolf_id),
+        );
+
+        for wolf_id in wolf_ids.drain(..) {
+            let Some(Entity(_, BaseKind::Living(pack_living, LivingKind::Wolf(pack_wolf)))) =
+                world.get_entity_mut(wolf_id)
+            else {
+                continue;
+            };
+
+            if pack_wolf.owner.is_some() || pack_living.attack_target.is_some() {
+                continue;
+            }
+
+            pack_living.attack_target = Some(origin_id);
+            if origin_player && !pack_wolf.angry {
+                pack_wolf.angry = true;
+                world.push_event(Event::Entity {
+                    id: wolf_id,
+                    inner: EntityEvent::Metadata,
+                });
+            }
+        }
+    });
+}
+
+/// Handle a pig zombie hurt by another entity. When hurt by a player, the pig zombie and
+/// all pig zombies around get angry against that player.
+///
+/// REF: EntityPigZombie::attackEntityFrom
+fn hurt_pig_zombie(
+    world: &mut World,
+    base: &mut Base,
+    living: &mut Living,
+    pig_zombie: &mut PigZombie,
+    origin_id: u32,
+) {
+    if !world
+        .get_entity(origin_id)
+        .is_some_and(|entity| entity.has::<Human>())
+    {
+        return;
+    }
+
+    common::ENTITY_ID.with_borrow_mut(|pig_zombie_ids| {
+        debug_assert!(pig_zombie_ids.is_empty());
+
+        let group_bb = base.bb.inflate(DVec3::splat(32.0));
+        pig_zombie_ids.extend(
+            world
+                .iter_entities_colliding(group_bb)
+                .filter(|(_, entity)| entity.has::<PigZombie>())
+                .map(|(pig_zombie_id, _)| pig_zombie_id),
+        );
+
+        for pig_zombie_id in pig_zombie_ids.drain(..) {
+            let Some(Entity(
+                group_base,
+                BaseKind::Living(group_living, LivingKind::PigZombie(group_pig_zombie)),
+            )) = world.get_entity_mut(pig_zombie_id)
+            else {
+                continue;
+            };
+
+            group_pig_zombie.become_angry(group_base, group_living, origin_id);
+        }
+    });
+
+    pig_zombie.become_angry(base, living, origin_id);
+}
+
+/// Split a dead slime into 4 slimes of half its size, if it isn't the smallest one.
+///
+/// REF: EntitySlime::setEntityDead
+fn split_slime(world: &mut World, base: &mut Base, slime: &Slime) {
+    let size = slime.real_size();
+    if size <= 1 {
+        return;
+    }
+
+    let child_size = size / 2;
+    for i in 0..4 {
+        let offset = DVec3 {
+            x: ((i % 2) as f64 - 0.5) * size as f64 / 4.0,
+            y: 0.5,
+            z: ((i / 2) as f64 - 0.5) * size as f64 / 4.0,
+        };
+        let yaw = base.rand.next_float() * std::f32::consts::TAU;
+        let child = Slime::new_with(|child_base, child_living, child| {
+            child_base.pos = base.pos + offset;
+            child_base.look.x = yaw;
+            child.size = (child_size - 1) as u8;
+            child_living.health = child_size.pow(2);
+        });
+        world.spawn_entity_with_reason(child, SpawnReason::Split);
+    }
+}
+
 fn spawn_living_loot(
     world: &mut World,
     base: &mut Base,
@@ -326,3 +706,399 @@ fn spawn_many_loot(world: &mut World, pos: DVec3, stack: ItemStack, count: usize
         world.spawn_loot(pos, stack, 0.0);
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use glam::IVec3;
+
+    use super::*;
+    use crate::world::Weather;
+
+    #[test]
+    fn wolf_pack_anger() {
+        use crate::entity::{Human, Hurt, LivingKind, Wolf};
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .player(Human::new_default(DVec3::new(4.5, 10.0, 4.5)))
+            .entity(Wolf::new_default(DVec3::new(8.5, 10.0, 8.5)))
+            .entity(Wolf::new_default(DVec3::new(12.5, 10.0, 8.5)))
+            .entity(Wolf::new_with(|base, _, wolf| {
+                base.pos = DVec3::new(12.5, 10.0, 12.5);
+                wolf.owner = Some("Owner".to_string());
+            }))
+            .build_with_entities();
+        let [player_id, ref wolf_ids @ ..] = ids[..] else {
+            unreachable!()
+        };
+        let wolf_ids: [u32; 3] = wolf_ids.try_into().unwrap();
+
+        world
+            .get_entity_mut(wolf_ids[0])
+            .unwrap()
+            .0
+            .hurt
+            .push(Hurt {
+                damage: 1,
+                origin_id: Some(player_id),
+            });
+        world.tick();
+
+        // The hurt wolf and the wild wolf around get angry, the tamed one ignores it.
+        for (wolf_id, angry) in wolf_ids.into_iter().zip([true, true, false]) {
+            let Some(Entity(_, BaseKind::Living(living, LivingKind::Wolf(wolf)))) =
+                world.get_entity(wolf_id)
+            else {
+                panic!("wolf should exists");
+            };
+            assert_eq!(wolf.angry, angry);
+            assert_eq!(living.attack_target.is_some(), angry);
+        }
+    }
+
+    #[test]
+    fn pig_zombie_group_anger() {
+        use crate::entity::{Human, Hurt, LivingKind, PigZombie};
+
+        let (mut world, ids) = World::builder()
+            .chunks(0, 0, 3, 0)
+            .platform(10, block::STONE, 0)
+            .player(Human::new_default(DVec3::new(4.5, 10.0, 4.5)))
+            .entity(PigZombie::new_default(DVec3::new(8.5, 10.0, 8.5)))
+            .entity(PigZombie::new_default(DVec3::new(30.5, 10.0, 8.5)))
+            .entity(PigZombie::new_default(DVec3::new(60.5, 10.0, 8.5)))
+            .build_with_entities();
+        let [player_id, ref pig_zombie_ids @ ..] = ids[..] else {
+            unreachable!()
+        };
+        let pig_zombie_ids: [u32; 3] = pig_zombie_ids.try_into().unwrap();
+
+        // Pig zombies are passive until provoked.
+        world.tick();
+        for &pig_zombie_id in &pig_zombie_ids {
+            let Some(Entity(_, BaseKind::Living(living, _))) = world.get_entity(pig_zombie_id)
+            else {
+                panic!("pig zombie should exists");
+            };
+            assert_eq!(living.attack_target, None);
+        }
+
+        world
+            .get_entity_mut(pig_zombie_ids[0])
+            .unwrap()
+            .0
+            .hurt
+            .push(Hurt {
+                damage: 1,
+                origin_id: Some(player_id),
+            });
+        world.tick();
+
+        // The hurt pig zombie and the one in range get angry, the far one ignores it.
+        for (pig_zombie_id, angry) in pig_zombie_ids.into_iter().zip([true, true, false]) {
+            let Some(Entity(_, BaseKind::Living(living, LivingKind::PigZombie(pig_zombie)))) =
+                world.get_entity(pig_zombie_id)
+            else {
+                panic!("pig zombie should exists");
+            };
+            assert_eq!(pig_zombie.anger != 0, angry);
+            assert_eq!(living.attack_target == Some(player_id), angry);
+        }
+
+        // Pig zombies calm down when their anger runs out.
+        let Some(Entity(_, BaseKind::Living(_, LivingKind::PigZombie(pig_zombie)))) =
+            world.get_entity_mut(pig_zombie_ids[1])
+        else {
+            panic!("pig zombie should exists");
+        };
+        pig_zombie.anger = 1;
+        world.tick();
+        let Some(Entity(_, BaseKind::Living(living, _))) = world.get_entity(pig_zombie_ids[1])
+        else {
+            panic!("pig zombie should exists");
+        };
+        assert_eq!(living.attack_target, None);
+    }
+
+    #[test]
+    fn zombie_daylight() {
+        use crate::entity::{Human, Zombie};
+
+        let (mut world, ids) = World::builder()
+            .chunks(0, 0, 2, 0)
+            .platform(10, block::STONE, 0)
+            .fill(
+                IVec3::new(32, 12, 0),
+                IVec3::new(47, 12, 15),
+                block::STONE,
+                0,
+            )
+            .light()
+            .player(Human::new_default(DVec3::new(3.5, 10.0, 8.5)))
+            .entity(Zombie::new_default(DVec3::new(8.5, 10.0, 8.5)))
+            .entity(Zombie::new_default(DVec3::new(40.5, 10.0, 8.5)))
+            .build_with_entities();
+
+        // Only the zombie under the sky catches fire, while pursuing the player.
+        let mut burnt = [false; 2];
+        let mut player_hurt = false;
+        for _ in 0..200 {
+            world.tick();
+            for (burnt, &id) in burnt.iter_mut().zip(&ids[1..]) {
+                *burnt |= world.get_entity(id).unwrap().0.fire_time > 0;
+            }
+            player_hurt |= match world.get_entity(ids[0]) {
+                Some(Entity(_, BaseKind::Living(living, _))) => living.health < 20,
+                _ => true,
+            };
+        }
+
+        assert_eq!(burnt, [true, false]);
+        assert!(player_hurt);
+    }
+
+    #[test]
+    fn slime_attack_and_split() {
+        use crate::entity::{Human, Hurt, LivingKind, Slime};
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .player(Human::new_default(DVec3::new(7.5, 10.0, 8.5)))
+            .entity(Slime::new_with(|base, living, slime| {
+                base.pos = DVec3::new(9.0, 10.0, 8.5);
+                slime.size = 3;
+                living.health = 16;
+            }))
+            .build_with_entities();
+
+        // The big slime damages the player it touches by its size, the damage is
+        // applied on the next player tick.
+        for _ in 0..2 {
+            world.tick();
+        }
+        let Some(Entity(_, BaseKind::Living(living, _))) = world.get_entity(ids[0]) else {
+            panic!("player should be alive");
+        };
+        assert_eq!(living.health, 16);
+
+        world.get_entity_mut(ids[1]).unwrap().0.hurt.push(Hurt {
+            damage: 100,
+            origin_id: None,
+        });
+        for _ in 0..30 {
+            world.tick();
+        }
+
+        // The dead slime is split in 4 slimes of half its size.
+        assert!(world.get_entity(ids[1]).is_none());
+        let children = world
+            .iter_entities()
+            .filter_map(|(_, entity)| match entity {
+                Entity(_, BaseKind::Living(living, LivingKind::Slime(slime))) => {
+                    Some((slime.real_size(), living.health))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(children, [(2, 4); 4]);
+    }
+
+    #[test]
+    fn armor_reduction() {
+        use crate::entity::{Human, Living};
+        use crate::item::{self, ItemStack};
+
+        let mut world = World::builder().platform(10, block::STONE, 0).build();
+        world.swap_events(Some(Vec::new()));
+        let id = world.spawn_entity(Human::new_with(|base, living, human| {
+            base.pos = DVec3::new(8.5, 10.0, 8.5);
+            living.health = 20;
+            human.armor[1] = ItemStack::new_single(item::DIAMOND_CHESTPLATE, 0);
+            human.armor[3] = ItemStack::new_single(item::LEATHER_BOOTS, 38);
+        }));
+
+        // Armor value is (8 + 3 - 1) * 385 / 423 + 1 = 10, so 10 * 15 / 25 = 6 damages.
+        world.hurt_entity(id, 10, None);
+        world.tick();
+
+        let entity = world.get_entity(id).unwrap();
+        assert_eq!(entity.get::<Living>().unwrap().health, 14);
+        let human = entity.get::<Human>().unwrap();
+        assert_eq!(human.damage_remainder, 0);
+        assert_eq!(
+            human.armor[1],
+            ItemStack::new_single(item::DIAMOND_CHESTPLATE, 10)
+        );
+        assert!(human.armor[3].is_empty(), "boots should be broken");
+
+        let events = world.swap_events(None).unwrap();
+        assert!(events.iter().any(|event| matches!(event, Event::Entity {
+            id: event_id,
+            inner: EntityEvent::Armor,
+        } if *event_id == id)));
+    }
+
+    #[test]
+    fn burning() {
+        use crate::entity::{Living, Pig};
+
+        let state = |world: &World, id| {
+            let entity = world.get_entity(id).unwrap();
+            (entity.get::<Living>().unwrap().health, entity.0.fire_time)
+        };
+
+        let mut world = World::builder()
+            .platform(10, block::STONE, 0)
+            .block(IVec3::new(2, 10, 2), block::LAVA_STILL, 0)
+            .block(IVec3::new(6, 10, 6), block::FIRE, 0)
+            .block(IVec3::new(10, 10, 10), block::WATER_STILL, 0)
+            .build();
+        let lava_id = world.spawn_entity(Pig::new_default(DVec3::new(2.5, 10.0, 2.5)));
+        let fire_id = world.spawn_entity(Pig::new_default(DVec3::new(6.5, 10.0, 6.5)));
+        let water_id = world.spawn_entity(Pig::new_default(DVec3::new(10.5, 10.0, 10.5)));
+        let full_health = state(&world, lava_id).0;
+
+        world.get_entity_mut(water_id).unwrap().0.fire_time = 100;
+        world.tick();
+
+        assert_eq!(state(&world, lava_id), (full_health - 4, 600));
+        assert_eq!(state(&world, fire_id), (full_health - 1, 300));
+        assert_eq!(state(&world, water_id), (full_health, 0));
+
+        // Burning entities out of fire are damaged every second until extinguished.
+        world.set_block(IVec3::new(6, 10, 6), block::AIR, 0);
+        for _ in 0..40 {
+            world.tick();
+        }
+        assert_eq!(state(&world, fire_id), (full_health - 2, 260));
+
+        // The rain extinguishes entities exposed to the sky, after the last burn damage.
+        world.set_weather(Weather::Rain);
+        world.tick();
+        assert_eq!(state(&world, fire_id), (full_health - 3, 0));
+    }
+
+    #[test]
+    fn squid_suffocation() {
+        use crate::entity::Squid;
+        use crate::item;
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .fill(IVec3::new(0, 10, 0), IVec3::new(8, 14, 8), block::STONE, 0)
+            .fill(
+                IVec3::new(1, 10, 1),
+                IVec3::new(7, 14, 7),
+                block::WATER_STILL,
+                0,
+            )
+            .entity(Squid::new_default(DVec3::new(4.5, 11.0, 4.5)))
+            .entity(Squid::new_default(DVec3::new(12.5, 10.0, 12.5)))
+            .build_with_entities();
+        let [wet_id, dry_id] = ids[..] else {
+            unreachable!()
+        };
+
+        for _ in 0..400 {
+            world.tick();
+        }
+
+        // The squid in water keeps its full air, the other one dies and drops ink sacs.
+        assert_eq!(world.get_entity(wet_id).unwrap().0.air_time, 300);
+        assert!(!world.contains_entity(dry_id));
+        assert!(world.iter_entities().any(|(_, entity)| matches!(
+            entity,
+            Entity(_, BaseKind::Item(ink)) if ink.stack.id == item::DYE && ink.stack.damage == 0
+        )));
+    }
+
+    #[test]
+    fn player_combat() {
+        use crate::entity::{Human, Hurt, LivingKind};
+
+        let (mut world, ids) = World::builder()
+            .entity(Human::new_default(DVec3::new(4.0, 10.0, 4.0)))
+            .entity(Human::new_with(|base, _, human| {
+                base.pos = DVec3::new(6.0, 10.0, 4.0);
+                human.invulnerable_time = 2;
+            }))
+            .build_with_entities();
+        let [attacker_id, victim_id] = ids[..] else {
+            unreachable!()
+        };
+
+        let hurt = |world: &mut World| {
+            let Entity(base, _) = world.get_entity_mut(victim_id).unwrap();
+            base.hurt.push(Hurt {
+                damage: 2,
+                origin_id: Some(attacker_id),
+            });
+            world.tick();
+            let Some(Entity(_, BaseKind::Living(living, LivingKind::Human(human)))) =
+                world.get_entity(victim_id)
+            else {
+                panic!("victim should be a living human");
+            };
+            (living.health, human.get_combat_attacker())
+        };
+
+        // The first hit is ignored because of invulnerability.
+        assert_eq!(hurt(&mut world), (20, None));
+        assert_eq!(hurt(&mut world), (18, Some(attacker_id)));
+
+        // Player damages are ignored when PvP is denied, unless overridden.
+        for _ in 0..10 {
+            world.tick();
+        }
+        world.set_pvp(false);
+        assert_eq!(hurt(&mut world).0, 18);
+        world.set_pvp_filter(Some(|_, _, pos, _| pos.x > 5.0));
+        assert_eq!(hurt(&mut world).0, 16);
+    }
+
+    #[test]
+    fn item_pickup_owner() {
+        use crate::entity::{Human, Item};
+        use crate::item;
+
+        let pos = DVec3::new(4.5, 10.0, 4.5);
+        let human = || {
+            Human::new_with(|base, _, _| {
+                base.pos = pos;
+                base.can_pickup = true;
+            })
+        };
+        let (mut world, ids) = World::builder()
+            .entity(human())
+            .entity(human())
+            .events()
+            .build_with_entities();
+        let [owner_id, other_id] = ids[..] else {
+            unreachable!()
+        };
+
+        world.spawn_entity(Item::new_with(|base, item| {
+            base.pos = pos;
+            item.stack = ItemStack::new_single(item::DIAMOND, 0);
+            item.owner_id = Some(owner_id);
+            item.owner_time = Item::THROWN_OWNER_TIME;
+        }));
+
+        world.tick();
+        let pickups = world
+            .swap_events(Some(Vec::new()))
+            .unwrap()
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Entity {
+                    id,
+                    inner: EntityEvent::Pickup { .. },
+                } => Some(id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(pickups, [other_id]);
+    }
+}
@@ -0,0 +1,99 @@
+//! Conformance of entity physics with trajectories of the Notchian implementation.
+//!
+//! Each trajectory file lists, for every tick, the position and velocity of a single
+//! entity after being ticked, as given by the b1.7.3 update of that entity. A last
+//! `removed` line can be used when the entity should be removed on the next tick.
+
+use glam::{DVec3, IVec3};
+
+use crate::block;
+use crate::world::World;
+
+use super::{Arrow, Entity, FallingBlock, Item};
+
+/// Maximum difference allowed on each component of position and velocity, we don't
+/// require exact equality because most Notchian constants are single precision floats
+/// and the error accumulates over ticks.
+const TOLERANCE: f64 = 1e-5;
+
+/// Tick the world once for each frame of the given trajectory and compare the entity
+/// with the given id to it, panicking on the first frame that diverges.
+fn check_trajectory(world: &mut World, id: u32, trajectory: &str) {
+    let frames = trajectory
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    for (tick, frame) in frames.enumerate() {
+        world.tick();
+
+        if frame == "removed" {
+            assert!(
+                !world.contains_entity(id),
+                "tick {tick}: entity should be removed"
+            );
+            return;
+        }
+
+        let values = frame
+            .split_whitespace()
+            .map(|value| value.parse::<f64>().unwrap())
+            .collect::<Vec<_>>();
+        let [x, y, z, vx, vy, vz] = values[..] else {
+            panic!("tick {tick}: invalid frame: {frame}");
+        };
+
+        let Some(Entity(base, _)) = world.get_entity(id) else {
+            panic!("tick {tick}: entity has been removed");
+        };
+
+        let expected_pos = DVec3::new(x, y, z);
+        let expected_vel = DVec3::new(vx, vy, vz);
+        assert!(
+            base.pos.abs_diff_eq(expected_pos, TOLERANCE),
+            "tick {tick}: position {} differs from {expected_pos}",
+            base.pos
+        );
+        assert!(
+            base.vel.abs_diff_eq(expected_vel, TOLERANCE),
+            "tick {tick}: velocity {} differs from {expected_vel}",
+            base.vel
+        );
+    }
+}
+
+/// A world with a stone platform whose top is at Y=10.
+fn new_world() -> World {
+    World::builder().platform(10, block::STONE, 0).build()
+}
+
+#[test]
+fn item_drop() {
+    let mut world = new_world();
+    let id = world.spawn_entity(Item::new_with(|base, _| {
+        base.pos = DVec3::new(8.5, 12.0, 8.5);
+        base.vel = DVec3::new(0.1, 0.2, -0.05);
+    }));
+
+    check_trajectory(&mut world, id, include_str!("trajectory/item_drop.txt"));
+}
+
+#[test]
+fn falling_sand() {
+    let mut world = new_world();
+    let id = world.spawn_entity(FallingBlock::new_default(DVec3::new(8.5, 20.5, 8.5)));
+
+    check_trajectory(&mut world, id, include_str!("trajectory/falling_sand.txt"));
+    assert!(world.is_block(IVec3::new(8, 10, 8), block::SAND));
+}
+
+#[test]
+fn arrow_flight() {
+    let mut world = new_world();
+    let id = world.spawn_entity(Arrow::new_with(|base, _, _| {
+        base.pos = DVec3::new(4.5, 20.0, 4.5);
+        base.vel = DVec3::new(0.5, 0.3, 0.2);
+    }));
+
+    check_trajectory(&mut world, id, include_str!("trajectory/arrow_flight.txt"));
+}
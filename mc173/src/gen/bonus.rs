@@ -0,0 +1,137 @@
+//! Bonus chest generator.
+
+use glam::IVec3;
+
+use crate::block;
+use crate::block_entity::chest::ChestBlockEntity;
+use crate::block_entity::BlockEntity;
+use crate::data;
+use crate::geom::Face;
+use crate::item::{self, ItemStack};
+use crate::loot::{LootEntry, LootTable};
+use crate::rand::JavaRandom;
+use crate::world::World;
+
+use super::FeatureGenerator;
+
+/// A generator for the bonus chest placed near the spawn point, surrounded by torches.
+///
+/// PARITY: Bonus chests don't exist in the Notchian beta 1.7.3 server, this is the
+/// later behavior of the world creation option.
+///
+/// REF (1.3.2): WorldGeneratorBonusChest
+pub struct BonusChestGenerator {}
+
+impl BonusChestGenerator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for BonusChestGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BonusChestGenerator {
+    /// Get the default loot table of bonus chests, the table actually used is the one
+    /// of the [data tables](crate::data).
+    pub fn default_loot() -> LootTable {
+        let item = |weight, id, min, max| {
+            LootEntry::new_stack(weight, ItemStack::new_single(id, 0), min, max)
+        };
+        let block = |weight, id, min, max| {
+            LootEntry::new_stack(weight, ItemStack::new_block(id, 0), min, max)
+        };
+
+        LootTable::new(10)
+            .with(item(10, item::STICK, 1, 3))
+            .with(block(10, block::WOOD, 1, 3))
+            .with(block(10, block::LOG, 1, 3))
+            .with(item(3, item::STONE_AXE, 1, 1))
+            .with(item(5, item::WOOD_AXE, 1, 1))
+            .with(item(3, item::STONE_PICKAXE, 1, 1))
+            .with(item(5, item::WOOD_PICKAXE, 1, 1))
+            .with(item(5, item::APPLE, 2, 3))
+            .with(item(3, item::BREAD, 2, 3))
+    }
+}
+
+impl FeatureGenerator for BonusChestGenerator {
+    fn generate(&mut self, world: &mut World, mut pos: IVec3, rand: &mut JavaRandom) -> bool {
+        // Go down to the ground, ignoring tree leaves.
+        while pos.y > 1 && matches!(world.get_block(pos), Some((block::AIR | block::LEAVES, _))) {
+            pos.y -= 1;
+        }
+
+        if pos.y < 1 {
+            return false;
+        }
+
+        pos.y += 1;
+
+        for _ in 0..4 {
+            let chest_pos = pos
+                + IVec3 {
+                    x: rand.next_int_bounded(4) - rand.next_int_bounded(4),
+                    y: rand.next_int_bounded(3) - rand.next_int_bounded(3),
+                    z: rand.next_int_bounded(4) - rand.next_int_bounded(4),
+                };
+
+            if !world.is_block_air(chest_pos) || !world.is_block_opaque_cube(chest_pos - IVec3::Y) {
+                continue;
+            }
+
+            let mut chest = ChestBlockEntity::default();
+            data::tables()
+                .bonus_chest_loot
+                .fill(rand, &mut chest.inv[..]);
+
+            world.set_block(chest_pos, block::CHEST, 0);
+            world.set_block_entity(chest_pos, BlockEntity::Chest(chest));
+
+            for face in Face::HORIZONTAL {
+                let torch_pos = chest_pos + face.delta();
+                if world.is_block_air(torch_pos) && world.is_block_opaque_cube(torch_pos - IVec3::Y)
+                {
+                    let mut metadata = 0;
+                    block::torch::set_face(&mut metadata, Face::NegY);
+                    world.set_block(torch_pos, block::TORCH, metadata);
+                }
+            }
+
+            return true;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn bonus_chest() {
+        let mut world = World::builder().platform(10, block::GRASS, 0).build();
+        let mut rand = JavaRandom::new(0);
+        assert!(BonusChestGenerator::new().generate(&mut world, IVec3::new(8, 100, 8), &mut rand));
+
+        let (chest_pos, chest) = (0..16)
+            .flat_map(|x| (0..16).flat_map(move |z| (9..13).map(move |y| IVec3::new(x, y, z))))
+            .find_map(|pos| match world.get_block_entity(pos) {
+                Some(BlockEntity::Chest(chest)) => Some((pos, chest.clone())),
+                _ => None,
+            })
+            .expect("bonus chest should be placed");
+
+        assert_eq!(chest_pos.y, 10);
+        assert!(world.is_block(chest_pos, block::CHEST));
+        assert!(chest.inv.iter().any(|stack| !stack.is_empty()));
+        assert!(Face::HORIZONTAL
+            .iter()
+            .all(|face| world.is_block(chest_pos + face.delta(), block::TORCH)));
+    }
+}
@@ -6,9 +6,11 @@ use crate::block;
 use crate::block_entity::chest::ChestBlockEntity;
 use crate::block_entity::spawner::SpawnerBlockEntity;
 use crate::block_entity::BlockEntity;
+use crate::data;
 use crate::entity::EntityKind;
 use crate::geom::Face;
 use crate::item::{self, ItemStack};
+use crate::loot::{LootEntry, LootTable};
 use crate::rand::JavaRandom;
 use crate::world::World;
 
@@ -30,27 +32,32 @@ impl Default for DungeonGenerator {
 }
 
 impl DungeonGenerator {
-    fn gen_chest_stack(&self, rand: &mut JavaRandom) -> ItemStack {
-        match rand.next_int_bounded(11) {
-            0 => ItemStack::new_single(item::SADDLE, 0),
-            1 => ItemStack::new_sized(item::IRON_INGOT, 0, rand.next_int_bounded(4) as u16 + 1),
-            2 => ItemStack::new_single(item::BREAD, 0),
-            3 => ItemStack::new_single(item::BREAD, 0),
-            4 => ItemStack::new_sized(item::GUNPOWDER, 0, rand.next_int_bounded(4) as u16 + 1),
-            5 => ItemStack::new_sized(item::STRING, 0, rand.next_int_bounded(4) as u16 + 1),
-            6 => ItemStack::new_single(item::BUCKET, 0),
-            7 if rand.next_int_bounded(100) == 0 => ItemStack::new_single(item::GOLD_APPLE, 0),
-            8 if rand.next_int_bounded(2) == 0 => {
-                ItemStack::new_sized(item::REDSTONE, 0, rand.next_int_bounded(4) as u16 + 1)
-            }
-            9 if rand.next_int_bounded(10) == 0 => match rand.next_int_bounded(2) {
-                0 => ItemStack::new_single(item::RECORD_13, 0),
-                1 => ItemStack::new_single(item::RECORD_CAT, 0),
-                _ => unreachable!(),
-            },
-            10 => ItemStack::new_single(item::DYE, 3),
-            _ => ItemStack::EMPTY,
-        }
+    /// Get the Notchian loot table of dungeon chests, the table actually used is the
+    /// one of the [data tables](crate::data).
+    pub fn default_loot() -> LootTable {
+        let single = |id| LootEntry::new_stack(1, ItemStack::new_single(id, 0), 1, 1);
+        let sized = |id| LootEntry::new_stack(1, ItemStack::new_single(id, 0), 1, 4);
+        let records = LootTable::new(1)
+            .with(single(item::RECORD_13))
+            .with(single(item::RECORD_CAT));
+
+        LootTable::new(8)
+            .with(single(item::SADDLE))
+            .with(sized(item::IRON_INGOT))
+            .with(single(item::BREAD))
+            .with(single(item::BREAD))
+            .with(sized(item::GUNPOWDER))
+            .with(sized(item::STRING))
+            .with(single(item::BUCKET))
+            .with(single(item::GOLD_APPLE).with_rarity(100))
+            .with(sized(item::REDSTONE).with_rarity(2))
+            .with(LootEntry::new_pool(1, records).with_rarity(10))
+            .with(LootEntry::new_stack(
+                1,
+                ItemStack::new_single(item::DYE, 3),
+                1,
+                1,
+            ))
     }
 
     fn gen_spawner_entity(&self, rand: &mut JavaRandom) -> EntityKind {
@@ -149,14 +156,7 @@ impl FeatureGenerator for DungeonGenerator {
                     }
 
                     let mut chest = ChestBlockEntity::default();
-
-                    // Pick 8 random items.
-                    for _ in 0..8 {
-                        let stack = self.gen_chest_stack(rand);
-                        if !stack.is_empty() {
-                            *rand.next_choice_mut(&mut chest.inv[..]) = stack;
-                        }
-                    }
+                    data::tables().dungeon_loot.fill(rand, &mut chest.inv[..]);
 
                     world.set_block(chest_pos, block::CHEST, 0);
                     world.set_block_entity(chest_pos, BlockEntity::Chest(chest));
@@ -142,7 +142,11 @@ impl FeatureGenerator for LakeGenerator {
     }
 }
 
-/// A generator for single liquid blocks.
+/// A generator for single liquid blocks, also known as springs, placed in stone walls
+/// with exactly one side open to air. The fluid is immediately ticked until it settles
+/// in order to create falls.
+///
+/// REF: WorldGenLiquids
 pub struct LiquidGenerator {
     fluid_id: u8,
 }
@@ -177,8 +181,40 @@ impl FeatureGenerator for LiquidGenerator {
 
         if stone_count == 3 && air_count == 1 {
             world.set_block(pos, self.fluid_id, 0);
+            world.tick_block_immediate(pos, self.fluid_id);
         }
 
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn spring_fall() {
+        let mut world = World::builder()
+            .chunks(-1, -1, 1, 1)
+            .platform(20, block::STONE, 0)
+            .fill(IVec3::new(9, 11, 8), IVec3::new(9, 15, 8), block::AIR, 0)
+            .build();
+
+        // Ticks already scheduled are not run by the spring.
+        world.schedule_block_tick(IVec3::new(2, 19, 2), block::STONE, 10);
+
+        let pos = IVec3::new(8, 15, 8);
+        let mut rand = JavaRandom::new(0);
+        assert!(LiquidGenerator::new(block::WATER_MOVING).generate(&mut world, pos, &mut rand));
+        let is_water = |world: &World, pos| {
+            matches!(
+                world.get_block(pos),
+                Some((block::WATER_MOVING | block::WATER_STILL, _))
+            )
+        };
+        assert!(is_water(&world, pos));
+        assert!(is_water(&world, IVec3::new(9, 11, 8)));
+        assert_eq!(world.get_block_tick_count(), 1);
+    }
+}
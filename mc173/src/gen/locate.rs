@@ -0,0 +1,229 @@
+//! Deterministic feature locators.
+//!
+//! This module computes positions of features directly from the world seed, without
+//! generating any chunk. This is possible because the random generator used to
+//! populate a chunk is seeded only from the world seed and chunk coordinates, and
+//! because the first features of the overworld consume random numbers before checking
+//! the terrain.
+//!
+//! PARITY: Features are only placed if the terrain allows it, so the positions returned
+//! here are attempts, not guaranteed features. A successful lava lake or dungeon also
+//! consumes more random numbers than a failed one, so all following attempts in the
+//! same chunk are only exact if the previous ones failed. Locators assume the default
+//! overworld features, see [`OverworldFeature::ALL`](super::OverworldFeature::ALL).
+
+use glam::IVec3;
+
+use crate::rand::JavaRandom;
+use crate::world::observe::ChunkRegion;
+
+/// Number of dungeon attempts per chunk.
+pub const DUNGEON_ATTEMPTS: usize = 8;
+
+/// Feature attempts of a single overworld chunk, positions are absolute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkFeatures {
+    /// The water lake attempt, if any. The lake is generated from the first non-air
+    /// block below this position.
+    pub water_lake: Option<IVec3>,
+    /// The lava lake attempt, if any.
+    pub lava_lake: Option<IVec3>,
+    /// All dungeon attempts, the first one is exact if there is no lava lake attempt,
+    /// following ones are exact if previous attempts failed.
+    pub dungeons: [IVec3; DUNGEON_ATTEMPTS],
+}
+
+/// Calculate the seed of the random generator used to populate the given chunk.
+///
+/// REF: ChunkProviderGenerate::populate
+pub fn calc_populate_seed(seed: i64, cx: i32, cz: i32) -> i64 {
+    let mut rand = JavaRandom::new(seed);
+
+    let x_mul = rand
+        .next_long()
+        .wrapping_div(2)
+        .wrapping_mul(2)
+        .wrapping_add(1);
+    let z_mul = rand
+        .next_long()
+        .wrapping_div(2)
+        .wrapping_mul(2)
+        .wrapping_add(1);
+
+    i64::wrapping_add(
+        (cx as i64).wrapping_mul(x_mul),
+        (cz as i64).wrapping_mul(z_mul),
+    ) ^ seed
+}
+
+/// Compute the feature attempts of the given overworld chunk.
+pub fn locate_chunk_features(seed: i64, cx: i32, cz: i32) -> ChunkFeatures {
+    let origin = IVec3::new(cx * 16 + 8, 0, cz * 16 + 8);
+    let mut rand = JavaRandom::new(calc_populate_seed(seed, cx, cz));
+
+    let mut water_lake = None;
+    if rand.next_int_bounded(4) == 0 {
+        water_lake = Some(origin + next_offset(&mut rand));
+        skip_lake(&mut rand);
+    }
+
+    let mut lava_lake = None;
+    if rand.next_int_bounded(8) == 0 {
+        let x = rand.next_int_bounded(16);
+        let y = {
+            let v = rand.next_int_bounded(120);
+            rand.next_int_bounded(v + 8)
+        };
+        let z = rand.next_int_bounded(16);
+        if y < 64 || rand.next_int_bounded(10) == 0 {
+            lava_lake = Some(origin + IVec3::new(x, y, z));
+            skip_lake(&mut rand);
+        }
+    }
+
+    let dungeons = std::array::from_fn(|_| {
+        let pos = origin + next_offset(&mut rand);
+        // Dungeon radius, consumed before checking terrain.
+        rand.next_int_bounded(2);
+        rand.next_int_bounded(2);
+        pos
+    });
+
+    ChunkFeatures {
+        water_lake,
+        lava_lake,
+        dungeons,
+    }
+}
+
+/// Locate all water lake attempts in the given region.
+pub fn locate_water_lakes(seed: i64, region: ChunkRegion) -> Vec<IVec3> {
+    locate_in_region(seed, region, |features| features.water_lake)
+}
+
+/// Locate all lava lake attempts in the given region.
+pub fn locate_lava_lakes(seed: i64, region: ChunkRegion) -> Vec<IVec3> {
+    locate_in_region(seed, region, |features| features.lava_lake)
+}
+
+/// Locate all dungeon attempts in the given region, only the exact attempts are
+/// returned, this means the first attempt of each chunk without lava lake.
+pub fn locate_dungeons(seed: i64, region: ChunkRegion) -> Vec<IVec3> {
+    locate_in_region(seed, region, |features| {
+        features.lava_lake.is_none().then_some(features.dungeons[0])
+    })
+}
+
+/// Return true if the given chunk is a slime chunk, where slimes can spawn below Y=16.
+///
+/// REF: Chunk::getRandomWithSeed
+pub fn is_slime_chunk(seed: i64, cx: i32, cz: i32) -> bool {
+    let chunk_seed = seed
+        .wrapping_add(cx.wrapping_mul(cx).wrapping_mul(4987142) as i64)
+        .wrapping_add(cx.wrapping_mul(5947611) as i64)
+        .wrapping_add((cz.wrapping_mul(cz) as i64).wrapping_mul(4392871))
+        .wrapping_add(cz.wrapping_mul(389711) as i64)
+        ^ 987234911;
+    JavaRandom::new(chunk_seed).next_int_bounded(10) == 0
+}
+
+/// Locate all slime chunks in the given region.
+pub fn locate_slime_chunks(seed: i64, region: ChunkRegion) -> Vec<(i32, i32)> {
+    let mut chunks = Vec::new();
+    for cx in region.min_cx..=region.max_cx {
+        for cz in region.min_cz..=region.max_cz {
+            if is_slime_chunk(seed, cx, cz) {
+                chunks.push((cx, cz));
+            }
+        }
+    }
+    chunks
+}
+
+/// Internal function to locate a single feature in all chunks of a region.
+fn locate_in_region(
+    seed: i64,
+    region: ChunkRegion,
+    mut func: impl FnMut(&ChunkFeatures) -> Option<IVec3>,
+) -> Vec<IVec3> {
+    let mut positions = Vec::new();
+    for cx in region.min_cx..=region.max_cx {
+        for cz in region.min_cz..=region.max_cz {
+            positions.extend(func(&locate_chunk_features(seed, cx, cz)));
+        }
+    }
+    positions
+}
+
+/// Pick a uniform random position offset, like the overworld populate does.
+fn next_offset(rand: &mut JavaRandom) -> IVec3 {
+    IVec3 {
+        x: rand.next_int_bounded(16),
+        y: rand.next_int_bounded(128),
+        z: rand.next_int_bounded(16),
+    }
+}
+
+/// Consume the random numbers of a lake generation before checking the terrain.
+fn skip_lake(rand: &mut JavaRandom) {
+    let count = rand.next_int_bounded(4) + 4;
+    for _ in 0..count {
+        rand.next_double_vec();
+        rand.next_double_vec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::block;
+    use crate::chunk::Chunk;
+    use crate::gen::{ChunkGenerator, OverworldGenerator};
+    use crate::world::{Dimension, World};
+
+    #[test]
+    fn locate_dungeons_parity() {
+        let seed = 9999;
+        let gen = OverworldGenerator::new(seed);
+        let mut state = Default::default();
+        let mut spawners = 0;
+
+        for cx in 0..12 {
+            for cz in 0..12 {
+                // Populating a chunk only modify the 2x2 chunks from its origin.
+                let mut world = World::new(Dimension::Overworld);
+                for (dx, dz) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let mut chunk = Chunk::new();
+                    gen.gen_terrain(
+                        cx + dx,
+                        cz + dz,
+                        Arc::get_mut(&mut chunk).unwrap(),
+                        &mut state,
+                    );
+                    world.set_chunk(cx + dx, cz + dz, chunk);
+                }
+
+                gen.gen_features(cx, cz, &mut world, &mut state);
+
+                let features = locate_chunk_features(seed, cx, cz);
+                for (pos, id, _) in world.iter_blocks_in(
+                    IVec3::new(cx * 16, 0, cz * 16),
+                    IVec3::new(cx * 16 + 32, 128, cz * 16 + 32),
+                ) {
+                    if id == block::SPAWNER {
+                        spawners += 1;
+                        assert!(
+                            features.dungeons.contains(&pos),
+                            "unexpected spawner at {pos}"
+                        );
+                    }
+                }
+            }
+        }
+
+        assert_ne!(spawners, 0);
+    }
+}
@@ -6,6 +6,8 @@
 //! not give exact same world on each generation, just like Minecraft. Terrain however,
 //! should be exactly the same on same run.
 
+use std::sync::Arc;
+
 use glam::IVec3;
 
 use crate::chunk::Chunk;
@@ -17,6 +19,7 @@ pub mod math;
 pub mod noise;
 
 // Feature generators.
+pub mod bonus;
 pub mod dungeon;
 pub mod liquid;
 pub mod plant;
@@ -28,7 +31,18 @@ pub mod cave;
 
 // World generators.
 mod overworld;
-pub use overworld::OverworldGenerator;
+mod void;
+
+// Schematics stamped by generators.
+pub mod schematic;
+
+// Feature locators.
+pub mod locate;
+
+// World pre-generation.
+pub mod pregen;
+pub use overworld::{OverworldFeature, OverworldGenerator, OverworldGeneratorOptions};
+pub use void::VoidGenerator;
 
 /// A trait for all chunk generators, a chunk generator is immutable, if any mutable
 /// state needs to be stored, the `State` associated type can be used.
@@ -53,6 +67,24 @@ pub trait ChunkGenerator {
     fn gen_features(&self, cx: i32, cz: i32, world: &mut World, state: &mut Self::State);
 }
 
+/// A shared chunk generator is also a chunk generator, this allows a generator to back
+/// multiple chunk storages.
+impl<G: ChunkGenerator + ?Sized> ChunkGenerator for Arc<G> {
+    type State = G::State;
+
+    fn gen_biomes(&self, cx: i32, cz: i32, chunk: &mut Chunk, state: &mut Self::State) {
+        (**self).gen_biomes(cx, cz, chunk, state)
+    }
+
+    fn gen_terrain(&self, cx: i32, cz: i32, chunk: &mut Chunk, state: &mut Self::State) {
+        (**self).gen_terrain(cx, cz, chunk, state)
+    }
+
+    fn gen_features(&self, cx: i32, cz: i32, world: &mut World, state: &mut Self::State) {
+        (**self).gen_features(cx, cz, world, state)
+    }
+}
+
 /// A trait common to all feature generators.
 pub trait FeatureGenerator {
     /// Generate the feature at the given position in the world with given RNG.
@@ -29,9 +29,11 @@ use crate::chunk::{Chunk, CHUNK_HEIGHT, CHUNK_WIDTH};
 use crate::rand::JavaRandom;
 use crate::world::World;
 
+use super::bonus::BonusChestGenerator;
 use super::cave::CaveGenerator;
 use super::dungeon::DungeonGenerator;
 use super::liquid::{LakeGenerator, LiquidGenerator};
+use super::locate;
 use super::noise::{NoiseCube, PerlinOctaveNoise};
 use super::plant::{CactusGenerator, PlantGenerator, PumpkinGenerator, SugarCanesGenerator};
 use super::tree::TreeGenerator;
@@ -48,11 +50,92 @@ const HUMIDITY_FREQ_FACTOR: f64 = 1.0 / 3.0;
 const BIOME_SCALE: DVec2 = DVec2::splat(0.25);
 const BIOME_FREQ_FACTOR: f64 = 0.5882352941176471;
 
+/// Options of the overworld generator.
+#[derive(Debug, Clone)]
+pub struct OverworldGeneratorOptions {
+    /// The list of features that are generated when populating chunks, by default all
+    /// features are generated. Note that removing a feature changes the random state
+    /// of all following features, so the decoration will no longer be the same as the
+    /// Notchian generator's one for the same seed.
+    pub features: Vec<OverworldFeature>,
+    /// When enabled, the bedrock floor is a single flat layer at Y=0 instead of the
+    /// randomized layers up to Y=4. This doesn't change the rest of the terrain.
+    pub flat_bedrock: bool,
+    /// When set to the world spawn position, a bonus chest is placed near it when the
+    /// chunk containing the spawn is populated, so only on first generation.
+    pub bonus_chest: Option<IVec3>,
+}
+
+impl Default for OverworldGeneratorOptions {
+    fn default() -> Self {
+        Self {
+            features: OverworldFeature::ALL.to_vec(),
+            flat_bedrock: false,
+            bonus_chest: None,
+        }
+    }
+}
+
+impl OverworldGeneratorOptions {
+    /// Return true if the given feature should be generated.
+    #[inline]
+    pub fn has_feature(&self, feature: OverworldFeature) -> bool {
+        self.features.contains(&feature)
+    }
+}
+
+/// A feature generated when populating overworld chunks, in order of generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverworldFeature {
+    WaterLakes,
+    LavaLakes,
+    Dungeons,
+    Clay,
+    Ores,
+    Trees,
+    Dandelions,
+    TallGrass,
+    DeadBushes,
+    Poppies,
+    Mushrooms,
+    SugarCanes,
+    Pumpkins,
+    Cacti,
+    WaterSprings,
+    LavaSprings,
+    Snow,
+}
+
+impl OverworldFeature {
+    /// All overworld features, in order of generation.
+    pub const ALL: [Self; 17] = [
+        Self::WaterLakes,
+        Self::LavaLakes,
+        Self::Dungeons,
+        Self::Clay,
+        Self::Ores,
+        Self::Trees,
+        Self::Dandelions,
+        Self::TallGrass,
+        Self::DeadBushes,
+        Self::Poppies,
+        Self::Mushrooms,
+        Self::SugarCanes,
+        Self::Pumpkins,
+        Self::Cacti,
+        Self::WaterSprings,
+        Self::LavaSprings,
+        Self::Snow,
+    ];
+}
+
 /// A chunk generator for the overworld dimension. This structure can be shared between
 /// workers.
 pub struct OverworldGenerator {
     /// The world seed.
     seed: i64,
+    /// The generator options.
+    options: OverworldGeneratorOptions,
     /// The noise used for generating biome temperature.
     temperature_noise: PerlinOctaveNoise,
     /// The noise used for generating biome humidity.
@@ -88,8 +171,13 @@ pub struct OverworldState {
 }
 
 impl OverworldGenerator {
-    /// Create a new overworld generator given a seed.
+    /// Create a new overworld generator given a seed, with default options.
     pub fn new(seed: i64) -> Self {
+        Self::with_options(seed, OverworldGeneratorOptions::default())
+    }
+
+    /// Create a new overworld generator given a seed and options.
+    pub fn with_options(seed: i64, options: OverworldGeneratorOptions) -> Self {
         let biome_lookup = Box::new(std::array::from_fn(|i| {
             let t = (i % 64) as f32 / 63.0;
             let h = (i / 64) as f32 / 63.0;
@@ -128,6 +216,7 @@ impl OverworldGenerator {
 
         Self {
             seed,
+            options,
             temperature_noise: PerlinOctaveNoise::new(
                 &mut JavaRandom::new(seed.wrapping_mul(9871)),
                 4,
@@ -409,7 +498,11 @@ impl OverworldGenerator {
         }
     }
 
-    /// Generate the primitive terrain of the chunk.
+    /// Generate the surface of the chunk, this replaces the top stone layers with the
+    /// biome's top and filler blocks, and generates sand and gravel beaches near the
+    /// sea level depending on the sand/gravel noise. Bedrock is also placed here.
+    ///
+    /// REF: ChunkProviderGenerate::replaceBlocksForBiome
     fn gen_surface(
         &self,
         cx: i32,
@@ -456,8 +549,12 @@ impl OverworldGenerator {
                 for y in (0..128).rev() {
                     pos.y = y;
 
+                    // NOTE: The random bedrock roll is always done, even with flat
+                    // bedrock, in order to keep the same surface.
                     if y <= rand.next_int_bounded(5) {
-                        chunk.set_block(pos, block::BEDROCK, 0);
+                        if y == 0 || !self.options.flat_bedrock {
+                            chunk.set_block(pos, block::BEDROCK, 0);
+                        }
                         continue;
                     }
 
@@ -472,6 +569,9 @@ impl OverworldGenerator {
                                 top_id = block::AIR;
                                 filler_id = block::STONE;
                             } else if y >= sea_level - 4 && y <= sea_level + 1 {
+                                // Beaches are only generated in a band around sea level,
+                                // sand beaches take precedence over gravel beaches, and
+                                // gravel beaches have no top block (water or air).
                                 top_id = biome_top_id;
                                 filler_id = biome_filler_id;
 
@@ -544,25 +644,7 @@ impl ChunkGenerator for OverworldGenerator {
         let biome = self.get_biome(pos.x + 16, pos.z + 16);
 
         // Start by calculating the chunk seed from chunk coordinates and world seed.
-        let mut rand = JavaRandom::new(self.seed);
-
-        let x_mul = rand
-            .next_long()
-            .wrapping_div(2)
-            .wrapping_mul(2)
-            .wrapping_add(1);
-        let z_mul = rand
-            .next_long()
-            .wrapping_div(2)
-            .wrapping_mul(2)
-            .wrapping_add(1);
-
-        let chunk_seed = i64::wrapping_add(
-            (cx as i64).wrapping_mul(x_mul),
-            (cz as i64).wrapping_mul(z_mul),
-        ) ^ self.seed;
-
-        rand.set_seed(chunk_seed);
+        let mut rand = JavaRandom::new(locate::calc_populate_seed(self.seed, cx, cz));
 
         // if cx == 0 && cz == 2 {
         //     println!("debugging chunk {cx}/{cz} biome: {biome:?}");
@@ -579,13 +661,13 @@ impl ChunkGenerator for OverworldGenerator {
         }
 
         // Water lakes...
-        if rand.next_int_bounded(4) == 0 {
+        if self.options.has_feature(OverworldFeature::WaterLakes) && rand.next_int_bounded(4) == 0 {
             let pos = pos + next_offset(&mut rand, 128, 8);
             LakeGenerator::new(block::WATER_STILL).generate(world, pos, &mut rand);
         }
 
         // Lava lakes...
-        if rand.next_int_bounded(8) == 0 {
+        if self.options.has_feature(OverworldFeature::LavaLakes) && rand.next_int_bounded(8) == 0 {
             let pos = pos
                 + IVec3 {
                     x: rand.next_int_bounded(16) + 8,
@@ -602,181 +684,194 @@ impl ChunkGenerator for OverworldGenerator {
         }
 
         // Mob dungeons...
-        for _ in 0..8 {
-            let pos = pos + next_offset(&mut rand, 128, 8);
-            DungeonGenerator::new().generate(world, pos, &mut rand);
+        if self.options.has_feature(OverworldFeature::Dungeons) {
+            for _ in 0..8 {
+                let pos = pos + next_offset(&mut rand, 128, 8);
+                DungeonGenerator::new().generate(world, pos, &mut rand);
+            }
         }
 
         // Clay veins (only in water).
-        for _ in 0..10 {
-            let pos = pos + next_offset(&mut rand, 128, 0);
-            if world.get_block_material(pos) == Material::Water {
+        if self.options.has_feature(OverworldFeature::Clay) {
+            for _ in 0..10 {
+                let pos = pos + next_offset(&mut rand, 128, 0);
                 VeinGenerator::new_clay(32).generate(world, pos, &mut rand);
             }
         }
 
-        // Dirt veins.
-        for _ in 0..20 {
-            let pos = pos + next_offset(&mut rand, 128, 0);
-            VeinGenerator::new_ore(block::DIRT, 32).generate(world, pos, &mut rand);
-        }
+        if self.options.has_feature(OverworldFeature::Ores) {
+            // Dirt veins.
+            for _ in 0..20 {
+                let pos = pos + next_offset(&mut rand, 128, 0);
+                VeinGenerator::new_ore(block::DIRT, 32).generate(world, pos, &mut rand);
+            }
 
-        // Gravel veins.
-        for _ in 0..10 {
-            let pos = pos + next_offset(&mut rand, 128, 0);
-            VeinGenerator::new_ore(block::GRAVEL, 32).generate(world, pos, &mut rand);
-        }
+            // Gravel veins.
+            for _ in 0..10 {
+                let pos = pos + next_offset(&mut rand, 128, 0);
+                VeinGenerator::new_ore(block::GRAVEL, 32).generate(world, pos, &mut rand);
+            }
 
-        // Coal veins.
-        for _ in 0..20 {
-            let pos = pos + next_offset(&mut rand, 128, 0);
-            VeinGenerator::new_ore(block::COAL_ORE, 16).generate(world, pos, &mut rand);
-        }
+            // Coal veins.
+            for _ in 0..20 {
+                let pos = pos + next_offset(&mut rand, 128, 0);
+                VeinGenerator::new_ore(block::COAL_ORE, 16).generate(world, pos, &mut rand);
+            }
 
-        // Iron veins.
-        for _ in 0..20 {
-            let pos = pos + next_offset(&mut rand, 64, 0);
-            VeinGenerator::new_ore(block::IRON_ORE, 8).generate(world, pos, &mut rand);
-        }
+            // Iron veins.
+            for _ in 0..20 {
+                let pos = pos + next_offset(&mut rand, 64, 0);
+                VeinGenerator::new_ore(block::IRON_ORE, 8).generate(world, pos, &mut rand);
+            }
 
-        // Gold veins.
-        for _ in 0..2 {
-            let pos = pos + next_offset(&mut rand, 32, 0);
-            VeinGenerator::new_ore(block::GOLD_ORE, 8).generate(world, pos, &mut rand);
-        }
+            // Gold veins.
+            for _ in 0..2 {
+                let pos = pos + next_offset(&mut rand, 32, 0);
+                VeinGenerator::new_ore(block::GOLD_ORE, 8).generate(world, pos, &mut rand);
+            }
 
-        // Redstone veins.
-        for _ in 0..8 {
-            let pos = pos + next_offset(&mut rand, 16, 0);
-            VeinGenerator::new_ore(block::REDSTONE_ORE, 7).generate(world, pos, &mut rand);
-        }
+            // Redstone veins.
+            for _ in 0..8 {
+                let pos = pos + next_offset(&mut rand, 16, 0);
+                VeinGenerator::new_ore(block::REDSTONE_ORE, 7).generate(world, pos, &mut rand);
+            }
 
-        // Diamond veins.
-        for _ in 0..1 {
-            let pos = pos + next_offset(&mut rand, 16, 0);
-            VeinGenerator::new_ore(block::DIAMOND_ORE, 7).generate(world, pos, &mut rand);
-        }
+            // Diamond veins.
+            for _ in 0..1 {
+                let pos = pos + next_offset(&mut rand, 16, 0);
+                VeinGenerator::new_ore(block::DIAMOND_ORE, 7).generate(world, pos, &mut rand);
+            }
 
-        // Lapis veins.
-        for _ in 0..1 {
-            let pos = pos
-                + IVec3 {
-                    x: rand.next_int_bounded(16),
-                    y: rand.next_int_bounded(16) + rand.next_int_bounded(16),
-                    z: rand.next_int_bounded(16),
-                };
+            // Lapis veins.
+            for _ in 0..1 {
+                let pos = pos
+                    + IVec3 {
+                        x: rand.next_int_bounded(16),
+                        y: rand.next_int_bounded(16) + rand.next_int_bounded(16),
+                        z: rand.next_int_bounded(16),
+                    };
 
-            VeinGenerator::new_ore(block::LAPIS_ORE, 6).generate(world, pos, &mut rand);
+                VeinGenerator::new_ore(block::LAPIS_ORE, 6).generate(world, pos, &mut rand);
+            }
         }
 
         // Trees, depending on biome and feature noise.
-        let feature_noise = self.feature_noise.gen_2d_point(pos.xz().as_dvec2() * 0.5);
-        let base_tree_count = ((feature_noise / 8.0 + rand.next_double() * 4.0 + 4.0) / 3.0) as i32;
-        let mut tree_count = 0;
-
-        if rand.next_int_bounded(10) == 0 {
-            tree_count += 1;
-        }
-
-        match biome {
-            Biome::Taiga | Biome::RainForest | Biome::Forest => tree_count += base_tree_count + 5,
-            Biome::SeasonalForest => tree_count += base_tree_count + 2,
-            Biome::Desert | Biome::Tundra | Biome::Plains => tree_count -= 20,
-            _ => {}
-        }
-
-        // if cx == 0 && cz == 2 {
-        //     println!("tree_count: {tree_count}");
-        // }
-
-        if tree_count > 0 {
-            for _ in 0..tree_count {
-                let mut pos = pos
-                    + IVec3 {
-                        x: rand.next_int_bounded(16) + 8,
-                        y: 0,
-                        z: rand.next_int_bounded(16) + 8,
-                    };
+        if self.options.has_feature(OverworldFeature::Trees) {
+            let feature_noise = self.feature_noise.gen_2d_point(pos.xz().as_dvec2() * 0.5);
+            let base_tree_count =
+                ((feature_noise / 8.0 + rand.next_double() * 4.0 + 4.0) / 3.0) as i32;
+            let mut tree_count = 0;
+
+            if rand.next_int_bounded(10) == 0 {
+                tree_count += 1;
+            }
 
-                pos.y = world.get_height(pos).unwrap();
+            match biome {
+                Biome::Taiga | Biome::RainForest | Biome::Forest => {
+                    tree_count += base_tree_count + 5
+                }
+                Biome::SeasonalForest => tree_count += base_tree_count + 2,
+                Biome::Desert | Biome::Tundra | Biome::Plains => tree_count -= 20,
+                _ => {}
+            }
 
-                let mut gen = match biome {
-                    Biome::Taiga => {
-                        if rand.next_int_bounded(3) == 0 {
-                            TreeGenerator::new_spruce1()
-                        } else {
-                            TreeGenerator::new_spruce2()
+            // if cx == 0 && cz == 2 {
+            //     println!("tree_count: {tree_count}");
+            // }
+
+            if tree_count > 0 {
+                for _ in 0..tree_count {
+                    let mut pos = pos
+                        + IVec3 {
+                            x: rand.next_int_bounded(16) + 8,
+                            y: 0,
+                            z: rand.next_int_bounded(16) + 8,
+                        };
+
+                    pos.y = world.get_height(pos).unwrap();
+
+                    let mut gen = match biome {
+                        Biome::Taiga => {
+                            if rand.next_int_bounded(3) == 0 {
+                                TreeGenerator::new_spruce1()
+                            } else {
+                                TreeGenerator::new_spruce2()
+                            }
                         }
-                    }
-                    Biome::Forest => {
-                        if rand.next_int_bounded(5) == 0 {
-                            TreeGenerator::new_birch()
-                        } else if rand.next_int_bounded(3) == 0 {
-                            TreeGenerator::new_big_natural()
-                        } else {
-                            TreeGenerator::new_oak()
+                        Biome::Forest => {
+                            if rand.next_int_bounded(5) == 0 {
+                                TreeGenerator::new_birch()
+                            } else if rand.next_int_bounded(3) == 0 {
+                                TreeGenerator::new_big_natural()
+                            } else {
+                                TreeGenerator::new_oak()
+                            }
                         }
-                    }
-                    Biome::RainForest => {
-                        if rand.next_int_bounded(3) == 0 {
-                            TreeGenerator::new_big_natural()
-                        } else {
-                            TreeGenerator::new_oak()
+                        Biome::RainForest => {
+                            if rand.next_int_bounded(3) == 0 {
+                                TreeGenerator::new_big_natural()
+                            } else {
+                                TreeGenerator::new_oak()
+                            }
                         }
-                    }
-                    _ => {
-                        if rand.next_int_bounded(10) == 0 {
-                            TreeGenerator::new_big_natural()
-                        } else {
-                            TreeGenerator::new_oak()
+                        _ => {
+                            if rand.next_int_bounded(10) == 0 {
+                                TreeGenerator::new_big_natural()
+                            } else {
+                                TreeGenerator::new_oak()
+                            }
                         }
-                    }
-                };
+                    };
 
-                gen.generate(world, pos, &mut rand);
+                    gen.generate(world, pos, &mut rand);
+                }
             }
-        }
 
-        // if cx == 0 && cz == 2 {
-        //     println!("next float: {}", rand.next_float());
-        // }
+            // if cx == 0 && cz == 2 {
+            //     println!("next float: {}", rand.next_float());
+            // }
+        }
 
         // Dandelion patches.
-        let dandelion_count = match biome {
-            Biome::Forest => 2,
-            Biome::Taiga => 2,
-            Biome::SeasonalForest => 4,
-            Biome::Plains => 3,
-            _ => 0,
-        };
-
-        for _ in 0..dandelion_count {
-            let pos = pos + next_offset(&mut rand, 128, 8);
-            PlantGenerator::new_flower(block::DANDELION).generate(world, pos, &mut rand);
+        if self.options.has_feature(OverworldFeature::Dandelions) {
+            let dandelion_count = match biome {
+                Biome::Forest => 2,
+                Biome::Taiga => 2,
+                Biome::SeasonalForest => 4,
+                Biome::Plains => 3,
+                _ => 0,
+            };
+
+            for _ in 0..dandelion_count {
+                let pos = pos + next_offset(&mut rand, 128, 8);
+                PlantGenerator::new_flower(block::DANDELION).generate(world, pos, &mut rand);
+            }
         }
 
         // Tall grass patches.
-        let tall_grass_count = match biome {
-            Biome::Forest => 2,
-            Biome::RainForest => 10,
-            Biome::SeasonalForest => 2,
-            Biome::Taiga => 1,
-            Biome::Plains => 10,
-            _ => 0,
-        };
-
-        for _ in 0..tall_grass_count {
-            let mut metadata = 1;
-            if biome == Biome::RainForest && rand.next_int_bounded(3) != 0 {
-                metadata = 2;
-            }
+        if self.options.has_feature(OverworldFeature::TallGrass) {
+            let tall_grass_count = match biome {
+                Biome::Forest => 2,
+                Biome::RainForest => 10,
+                Biome::SeasonalForest => 2,
+                Biome::Taiga => 1,
+                Biome::Plains => 10,
+                _ => 0,
+            };
+
+            for _ in 0..tall_grass_count {
+                let mut metadata = 1;
+                if biome == Biome::RainForest && rand.next_int_bounded(3) != 0 {
+                    metadata = 2;
+                }
 
-            let pos = pos + next_offset(&mut rand, 128, 8);
-            PlantGenerator::new_tall_grass(metadata).generate(world, pos, &mut rand);
+                let pos = pos + next_offset(&mut rand, 128, 8);
+                PlantGenerator::new_tall_grass(metadata).generate(world, pos, &mut rand);
+            }
         }
 
         // Dead bush in deserts.
-        if biome == Biome::Desert {
+        if self.options.has_feature(OverworldFeature::DeadBushes) && biome == Biome::Desert {
             for _ in 0..2 {
                 let pos = pos + next_offset(&mut rand, 128, 8);
                 PlantGenerator::new_dead_bush().generate(world, pos, &mut rand);
@@ -784,109 +879,127 @@ impl ChunkGenerator for OverworldGenerator {
         }
 
         // Poppy.
-        if rand.next_int_bounded(2) == 0 {
+        if self.options.has_feature(OverworldFeature::Poppies) && rand.next_int_bounded(2) == 0 {
             let pos = pos + next_offset(&mut rand, 128, 8);
             PlantGenerator::new_flower(block::POPPY).generate(world, pos, &mut rand);
         }
 
         // Brown mushroom.
-        if rand.next_int_bounded(4) == 0 {
+        if self.options.has_feature(OverworldFeature::Mushrooms) && rand.next_int_bounded(4) == 0 {
             let pos = pos + next_offset(&mut rand, 128, 8);
             PlantGenerator::new_flower(block::BROWN_MUSHROOM).generate(world, pos, &mut rand);
         }
 
         // Red mushroom.
-        if rand.next_int_bounded(8) == 0 {
+        if self.options.has_feature(OverworldFeature::Mushrooms) && rand.next_int_bounded(8) == 0 {
             let pos = pos + next_offset(&mut rand, 128, 8);
             PlantGenerator::new_flower(block::RED_MUSHROOM).generate(world, pos, &mut rand);
         }
 
         // Sugar canes.
-        for _ in 0..10 {
-            let pos = pos + next_offset(&mut rand, 128, 8);
-            SugarCanesGenerator::new().generate(world, pos, &mut rand);
+        if self.options.has_feature(OverworldFeature::SugarCanes) {
+            for _ in 0..10 {
+                let pos = pos + next_offset(&mut rand, 128, 8);
+                SugarCanesGenerator::new().generate(world, pos, &mut rand);
+            }
         }
 
         // Pumpkin.
-        if rand.next_int_bounded(32) == 0 {
+        if self.options.has_feature(OverworldFeature::Pumpkins) && rand.next_int_bounded(32) == 0 {
             let pos = pos + next_offset(&mut rand, 128, 8);
             PumpkinGenerator::new().generate(world, pos, &mut rand);
         }
 
         // Cactus.
-        if biome == Biome::Desert {
+        if self.options.has_feature(OverworldFeature::Cacti) && biome == Biome::Desert {
             for _ in 0..10 {
                 let pos = pos + next_offset(&mut rand, 128, 8);
                 CactusGenerator::new().generate(world, pos, &mut rand);
             }
         }
 
-        // Water sources.
-        for _ in 0..50 {
-            let pos = pos
-                + IVec3 {
-                    x: rand.next_int_bounded(16) + 8,
-                    y: {
-                        let v = rand.next_int_bounded(120);
-                        rand.next_int_bounded(v + 8)
-                    },
-                    z: rand.next_int_bounded(16) + 8,
-                };
-
-            LiquidGenerator::new(block::WATER_MOVING).generate(world, pos, &mut rand);
-        }
-
-        // Lava sources.
-        for _ in 0..20 {
-            let pos = pos
-                + IVec3 {
-                    x: rand.next_int_bounded(16) + 8,
-                    y: {
-                        let v = rand.next_int_bounded(112);
-                        let v = rand.next_int_bounded(v + 8);
-                        rand.next_int_bounded(v + 8)
-                    },
-                    z: rand.next_int_bounded(16) + 8,
-                };
+        // Water springs.
+        if self.options.has_feature(OverworldFeature::WaterSprings) {
+            for _ in 0..50 {
+                let pos = pos
+                    + IVec3 {
+                        x: rand.next_int_bounded(16) + 8,
+                        y: {
+                            let v = rand.next_int_bounded(120);
+                            rand.next_int_bounded(v + 8)
+                        },
+                        z: rand.next_int_bounded(16) + 8,
+                    };
 
-            LiquidGenerator::new(block::LAVA_MOVING).generate(world, pos, &mut rand);
+                LiquidGenerator::new(block::WATER_MOVING).generate(world, pos, &mut rand);
+            }
         }
 
-        // Finally add snow layer if cold enought.
-        let offset = DVec2::new((pos.x + 8) as f64, (pos.y + 8) as f64);
-        let temperature = &mut state.temperature;
-        let biome = &mut state.biome;
-        self.temperature_noise.gen_weird_2d(
-            temperature,
-            offset,
-            TEMPERATURE_SCALE,
-            TEMPERATURE_FREQ_FACTOR,
-        );
-        self.biome_noise
-            .gen_weird_2d(biome, offset, BIOME_SCALE, BIOME_FREQ_FACTOR);
-
-        for dx in 0usize..16 {
-            for dz in 0usize..16 {
-                let snow_pos = pos
+        // Lava springs.
+        if self.options.has_feature(OverworldFeature::LavaSprings) {
+            for _ in 0..20 {
+                let pos = pos
                     + IVec3 {
-                        x: dx as i32,
-                        y: 0,
-                        z: dz as i32,
+                        x: rand.next_int_bounded(16) + 8,
+                        y: {
+                            let v = rand.next_int_bounded(112);
+                            let v = rand.next_int_bounded(v + 8);
+                            rand.next_int_bounded(v + 8)
+                        },
+                        z: rand.next_int_bounded(16) + 8,
                     };
 
-                // Find highest block and set pos.y.
+                LiquidGenerator::new(block::LAVA_MOVING).generate(world, pos, &mut rand);
+            }
+        }
 
-                let temp = temperature.get(dx, 0, dz) - (snow_pos.y - 64) as f64 / 64.0 * 0.3;
-                if temp < 0.5 && snow_pos.y > 0 && snow_pos.y < 128 && world.is_block_air(snow_pos)
-                {
-                    let material = world.get_block_material(snow_pos - IVec3::Y);
-                    if material.is_solid() && material != Material::Ice {
-                        world.set_block(snow_pos, block::SNOW, 0);
+        // Finally add snow layer if cold enought.
+        if self.options.has_feature(OverworldFeature::Snow) {
+            let offset = DVec2::new((pos.x + 8) as f64, (pos.y + 8) as f64);
+            let temperature = &mut state.temperature;
+            let biome = &mut state.biome;
+            self.temperature_noise.gen_weird_2d(
+                temperature,
+                offset,
+                TEMPERATURE_SCALE,
+                TEMPERATURE_FREQ_FACTOR,
+            );
+            self.biome_noise
+                .gen_weird_2d(biome, offset, BIOME_SCALE, BIOME_FREQ_FACTOR);
+
+            for dx in 0usize..16 {
+                for dz in 0usize..16 {
+                    let snow_pos = pos
+                        + IVec3 {
+                            x: dx as i32,
+                            y: 0,
+                            z: dz as i32,
+                        };
+
+                    // Find highest block and set pos.y.
+
+                    let temp = temperature.get(dx, 0, dz) - (snow_pos.y - 64) as f64 / 64.0 * 0.3;
+                    if temp < 0.5
+                        && snow_pos.y > 0
+                        && snow_pos.y < 128
+                        && world.is_block_air(snow_pos)
+                    {
+                        let material = world.get_block_material(snow_pos - IVec3::Y);
+                        if material.is_solid() && material != Material::Ice {
+                            world.set_block(snow_pos, block::SNOW, 0);
+                        }
                     }
                 }
             }
         }
 
+        // Bonus chest, only in the chunk populating the area around the spawn.
+        if let Some(spawn_pos) = self.options.bonus_chest {
+            if (spawn_pos.x - 8).div_euclid(16) == cx && (spawn_pos.z - 8).div_euclid(16) == cz {
+                BonusChestGenerator::new().generate(world, spawn_pos, &mut rand);
+            }
+        }
+
         // TODO: This is temporary code to avoid light bugs at generation, but this
         // considerably slows down the feature generation (that is currently
         // single-threaded).
@@ -10,7 +10,9 @@ use crate::world::World;
 
 use super::FeatureGenerator;
 
-/// A generator for flower patch.
+/// A generator for flower, mushroom, tall grass and dead bush patches.
+///
+/// REF: WorldGenFlowers, WorldGenTallGrass, WorldGenDeadBush
 pub struct PlantGenerator {
     plant_id: u8,
     plant_metadata: u8,
@@ -64,11 +66,8 @@ impl FeatureGenerator for PlantGenerator {
                     z: rand.next_int_bounded(8) - rand.next_int_bounded(8),
                 };
 
-            // PARITY: Check parity of "canBlockStay"...
-            if world.is_block_air(place_pos)
-                && world.can_place_block(place_pos, Face::NegY, self.plant_id)
-            {
-                world.set_block(place_pos, self.plant_id, self.plant_metadata);
+            if can_plant_stay(world, place_pos, self.plant_id) {
+                place_plant(world, place_pos, self.plant_id, self.plant_metadata);
             }
         }
 
@@ -76,6 +75,93 @@ impl FeatureGenerator for PlantGenerator {
     }
 }
 
+/// Check the light requirements of a plant at the given position, the block below is
+/// checked when placing the plant.
+///
+/// REF: BlockFlower::canBlockStay, BlockMushroom::canBlockStay
+fn can_plant_stay(world: &mut World, pos: IVec3, id: u8) -> bool {
+    let light = world.get_light(pos);
+    match id {
+        block::BROWN_MUSHROOM | block::RED_MUSHROOM => {
+            pos.y >= 0 && pos.y < 128 && light.max_real() < 13
+        }
+        _ => light.max_real() >= 8 || world.get_height(pos).is_some_and(|height| pos.y >= height),
+    }
+}
+
+/// A generator for tall grass and flowers when using bone meal on a grass block.
+pub struct BoneMealGenerator(());
+
+impl BoneMealGenerator {
+    #[inline]
+    pub fn new() -> Self {
+        Self(())
+    }
+
+    /// Generate plants around a grass block where bone meal has been used, the world's
+    /// random number generator is used.
+    pub fn generate_from_bone_meal(&mut self, world: &mut World, pos: IVec3) -> bool {
+        let mut rand = world.get_rand_mut().clone();
+        let success = self.generate(world, pos, &mut rand);
+        *world.get_rand_mut() = rand;
+        success
+    }
+}
+
+impl Default for BoneMealGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureGenerator for BoneMealGenerator {
+    /// The given position is the grass block where bone meal is used, plants are placed
+    /// by randomly walking on grass blocks from the block above it.
+    ///
+    /// REF: ItemDye::onItemUse
+    fn generate(&mut self, world: &mut World, pos: IVec3, rand: &mut JavaRandom) -> bool {
+        'try_place: for i in 0..128 {
+            let mut place_pos = pos + IVec3::Y;
+
+            for _ in 0..i / 16 {
+                place_pos.x += rand.next_int_bounded(3) - 1;
+                place_pos.y += (rand.next_int_bounded(3) - 1) * rand.next_int_bounded(3) / 2;
+                place_pos.z += rand.next_int_bounded(3) - 1;
+
+                if !world.is_block(place_pos - IVec3::Y, block::GRASS)
+                    || world.is_block_opaque_cube(place_pos)
+                {
+                    continue 'try_place;
+                }
+            }
+
+            if world.is_block_air(place_pos) {
+                if rand.next_int_bounded(10) != 0 {
+                    place_plant(world, place_pos, block::TALL_GRASS, 1);
+                } else if rand.next_int_bounded(3) != 0 {
+                    place_plant(world, place_pos, block::DANDELION, 0);
+                } else {
+                    place_plant(world, place_pos, block::POPPY, 0);
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Place a plant block at the given position if the position is air and the plant can
+/// be placed on the block below, return true if the plant has been placed.
+pub fn place_plant(world: &mut World, pos: IVec3, id: u8, metadata: u8) -> bool {
+    // PARITY: Check parity of "canBlockStay"...
+    if world.is_block_air(pos) && world.can_place_block(pos, Face::NegY, id) {
+        world.set_block(pos, id, metadata);
+        true
+    } else {
+        false
+    }
+}
+
 /// A generator for sugar canes.
 pub struct SugarCanesGenerator(());
 
@@ -93,6 +179,7 @@ impl Default for SugarCanesGenerator {
 }
 
 impl FeatureGenerator for SugarCanesGenerator {
+    /// REF: WorldGenReed::generate
     fn generate(&mut self, world: &mut World, pos: IVec3, rand: &mut JavaRandom) -> bool {
         for _ in 0..20 {
             let place_pos = pos
@@ -102,24 +189,18 @@ impl FeatureGenerator for SugarCanesGenerator {
                     z: rand.next_int_bounded(4) - rand.next_int_bounded(4),
                 };
 
-            if world.is_block_air(place_pos) {
-                for face in Face::HORIZONTAL {
-                    if world.get_block_material(place_pos - IVec3::Y + face.delta())
-                        == Material::Water
-                    {
-                        let v = rand.next_int_bounded(3) + 1;
-                        let height = rand.next_int_bounded(v) + 2;
-
-                        // Check that the bottom cane can be placed.
-                        if world.can_place_block(place_pos, Face::NegY, block::SUGAR_CANES) {
-                            for dy in 0..height {
-                                world.set_block(
-                                    place_pos + IVec3::new(0, dy, 0),
-                                    block::SUGAR_CANES,
-                                    0,
-                                );
-                            }
-                        }
+            if world.is_block_air(place_pos)
+                && Face::HORIZONTAL.into_iter().any(|face| {
+                    world.get_block_material(place_pos - IVec3::Y + face.delta()) == Material::Water
+                })
+            {
+                let v = rand.next_int_bounded(3) + 1;
+                let height = rand.next_int_bounded(v) + 2;
+
+                for dy in 0..height {
+                    let cane_pos = place_pos + IVec3::new(0, dy, 0);
+                    if world.can_place_block(cane_pos, Face::NegY, block::SUGAR_CANES) {
+                        world.set_block(cane_pos, block::SUGAR_CANES, 0);
                     }
                 }
             }
@@ -146,6 +227,7 @@ impl Default for PumpkinGenerator {
 }
 
 impl FeatureGenerator for PumpkinGenerator {
+    /// REF: WorldGenPumpkin::generate
     fn generate(&mut self, world: &mut World, pos: IVec3, rand: &mut JavaRandom) -> bool {
         for _ in 0..64 {
             let place_pos = pos
@@ -155,8 +237,10 @@ impl FeatureGenerator for PumpkinGenerator {
                     z: rand.next_int_bounded(8) - rand.next_int_bounded(8),
                 };
 
-            // PARITY: Check parity of "canBlockStay"...
-            if world.is_block_air(place_pos) && world.is_block(place_pos - IVec3::Y, block::GRASS) {
+            if world.is_block_air(place_pos)
+                && world.is_block(place_pos - IVec3::Y, block::GRASS)
+                && world.can_place_block(place_pos, Face::NegY, block::PUMPKIN)
+            {
                 world.set_block(place_pos, block::PUMPKIN, rand.next_int_bounded(4) as u8);
             }
         }
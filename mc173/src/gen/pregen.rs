@@ -0,0 +1,259 @@
+//! World pre-generation driver.
+//!
+//! Pre-generation loads or generates all chunks in a square area using a threaded
+//! [`ChunkStorage`], generated chunks are directly saved to the storage's region files
+//! by the storage worker. Chunks are processed in a fixed order, the progress contains
+//! a resume index that can be used to continue an interrupted pre-generation.
+
+use std::time::{Duration, Instant};
+
+use crate::storage::{ChunkStorage, ChunkStorageReply};
+
+/// Default number of chunks that can be requested and not yet returned by the storage.
+const DEFAULT_MAX_PENDING: usize = 64;
+
+/// State of a single chunk in the pre-generation area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkState {
+    /// The chunk has not yet been requested.
+    Waiting,
+    /// The chunk has been requested to the storage.
+    Pending,
+    /// The chunk has been returned by the storage.
+    Done,
+}
+
+/// A pre-generation of all chunks in a square area around a center chunk. This driver
+/// can either be used with the blocking [`pregenerate`] function, or driven manually
+/// with [`request`](Self::request) and [`handle_load`](Self::handle_load).
+#[derive(Debug, Clone)]
+pub struct Pregen {
+    /// Minimum chunk X coordinate of the area.
+    min_cx: i32,
+    /// Minimum chunk Z coordinate of the area.
+    min_cz: i32,
+    /// Size of the area side, in chunks.
+    size: usize,
+    /// State of each chunk, indexed by Z then X.
+    states: Vec<ChunkState>,
+    /// Index of the next chunk to request.
+    next_index: usize,
+    /// The first index that is not done yet.
+    resume_index: usize,
+    /// Number of chunks currently pending.
+    pending: usize,
+    /// Maximum number of pending chunks.
+    max_pending: usize,
+    /// Number of chunks done, including chunks skipped by resuming.
+    done: usize,
+    /// Number of chunks done since start, used for speed.
+    session_done: usize,
+    /// Number of chunks that failed to load.
+    failed: usize,
+    /// Instant when the pre-generation started.
+    start: Instant,
+}
+
+/// Progress of a pre-generation.
+#[derive(Debug, Clone)]
+pub struct PregenProgress {
+    /// Number of chunks done, including the failed ones.
+    pub done: usize,
+    /// Total number of chunks to pre-generate.
+    pub total: usize,
+    /// Number of chunks that failed to load or generate.
+    pub failed: usize,
+    /// The index to give to [`Pregen::resume`] in order to continue this pre-generation
+    /// if interrupted, all chunks before this index are done.
+    pub resume_index: usize,
+    /// Number of chunks done since this pre-generation has been started or resumed.
+    pub session_done: usize,
+    /// Time elapsed since this pre-generation has been started or resumed.
+    pub elapsed: Duration,
+}
+
+impl Pregen {
+    /// Create a new pre-generation of all chunks in the given radius, in chunks, around
+    /// the given center chunk.
+    pub fn new(center_cx: i32, center_cz: i32, radius: u32) -> Self {
+        let size = radius as usize * 2 + 1;
+        Self {
+            min_cx: center_cx - radius as i32,
+            min_cz: center_cz - radius as i32,
+            size,
+            states: vec![ChunkState::Waiting; size * size],
+            next_index: 0,
+            resume_index: 0,
+            pending: 0,
+            max_pending: DEFAULT_MAX_PENDING,
+            done: 0,
+            session_done: 0,
+            failed: 0,
+            start: Instant::now(),
+        }
+    }
+
+    /// Resume this pre-generation from the given index, all chunks before this index
+    /// are considered done, see [`PregenProgress::resume_index`].
+    pub fn resume(mut self, index: usize) -> Self {
+        let index = index.min(self.states.len());
+        self.states[..index].fill(ChunkState::Done);
+        self.next_index = index;
+        self.resume_index = index;
+        self.done = index;
+        self
+    }
+
+    /// Change the maximum number of chunks that can be requested to the storage and not
+    /// yet returned, this defaults to 64.
+    pub fn max_pending(mut self, max_pending: usize) -> Self {
+        self.max_pending = max_pending.max(1);
+        self
+    }
+
+    /// Return true if all chunks of this pre-generation are done.
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.done == self.states.len()
+    }
+
+    /// Return the index of the given chunk if it is in the pre-generation area.
+    fn index(&self, cx: i32, cz: i32) -> Option<usize> {
+        let dx = usize::try_from(cx - self.min_cx).ok()?;
+        let dz = usize::try_from(cz - self.min_cz).ok()?;
+        (dx < self.size && dz < self.size).then_some(dz * self.size + dx)
+    }
+
+    /// Request the next chunks to the storage, up to the maximum number of pending
+    /// chunks.
+    pub fn request(&mut self, storage: &mut ChunkStorage) {
+        while self.pending < self.max_pending && self.next_index < self.states.len() {
+            let index = self.next_index;
+            self.next_index += 1;
+            if self.states[index] == ChunkState::Waiting {
+                self.states[index] = ChunkState::Pending;
+                self.pending += 1;
+                let cx = self.min_cx + (index % self.size) as i32;
+                let cz = self.min_cz + (index / self.size) as i32;
+                storage.request_load(cx, cz);
+            }
+        }
+    }
+
+    /// Handle a chunk load reply from the storage, the success indicates if the chunk
+    /// has been successfully loaded. This returns true if the chunk has been requested
+    /// by this pre-generation and is now done, in such case the reply is claimed by the
+    /// pre-generation and the chunk may be dropped by the caller if not needed.
+    ///
+    /// Replies for chunks not requested by this pre-generation, even in its area, are
+    /// not claimed: these chunks have been requested by someone else.
+    pub fn handle_load(&mut self, cx: i32, cz: i32, success: bool) -> bool {
+        let Some(index) = self.index(cx, cz) else {
+            return false;
+        };
+
+        if self.states[index] != ChunkState::Pending {
+            return false;
+        }
+
+        self.pending -= 1;
+        self.states[index] = ChunkState::Done;
+        self.done += 1;
+        self.session_done += 1;
+        if !success {
+            self.failed += 1;
+        }
+
+        while self.resume_index < self.states.len()
+            && self.states[self.resume_index] == ChunkState::Done
+        {
+            self.resume_index += 1;
+        }
+
+        true
+    }
+
+    /// Get the current progress of this pre-generation.
+    pub fn progress(&self) -> PregenProgress {
+        PregenProgress {
+            done: self.done,
+            total: self.states.len(),
+            failed: self.failed,
+            resume_index: self.resume_index,
+            session_done: self.session_done,
+            elapsed: self.start.elapsed(),
+        }
+    }
+}
+
+impl PregenProgress {
+    /// Return the percentage of chunks done, between 0 and 100.
+    pub fn percent(&self) -> f32 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.done as f32 / self.total as f32 * 100.0
+        }
+    }
+
+    /// Return the average number of chunks done per second.
+    pub fn chunks_per_sec(&self) -> f32 {
+        let secs = self.elapsed.as_secs_f32();
+        if secs == 0.0 {
+            0.0
+        } else {
+            self.session_done as f32 / secs
+        }
+    }
+
+    /// Return the estimated remaining duration, if the speed is known.
+    pub fn eta(&self) -> Option<Duration> {
+        let speed = self.chunks_per_sec();
+        if speed == 0.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f32(
+                (self.total - self.done) as f32 / speed,
+            ))
+        }
+    }
+}
+
+/// Run the given pre-generation to completion with the given storage, blocking until
+/// all chunks are done. The progress callback is called each time a chunk is done.
+/// Chunks returned by the storage are dropped, so the storage should not be shared with
+/// a world while pre-generating.
+pub fn pregenerate(
+    storage: &mut ChunkStorage,
+    pregen: &mut Pregen,
+    mut progress: impl FnMut(&PregenProgress),
+) {
+    while !pregen.is_done() {
+        pregen.request(storage);
+        if let Some(ChunkStorageReply::Load { cx, cz, res }) =
+            storage.poll_timeout(Duration::from_millis(100))
+        {
+            if pregen.handle_load(cx, cz, res.is_ok()) {
+                progress(&pregen.progress());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn unrequested_loads() {
+        let mut pregen = Pregen::new(0, 0, 1);
+        assert!(!pregen.handle_load(0, 0, true));
+        assert!(!pregen.handle_load(5, 5, true));
+        assert_eq!(pregen.progress().done, 0);
+
+        let mut pregen = Pregen::new(0, 0, 1).resume(9);
+        assert!(pregen.is_done());
+        assert!(!pregen.handle_load(0, 0, true));
+    }
+}
@@ -0,0 +1,175 @@
+//! Block schematics, a cuboid of blocks with their block entities that can be stamped
+//! into chunks during generation, or pasted into a running world.
+
+use glam::IVec3;
+
+use crate::block;
+use crate::block_entity::BlockEntity;
+use crate::chunk::{Chunk, CHUNK_HEIGHT, CHUNK_WIDTH};
+use crate::world::silent::BlockChanges;
+use crate::world::World;
+
+/// A cuboid of blocks and block entities, positions are relative to its origin.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    /// Size of the schematic on each axis.
+    size: IVec3,
+    /// Block ids, indexed by Y, then Z, then X.
+    blocks: Vec<u8>,
+    /// Block metadata, with the same layout as blocks.
+    metadata: Vec<u8>,
+    /// Block entities with their relative position.
+    block_entities: Vec<(IVec3, Box<BlockEntity>)>,
+}
+
+impl Schematic {
+    /// Create a new schematic of the given size, full of air. Panics if any component of
+    /// the size is negative.
+    pub fn new(size: IVec3) -> Self {
+        assert!(size.cmpge(IVec3::ZERO).all(), "negative schematic size");
+        let len = (size.x * size.y * size.z) as usize;
+        Self {
+            size,
+            blocks: vec![block::AIR; len],
+            metadata: vec![0; len],
+            block_entities: Vec::new(),
+        }
+    }
+
+    /// Create a new schematic from its raw blocks and metadata, indexed by Y, then Z,
+    /// then X. None is returned if the size doesn't match the arrays.
+    pub fn with_blocks(size: IVec3, blocks: Vec<u8>, metadata: Vec<u8>) -> Option<Self> {
+        if size.cmplt(IVec3::ZERO).any() {
+            return None;
+        }
+
+        let len = (size.x as usize) * (size.y as usize) * (size.z as usize);
+        if blocks.len() != len || metadata.len() != len {
+            return None;
+        }
+
+        Some(Self {
+            size,
+            blocks,
+            metadata,
+            block_entities: Vec::new(),
+        })
+    }
+
+    /// Get the size of this schematic.
+    #[inline]
+    pub fn get_size(&self) -> IVec3 {
+        self.size
+    }
+
+    /// Get the raw block ids, indexed by Y, then Z, then X.
+    #[inline]
+    pub fn get_blocks(&self) -> &[u8] {
+        &self.blocks
+    }
+
+    /// Get the raw block metadata, indexed by Y, then Z, then X.
+    #[inline]
+    pub fn get_metadata(&self) -> &[u8] {
+        &self.metadata
+    }
+
+    /// Get the block at the given relative position, none if outside of the schematic.
+    pub fn get_block(&self, pos: IVec3) -> Option<(u8, u8)> {
+        let index = self.index(pos)?;
+        Some((self.blocks[index], self.metadata[index]))
+    }
+
+    /// Set the block at the given relative position, ignored if outside of the
+    /// schematic.
+    pub fn set_block(&mut self, pos: IVec3, id: u8, metadata: u8) {
+        if let Some(index) = self.index(pos) {
+            self.blocks[index] = id;
+            self.metadata[index] = metadata;
+        }
+    }
+
+    /// Add a block entity at the given relative position, replacing any previous one.
+    pub fn set_block_entity(&mut self, pos: IVec3, block_entity: impl Into<Box<BlockEntity>>) {
+        self.block_entities
+            .retain(|(other_pos, _)| *other_pos != pos);
+        self.block_entities.push((pos, block_entity.into()));
+    }
+
+    /// Iterate over all block entities with their relative position.
+    pub fn iter_block_entities(&self) -> impl Iterator<Item = (IVec3, &BlockEntity)> + '_ {
+        self.block_entities
+            .iter()
+            .map(|(pos, block_entity)| (*pos, &**block_entity))
+    }
+
+    /// Stamp all non-air blocks of this schematic that are in the given chunk, the
+    /// schematic being placed at the given absolute origin. Block entities are not
+    /// stamped, see [`stamp_block_entities`](Self::stamp_block_entities). The height map
+    /// of the chunk is not recomputed.
+    pub fn stamp_chunk(&self, origin: IVec3, cx: i32, cz: i32, chunk: &mut Chunk) {
+        let chunk_min = IVec3::new(cx * CHUNK_WIDTH as i32, 0, cz * CHUNK_WIDTH as i32);
+        let chunk_max = chunk_min + IVec3::new(16, CHUNK_HEIGHT as i32, 16);
+
+        let min = origin.max(chunk_min);
+        let max = (origin + self.size).min(chunk_max);
+
+        for y in min.y..max.y {
+            for z in min.z..max.z {
+                for x in min.x..max.x {
+                    let pos = IVec3::new(x, y, z);
+                    let (id, metadata) = self.get_block(pos - origin).unwrap();
+                    if id != block::AIR {
+                        chunk.set_block(pos, id, metadata);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Paste all non-air blocks and block entities of this schematic into a running
+    /// world, the schematic being placed at the given absolute origin. Blocks are changed
+    /// in a silent scope, see [`World::with_silent_changes`], and blocks in unloaded
+    /// chunks are ignored, the aggregated changes are returned.
+    pub fn paste(&self, origin: IVec3, world: &mut World) -> BlockChanges {
+        let ((), changes) = world.with_silent_changes(|world| {
+            for y in 0..self.size.y {
+                for z in 0..self.size.z {
+                    for x in 0..self.size.x {
+                        let pos = IVec3::new(x, y, z);
+                        let (id, metadata) = self.get_block(pos).unwrap();
+                        if id != block::AIR {
+                            world.set_block(origin + pos, id, metadata);
+                        }
+                    }
+                }
+            }
+
+            for (pos, block_entity) in &self.block_entities {
+                world.set_block_entity(origin + *pos, block_entity.clone());
+            }
+        });
+
+        changes
+    }
+
+    /// Stamp all block entities of this schematic that are in the given chunk, the
+    /// schematic being placed at the given absolute origin. This is intended to be used
+    /// when populating the chunk.
+    pub fn stamp_block_entities(&self, origin: IVec3, cx: i32, cz: i32, world: &mut World) {
+        for (pos, block_entity) in &self.block_entities {
+            let pos = origin + *pos;
+            if pos.x.div_euclid(16) == cx && pos.z.div_euclid(16) == cz {
+                world.set_block_entity(pos, block_entity.clone());
+            }
+        }
+    }
+
+    /// Internal function to get the index of a relative position.
+    fn index(&self, pos: IVec3) -> Option<usize> {
+        if pos.cmplt(IVec3::ZERO).any() || pos.cmpge(self.size).any() {
+            return None;
+        }
+        Some(((pos.y * self.size.z + pos.z) * self.size.x + pos.x) as usize)
+    }
+}
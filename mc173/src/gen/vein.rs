@@ -3,17 +3,22 @@
 use glam::{DVec3, IVec3};
 
 use crate::block;
+use crate::block::material::Material;
 use crate::rand::JavaRandom;
 use crate::world::World;
 
 use super::math::MinecraftMath;
 use super::FeatureGenerator;
 
-/// A generator for mob spawner dungeon.
+/// A generator for clay and ore veins.
 pub struct VeinGenerator {
     replace_id: u8,
     place_id: u8,
     count: u8,
+    /// Vertical offset of the vein line from the given position.
+    y_offset: i32,
+    /// True if the vein can only be generated if the given position is in water.
+    in_water: bool,
 }
 
 impl VeinGenerator {
@@ -23,14 +28,26 @@ impl VeinGenerator {
             replace_id,
             place_id,
             count,
+            y_offset: 2,
+            in_water: false,
         }
     }
 
+    /// A clay vein replacing sand, only generated if the position is in water.
+    ///
+    /// REF: WorldGenClay
     #[inline]
     pub fn new_clay(count: u8) -> Self {
-        Self::new(block::SAND, block::CLAY, count)
+        Self {
+            y_offset: -2,
+            in_water: true,
+            ..Self::new(block::SAND, block::CLAY, count)
+        }
     }
 
+    /// An ore vein replacing stone.
+    ///
+    /// REF: WorldGenMinable
     #[inline]
     pub fn new_ore(place_id: u8, count: u8) -> Self {
         Self::new(block::STONE, place_id, count)
@@ -39,6 +56,10 @@ impl VeinGenerator {
 
 impl FeatureGenerator for VeinGenerator {
     fn generate(&mut self, world: &mut World, pos: IVec3, rand: &mut JavaRandom) -> bool {
+        if self.in_water && world.get_block_material(pos) != Material::Water {
+            return false;
+        }
+
         let angle = rand.next_float() * f32::MC_PI;
         let (angle_sin, angle_cos) = angle.mc_sin_cos();
         let angle_sin = angle_sin * self.count as f32 / 8.0;
@@ -46,13 +67,13 @@ impl FeatureGenerator for VeinGenerator {
 
         let line_start = DVec3 {
             x: ((pos.x + 8) as f32 + angle_sin) as f64,
-            y: (pos.y + rand.next_int_bounded(3) + 2) as f64,
+            y: (pos.y + rand.next_int_bounded(3) + self.y_offset) as f64,
             z: ((pos.z + 8) as f32 + angle_cos) as f64,
         };
 
         let line_stop = DVec3 {
             x: ((pos.x + 8) as f32 - angle_sin) as f64,
-            y: (pos.y + rand.next_int_bounded(3) + 2) as f64,
+            y: (pos.y + rand.next_int_bounded(3) + self.y_offset) as f64,
             z: ((pos.z + 8) as f32 - angle_cos) as f64,
         };
 
@@ -0,0 +1,136 @@
+//! Void chunk generator, for skyblock-like worlds.
+
+use glam::IVec3;
+
+use crate::biome::Biome;
+use crate::chunk::Chunk;
+use crate::world::World;
+
+use super::schematic::Schematic;
+use super::ChunkGenerator;
+
+/// A chunk generator producing empty chunks, with an optional island schematic stamped
+/// at a fixed position, typically the spawn.
+pub struct VoidGenerator {
+    /// The biome of all generated chunks.
+    biome: Biome,
+    /// The island schematic with its absolute origin.
+    island: Option<(IVec3, Schematic)>,
+}
+
+impl Default for VoidGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VoidGenerator {
+    /// Create a new void generator, chunks have the plains biome by default.
+    pub fn new() -> Self {
+        Self {
+            biome: Biome::Plains,
+            island: None,
+        }
+    }
+
+    /// Set the biome of all generated chunks, this has an effect on natural spawning.
+    pub fn with_biome(mut self, biome: Biome) -> Self {
+        self.biome = biome;
+        self
+    }
+
+    /// Stamp the given island schematic with its lower corner at the given origin.
+    pub fn with_island(mut self, origin: IVec3, schematic: Schematic) -> Self {
+        self.island = Some((origin, schematic));
+        self
+    }
+
+    /// Stamp the given island schematic centered on the given spawn position, the
+    /// spawn column being above the island's top.
+    pub fn with_island_at_spawn(self, spawn_pos: IVec3, schematic: Schematic) -> Self {
+        let size = schematic.get_size();
+        let origin = IVec3::new(
+            spawn_pos.x - size.x / 2,
+            spawn_pos.y - size.y,
+            spawn_pos.z - size.z / 2,
+        );
+        self.with_island(origin, schematic)
+    }
+}
+
+impl ChunkGenerator for VoidGenerator {
+    type State = ();
+
+    fn gen_biomes(&self, _cx: i32, _cz: i32, chunk: &mut Chunk, _state: &mut Self::State) {
+        chunk.biome.fill(self.biome);
+    }
+
+    fn gen_terrain(&self, cx: i32, cz: i32, chunk: &mut Chunk, state: &mut Self::State) {
+        self.gen_biomes(cx, cz, chunk, state);
+
+        if let Some((origin, schematic)) = &self.island {
+            schematic.stamp_chunk(*origin, cx, cz, chunk);
+            chunk.recompute_all_height();
+        }
+    }
+
+    fn gen_features(&self, cx: i32, cz: i32, world: &mut World, _state: &mut Self::State) {
+        if let Some((origin, schematic)) = &self.island {
+            schematic.stamp_block_entities(*origin, cx, cz, world);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::block;
+    use crate::block_entity::chest::ChestBlockEntity;
+    use crate::block_entity::BlockEntity;
+    use crate::world::Dimension;
+
+    #[test]
+    fn void_island() {
+        let mut island = Schematic::new(IVec3::new(3, 2, 3));
+        for x in 0..3 {
+            for z in 0..3 {
+                island.set_block(IVec3::new(x, 0, z), block::DIRT, 0);
+            }
+        }
+        island.set_block(IVec3::new(1, 1, 1), block::CHEST, 0);
+        island.set_block_entity(
+            IVec3::new(1, 1, 1),
+            BlockEntity::Chest(ChestBlockEntity::default()),
+        );
+
+        // The island is centered on spawn and crosses chunk borders.
+        let gen = VoidGenerator::new().with_island_at_spawn(IVec3::new(0, 66, 0), island);
+        let mut world = World::new(Dimension::Overworld);
+        for (cx, cz) in [(-1, -1), (0, -1), (-1, 0), (0, 0)] {
+            let mut chunk = Chunk::new();
+            gen.gen_terrain(cx, cz, Arc::get_mut(&mut chunk).unwrap(), &mut ());
+            world.set_chunk(cx, cz, chunk);
+        }
+
+        gen.gen_features(0, 0, &mut world, &mut ());
+
+        assert_eq!(
+            world.get_block(IVec3::new(-1, 64, -1)),
+            Some((block::DIRT, 0))
+        );
+        assert_eq!(
+            world.get_block(IVec3::new(1, 64, 1)),
+            Some((block::DIRT, 0))
+        );
+        assert_eq!(world.get_block(IVec3::new(2, 64, 2)), Some((block::AIR, 0)));
+        assert_eq!(
+            world.get_block(IVec3::new(0, 65, 0)),
+            Some((block::CHEST, 0))
+        );
+        assert_eq!(world.get_height(IVec3::new(-1, 0, -1)), Some(65));
+        assert!(world.contains_block_entity(IVec3::new(0, 65, 0)));
+    }
+}
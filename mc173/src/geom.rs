@@ -273,6 +273,50 @@ impl BoundingBox {
 
         None
     }
+
+    /// Compute the first collision of the given bounding box moving by the given delta
+    /// with this bounding box. If the moving box hits this box, the fraction of the
+    /// delta, in 0..=1, that can be applied before touching is returned along with the
+    /// face of this box that has been hit. Boxes that are already intersecting are
+    /// ignored, like with [`Self::calc_x_delta`] and other functions.
+    pub fn calc_sweep(self, other: Self, delta: DVec3) -> Option<(f64, Face)> {
+        let mut entry = f64::NEG_INFINITY;
+        let mut exit = f64::INFINITY;
+        let mut entry_face = None;
+
+        for (axis, pos_face, neg_face) in [
+            (0, Face::PosX, Face::NegX),
+            (1, Face::PosY, Face::NegY),
+            (2, Face::PosZ, Face::NegZ),
+        ] {
+            let (min, max) = (self.min[axis], self.max[axis]);
+            let (other_min, other_max) = (other.min[axis], other.max[axis]);
+            let d = delta[axis];
+
+            let (axis_entry, axis_exit, face) = if d > 0.0 {
+                ((min - other_max) / d, (max - other_min) / d, neg_face)
+            } else if d < 0.0 {
+                ((max - other_min) / d, (min - other_max) / d, pos_face)
+            } else if other_max > min && other_min < max {
+                continue;
+            } else {
+                return None;
+            };
+
+            if axis_entry > entry {
+                entry = axis_entry;
+                entry_face = Some(face);
+            }
+
+            exit = exit.min(axis_exit);
+        }
+
+        if !(0.0..=1.0).contains(&entry) || entry >= exit {
+            return None;
+        }
+
+        entry_face.map(|face| (entry, face))
+    }
 }
 
 impl Add<DVec3> for BoundingBox {
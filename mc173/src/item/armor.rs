@@ -0,0 +1,67 @@
+//! Module to query armor items and compute damage reduction of worn armor.
+
+use crate::item::{self, ItemStack};
+
+/// Get the armor points of an item, only depending on the armor type, or none if the
+/// item is not an armor piece.
+///
+/// PARITY: In the Notchian beta, the armor material only changes the durability.
+pub fn get_armor_points(item: u16) -> Option<u16> {
+    match item {
+        item::LEATHER_HELMET | item::CHAIN_HELMET | item::IRON_HELMET => Some(3),
+        item::DIAMOND_HELMET | item::GOLD_HELMET => Some(3),
+        item::LEATHER_CHESTPLATE | item::CHAIN_CHESTPLATE | item::IRON_CHESTPLATE => Some(8),
+        item::DIAMOND_CHESTPLATE | item::GOLD_CHESTPLATE => Some(8),
+        item::LEATHER_LEGGINGS | item::CHAIN_LEGGINGS | item::IRON_LEGGINGS => Some(6),
+        item::DIAMOND_LEGGINGS | item::GOLD_LEGGINGS => Some(6),
+        item::LEATHER_BOOTS | item::CHAIN_BOOTS | item::IRON_BOOTS => Some(3),
+        item::DIAMOND_BOOTS | item::GOLD_BOOTS => Some(3),
+        _ => None,
+    }
+}
+
+/// Get the total armor value of the given worn armor, out of 25, the points of each
+/// piece are scaled by the remaining durability of all pieces.
+///
+/// REF: InventoryPlayer::getTotalArmorValue
+pub fn get_total_armor(armor: &[ItemStack]) -> u16 {
+    let mut points = 0;
+    let mut durability = 0;
+    let mut max_durability = 0;
+
+    for &stack in armor {
+        if stack.is_empty() {
+            continue;
+        }
+        if let Some(piece_points) = get_armor_points(stack.id) {
+            let max_damage = item::from_id(stack.id).max_damage as u32;
+            durability += max_damage.saturating_sub(stack.damage as u32);
+            max_durability += max_damage;
+            points += piece_points as u32;
+        }
+    }
+
+    if max_durability == 0 {
+        return 0;
+    }
+
+    ((points - 1) * durability / max_durability + 1) as u16
+}
+
+/// Apply the damage dealt to the wearer on each armor piece durability, broken pieces
+/// are removed. Returns true if any piece has been damaged.
+///
+/// REF: InventoryPlayer::damageArmor
+pub fn damage_armor(armor: &mut [ItemStack], damage: u16) -> bool {
+    let mut damaged = false;
+    for stack in armor {
+        if !stack.is_empty() && get_armor_points(stack.id).is_some() {
+            *stack = stack.inc_damage(damage);
+            if stack.size == 0 {
+                *stack = ItemStack::EMPTY;
+            }
+            damaged = true;
+        }
+    }
+    damaged
+}
@@ -2,6 +2,7 @@
 
 use crate::block;
 
+pub mod armor;
 pub mod attack;
 
 /// Internal macro to easily define blocks registry.
@@ -145,6 +146,16 @@ pub fn from_id(id: u16) -> &'static Item {
     }
 }
 
+/// Get an item from its numeric id, returning none if the id is not a known item.
+pub fn try_from_id(id: u16) -> Option<&'static Item> {
+    let item = if id < 256 {
+        block::item(id as u8)
+    } else {
+        ITEMS.get((id - 256) as usize)?
+    };
+    (!item.name.is_empty()).then_some(item)
+}
+
 /// Find an item id from its name. **Note that this will not find block items.
 pub fn from_name(name: &str) -> Option<u16> {
     ITEMS
@@ -12,11 +12,14 @@ pub mod entity;
 pub mod item;
 
 pub mod craft;
+pub mod data;
 pub mod inventory;
+pub mod loot;
 pub mod smelt;
 
 pub mod chunk;
 pub mod gen;
+pub mod map;
 pub mod serde;
 pub mod storage;
 pub mod world;
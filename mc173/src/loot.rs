@@ -0,0 +1,300 @@
+//! Loot tables used to fill the chests of generated structures.
+//!
+//! A [`LootTable`] is rolled a number of times, each roll picks a weighted entry that can
+//! loot nothing, an item stack with a random size, or roll a nested table. Tables are
+//! used by dungeons and are part of the [data tables](crate::data), so they can be
+//! customized by servers.
+
+use crate::data::{self, DataError};
+use crate::item::ItemStack;
+use crate::rand::JavaRandom;
+
+/// A table of weighted loot entries, rolled a given number of times.
+#[derive(Debug, Clone)]
+pub struct LootTable {
+    /// Number of times the table is rolled when filling an inventory.
+    rolls: u16,
+    /// All the entries of the table.
+    entries: Vec<LootEntry>,
+}
+
+/// An entry of a [`LootTable`].
+#[derive(Debug, Clone)]
+pub struct LootEntry {
+    /// The weight of the entry when picked in its table.
+    pub weight: u16,
+    /// When picked, the entry only loots one time out of this rarity, 1 to always loot.
+    pub rarity: u16,
+    /// The loot of this entry.
+    pub kind: LootKind,
+}
+
+/// The loot of a [`LootEntry`].
+#[derive(Debug, Clone)]
+pub enum LootKind {
+    /// Nothing is looted.
+    Empty,
+    /// An item stack with a random size within the inclusive range.
+    Stack {
+        stack: ItemStack,
+        min_size: u16,
+        max_size: u16,
+    },
+    /// A nested table that is rolled once.
+    Pool(LootTable),
+}
+
+impl Default for LootTable {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl LootTable {
+    /// Create a new empty table rolled the given number of times.
+    pub fn new(rolls: u16) -> Self {
+        Self {
+            rolls,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add an entry to the table.
+    pub fn with(mut self, entry: LootEntry) -> Self {
+        self.push(entry);
+        self
+    }
+
+    /// Add an entry to the table.
+    pub fn push(&mut self, entry: LootEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Remove all the entries of the table.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Return the number of times the table is rolled when filling an inventory.
+    pub fn rolls(&self) -> u16 {
+        self.rolls
+    }
+
+    /// Set the number of times the table is rolled when filling an inventory.
+    pub fn set_rolls(&mut self, rolls: u16) {
+        self.rolls = rolls;
+    }
+
+    /// Return the entries of the table.
+    pub fn entries(&self) -> &[LootEntry] {
+        &self.entries
+    }
+
+    /// Roll the table once and return the loot, which may be empty. Random is only
+    /// consumed where a choice has to be made, so that tables mirroring the Notchian
+    /// loot keep the same random sequence.
+    pub fn pick(&self, rand: &mut JavaRandom) -> ItemStack {
+        let entry = match self.entries[..] {
+            [] => return ItemStack::EMPTY,
+            [ref entry] => entry,
+            ref entries => {
+                let total_weight = entries.iter().map(|e| e.weight as i32).sum::<i32>();
+                if total_weight <= 0 {
+                    return ItemStack::EMPTY;
+                }
+                let mut index = rand.next_int_bounded(total_weight);
+                entries
+                    .iter()
+                    .find(|entry| {
+                        index -= entry.weight as i32;
+                        index < 0
+                    })
+                    .unwrap()
+            }
+        };
+
+        if entry.rarity > 1 && rand.next_int_bounded(entry.rarity as i32) != 0 {
+            return ItemStack::EMPTY;
+        }
+
+        match entry.kind {
+            LootKind::Empty => ItemStack::EMPTY,
+            LootKind::Stack {
+                stack,
+                min_size,
+                max_size,
+            } if max_size > min_size => {
+                let range = (max_size - min_size) as i32 + 1;
+                stack.with_size(min_size + rand.next_int_bounded(range) as u16)
+            }
+            LootKind::Stack {
+                stack, min_size, ..
+            } => stack.with_size(min_size),
+            LootKind::Pool(ref table) => table.pick(rand),
+        }
+    }
+
+    /// Roll the table for its number of rolls and place each loot in a random slot of
+    /// the given inventory, possibly replacing a previous loot.
+    pub fn fill(&self, rand: &mut JavaRandom, inv: &mut [ItemStack]) {
+        for _ in 0..self.rolls {
+            let stack = self.pick(rand);
+            if !stack.is_empty() {
+                *rand.next_choice_mut(inv) = stack;
+            }
+        }
+    }
+
+    /// Parse a loot table from its text format. Each non-empty line is a directive, `#`
+    /// starts a comment:
+    ///
+    /// - `rolls <count>` to set the number of rolls of the table, 1 by default;
+    /// - `empty <weight>` to add an entry that never loots;
+    /// - `loot <weight> <rarity> <min_size> <max_size> <item>...` to add an entry looting
+    ///   one time out of the rarity, with multiple items the entry is a nested pool of
+    ///   these items with equal weights.
+    ///
+    /// Items are written `<item>[:<damage>]`, with a numeric id, an item or block name.
+    pub fn parse(text: &str) -> Result<Self, DataError> {
+        let mut table = Self::default();
+
+        data::parse_lines(text, |parts| {
+            match *parts {
+                ["rolls", rolls] => {
+                    table.set_rolls(rolls.parse().map_err(|_| "invalid roll count")?);
+                }
+                ["empty", weight] => {
+                    table.push(LootEntry::new_empty(parse_weight(weight)?));
+                }
+                ["loot", weight, rarity, min_size, max_size, ref items @ ..]
+                    if !items.is_empty() =>
+                {
+                    let weight = parse_weight(weight)?;
+                    let rarity = rarity
+                        .parse::<u16>()
+                        .ok()
+                        .filter(|&rarity| rarity != 0)
+                        .ok_or("invalid rarity")?;
+                    let min_size = min_size.parse::<u16>().map_err(|_| "invalid size")?;
+                    let max_size = max_size.parse::<u16>().map_err(|_| "invalid size")?;
+                    if min_size == 0 || max_size < min_size {
+                        return Err("invalid size");
+                    }
+
+                    let mut stacks = items.iter().map(|&item| {
+                        data::parse_stack(item)
+                            .map(|stack| LootEntry::new_stack(1, stack, min_size, max_size))
+                    });
+
+                    let entry = if items.len() == 1 {
+                        stacks.next().unwrap()?
+                    } else {
+                        let mut pool = LootTable::new(1);
+                        for stack in stacks {
+                            pool.push(stack?);
+                        }
+                        LootEntry::new_pool(weight, pool)
+                    };
+
+                    table.push(entry.with_weight(weight).with_rarity(rarity));
+                }
+                _ => return Err("invalid directive"),
+            }
+            Ok(())
+        })?;
+
+        Ok(table)
+    }
+}
+
+/// Parse the weight of an entry, which cannot be zero.
+fn parse_weight(weight: &str) -> Result<u16, &'static str> {
+    weight
+        .parse::<u16>()
+        .ok()
+        .filter(|&weight| weight != 0)
+        .ok_or("invalid weight")
+}
+
+impl LootEntry {
+    /// Create a new entry with the given weight that never loots.
+    pub fn new_empty(weight: u16) -> Self {
+        Self {
+            weight,
+            rarity: 1,
+            kind: LootKind::Empty,
+        }
+    }
+
+    /// Create a new entry with the given weight, looting the given stack with a random
+    /// size in the inclusive range.
+    pub fn new_stack(weight: u16, stack: ItemStack, min_size: u16, max_size: u16) -> Self {
+        Self {
+            weight,
+            rarity: 1,
+            kind: LootKind::Stack {
+                stack,
+                min_size,
+                max_size,
+            },
+        }
+    }
+
+    /// Create a new entry with the given weight, looting a single roll of the given
+    /// nested table.
+    pub fn new_pool(weight: u16, table: LootTable) -> Self {
+        Self {
+            weight,
+            rarity: 1,
+            kind: LootKind::Pool(table),
+        }
+    }
+
+    /// Set the weight of this entry.
+    pub fn with_weight(mut self, weight: u16) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Set the rarity of this entry, it only loots one time out of this rarity.
+    pub fn with_rarity(mut self, rarity: u16) -> Self {
+        self.rarity = rarity;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::item;
+
+    #[test]
+    fn rolls_and_pools() {
+        let table = LootTable::parse(
+            "rolls 3
+            loot 1 1 1 1 diamond gold_ingot",
+        )
+        .unwrap();
+        assert_eq!(table.rolls(), 3);
+        assert!(matches!(table.entries()[0].kind, LootKind::Pool(_)));
+
+        let mut rand = JavaRandom::new(0);
+        for _ in 0..16 {
+            let stack = table.pick(&mut rand);
+            assert!(stack.id == item::DIAMOND || stack.id == item::GOLD_INGOT);
+            assert_eq!(stack.size, 1);
+        }
+
+        let mut inv = [ItemStack::EMPTY; 4];
+        table.fill(&mut rand, &mut inv);
+        assert!(inv.iter().any(|stack| !stack.is_empty()));
+
+        let mut inv0 = [ItemStack::EMPTY; 27];
+        let mut inv1 = [ItemStack::EMPTY; 27];
+        let loot = crate::gen::dungeon::DungeonGenerator::default_loot();
+        loot.fill(&mut JavaRandom::new(42), &mut inv0);
+        loot.fill(&mut JavaRandom::new(42), &mut inv1);
+        assert_eq!(inv0, inv1);
+    }
+}
@@ -0,0 +1,435 @@
+//! Top-down map rendering of chunks into PNG tiles.
+//!
+//! The map renderer runs on a background thread and renders chunks requested by the
+//! owner into square tiles of [`TILE_CHUNKS`] by [`TILE_CHUNKS`] chunks, each chunk
+//! being 16x16 pixels. Tiles are saved as PNG files named `<tx>_<tz>.png` in the output
+//! directory, where `tx` and `tz` are the tile coordinates (the chunk coordinates
+//! divided by [`TILE_CHUNKS`]), north is up. Pixels of chunks that have never been
+//! rendered are fully transparent, this layout can directly be served to a web map.
+//!
+//! Existing tiles are loaded back before being updated, so only dirty chunks need to
+//! be rendered again when the world is modified.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use glam::IVec3;
+use tracing::warn;
+
+use crate::block;
+use crate::block::material::Material;
+use crate::chunk::{Chunk, CHUNK_HEIGHT, CHUNK_WIDTH};
+
+/// Number of chunks on each side of a tile.
+pub const TILE_CHUNKS: i32 = 16;
+/// Number of pixels on each side of a tile.
+const TILE_SIZE: usize = TILE_CHUNKS as usize * CHUNK_WIDTH;
+/// Maximum number of clean tiles kept in memory by the worker.
+const MAX_CACHED_TILES: usize = 64;
+
+/// Shade multiplier when the column is lower than its north neighbor.
+const SHADE_LOW: u16 = 180;
+/// Shade multiplier when the column is at the same height as its north neighbor.
+const SHADE_FLAT: u16 = 220;
+/// Shade multiplier when the column is higher than its north neighbor.
+const SHADE_HIGH: u16 = 255;
+
+/// Get the map color of a block, none is returned for blocks that are not visible on
+/// maps, such as air or glass, in such case the block below should be used.
+///
+/// REF: MapColor
+pub fn get_block_color(id: u8) -> Option<[u8; 3]> {
+    let color = match block::material::get_material(id) {
+        Material::Grass => 0x7FB238,
+        Material::Sand => 0xF7E9A3,
+        Material::Cloth | Material::Sponge | Material::Wug | Material::Cobweb => 0xA7A7A7,
+        Material::Iron => 0xA7A7A7,
+        Material::Lava | Material::Tnt => 0xFF0000,
+        Material::Ice => 0xA0A0FF,
+        Material::Leaves | Material::Plant | Material::Cactus | Material::Pumpkin => 0x007C00,
+        Material::Snow | Material::SnowBlock => 0xFFFFFF,
+        Material::Clay => 0xA4A8B8,
+        Material::Ground => 0xB76A2F,
+        Material::Rock | Material::Piston => 0x707070,
+        Material::Water => 0x4040FF,
+        Material::Wood => 0x685332,
+        Material::Air
+        | Material::Fire
+        | Material::Circuit
+        | Material::Glass
+        | Material::Portal
+        | Material::Cake => return None,
+    };
+
+    Some([(color >> 16) as u8, (color >> 8) as u8, color as u8])
+}
+
+/// A rendered chunk, 16x16 RGBA pixels indexed by Z then X, columns without any
+/// visible block are fully transparent.
+pub type ChunkRender = [[u8; 4]; CHUNK_WIDTH * CHUNK_WIDTH];
+
+/// Find the top visible block of a column, returning its Y coordinate, color and
+/// water depth if the top block is water.
+fn find_column_top(chunk: &Chunk, x: i32, z: i32) -> Option<(i32, [u8; 3], u8)> {
+    let mut pos = IVec3::new(x, CHUNK_HEIGHT as i32 - 1, z);
+    while pos.y >= 0 {
+        let (id, _) = chunk.get_block(pos);
+        if let Some(color) = get_block_color(id) {
+            let mut depth = 0u8;
+            if block::material::get_material(id) == Material::Water {
+                let mut below = pos;
+                while below.y >= 0 && block::material::is_fluid(chunk.get_block(below).0) {
+                    depth = depth.saturating_add(1);
+                    below.y -= 1;
+                }
+            }
+            return Some((pos.y, color, depth));
+        }
+        pos.y -= 1;
+    }
+    None
+}
+
+/// Render the given chunk from the top, with simple hillshading computed from the
+/// height difference of each column with its north neighbor. The chunk north of the
+/// rendered one can be given to shade its first row, water is shaded by its depth.
+pub fn render_chunk(chunk: &Chunk, north: Option<&Chunk>) -> ChunkRender {
+    let mut render = [[0; 4]; CHUNK_WIDTH * CHUNK_WIDTH];
+
+    for x in 0..CHUNK_WIDTH as i32 {
+        // Height of the previous north column, initialized from the north chunk.
+        let mut north_y = north.and_then(|north| find_column_top(north, x, 15).map(|(y, _, _)| y));
+
+        for z in 0..CHUNK_WIDTH as i32 {
+            let top = find_column_top(chunk, x, z);
+            let color = match top {
+                Some((_, color, depth)) if depth > 0 => {
+                    let shade = match depth {
+                        0..=2 => SHADE_HIGH,
+                        3..=6 => SHADE_FLAT,
+                        _ => SHADE_LOW,
+                    };
+                    shade_color(color, shade)
+                }
+                Some((y, color, _)) => {
+                    let shade = match north_y {
+                        Some(north_y) if y > north_y => SHADE_HIGH,
+                        Some(north_y) if y < north_y => SHADE_LOW,
+                        _ => SHADE_FLAT,
+                    };
+                    shade_color(color, shade)
+                }
+                None => {
+                    north_y = None;
+                    continue;
+                }
+            };
+
+            let [r, g, b] = color;
+            render[z as usize * CHUNK_WIDTH + x as usize] = [r, g, b, 255];
+            north_y = top.map(|(y, _, _)| y);
+        }
+    }
+
+    render
+}
+
+/// Multiply a color by a shade in range 0..=255.
+#[inline]
+fn shade_color(color: [u8; 3], shade: u16) -> [u8; 3] {
+    color.map(|c| (c as u16 * shade / 255) as u8)
+}
+
+/// Return the tile coordinates of the given chunk.
+#[inline]
+pub fn calc_tile_pos(cx: i32, cz: i32) -> (i32, i32) {
+    (cx.div_euclid(TILE_CHUNKS), cz.div_euclid(TILE_CHUNKS))
+}
+
+/// This structure is a handle around a map renderer running in a background thread,
+/// the worker stops when this handle is dropped, after saving all its dirty tiles.
+pub struct MapRenderer {
+    /// Request sender to the render worker.
+    request_sender: Sender<RenderRequest>,
+}
+
+/// A request to render a chunk.
+struct RenderRequest {
+    cx: i32,
+    cz: i32,
+    chunk: Arc<Chunk>,
+    north: Option<Arc<Chunk>>,
+}
+
+/// The render worker keeps recently used tiles in memory and save dirty ones when all
+/// pending requests have been processed.
+struct RenderWorker {
+    /// The directory where tiles are saved.
+    dir: PathBuf,
+    /// Request receiver from the handle.
+    request_receiver: Receiver<RenderRequest>,
+    /// Tiles cached in memory.
+    tiles: HashMap<(i32, i32), Tile>,
+}
+
+/// A tile cached in memory.
+struct Tile {
+    /// RGBA pixels indexed by Y then X.
+    pixels: Vec<u8>,
+    /// True if the tile has been modified since last saved.
+    dirty: bool,
+}
+
+impl MapRenderer {
+    /// Create a new map renderer saving its tiles in the given directory.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        let (request_sender, request_receiver) = unbounded();
+        let dir = dir.into();
+
+        thread::Builder::new()
+            .name("Map Render Worker".to_string())
+            .spawn(move || {
+                RenderWorker {
+                    dir,
+                    request_receiver,
+                    tiles: HashMap::new(),
+                }
+                .run()
+            })
+            .unwrap();
+
+        Self { request_sender }
+    }
+
+    /// Request rendering of the given chunk, the chunk north of it can be given in
+    /// order to properly shade the first row.
+    pub fn request_render(&self, cx: i32, cz: i32, chunk: Arc<Chunk>, north: Option<Arc<Chunk>>) {
+        self.request_sender
+            .send(RenderRequest {
+                cx,
+                cz,
+                chunk,
+                north,
+            })
+            .expect("worker should not disconnect while this handle exists");
+    }
+}
+
+impl RenderWorker {
+    fn run(mut self) {
+        // The loop exits when the handle is dropped.
+        while let Ok(request) = self.request_receiver.recv() {
+            self.handle_render(request);
+
+            // Process all pending requests before saving.
+            while let Ok(request) = self.request_receiver.try_recv() {
+                self.handle_render(request);
+            }
+
+            self.save_tiles();
+        }
+    }
+
+    fn handle_render(&mut self, request: RenderRequest) {
+        let render = render_chunk(&request.chunk, request.north.as_deref());
+
+        let (tx, tz) = calc_tile_pos(request.cx, request.cz);
+        let tile = match self.tiles.entry((tx, tz)) {
+            Entry::Occupied(o) => o.into_mut(),
+            Entry::Vacant(v) => {
+                let path = tile_path(&self.dir, tx, tz);
+                let pixels = match load_tile(&path) {
+                    Ok(Some(pixels)) => pixels,
+                    Ok(None) => vec![0; TILE_SIZE * TILE_SIZE * 4],
+                    Err(e) => {
+                        warn!("failed to load map tile {}: {e}", path.display());
+                        vec![0; TILE_SIZE * TILE_SIZE * 4]
+                    }
+                };
+                v.insert(Tile {
+                    pixels,
+                    dirty: false,
+                })
+            }
+        };
+
+        let origin_x = (request.cx - tx * TILE_CHUNKS) as usize * CHUNK_WIDTH;
+        let origin_y = (request.cz - tz * TILE_CHUNKS) as usize * CHUNK_WIDTH;
+
+        for z in 0..CHUNK_WIDTH {
+            for x in 0..CHUNK_WIDTH {
+                let index = ((origin_y + z) * TILE_SIZE + origin_x + x) * 4;
+                tile.pixels[index..index + 4].copy_from_slice(&render[z * CHUNK_WIDTH + x]);
+            }
+        }
+
+        tile.dirty = true;
+    }
+
+    /// Save all dirty tiles and evict clean ones if too many are cached.
+    fn save_tiles(&mut self) {
+        if let Err(e) = std::fs::create_dir_all(&self.dir) {
+            warn!("failed to create map directory {}: {e}", self.dir.display());
+            return;
+        }
+
+        for (&(tx, tz), tile) in &mut self.tiles {
+            if tile.dirty {
+                let path = tile_path(&self.dir, tx, tz);
+                if let Err(e) = save_tile(&path, &tile.pixels) {
+                    warn!("failed to save map tile {}: {e}", path.display());
+                }
+                tile.dirty = false;
+            }
+        }
+
+        if self.tiles.len() > MAX_CACHED_TILES {
+            self.tiles.clear();
+        }
+    }
+}
+
+/// Return the path of a tile file in the given directory.
+fn tile_path(dir: &Path, tx: i32, tz: i32) -> PathBuf {
+    dir.join(format!("{tx}_{tz}.png"))
+}
+
+/// Load the RGBA pixels of a tile, none is returned if the file doesn't exist.
+fn load_tile(path: &Path) -> io::Result<Option<Vec<u8>>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let decoder = png::Decoder::new(BufReader::new(file));
+    let mut reader = decoder.read_info().map_err(io::Error::other)?;
+    let info = reader.info();
+    if info.width as usize != TILE_SIZE
+        || info.height as usize != TILE_SIZE
+        || info.color_type != png::ColorType::Rgba
+        || info.bit_depth != png::BitDepth::Eight
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid tile format",
+        ));
+    }
+
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    reader.next_frame(&mut pixels).map_err(io::Error::other)?;
+    Ok(Some(pixels))
+}
+
+/// Save the RGBA pixels of a tile.
+fn save_tile(path: &Path, pixels: &[u8]) -> io::Result<()> {
+    let file = File::create(path)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), TILE_SIZE as u32, TILE_SIZE as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(pixels).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn render_colors() {
+        let mut north = Chunk::new();
+        let north_mut = Arc::get_mut(&mut north).unwrap();
+        north_mut.fill_block(IVec3::ZERO, IVec3::new(16, 10, 16), block::STONE, 0);
+
+        let mut chunk = Chunk::new();
+        let chunk_mut = Arc::get_mut(&mut chunk).unwrap();
+        chunk_mut.fill_block(IVec3::ZERO, IVec3::new(16, 10, 16), block::STONE, 0);
+        // A grass step going up, then a glass pane above a lower stone block.
+        chunk_mut.set_block(IVec3::new(0, 10, 1), block::GRASS, 0);
+        chunk_mut.set_block(IVec3::new(0, 20, 3), block::GLASS, 0);
+        // A deep pool of water and an empty column.
+        chunk_mut.fill_block(
+            IVec3::new(1, 0, 0),
+            IVec3::new(1, 10, 1),
+            block::WATER_STILL,
+            0,
+        );
+        chunk_mut.fill_block(IVec3::new(2, 0, 0), IVec3::new(1, 10, 1), block::AIR, 0);
+
+        let render = render_chunk(&chunk, Some(&north));
+        let pixel = |x: usize, z: usize| render[z * CHUNK_WIDTH + x];
+
+        let stone = get_block_color(block::STONE).unwrap();
+        let grass = get_block_color(block::GRASS).unwrap();
+        let water = get_block_color(block::WATER_STILL).unwrap();
+        let rgba = |[r, g, b]: [u8; 3]| [r, g, b, 255];
+
+        assert_eq!(pixel(0, 0), rgba(shade_color(stone, SHADE_FLAT)));
+        assert_eq!(pixel(0, 1), rgba(shade_color(grass, SHADE_HIGH)));
+        assert_eq!(pixel(0, 2), rgba(shade_color(stone, SHADE_LOW)));
+        assert_eq!(pixel(0, 3), rgba(shade_color(stone, SHADE_FLAT)));
+        assert_eq!(pixel(1, 0), rgba(shade_color(water, SHADE_LOW)));
+        assert_eq!(pixel(2, 0), [0; 4]);
+
+        // Without the north chunk, the first row is flat.
+        let render = render_chunk(&chunk, None);
+        assert_eq!(render[0], rgba(shade_color(stone, SHADE_FLAT)));
+    }
+
+    #[test]
+    fn tile_save_load() {
+        let dir = std::env::temp_dir().join(format!("mc173-map-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut chunk = Chunk::new();
+        let chunk_mut = Arc::get_mut(&mut chunk).unwrap();
+        chunk_mut.fill_block(IVec3::ZERO, IVec3::new(16, 10, 16), block::SAND, 0);
+
+        let (_, request_receiver) = unbounded();
+        let mut worker = RenderWorker {
+            dir: dir.clone(),
+            request_receiver,
+            tiles: HashMap::new(),
+        };
+
+        // The chunk at -1/-1 is in the last row and column of the tile -1/-1.
+        worker.handle_render(RenderRequest {
+            cx: -1,
+            cz: -1,
+            chunk,
+            north: None,
+        });
+        worker.save_tiles();
+
+        assert_eq!(load_tile(&tile_path(&dir, 0, 0)).unwrap(), None);
+        let pixels = load_tile(&tile_path(&dir, -1, -1)).unwrap().unwrap();
+        assert_eq!(pixels.len(), TILE_SIZE * TILE_SIZE * 4);
+
+        let sand = get_block_color(block::SAND).unwrap();
+        let [r, g, b] = shade_color(sand, SHADE_FLAT);
+        let pixel = |x: usize, y: usize| {
+            let index = (y * TILE_SIZE + x) * 4;
+            &pixels[index..index + 4]
+        };
+        assert_eq!(pixel(TILE_SIZE - 1, TILE_SIZE - 1), [r, g, b, 255]);
+        assert_eq!(pixel(TILE_SIZE - 16, TILE_SIZE - 16), [r, g, b, 255]);
+        assert_eq!(pixel(TILE_SIZE - 17, TILE_SIZE - 1), [0; 4]);
+        assert_eq!(pixel(0, 0), [0; 4]);
+
+        // Invalid tiles are rejected.
+        fs::write(tile_path(&dir, 1, 1), b"not a png").unwrap();
+        assert!(load_tile(&tile_path(&dir, 1, 1)).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -63,6 +63,16 @@ impl JavaRandom {
         }
     }
 
+    /// Create a random generator from an internal state returned by
+    /// [`get_seed`](Self::get_seed), unlike [`new`](Self::new) the seed is not scrambled.
+    #[inline]
+    pub fn new_raw(state: i64) -> JavaRandom {
+        JavaRandom {
+            seed: Wrapping(state),
+            next_gaussian: None,
+        }
+    }
+
     #[inline]
     pub fn new_seeded() -> JavaRandom {
         Self::new(gen_seed())
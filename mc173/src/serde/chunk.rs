@@ -1,9 +1,14 @@
 //! Chunk serialization and deserialization from NBT compound.
 
+use std::collections::HashMap;
+use std::io::Write;
+
 use crate::world::ChunkSnapshot;
 
-use super::nbt::{Nbt, NbtCompound, NbtParseError};
+use super::nbt::{Nbt, NbtCompound, NbtError, NbtParseError};
+use super::remap::IdRemap;
 
+pub mod biome_nbt;
 pub mod block_entity_nbt;
 pub mod chunk_nbt;
 pub mod entity_kind_nbt;
@@ -13,7 +18,13 @@ pub mod painting_art_nbt;
 pub mod slot_nbt;
 
 pub fn from_nbt(root: &Nbt) -> Result<ChunkSnapshot, NbtParseError> {
-    chunk_nbt::from_nbt(root.parse().as_compound()?)
+    chunk_nbt::from_nbt(root.parse().as_compound()?, None)
+}
+
+/// Deserialize a chunk while translating its block and item ids through the given
+/// remapping table, see [`IdRemap`].
+pub fn from_nbt_remapped(root: &Nbt, remap: &IdRemap) -> Result<ChunkSnapshot, NbtParseError> {
+    chunk_nbt::from_nbt(root.parse().as_compound()?, Some(remap))
 }
 
 pub fn to_nbt(snapshot: &ChunkSnapshot) -> Nbt {
@@ -21,3 +32,82 @@ pub fn to_nbt(snapshot: &ChunkSnapshot) -> Nbt {
     chunk_nbt::to_nbt(&mut comp, snapshot);
     Nbt::Compound(comp)
 }
+
+/// A cache of the serialized entities of the last saved snapshot of each chunk, used by
+/// [`to_writer_cached`] to avoid serializing again entities that did not change.
+#[derive(Debug, Default)]
+pub struct EntityNbtCache {
+    /// For each chunk, the serialized entities mapped to their id.
+    chunks: HashMap<(i32, i32), HashMap<u32, Nbt>>,
+}
+
+/// Serialize a chunk snapshot and write it to the given writer, entities that did not
+/// change since the previous save of the same chunk, as given by the snapshot's
+/// [`entities_changes`](ChunkSnapshot::entities_changes), are taken from the cache
+/// instead of being serialized again. If the changes are unknown, all entities are
+/// serialized and the chunk is removed from the cache because this snapshot is
+/// probably the last one of the chunk. The number of entities that have actually been
+/// serialized is returned.
+pub fn to_writer_cached(
+    writer: impl Write,
+    snapshot: &ChunkSnapshot,
+    cache: &mut EntityNbtCache,
+) -> Result<usize, NbtError> {
+    let chunk_pos = (snapshot.cx, snapshot.cz);
+    let mut cached = cache.chunks.remove(&chunk_pos).unwrap_or_default();
+    let known_changes = snapshot.entities_changes.len() == snapshot.entities.len();
+
+    let mut entities = Vec::with_capacity(snapshot.entities.len());
+    let mut ids = Vec::with_capacity(snapshot.entities.len());
+    let mut serialized = 0;
+
+    for (index, entity) in snapshot.entities.iter().enumerate() {
+        let change = snapshot.entities_changes.get(index).copied();
+        let tag = match change {
+            Some((id, false)) if known_changes => cached.remove(&id),
+            _ => None,
+        };
+
+        let tag = match tag {
+            Some(tag) => tag,
+            None => match chunk_nbt::entity_to_nbt(
+                entity,
+                snapshot.entities_ids.get(index).copied().flatten(),
+            ) {
+                Some(tag) => {
+                    serialized += 1;
+                    tag
+                }
+                None => continue,
+            },
+        };
+
+        entities.push(tag);
+        ids.push(change.map(|(id, _)| id));
+    }
+
+    let mut comp = NbtCompound::new();
+    chunk_nbt::to_nbt_with_entities(&mut comp, snapshot, entities);
+    let mut root = Nbt::Compound(comp);
+    super::nbt::to_writer(writer, &root)?;
+
+    // Give back the serialized entities to the cache, entities not present in this
+    // snapshot are forgotten.
+    if known_changes {
+        let entities = root
+            .as_compound_mut()
+            .and_then(|comp| comp.get_mut("Level"))
+            .and_then(Nbt::as_compound_mut)
+            .and_then(|level| level.remove("Entities"));
+        if let Some(Nbt::List(entities)) = entities {
+            let cached = ids
+                .into_iter()
+                .zip(entities)
+                .filter_map(|(id, tag)| Some((id?, tag)))
+                .collect();
+            cache.chunks.insert(chunk_pos, cached);
+        }
+    }
+
+    Ok(serialized)
+}
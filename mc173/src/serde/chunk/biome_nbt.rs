@@ -0,0 +1,45 @@
+//! NBT serialization and deserialization for [`Biome`] enumeration.
+//!
+//! Biomes are not saved by the Notchian implementation, so these ids are specific to
+//! this implementation.
+
+use crate::biome::Biome;
+
+pub fn from_nbt(id: u8) -> Option<Biome> {
+    Some(match id {
+        0 => Biome::Void,
+        1 => Biome::RainForest,
+        2 => Biome::Swampland,
+        3 => Biome::SeasonalForest,
+        4 => Biome::Forest,
+        5 => Biome::Savanna,
+        6 => Biome::ShrubLand,
+        7 => Biome::Taiga,
+        8 => Biome::Desert,
+        9 => Biome::Plains,
+        10 => Biome::IceDesert,
+        11 => Biome::Tundra,
+        12 => Biome::Nether,
+        13 => Biome::Sky,
+        _ => return None,
+    })
+}
+
+pub fn to_nbt(biome: Biome) -> u8 {
+    match biome {
+        Biome::Void => 0,
+        Biome::RainForest => 1,
+        Biome::Swampland => 2,
+        Biome::SeasonalForest => 3,
+        Biome::Forest => 4,
+        Biome::Savanna => 5,
+        Biome::ShrubLand => 6,
+        Biome::Taiga => 7,
+        Biome::Desert => 8,
+        Biome::Plains => 9,
+        Biome::IceDesert => 10,
+        Biome::Tundra => 11,
+        Biome::Nether => 12,
+        Biome::Sky => 13,
+    }
+}
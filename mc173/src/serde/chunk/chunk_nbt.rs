@@ -2,13 +2,23 @@
 
 use std::sync::Arc;
 
+use glam::IVec3;
+
+use crate::block;
+use crate::chunk::{CHUNK_HEIGHT, CHUNK_WIDTH};
+use crate::entity::Entity;
 use crate::serde::nbt::{Nbt, NbtCompound, NbtCompoundParse, NbtParseError};
+use crate::serde::remap::IdRemap;
 use crate::world::ChunkSnapshot;
 
+use super::biome_nbt;
 use super::block_entity_nbt;
 use super::entity_nbt;
 
-pub fn from_nbt(comp: NbtCompoundParse) -> Result<ChunkSnapshot, NbtParseError> {
+pub fn from_nbt(
+    comp: NbtCompoundParse,
+    remap: Option<&IdRemap>,
+) -> Result<ChunkSnapshot, NbtParseError> {
     let level = comp.get_compound("Level")?;
     let cx = level.get_int("xPos")?;
     let cz = level.get_int("zPos")?;
@@ -18,37 +28,124 @@ pub fn from_nbt(comp: NbtCompoundParse) -> Result<ChunkSnapshot, NbtParseError>
 
     // This is annoying to make so much copies but we have no choice for know because
     // this is not yet possible to directly deserialize into an existing buffer.
-    chunk.block.copy_from_slice(level.get_byte_array("Blocks")?);
-    chunk
-        .metadata
-        .inner
-        .copy_from_slice(level.get_byte_array("Data")?);
-    chunk
-        .block_light
-        .inner
-        .copy_from_slice(level.get_byte_array("BlockLight")?);
-    chunk
-        .sky_light
-        .inner
-        .copy_from_slice(level.get_byte_array("SkyLight")?);
-    chunk
-        .height
-        .copy_from_slice(level.get_byte_array("HeightMap")?);
+    copy_byte_array(&level, "Blocks", &mut chunk.block[..])?;
+    copy_byte_array(&level, "Data", &mut chunk.metadata.inner[..])?;
+
+    // Light data may be missing or invalid, for example in chunks coming from converters,
+    // in such case the chunk is marked as needing to be relit.
+    let light_valid = copy_byte_array(&level, "BlockLight", &mut chunk.block_light.inner[..])
+        .and_then(|_| copy_byte_array(&level, "SkyLight", &mut chunk.sky_light.inner[..]))
+        .and_then(|_| copy_byte_array(&level, "HeightMap", &mut chunk.height[..]))
+        .is_ok();
+
+    if light_valid {
+        // A top layer of air without any sky light is suspect, this is typical of light
+        // data that has just been zeroed.
+        let top_y = CHUNK_HEIGHT as i32 - 1;
+        snapshot.light_invalid = (0..CHUNK_WIDTH as i32).all(|x| {
+            (0..CHUNK_WIDTH as i32).all(|z| {
+                let pos = IVec3::new(x, top_y, z);
+                chunk.get_block(pos).0 == block::AIR && chunk.get_sky_light(pos) == 0
+            })
+        });
+    } else {
+        chunk.reset_light();
+        snapshot.light_invalid = true;
+    }
+    chunk.populated = level.get_boolean("TerrainPopulated").unwrap_or_default();
+
+    // Biomes are not saved by the Notchian implementation, so this is optional and the
+    // biomes are left to void if not present or invalid.
+    if let Ok(biomes) = level.get_byte_array("Biomes") {
+        if biomes.len() == chunk.biome.len() {
+            for (biome, &id) in chunk.biome.iter_mut().zip(biomes) {
+                *biome = biome_nbt::from_nbt(id).unwrap_or_default();
+            }
+        }
+    }
+
+    // Entities and block entities that cannot be decoded may be dropped if remapping.
+    let drop_unknown = remap.is_some_and(IdRemap::is_dropping_unknown_entities);
 
     for item in level.get_list("Entities")?.iter() {
-        let entity = entity_nbt::from_nbt(item.as_compound()?)?;
-        snapshot.entities.push(entity);
+        let item = item.as_compound()?;
+        match entity_nbt::from_nbt(item.clone()) {
+            Ok(entity) => {
+                snapshot.entities.push(entity);
+                snapshot
+                    .entities_ids
+                    .push(item.get_int("EntityId").ok().map(|id| id as u32));
+            }
+            Err(_) if drop_unknown => {}
+            Err(e) => return Err(e),
+        }
     }
 
     for item in level.get_list("TileEntities")?.iter() {
-        let (pos, block_entity) = block_entity_nbt::from_nbt(item.as_compound()?)?;
-        snapshot.block_entities.insert(pos, block_entity);
+        match block_entity_nbt::from_nbt(item.as_compound()?) {
+            Ok((pos, block_entity)) => {
+                snapshot.block_entities.insert(pos, block_entity);
+            }
+            Err(_) if drop_unknown => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    if let Some(remap) = remap {
+        remap.apply(&mut snapshot);
     }
 
     Ok(snapshot)
 }
 
+/// Copy a byte array from the compound into the given buffer, an error is returned if
+/// the byte array has not the same length as the buffer.
+fn copy_byte_array(
+    comp: &NbtCompoundParse,
+    key: &str,
+    dst: &mut [u8],
+) -> Result<(), NbtParseError> {
+    let tag = comp.get(key)?;
+    let path = tag.path().to_string();
+    let src = tag.as_byte_array()?;
+    if src.len() != dst.len() {
+        return Err(NbtParseError::new(path, "byte array of chunk size"));
+    }
+    dst.copy_from_slice(src);
+    Ok(())
+}
+
 pub fn to_nbt<'a>(comp: &'a mut NbtCompound, snapshot: &ChunkSnapshot) -> &'a mut NbtCompound {
+    let entities = snapshot
+        .entities
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entity)| {
+            entity_to_nbt(entity, snapshot.entities_ids.get(index).copied().flatten())
+        })
+        .collect();
+    to_nbt_with_entities(comp, snapshot, entities)
+}
+
+/// Serialize a single entity to be added to the entities list of a chunk, none is
+/// returned if the entity is not persistent. The entity id is saved if known, so that
+/// the entity keeps its id when loaded again.
+pub fn entity_to_nbt(entity: &Entity, id: Option<u32>) -> Option<Nbt> {
+    let mut comp = NbtCompound::new();
+    entity_nbt::to_nbt(&mut comp, entity)?;
+    if let Some(id) = id {
+        comp.insert("EntityId", id as i32);
+    }
+    Some(Nbt::Compound(comp))
+}
+
+/// Same as [`to_nbt`] but with the given already serialized entities instead of the
+/// snapshot's entities.
+pub fn to_nbt_with_entities<'a>(
+    comp: &'a mut NbtCompound,
+    snapshot: &ChunkSnapshot,
+    entities: Vec<Nbt>,
+) -> &'a mut NbtCompound {
     let mut level = NbtCompound::new();
 
     level.insert("xPos", snapshot.cx);
@@ -59,23 +156,19 @@ pub fn to_nbt<'a>(comp: &'a mut NbtCompound, snapshot: &ChunkSnapshot) -> &'a mu
     level.insert("BlockLight", snapshot.chunk.block_light.inner.to_vec());
     level.insert("SkyLight", snapshot.chunk.sky_light.inner.to_vec());
     level.insert("HeightMap", snapshot.chunk.height.to_vec());
-
+    level.insert("TerrainPopulated", snapshot.chunk.populated);
     level.insert(
-        "Entities",
+        "Biomes",
         snapshot
-            .entities
+            .chunk
+            .biome
             .iter()
-            .filter_map(|entity| {
-                let mut comp = NbtCompound::new();
-                if entity_nbt::to_nbt(&mut comp, entity).is_some() {
-                    Some(Nbt::Compound(comp))
-                } else {
-                    None
-                }
-            })
+            .map(|&biome| biome_nbt::to_nbt(biome))
             .collect::<Vec<_>>(),
     );
 
+    level.insert("Entities", entities);
+
     level.insert(
         "TileEntities",
         snapshot
@@ -39,6 +39,7 @@ pub fn from_nbt(comp: NbtCompoundParse) -> Result<Box<Entity>, NbtParseError> {
     base.fire_time = comp.get_short("Fire").unwrap_or_default().max(0) as u32;
     base.air_time = comp.get_short("Air").unwrap_or_default().max(0) as u32;
     base.on_ground = comp.get_boolean("OnGround").unwrap_or_default();
+    base.name = comp.get_string("CustomName").ok().map(str::to_string);
 
     let id = comp.get_string("id")?;
     let base_kind = match id {
@@ -105,6 +106,9 @@ pub fn from_nbt(comp: NbtCompoundParse) -> Result<Box<Entity>, NbtParseError> {
 
             projectile.shake = comp.get_byte("shake")?.max(0) as u8;
 
+            // Not saved by the Notchian implementation, only valid with persisted ids.
+            projectile.owner_id = comp.get_int("OwnerId").ok().map(|id| id as u32);
+
             let projectile_kind = match id {
                 "Arrow" => ProjectileKind::Arrow(e::Arrow {
                     from_player: comp.get_boolean("player").unwrap_or_default(),
@@ -128,7 +132,7 @@ pub fn from_nbt(comp: NbtCompoundParse) -> Result<Box<Entity>, NbtParseError> {
             let living_kind = match id {
                 "Creeper" => LivingKind::Creeper(e::Creeper {
                     powered: comp.get_boolean("powered")?,
-                    ignited_time: None,
+                    ..Default::default()
                 }),
                 "Skeleton" => LivingKind::Skeleton(e::Skeleton::default()),
                 "Spider" => LivingKind::Spider(e::Spider::default()),
@@ -140,7 +144,8 @@ pub fn from_nbt(comp: NbtCompoundParse) -> Result<Box<Entity>, NbtParseError> {
                 }),
                 "Ghast" => LivingKind::Ghast(e::Ghast::default()),
                 "PigZombie" => LivingKind::PigZombie(e::PigZombie {
-                    anger: comp.get_short("Anger")? != 0,
+                    anger: comp.get_short("Anger")?.max(0) as u16,
+                    ..Default::default()
                 }),
                 "Pig" => LivingKind::Pig(e::Pig {
                     saddle: comp.get_boolean("Saddle")?,
@@ -148,6 +153,7 @@ pub fn from_nbt(comp: NbtCompoundParse) -> Result<Box<Entity>, NbtParseError> {
                 "Sheep" => LivingKind::Sheep(e::Sheep {
                     sheared: comp.get_boolean("Sheared")?,
                     color: comp.get_byte("Color")? as u8,
+                    ..Default::default()
                 }),
                 "Cow" => LivingKind::Cow(e::Cow::default()),
                 "Chicken" => LivingKind::Chicken(e::Chicken::default()),
@@ -260,6 +266,9 @@ pub fn to_nbt<'a>(comp: &'a mut NbtCompound, entity: &Entity) -> Option<&'a mut
             comp.insert("inData", block.metadata);
             comp.insert("inGround", projectile.state.is_some());
             comp.insert("shake", projectile.shake.min(i8::MAX as _) as i8);
+            if let Some(owner_id) = projectile.owner_id {
+                comp.insert("OwnerId", owner_id as i32);
+            }
         }
         BaseKind::Living(living, living_kind) => {
             match living_kind {
@@ -317,6 +326,9 @@ pub fn to_nbt<'a>(comp: &'a mut NbtCompound, entity: &Entity) -> Option<&'a mut
     comp.insert("Fire", base.fire_time.min(i16::MAX as _) as i16);
     comp.insert("Air", base.air_time.min(i16::MAX as _) as i16);
     comp.insert("OnGround", base.on_ground);
+    if let Some(name) = &base.name {
+        comp.insert("CustomName", name.clone());
+    }
 
     Some(comp)
 }
@@ -0,0 +1,316 @@
+//! Serialization and deserialization of frozen worlds into a single NBT file.
+//!
+//! The file is a gzip-compressed NBT compound, chunks, entities and block entities are
+//! encoded with the same structures as in region files, see [`chunk`](super::chunk).
+//!
+//! PARITY: Entities are encoded with their chunk NBT structure, so their transient
+//! state that is not saved by the Notchian server, such as their path or attack target,
+//! is not restored.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use glam::IVec3;
+
+use crate::world::freeze::{FrozenBlockTick, FrozenLightUpdate, WorldFreeze};
+use crate::world::{BlockTickPriority, ChunkSnapshot, Difficulty, Dimension, LightKind, Weather};
+
+use super::chunk::{block_entity_nbt, chunk_nbt, entity_nbt};
+use super::nbt::{self, Nbt, NbtCompound, NbtCompression, NbtError, NbtParseError};
+
+/// Current version of the frozen world format.
+const VERSION: i32 = 1;
+
+/// Error while loading a frozen world.
+#[derive(thiserror::Error, Debug)]
+pub enum FreezeError {
+    #[error("nbt: {0}")]
+    Nbt(#[from] NbtError),
+    #[error("nbt parse: {0}")]
+    NbtParse(#[from] NbtParseError),
+}
+
+/// Save a frozen world to the given file, replacing it.
+pub fn save(path: impl AsRef<Path>, freeze: &WorldFreeze) -> Result<(), NbtError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    nbt::to_compressed_writer(&mut writer, &to_nbt(freeze), NbtCompression::Gzip)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Load a frozen world from the given file.
+pub fn load(path: impl AsRef<Path>) -> Result<WorldFreeze, FreezeError> {
+    let reader = BufReader::new(File::open(path).map_err(NbtError::Io)?);
+    let root = nbt::from_detected_reader(reader)?;
+    Ok(from_nbt(&root)?)
+}
+
+pub fn from_nbt(root: &Nbt) -> Result<WorldFreeze, NbtParseError> {
+    let comp = root.parse().as_compound()?;
+
+    let version = comp.get("Version")?;
+    if version.clone().as_int()? != VERSION {
+        return Err(NbtParseError::new(
+            version.path().to_string(),
+            "supported version",
+        ));
+    }
+
+    let dimension = Dimension::from_id(comp.get_byte("Dimension")?);
+
+    let weather = comp.get("Weather")?;
+    let weather = match weather.clone().as_byte()? {
+        0 => Weather::Clear,
+        1 => Weather::Rain,
+        2 => Weather::Thunder,
+        _ => {
+            return Err(NbtParseError::new(
+                weather.path().to_string(),
+                "valid weather",
+            ))
+        }
+    };
+
+    let mut freeze = WorldFreeze {
+        dimension,
+        time: comp.get_long("Time")? as u64,
+        rand_state: comp.get_long("RandState")?,
+        random_ticks_seed: comp.get_int("RandomTicksSeed")?,
+        cave_sound_time: comp.get_short("CaveSoundTime").unwrap_or(0) as u16,
+        weather,
+        weather_next_time: comp.get_long("WeatherNextTime")? as u64,
+        sky_light_subtracted: comp.get_byte("SkyLightSubtracted")? as u8,
+        difficulty: comp
+            .get_byte("Difficulty")
+            .ok()
+            .and_then(|id| Difficulty::from_id(id as u8))
+            .unwrap_or_default(),
+        pvp: comp.get_boolean("Pvp").unwrap_or(true),
+        next_entity_id: comp.get_int("NextEntityId")? as u32,
+        chunks: Vec::new(),
+        entities: Vec::new(),
+        block_entities: Vec::new(),
+        block_ticks: Vec::new(),
+        light_updates: Vec::new(),
+    };
+
+    for item in comp.get_list("Chunks")?.iter() {
+        let snapshot = chunk_nbt::from_nbt(item.as_compound()?, None)?;
+        freeze
+            .chunks
+            .push((snapshot.cx, snapshot.cz, snapshot.chunk));
+    }
+
+    for item in comp.get_list("Entities")?.iter() {
+        let item = item.as_compound()?;
+        let id = item.get_int("Id")? as u32;
+        freeze.entities.push((id, entity_nbt::from_nbt(item)?));
+    }
+
+    for item in comp.get_list("TileEntities")?.iter() {
+        freeze
+            .block_entities
+            .push(block_entity_nbt::from_nbt(item.as_compound()?)?);
+    }
+
+    for item in comp.get_list("TileTicks")?.iter() {
+        let item = item.as_compound()?;
+        freeze.block_ticks.push(FrozenBlockTick {
+            pos: IVec3::new(item.get_int("x")?, item.get_int("y")?, item.get_int("z")?),
+            id: item.get_byte("i")? as u8,
+            time: item.get_long("t")? as u64,
+            priority: match item.get_byte("p").unwrap_or(0) {
+                ..=-1 => BlockTickPriority::High,
+                0 => BlockTickPriority::Normal,
+                1.. => BlockTickPriority::Low,
+            },
+        });
+    }
+
+    for item in comp.get_list("LightUpdates")?.iter() {
+        let item = item.as_compound()?;
+        freeze.light_updates.push(FrozenLightUpdate {
+            kind: if item.get_boolean("Sky")? {
+                LightKind::Sky
+            } else {
+                LightKind::Block
+            },
+            pos: IVec3::new(item.get_int("x")?, item.get_int("y")?, item.get_int("z")?),
+            credit: item.get_byte("Credit")? as u8,
+        });
+    }
+
+    Ok(freeze)
+}
+
+pub fn to_nbt(freeze: &WorldFreeze) -> Nbt {
+    let mut comp = NbtCompound::new();
+
+    comp.insert("Version", VERSION);
+    comp.insert("Dimension", freeze.dimension.id());
+    comp.insert("Time", freeze.time);
+    comp.insert("RandState", freeze.rand_state);
+    comp.insert("RandomTicksSeed", freeze.random_ticks_seed);
+    comp.insert("CaveSoundTime", freeze.cave_sound_time);
+    comp.insert(
+        "Weather",
+        match freeze.weather {
+            Weather::Clear => 0i8,
+            Weather::Rain => 1i8,
+            Weather::Thunder => 2i8,
+        },
+    );
+    comp.insert("WeatherNextTime", freeze.weather_next_time);
+    comp.insert("SkyLightSubtracted", freeze.sky_light_subtracted);
+    comp.insert("Difficulty", freeze.difficulty.id());
+    comp.insert("Pvp", freeze.pvp);
+    comp.insert("NextEntityId", freeze.next_entity_id);
+
+    comp.insert(
+        "Chunks",
+        freeze
+            .chunks
+            .iter()
+            .map(|(cx, cz, chunk)| {
+                // Entities and block entities are saved apart to keep their order.
+                let mut snapshot = ChunkSnapshot::new(*cx, *cz);
+                snapshot.chunk = chunk.clone();
+                let mut comp = NbtCompound::new();
+                chunk_nbt::to_nbt(&mut comp, &snapshot);
+                Nbt::Compound(comp)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    comp.insert(
+        "Entities",
+        freeze
+            .entities
+            .iter()
+            .filter_map(|(id, entity)| {
+                let mut comp = NbtCompound::new();
+                entity_nbt::to_nbt(&mut comp, entity)?;
+                comp.insert("Id", *id);
+                Some(Nbt::Compound(comp))
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    comp.insert(
+        "TileEntities",
+        freeze
+            .block_entities
+            .iter()
+            .map(|(pos, block_entity)| {
+                let mut comp = NbtCompound::new();
+                block_entity_nbt::to_nbt(&mut comp, *pos, block_entity);
+                Nbt::Compound(comp)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    comp.insert(
+        "TileTicks",
+        freeze
+            .block_ticks
+            .iter()
+            .map(|tick| {
+                let mut comp = NbtCompound::new();
+                comp.insert("x", tick.pos.x);
+                comp.insert("y", tick.pos.y);
+                comp.insert("z", tick.pos.z);
+                comp.insert("i", tick.id);
+                comp.insert("t", tick.time);
+                comp.insert(
+                    "p",
+                    match tick.priority {
+                        BlockTickPriority::High => -1i8,
+                        BlockTickPriority::Normal => 0i8,
+                        BlockTickPriority::Low => 1i8,
+                    },
+                );
+                Nbt::Compound(comp)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    comp.insert(
+        "LightUpdates",
+        freeze
+            .light_updates
+            .iter()
+            .map(|update| {
+                let mut comp = NbtCompound::new();
+                comp.insert("x", update.pos.x);
+                comp.insert("y", update.pos.y);
+                comp.insert("z", update.pos.z);
+                comp.insert("Sky", update.kind == LightKind::Sky);
+                comp.insert("Credit", update.credit);
+                Nbt::Compound(comp)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    Nbt::Compound(comp)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use glam::DVec3;
+
+    use super::*;
+    use crate::block;
+    use crate::chunk::Chunk;
+    use crate::entity::{Entity, Item};
+    use crate::item::{self, ItemStack};
+    use crate::world::World;
+
+    #[test]
+    fn freeze_round_trip() {
+        let mut world = World::new(Dimension::Overworld);
+        world.set_chunk(0, 0, Chunk::new());
+        world.set_difficulty(Difficulty::Hard);
+        world.set_pvp(false);
+        world.set_block(IVec3::new(1, 10, 1), block::SAND, 0);
+        world.schedule_block_tick(IVec3::new(1, 10, 1), block::SAND, 3);
+        for i in 0..3 {
+            world.spawn_entity(Item::new_with(|base, item| {
+                base.pos = DVec3::new(4.0 + i as f64, 20.0, 4.0);
+                item.stack = ItemStack::new_single(item::DIAMOND, 0);
+            }));
+        }
+
+        let root = to_nbt(&world.freeze());
+        let mut thawed = World::thaw(from_nbt(&root).unwrap());
+
+        assert_eq!(thawed.get_time(), world.get_time());
+        assert_eq!(thawed.get_difficulty(), Difficulty::Hard);
+        assert!(!thawed.is_pvp());
+        assert_eq!(
+            thawed.get_block(IVec3::new(1, 10, 1)),
+            Some((block::SAND, 0))
+        );
+        assert_eq!(thawed.get_block_tick_count(), 1);
+
+        // Both worlds should evolve exactly the same way.
+        for _ in 0..20 {
+            world.tick();
+            thawed.tick();
+        }
+
+        let positions = |world: &World| {
+            world
+                .iter_entities()
+                .map(|(id, Entity(base, _))| (id, base.pos))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(positions(&thawed), positions(&world));
+        assert_eq!(
+            thawed.get_block(IVec3::new(1, 10, 1)),
+            world.get_block(IVec3::new(1, 10, 1))
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Serialization and deserialization of world metadata into a single NBT file.
+//!
+//! The metadata is the small part of the world state that is not stored in region files
+//! but must persist across restarts, such as the next entity id to allocate, so that ids
+//! of entities saved in chunks are never given to other entities.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::world::{Difficulty, World};
+
+use super::nbt::{self, Nbt, NbtCompound, NbtCompression, NbtError, NbtParseError};
+
+/// Error while loading world metadata.
+#[derive(thiserror::Error, Debug)]
+pub enum MetaError {
+    #[error("nbt: {0}")]
+    Nbt(#[from] NbtError),
+    #[error("nbt parse: {0}")]
+    NbtParse(#[from] NbtParseError),
+}
+
+/// The persisted metadata of a world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldMeta {
+    /// The next entity id to be allocated, see [`World::get_next_entity_id`].
+    pub next_entity_id: u32,
+    /// The difficulty of the world, see [`World::get_difficulty`].
+    pub difficulty: Difficulty,
+    /// True if players can attack each other, see [`World::is_pvp`].
+    pub pvp: bool,
+}
+
+impl Default for WorldMeta {
+    fn default() -> Self {
+        Self {
+            next_entity_id: 0,
+            difficulty: Difficulty::default(),
+            pvp: true,
+        }
+    }
+}
+
+impl WorldMeta {
+    /// Take the metadata of the given world.
+    pub fn from_world(world: &World) -> Self {
+        Self {
+            next_entity_id: world.get_next_entity_id(),
+            difficulty: world.get_difficulty(),
+            pvp: world.is_pvp(),
+        }
+    }
+
+    /// Apply this metadata to the given world, this should be done before inserting
+    /// any chunk in the world.
+    pub fn apply(&self, world: &mut World) {
+        world.set_next_entity_id(self.next_entity_id);
+        world.set_difficulty(self.difficulty);
+        world.set_pvp(self.pvp);
+    }
+}
+
+/// Save world metadata to the given file, replacing it.
+pub fn save(path: impl AsRef<Path>, meta: &WorldMeta) -> Result<(), NbtError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    nbt::to_compressed_writer(&mut writer, &to_nbt(meta), NbtCompression::Gzip)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Load world metadata from the given file.
+pub fn load(path: impl AsRef<Path>) -> Result<WorldMeta, MetaError> {
+    let reader = BufReader::new(File::open(path).map_err(NbtError::Io)?);
+    let root = nbt::from_detected_reader(reader)?;
+    Ok(from_nbt(&root)?)
+}
+
+pub fn from_nbt(root: &Nbt) -> Result<WorldMeta, NbtParseError> {
+    let comp = root.parse().as_compound()?;
+    Ok(WorldMeta {
+        next_entity_id: comp.get_int("NextEntityId")? as u32,
+        difficulty: comp
+            .get_byte("Difficulty")
+            .ok()
+            .and_then(|id| Difficulty::from_id(id as u8))
+            .unwrap_or_default(),
+        pvp: comp.get_boolean("Pvp").unwrap_or(true),
+    })
+}
+
+pub fn to_nbt(meta: &WorldMeta) -> Nbt {
+    let mut comp = NbtCompound::new();
+    comp.insert("NextEntityId", meta.next_entity_id);
+    comp.insert("Difficulty", meta.difficulty.id());
+    comp.insert("Pvp", meta.pvp);
+    Nbt::Compound(comp)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut world = World::builder().build();
+        world.set_next_entity_id(42);
+        world.set_difficulty(Difficulty::Hard);
+        world.set_pvp(false);
+
+        let meta = from_nbt(&to_nbt(&WorldMeta::from_world(&world))).unwrap();
+        assert_eq!(meta.next_entity_id, 42);
+        assert_eq!(meta.difficulty, Difficulty::Hard);
+        assert!(!meta.pvp);
+
+        let mut restored = World::builder().build();
+        meta.apply(&mut restored);
+        assert_eq!(restored.get_next_entity_id(), 42);
+        assert_eq!(restored.get_difficulty(), Difficulty::Hard);
+        assert!(!restored.is_pvp());
+    }
+
+    #[test]
+    fn missing_keys() {
+        let mut comp = NbtCompound::new();
+        comp.insert("NextEntityId", 7u32);
+
+        let meta = from_nbt(&Nbt::Compound(comp)).unwrap();
+        assert_eq!(meta.next_entity_id, 7);
+        assert_eq!(meta.difficulty, Difficulty::Normal);
+        assert!(meta.pvp);
+        assert_eq!(
+            meta,
+            WorldMeta {
+                next_entity_id: 7,
+                ..WorldMeta::default()
+            }
+        );
+    }
+}
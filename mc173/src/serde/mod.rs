@@ -1,5 +1,9 @@
 //! Serialization and deserialization utilities for worlds, chunks and entities.
 
 pub mod chunk;
+pub mod freeze;
+pub mod meta;
 pub mod nbt;
 pub mod region;
+pub mod remap;
+pub mod schematic;
@@ -1,11 +1,30 @@
 //! NBT format serialization and deserialization.
-
-use std::collections::BTreeMap;
+//!
+//! This module provides two ways of working with NBT data:
+//! - A DOM-style API, where a whole tag is read into a [`Nbt`] value with the
+//!   [`from_reader`] function, and written back with [`to_writer`]. Compounds are
+//!   represented by [`NbtCompound`], and the [`Nbt::parse`] method gives wrappers that
+//!   produce errors with the path of the faulty tag;
+//! - A streaming API, where tags are read as a sequence of tokens by [`NbtReader`] and
+//!   written incrementally by [`NbtWriter`], without materializing the whole tree.
+//!
+//! NBT files are usually compressed with GZip (such as `level.dat`) or Zlib (such as
+//! chunks in region files), the [`NbtCompression`] enumeration and the
+//! [`from_compressed_reader`] and [`to_compressed_writer`] functions handle this.
+
+use std::collections::{btree_map, BTreeMap};
 use std::fmt;
-use std::io::{self, Read, Write};
+use std::io::{self, BufRead, Read, Write};
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
 
 use crate::io::{ReadJavaExt, WriteJavaExt};
 
+mod stream;
+pub use stream::{NbtReader, NbtToken, NbtWriter};
+
 const NBT_BYTE: i8 = 1;
 const NBT_SHORT: i8 = 2;
 const NBT_INT: i8 = 3;
@@ -17,6 +36,48 @@ const NBT_STRING: i8 = 8;
 const NBT_LIST: i8 = 9;
 const NBT_COMPOUND: i8 = 10;
 
+/// The type of a NBT tag, the discriminant is the type id used in the binary format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum NbtType {
+    Byte = NBT_BYTE,
+    Short = NBT_SHORT,
+    Int = NBT_INT,
+    Long = NBT_LONG,
+    Float = NBT_FLOAT,
+    Double = NBT_DOUBLE,
+    ByteArray = NBT_BYTE_ARRAY,
+    String = NBT_STRING,
+    List = NBT_LIST,
+    Compound = NBT_COMPOUND,
+}
+
+impl NbtType {
+    /// Get the tag type from its binary type id, none is returned for unknown type ids
+    /// and for the end tag.
+    pub fn from_id(id: i8) -> Option<Self> {
+        Some(match id {
+            NBT_BYTE => Self::Byte,
+            NBT_SHORT => Self::Short,
+            NBT_INT => Self::Int,
+            NBT_LONG => Self::Long,
+            NBT_FLOAT => Self::Float,
+            NBT_DOUBLE => Self::Double,
+            NBT_BYTE_ARRAY => Self::ByteArray,
+            NBT_STRING => Self::String,
+            NBT_LIST => Self::List,
+            NBT_COMPOUND => Self::Compound,
+            _ => return None,
+        })
+    }
+
+    /// Get the binary type id of this tag type.
+    #[inline]
+    pub fn id(self) -> i8 {
+        self as i8
+    }
+}
+
 /// A generic NBT tag, this structure has a size of 32 bytes.
 #[derive(Clone, PartialEq)]
 pub enum Nbt {
@@ -89,6 +150,22 @@ impl<'a> From<&'a str> for Nbt {
 
 /// Basic methods to interpret a tag as its inner type if possible.
 impl Nbt {
+    /// Get the type of this tag.
+    pub fn get_type(&self) -> NbtType {
+        match self {
+            Nbt::Byte(_) => NbtType::Byte,
+            Nbt::Short(_) => NbtType::Short,
+            Nbt::Int(_) => NbtType::Int,
+            Nbt::Long(_) => NbtType::Long,
+            Nbt::Float(_) => NbtType::Float,
+            Nbt::Double(_) => NbtType::Double,
+            Nbt::ByteArray(_) => NbtType::ByteArray,
+            Nbt::String(_) => NbtType::String,
+            Nbt::List(_) => NbtType::List,
+            Nbt::Compound(_) => NbtType::Compound,
+        }
+    }
+
     #[inline]
     pub fn as_boolean(&self) -> Option<bool> {
         self.as_byte().map(|b| b != 0)
@@ -174,6 +251,26 @@ impl Nbt {
         }
     }
 
+    /// Get a mutable reference to the inner compound, if this tag is a compound.
+    #[inline]
+    pub fn as_compound_mut(&mut self) -> Option<&mut NbtCompound> {
+        match self {
+            Self::Compound(comp) => Some(comp),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable reference to the inner list, if this tag is a list.
+    #[inline]
+    pub fn as_list_mut(&mut self) -> Option<&mut Vec<Nbt>> {
+        match self {
+            Self::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    /// Start parsing this tag with wrappers that return errors containing the path of
+    /// the faulty tag.
     pub fn parse(&self) -> NbtParse<'_> {
         NbtParse {
             inner: self,
@@ -210,6 +307,29 @@ impl NbtCompound {
         self.inner.get(key)
     }
 
+    #[inline]
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Nbt> {
+        self.inner.get_mut(key)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, key: &str) -> Option<Nbt> {
+        self.inner.remove(key)
+    }
+
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.inner.contains_key(key)
+    }
+
+    /// Iterate over all keys and tags of this compound, sorted by key.
+    #[inline]
+    pub fn iter(&self) -> NbtCompoundIter<'_> {
+        NbtCompoundIter {
+            inner: self.inner.iter(),
+        }
+    }
+
     #[inline]
     pub fn get_boolean(&self, key: &str) -> Option<bool> {
         self.get(key).and_then(Nbt::as_boolean)
@@ -266,6 +386,36 @@ impl NbtCompound {
     }
 }
 
+/// An iterator over the keys and tags of a compound, sorted by key.
+#[derive(Debug, Clone)]
+pub struct NbtCompoundIter<'a> {
+    inner: btree_map::Iter<'a, String, Nbt>,
+}
+
+impl<'a> Iterator for NbtCompoundIter<'a> {
+    type Item = (&'a str, &'a Nbt);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, tag)| (key.as_str(), tag))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> IntoIterator for &'a NbtCompound {
+    type Item = (&'a str, &'a Nbt);
+    type IntoIter = NbtCompoundIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl Default for NbtCompound {
     fn default() -> Self {
         Self::new()
@@ -297,16 +447,40 @@ impl fmt::Debug for Nbt {
     }
 }
 
-/// Deserialize a NBT tag from a reader.
-pub fn from_reader(mut reader: impl Read) -> Result<Nbt, NbtError> {
+/// Deserialize a NBT tag from a reader, the name of the root tag is ignored.
+pub fn from_reader(reader: impl Read) -> Result<Nbt, NbtError> {
+    from_reader_named(reader).map(|(_, tag)| tag)
+}
+
+/// Deserialize a NBT tag from a reader, returning the name of the root tag with it.
+pub fn from_reader_named(mut reader: impl Read) -> Result<(String, Nbt), NbtError> {
     let type_id = reader.read_java_byte()?;
     if type_id == 0 {
         // We should not get a end tag directly.
         return Err(NbtError::IllegalTagType);
     }
 
-    let _key = reader.read_java_string8()?;
-    from_reader_with_type(&mut reader, type_id)
+    let key = reader.read_java_string8()?;
+    Ok((key, from_reader_with_type(&mut reader, type_id)?))
+}
+
+/// Deserialize a NBT tag from a reader with the given compression.
+pub fn from_compressed_reader(
+    reader: impl Read,
+    compression: NbtCompression,
+) -> Result<Nbt, NbtError> {
+    match compression {
+        NbtCompression::None => from_reader(reader),
+        NbtCompression::Gzip => from_reader(GzDecoder::new(reader)),
+        NbtCompression::Zlib => from_reader(ZlibDecoder::new(reader)),
+    }
+}
+
+/// Deserialize a NBT tag from a buffered reader, the compression is detected from the
+/// first bytes of the data, see [`NbtCompression::detect`].
+pub fn from_detected_reader(mut reader: impl BufRead) -> Result<Nbt, NbtError> {
+    let compression = NbtCompression::detect(reader.fill_buf()?);
+    from_compressed_reader(reader, compression)
 }
 
 /// Internal function to read a NBT tag of a specific type.
@@ -360,13 +534,41 @@ fn from_reader_with_type(reader: &mut impl Read, type_id: i8) -> Result<Nbt, Nbt
     })
 }
 
-/// Serialize a NBT tag into a writer.
-pub fn to_writer(mut writer: impl Write, tag: &Nbt) -> Result<(), NbtError> {
+/// Serialize a NBT tag into a writer, the root tag has an empty name.
+pub fn to_writer(writer: impl Write, tag: &Nbt) -> Result<(), NbtError> {
+    to_writer_named(writer, "", tag)
+}
+
+/// Serialize a NBT tag into a writer with the given name for the root tag.
+pub fn to_writer_named(mut writer: impl Write, name: &str, tag: &Nbt) -> Result<(), NbtError> {
     writer.write_java_byte(get_nbt_type_id(tag))?;
-    writer.write_java_string8("")?; // Root tag has empty key.
+    writer.write_java_string8(name)?;
     to_writer_raw(&mut writer, tag)
 }
 
+/// Serialize a NBT tag into a writer with the given compression.
+pub fn to_compressed_writer(
+    writer: impl Write,
+    tag: &Nbt,
+    compression: NbtCompression,
+) -> Result<(), NbtError> {
+    match compression {
+        NbtCompression::None => to_writer(writer, tag),
+        NbtCompression::Gzip => {
+            let mut encoder = GzEncoder::new(writer, Compression::default());
+            to_writer(&mut encoder, tag)?;
+            encoder.finish()?;
+            Ok(())
+        }
+        NbtCompression::Zlib => {
+            let mut encoder = ZlibEncoder::new(writer, Compression::default());
+            to_writer(&mut encoder, tag)?;
+            encoder.finish()?;
+            Ok(())
+        }
+    }
+}
+
 /// Internal function to write a NBT tag content.
 fn to_writer_raw(writer: &mut impl Write, tag: &Nbt) -> Result<(), NbtError> {
     match *tag {
@@ -410,22 +612,39 @@ fn to_writer_raw(writer: &mut impl Write, tag: &Nbt) -> Result<(), NbtError> {
 }
 
 /// Internal function to get the NBT type id of a tag.
+#[inline]
 fn get_nbt_type_id(tag: &Nbt) -> i8 {
-    match tag {
-        Nbt::Byte(_) => NBT_BYTE,
-        Nbt::Short(_) => NBT_SHORT,
-        Nbt::Int(_) => NBT_INT,
-        Nbt::Long(_) => NBT_LONG,
-        Nbt::Float(_) => NBT_FLOAT,
-        Nbt::Double(_) => NBT_DOUBLE,
-        Nbt::ByteArray(_) => NBT_BYTE_ARRAY,
-        Nbt::String(_) => NBT_STRING,
-        Nbt::List(_) => NBT_LIST,
-        Nbt::Compound(_) => NBT_COMPOUND,
+    tag.get_type().id()
+}
+
+/// The compression of a NBT data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NbtCompression {
+    /// Raw NBT data.
+    None,
+    /// GZip compression, used by `level.dat` for example.
+    Gzip,
+    /// Zlib compression, used by chunks in region files for example.
+    Zlib,
+}
+
+impl NbtCompression {
+    /// Detect the compression of a NBT data from its first bytes, raw data is assumed
+    /// if neither the GZip magic nor a Zlib header is found.
+    pub fn detect(header: &[u8]) -> Self {
+        match *header {
+            [0x1F, 0x8B, ..] => Self::Gzip,
+            [cmf, flg, ..]
+                if cmf & 0x0F == 8 && (cmf as u16 * 256 + flg as u16).is_multiple_of(31) =>
+            {
+                Self::Zlib
+            }
+            _ => Self::None,
+        }
     }
 }
 
-/// Error type used together with `RegionResult` for every call on region file methods.
+/// Error type returned when reading or writing NBT data.
 #[derive(thiserror::Error, Debug)]
 pub enum NbtError {
     #[error("io: {0}")]
@@ -434,6 +653,8 @@ pub enum NbtError {
     IllegalTagType,
     #[error("illegal decoded length")]
     IllegalLength,
+    #[error("illegal streaming state")]
+    IllegalState,
 }
 
 /// Parsing utility structure for anonymous NBT.
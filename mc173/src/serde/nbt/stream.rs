@@ -0,0 +1,457 @@
+//! Streaming NBT reader and writer.
+
+use std::io::{Read, Write};
+
+use crate::io::{ReadJavaExt, WriteJavaExt};
+
+use super::*;
+
+/// A token read by the streaming [`NbtReader`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NbtToken {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<u8>),
+    String(String),
+    /// Start of a list with the given number of items, the items follow and then a
+    /// [`NbtToken::ListEnd`] token.
+    ListStart {
+        len: usize,
+    },
+    /// End of the current list.
+    ListEnd,
+    /// Start of a compound, its entries follow and then a [`NbtToken::CompoundEnd`].
+    CompoundStart,
+    /// End of the current compound.
+    CompoundEnd,
+}
+
+/// Internal container state of the reader and writer.
+#[derive(Debug, Clone, Copy)]
+enum Frame {
+    /// Currently in a compound.
+    Compound,
+    /// Currently in a list of the given type id, with the given remaining items.
+    List { type_id: i8, remaining: usize },
+}
+
+/// A streaming NBT reader that reads a single root tag as a sequence of tokens, each
+/// token is returned with its key if it's an entry of a compound or the root tag.
+///
+/// This reader can be used as an iterator, which returns none after the root tag has
+/// been fully read.
+pub struct NbtReader<R> {
+    /// The inner reader.
+    inner: R,
+    /// Stack of containers currently being read.
+    stack: Vec<Frame>,
+    /// Set to true when the root tag has been fully read.
+    done: bool,
+}
+
+impl<R: Read> NbtReader<R> {
+    /// Create a new streaming reader from the given raw NBT data reader.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            stack: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Return the current depth of containers being read, zero being the root.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Read the next token, returning none when the root tag has been fully read.
+    pub fn next_token(&mut self) -> Result<Option<(Option<String>, NbtToken)>, NbtError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let (key, type_id) = match self.stack.last_mut() {
+            None => {
+                let type_id = self.inner.read_java_byte()?;
+                if type_id == 0 {
+                    return Err(NbtError::IllegalTagType);
+                }
+                (Some(self.inner.read_java_string8()?), type_id)
+            }
+            Some(Frame::Compound) => {
+                let type_id = self.inner.read_java_byte()?;
+                if type_id == 0 {
+                    self.stack.pop();
+                    self.done = self.stack.is_empty();
+                    return Ok(Some((None, NbtToken::CompoundEnd)));
+                }
+                (Some(self.inner.read_java_string8()?), type_id)
+            }
+            Some(Frame::List { remaining: 0, .. }) => {
+                self.stack.pop();
+                self.done = self.stack.is_empty();
+                return Ok(Some((None, NbtToken::ListEnd)));
+            }
+            Some(Frame::List { type_id, remaining }) => {
+                *remaining -= 1;
+                (None, *type_id)
+            }
+        };
+
+        let token = match type_id {
+            NBT_BYTE => NbtToken::Byte(self.inner.read_java_byte()?),
+            NBT_SHORT => NbtToken::Short(self.inner.read_java_short()?),
+            NBT_INT => NbtToken::Int(self.inner.read_java_int()?),
+            NBT_LONG => NbtToken::Long(self.inner.read_java_long()?),
+            NBT_FLOAT => NbtToken::Float(self.inner.read_java_float()?),
+            NBT_DOUBLE => NbtToken::Double(self.inner.read_java_double()?),
+            NBT_BYTE_ARRAY => {
+                let len: usize = self
+                    .inner
+                    .read_java_int()?
+                    .try_into()
+                    .map_err(|_| NbtError::IllegalLength)?;
+                let mut buf = vec![0u8; len];
+                self.inner.read_exact(&mut buf)?;
+                NbtToken::ByteArray(buf)
+            }
+            NBT_STRING => NbtToken::String(self.inner.read_java_string8()?),
+            NBT_LIST => {
+                let type_id = self.inner.read_java_byte()?;
+                let len: usize = self
+                    .inner
+                    .read_java_int()?
+                    .try_into()
+                    .map_err(|_| NbtError::IllegalLength)?;
+                self.stack.push(Frame::List {
+                    type_id,
+                    remaining: len,
+                });
+                NbtToken::ListStart { len }
+            }
+            NBT_COMPOUND => {
+                self.stack.push(Frame::Compound);
+                NbtToken::CompoundStart
+            }
+            _ => return Err(NbtError::IllegalTagType),
+        };
+
+        self.done = self.stack.is_empty();
+        Ok(Some((key, token)))
+    }
+
+    /// Read the remaining of the container that has just been started into a tag, this
+    /// can be used to materialize only some parts of the data. The given token must be
+    /// the [`NbtToken::ListStart`] or [`NbtToken::CompoundStart`] just returned, other
+    /// tokens are directly converted to a tag.
+    pub fn read_tag(&mut self, token: NbtToken) -> Result<Nbt, NbtError> {
+        Ok(match token {
+            NbtToken::Byte(n) => Nbt::Byte(n),
+            NbtToken::Short(n) => Nbt::Short(n),
+            NbtToken::Int(n) => Nbt::Int(n),
+            NbtToken::Long(n) => Nbt::Long(n),
+            NbtToken::Float(n) => Nbt::Float(n),
+            NbtToken::Double(n) => Nbt::Double(n),
+            NbtToken::ByteArray(buf) => Nbt::ByteArray(buf),
+            NbtToken::String(string) => Nbt::String(string),
+            NbtToken::ListStart { len } => {
+                let mut list = Vec::with_capacity(len);
+                loop {
+                    match self.next_token()? {
+                        Some((_, NbtToken::ListEnd)) => break Nbt::List(list),
+                        Some((_, token)) => list.push(self.read_tag(token)?),
+                        None => return Err(NbtError::IllegalState),
+                    }
+                }
+            }
+            NbtToken::CompoundStart => {
+                let mut compound = NbtCompound::new();
+                loop {
+                    match self.next_token()? {
+                        Some((_, NbtToken::CompoundEnd)) => break Nbt::Compound(compound),
+                        Some((Some(key), token)) => {
+                            let tag = self.read_tag(token)?;
+                            compound.insert(key, tag);
+                        }
+                        _ => return Err(NbtError::IllegalState),
+                    }
+                }
+            }
+            NbtToken::ListEnd | NbtToken::CompoundEnd => return Err(NbtError::IllegalState),
+        })
+    }
+
+    /// Skip the remaining of the container that has just been started, this does
+    /// nothing if the last token was not a container start.
+    pub fn skip_container(&mut self, token: &NbtToken) -> Result<(), NbtError> {
+        if !matches!(token, NbtToken::ListStart { .. } | NbtToken::CompoundStart) {
+            return Ok(());
+        }
+
+        let depth = self.stack.len();
+        while self.stack.len() >= depth {
+            if self.next_token()?.is_none() {
+                return Err(NbtError::IllegalState);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the inner reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Iterator for NbtReader<R> {
+    type Item = Result<(Option<String>, NbtToken), NbtError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token().transpose()
+    }
+}
+
+/// A streaming NBT writer that writes a single root tag incrementally. Every write
+/// method takes the key of the written tag, which is ignored for items of lists.
+/// The structure is checked while writing, list items must all have the declared type
+/// and count, and containers must be ended in order, [`NbtError::IllegalState`] or
+/// [`NbtError::IllegalTagType`] is returned otherwise.
+pub struct NbtWriter<W> {
+    /// The inner writer.
+    inner: W,
+    /// Stack of containers currently being written.
+    stack: Vec<Frame>,
+}
+
+impl<W: Write> NbtWriter<W> {
+    /// Create a new streaming writer to the given raw NBT data writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Internal function to write the header of a tag, depending on the container.
+    fn write_header(&mut self, key: &str, type_id: i8) -> Result<(), NbtError> {
+        match self.stack.last_mut() {
+            Some(Frame::List {
+                type_id: list_type_id,
+                remaining,
+            }) => {
+                if *list_type_id != type_id {
+                    return Err(NbtError::IllegalTagType);
+                } else if *remaining == 0 {
+                    return Err(NbtError::IllegalLength);
+                }
+                *remaining -= 1;
+            }
+            _ => {
+                self.inner.write_java_byte(type_id)?;
+                self.inner.write_java_string8(key)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write_byte(&mut self, key: &str, n: i8) -> Result<(), NbtError> {
+        self.write_header(key, NBT_BYTE)?;
+        Ok(self.inner.write_java_byte(n)?)
+    }
+
+    pub fn write_short(&mut self, key: &str, n: i16) -> Result<(), NbtError> {
+        self.write_header(key, NBT_SHORT)?;
+        Ok(self.inner.write_java_short(n)?)
+    }
+
+    pub fn write_int(&mut self, key: &str, n: i32) -> Result<(), NbtError> {
+        self.write_header(key, NBT_INT)?;
+        Ok(self.inner.write_java_int(n)?)
+    }
+
+    pub fn write_long(&mut self, key: &str, n: i64) -> Result<(), NbtError> {
+        self.write_header(key, NBT_LONG)?;
+        Ok(self.inner.write_java_long(n)?)
+    }
+
+    pub fn write_float(&mut self, key: &str, n: f32) -> Result<(), NbtError> {
+        self.write_header(key, NBT_FLOAT)?;
+        Ok(self.inner.write_java_float(n)?)
+    }
+
+    pub fn write_double(&mut self, key: &str, n: f64) -> Result<(), NbtError> {
+        self.write_header(key, NBT_DOUBLE)?;
+        Ok(self.inner.write_java_double(n)?)
+    }
+
+    pub fn write_byte_array(&mut self, key: &str, buf: &[u8]) -> Result<(), NbtError> {
+        let len: i32 = buf.len().try_into().map_err(|_| NbtError::IllegalLength)?;
+        self.write_header(key, NBT_BYTE_ARRAY)?;
+        self.inner.write_java_int(len)?;
+        Ok(self.inner.write_all(buf)?)
+    }
+
+    pub fn write_string(&mut self, key: &str, string: &str) -> Result<(), NbtError> {
+        self.write_header(key, NBT_STRING)?;
+        Ok(self.inner.write_java_string8(string)?)
+    }
+
+    /// Write a whole tag, this can be used to write parts of the data that are already
+    /// materialized.
+    pub fn write_tag(&mut self, key: &str, tag: &Nbt) -> Result<(), NbtError> {
+        self.write_header(key, get_nbt_type_id(tag))?;
+        to_writer_raw(&mut self.inner, tag)
+    }
+
+    /// Start a list of the given length, where all items have the given type. The list
+    /// must be ended with [`end_list`](Self::end_list) after all items are written.
+    pub fn begin_list(
+        &mut self,
+        key: &str,
+        item_type: NbtType,
+        len: usize,
+    ) -> Result<(), NbtError> {
+        let len_i32: i32 = len.try_into().map_err(|_| NbtError::IllegalLength)?;
+        self.write_header(key, NBT_LIST)?;
+        self.inner.write_java_byte(item_type.id())?;
+        self.inner.write_java_int(len_i32)?;
+        self.stack.push(Frame::List {
+            type_id: item_type.id(),
+            remaining: len,
+        });
+        Ok(())
+    }
+
+    /// End the current list, all of its items must have been written.
+    pub fn end_list(&mut self) -> Result<(), NbtError> {
+        match self.stack.last() {
+            Some(Frame::List { remaining: 0, .. }) => {
+                self.stack.pop();
+                Ok(())
+            }
+            Some(Frame::List { .. }) => Err(NbtError::IllegalLength),
+            _ => Err(NbtError::IllegalState),
+        }
+    }
+
+    /// Start a compound, it must be ended with [`end_compound`](Self::end_compound)
+    /// after all entries are written.
+    pub fn begin_compound(&mut self, key: &str) -> Result<(), NbtError> {
+        self.write_header(key, NBT_COMPOUND)?;
+        self.stack.push(Frame::Compound);
+        Ok(())
+    }
+
+    /// End the current compound.
+    pub fn end_compound(&mut self) -> Result<(), NbtError> {
+        match self.stack.last() {
+            Some(Frame::Compound) => {
+                self.stack.pop();
+                Ok(self.inner.write_java_byte(0)?)
+            }
+            _ => Err(NbtError::IllegalState),
+        }
+    }
+
+    /// Return the inner writer, an error is returned if some containers have not been
+    /// ended.
+    pub fn finish(self) -> Result<W, NbtError> {
+        if self.stack.is_empty() {
+            Ok(self.inner)
+        } else {
+            Err(NbtError::IllegalState)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn stream_round_trip() {
+        let mut writer = NbtWriter::new(Vec::new());
+        writer.begin_compound("root").unwrap();
+        writer.write_string("name", "hello").unwrap();
+        writer.begin_list("pos", NbtType::Double, 2).unwrap();
+        writer.write_double("", 1.5).unwrap();
+        assert!(writer.write_int("", 2).is_err());
+        writer.write_double("", -3.0).unwrap();
+        writer.end_list().unwrap();
+        writer.begin_compound("inner").unwrap();
+        writer.write_byte("flag", 1).unwrap();
+        writer.end_compound().unwrap();
+        writer.end_compound().unwrap();
+        let data = writer.finish().unwrap();
+
+        let (name, tag) = from_reader_named(&data[..]).unwrap();
+        assert_eq!(name, "root");
+        let comp = tag.as_compound().unwrap();
+        assert_eq!(comp.get_string("name"), Some("hello"));
+        assert_eq!(
+            comp.get_list("pos").unwrap(),
+            [Nbt::Double(1.5), Nbt::Double(-3.0)]
+        );
+        assert_eq!(
+            comp.get_compound("inner").unwrap().get_boolean("flag"),
+            Some(true)
+        );
+
+        let tokens = NbtReader::new(&data[..])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(tokens.len(), 10);
+        assert_eq!(
+            tokens[0],
+            (Some("root".to_string()), NbtToken::CompoundStart)
+        );
+        assert_eq!(
+            tokens[2],
+            (Some("pos".to_string()), NbtToken::ListStart { len: 2 })
+        );
+        assert_eq!(tokens[3], (None, NbtToken::Double(1.5)));
+        assert_eq!(tokens[9], (None, NbtToken::CompoundEnd));
+
+        // Skip the list and materialize the inner compound.
+        let mut reader = NbtReader::new(&data[..]);
+        reader.next_token().unwrap();
+        reader.next_token().unwrap();
+        let (_, token) = reader.next_token().unwrap().unwrap();
+        reader.skip_container(&token).unwrap();
+        let (key, token) = reader.next_token().unwrap().unwrap();
+        assert_eq!(key.as_deref(), Some("inner"));
+        let inner = reader.read_tag(token).unwrap();
+        assert_eq!(&inner, comp.get("inner").unwrap());
+        assert_eq!(
+            reader.next_token().unwrap(),
+            Some((None, NbtToken::CompoundEnd))
+        );
+        assert_eq!(reader.next_token().unwrap(), None);
+    }
+
+    #[test]
+    fn compression() {
+        let mut comp = NbtCompound::new();
+        comp.insert("key", 42i32);
+        let tag = Nbt::Compound(comp);
+
+        for compression in [
+            NbtCompression::None,
+            NbtCompression::Gzip,
+            NbtCompression::Zlib,
+        ] {
+            let mut data = Vec::new();
+            to_compressed_writer(&mut data, &tag, compression).unwrap();
+            assert_eq!(NbtCompression::detect(&data), compression);
+            assert_eq!(from_detected_reader(&data[..]).unwrap(), tag);
+        }
+    }
+}
@@ -41,6 +41,12 @@ impl RegionDir {
         }
     }
 
+    /// Return the path of the region directory.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     /// Ensure that a region file exists for the given chunk coordinates. If false is
     /// given as the 'create' argument, then the file will not be created and initialized
     /// if not existing.
@@ -220,6 +226,63 @@ where
         Ok(ChunkReader { inner })
     }
 
+    /// Read the raw sectors allocated to the chunk at the given position, including the
+    /// chunk header and compressed data, this is intended for backup of chunks that
+    /// cannot be decoded. Sectors past the end of the file are truncated.
+    pub fn read_chunk_raw(&mut self, cx: i32, cz: i32) -> Result<Vec<u8>, RegionError> {
+        let chunk = self.get_chunk_meta(cx, cz);
+        if chunk.is_empty() {
+            return Err(RegionError::EmptyChunk);
+        }
+
+        if chunk.range.offset < 2 {
+            return Err(RegionError::IllegalRange);
+        }
+
+        self.inner
+            .seek(SeekFrom::Start(chunk.range.offset as u64 * 4096))?;
+
+        let mut data = Vec::new();
+        Read::take(&mut self.inner, chunk.range.count as u64 * 4096).read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    /// Return true if a chunk is stored at the given position, the chunk position is at
+    /// modulo 32 in order to respect the limitations of the region size.
+    pub fn contains_chunk(&self, cx: i32, cz: i32) -> bool {
+        !self.get_chunk_meta(cx, cz).is_empty()
+    }
+
+    /// Remove the chunk at the given position, its sectors are cleared and freed. The
+    /// chunk position is at modulo 32 in order to respect the limitations of the region
+    /// size, caller don't have to do it.
+    pub fn remove_chunk(&mut self, cx: i32, cz: i32) -> Result<(), RegionError> {
+        let chunk = self.get_chunk_meta(cx, cz);
+        if chunk.is_empty() {
+            return Ok(());
+        }
+
+        // Same as the write function, never clear reserved sectors.
+        assert!(
+            chunk.range.offset >= 2,
+            "chunk metadata uses reserved sectors"
+        );
+
+        self.set_chunk_meta_and_sync(cx, cz, ChunkMeta::INIT)?;
+
+        self.inner
+            .seek(SeekFrom::Start(chunk.range.offset as u64 * 4096))?;
+        for offset in chunk.range.offset..chunk.range.offset + chunk.range.count {
+            if let Some(slot) = self.sectors.get_mut(offset as usize / 64) {
+                *slot &= !(1u64 << (offset % 64));
+            }
+            self.inner.write_all(EMPTY_SECTOR)?;
+        }
+
+        self.inner.flush()?;
+        Ok(())
+    }
+
     /// Write a chunk at the given position, the chunk position is at modulo 32 in order
     /// to respect the limitations of the region size, caller don't have to do it.
     pub fn write_chunk(&mut self, cx: i32, cz: i32) -> ChunkWriter<'_, I> {
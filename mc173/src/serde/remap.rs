@@ -0,0 +1,409 @@
+//! Block and item ids remapping on chunk load.
+//!
+//! Worlds coming from slightly different, modded servers may contain block and item
+//! ids that are unknown to this implementation, or that have been assigned to other
+//! blocks or items. An [`IdRemap`] table translates these ids when a chunk is loaded,
+//! unknown ids that are not explicitly remapped are replaced following the table's
+//! policy. Entities and block entities that cannot be decoded, typically added by mods,
+//! can also be dropped instead of failing the whole chunk.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::block;
+use crate::block_entity::BlockEntity;
+use crate::chunk::CHUNK_HEIGHT;
+use crate::entity::{BaseKind, Minecart};
+use crate::item::{self, ItemStack};
+use crate::world::ChunkSnapshot;
+
+/// A remapping table for block and item ids.
+#[derive(Debug, Clone)]
+pub struct IdRemap {
+    /// Explicit translation of block ids.
+    blocks: Box<[Option<u8>; 256]>,
+    /// The block replacing unknown blocks that are not explicitly remapped.
+    unknown_block: u8,
+    /// Explicit translation of item ids, none to remove the item.
+    items: HashMap<u16, Option<u16>>,
+    /// True to remove unknown items that are not explicitly remapped.
+    remove_unknown_items: bool,
+    /// True to drop entities and block entities that cannot be decoded.
+    drop_unknown_entities: bool,
+}
+
+/// Error while parsing a remapping table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdRemapError {
+    /// The line number, starting at 1.
+    pub line: usize,
+    /// Description of the error.
+    pub message: &'static str,
+}
+
+impl Default for IdRemap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdRemap {
+    /// Create a new remapping table with the default policy: unknown blocks are replaced
+    /// by air, unknown items are removed and entities that cannot be decoded are dropped.
+    pub fn new() -> Self {
+        Self {
+            blocks: Box::new([None; 256]),
+            unknown_block: block::AIR,
+            items: HashMap::new(),
+            remove_unknown_items: true,
+            drop_unknown_entities: true,
+        }
+    }
+
+    /// Replace all unknown blocks that are not explicitly remapped by the given block,
+    /// typically air or stone.
+    pub fn set_unknown_block(&mut self, to: u8) {
+        self.unknown_block = to;
+    }
+
+    /// Set if unknown items that are not explicitly remapped should be removed, if
+    /// not removed they are kept as-is.
+    pub fn set_remove_unknown_items(&mut self, remove: bool) {
+        self.remove_unknown_items = remove;
+    }
+
+    /// Set if entities and block entities that cannot be decoded should be dropped
+    /// instead of failing to load the whole chunk.
+    pub fn set_drop_unknown_entities(&mut self, drop: bool) {
+        self.drop_unknown_entities = drop;
+    }
+
+    /// Remap a block id to another one.
+    pub fn remap_block(&mut self, from: u8, to: u8) {
+        self.blocks[from as usize] = Some(to);
+    }
+
+    /// Remap an item id to another one, or remove it if none.
+    pub fn remap_item(&mut self, from: u16, to: Option<u16>) {
+        self.items.insert(from, to);
+    }
+
+    /// Get the translated block id.
+    #[inline]
+    pub fn get_block(&self, id: u8) -> u8 {
+        match self.blocks[id as usize] {
+            Some(to) => to,
+            None if block::name(id).is_empty() => self.unknown_block,
+            None => id,
+        }
+    }
+
+    /// Get the translated item id, none if the item should be removed.
+    pub fn get_item(&self, id: u16) -> Option<u16> {
+        if let Some(&to) = self.items.get(&id) {
+            to
+        } else if id < 256 {
+            // Block items follow the blocks remapping.
+            match self.get_block(id as u8) {
+                block::AIR if id != 0 => None,
+                to => Some(to as u16),
+            }
+        } else if self.remove_unknown_items && item::try_from_id(id).is_none() {
+            None
+        } else {
+            Some(id)
+        }
+    }
+
+    /// Return true if entities and block entities that cannot be decoded should be
+    /// dropped.
+    #[inline]
+    pub fn is_dropping_unknown_entities(&self) -> bool {
+        self.drop_unknown_entities
+    }
+
+    /// Parse a remapping table from its text format, starting from the default policy.
+    /// Each non-empty line is a directive, `#` starts a comment:
+    ///
+    /// - `block <from> <to>` to remap a block id;
+    /// - `item <from> <to>|remove` to remap or remove an item id;
+    /// - `unknown_block <to>` to replace all unknown blocks;
+    /// - `unknown_items keep|remove` to keep or remove unknown items;
+    /// - `unknown_entities keep|drop` to drop or fail on entities that cannot be decoded.
+    pub fn parse(text: &str) -> Result<Self, IdRemapError> {
+        let mut remap = Self::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let err = |message| IdRemapError {
+                line: index + 1,
+                message,
+            };
+
+            let line = line.split_once('#').map_or(line, |(line, _)| line);
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            match parts[..] {
+                [] => {}
+                ["block", from, to] => {
+                    let from = from.parse().map_err(|_| err("invalid block id"))?;
+                    let to = to.parse().map_err(|_| err("invalid block id"))?;
+                    remap.remap_block(from, to);
+                }
+                ["item", from, "remove"] => {
+                    let from = from.parse().map_err(|_| err("invalid item id"))?;
+                    remap.remap_item(from, None);
+                }
+                ["item", from, to] => {
+                    let from = from.parse().map_err(|_| err("invalid item id"))?;
+                    let to = to.parse().map_err(|_| err("invalid item id"))?;
+                    remap.remap_item(from, Some(to));
+                }
+                ["unknown_block", to] => {
+                    remap.set_unknown_block(to.parse().map_err(|_| err("invalid block id"))?);
+                }
+                ["unknown_items", "keep"] => remap.set_remove_unknown_items(false),
+                ["unknown_items", "remove"] => remap.set_remove_unknown_items(true),
+                ["unknown_entities", "keep"] => remap.set_drop_unknown_entities(false),
+                ["unknown_entities", "drop"] => remap.set_drop_unknown_entities(true),
+                _ => return Err(err("invalid directive")),
+            }
+        }
+
+        Ok(remap)
+    }
+
+    /// Apply this remapping to all blocks, entities and block entities of a chunk
+    /// snapshot, returning the number of blocks, items and block entities that have
+    /// been changed or removed. If any block is changed, the height map is recomputed
+    /// and the light of the chunk is marked invalid.
+    pub fn apply(&self, snapshot: &mut ChunkSnapshot) -> usize {
+        let mut count = 0;
+
+        let chunk = Arc::make_mut(&mut snapshot.chunk);
+        for i in 0..chunk.block.len() {
+            let id = chunk.block[i];
+            let to = self.get_block(id);
+            if to != id {
+                chunk.block[i] = to;
+                // Metadata is only kept for explicitly remapped blocks.
+                if self.blocks[id as usize].is_none() {
+                    chunk.metadata.set(i, 0);
+                }
+                count += 1;
+            }
+        }
+
+        // Remapped blocks may have different opacity or light emission.
+        if count != 0 {
+            chunk.recompute_all_height();
+            snapshot.light_invalid = true;
+        }
+
+        for entity in &mut snapshot.entities {
+            match &mut entity.1 {
+                BaseKind::Item(item) => count += self.apply_stack(&mut item.stack),
+                BaseKind::FallingBlock(falling_block) => {
+                    count += self.apply_block(&mut falling_block.block_id);
+                }
+                BaseKind::Minecart(Minecart::Chest { inv }) => {
+                    count += self.apply_stacks(&mut inv[..]);
+                }
+                _ => {}
+            }
+        }
+
+        // Item entities with removed items are also removed, along with their id.
+        let mut index = 0;
+        let entities_ids = &mut snapshot.entities_ids;
+        snapshot.entities.retain(|entity| {
+            let keep = match &entity.1 {
+                BaseKind::Item(item) => !item.stack.is_empty(),
+                _ => true,
+            };
+            if !keep && index < entities_ids.len() {
+                entities_ids.remove(index);
+            } else {
+                index += 1;
+            }
+            keep
+        });
+
+        for block_entity in snapshot.block_entities.values_mut() {
+            match &mut **block_entity {
+                BlockEntity::Chest(chest) => count += self.apply_stacks(&mut chest.inv[..]),
+                BlockEntity::Dispenser(dispenser) => {
+                    count += self.apply_stacks(&mut dispenser.inv[..]);
+                }
+                BlockEntity::Furnace(furnace) => {
+                    count += self.apply_stack(&mut furnace.input_stack);
+                    count += self.apply_stack(&mut furnace.fuel_stack);
+                    count += self.apply_stack(&mut furnace.output_stack);
+                }
+                BlockEntity::Piston(piston) => count += self.apply_block(&mut piston.block),
+                BlockEntity::Jukebox(jukebox) if jukebox.record != 0 => {
+                    let record = jukebox.record as u16;
+                    let to = self.get_item(record).unwrap_or(0);
+                    if to != record {
+                        jukebox.record = to as u32;
+                        count += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Block entities are removed if their block has been remapped to another one.
+        let chunk = &snapshot.chunk;
+        snapshot.block_entities.retain(|&pos, block_entity| {
+            let keep = (0..CHUNK_HEIGHT as i32).contains(&pos.y)
+                && block_entity.is_valid_for(chunk.get_block(pos).0);
+            count += !keep as usize;
+            keep
+        });
+
+        count
+    }
+
+    fn apply_block(&self, id: &mut u8) -> usize {
+        let to = self.get_block(*id);
+        let changed = to != *id;
+        *id = to;
+        changed as usize
+    }
+
+    fn apply_stack(&self, stack: &mut ItemStack) -> usize {
+        if stack.is_empty() {
+            return 0;
+        }
+
+        match self.get_item(stack.id) {
+            Some(to) if to == stack.id => 0,
+            Some(to) => {
+                stack.id = to;
+                1
+            }
+            None => {
+                *stack = ItemStack::EMPTY;
+                1
+            }
+        }
+    }
+
+    fn apply_stacks(&self, stacks: &mut [ItemStack]) -> usize {
+        stacks.iter_mut().map(|stack| self.apply_stack(stack)).sum()
+    }
+}
+
+impl fmt::Display for IdRemapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for IdRemapError {}
+
+#[cfg(test)]
+mod tests {
+
+    use glam::IVec3;
+
+    use super::*;
+    use crate::block_entity::chest::ChestBlockEntity;
+    use crate::block_entity::furnace::FurnaceBlockEntity;
+
+    #[test]
+    fn remap_chunk() {
+        let remap = IdRemap::parse(
+            "# Test table.
+            block 210 4
+            unknown_block 1
+            item 3000 remove
+            item 3001 264",
+        )
+        .unwrap();
+
+        let mut snapshot = ChunkSnapshot::new(0, 0);
+        let chunk = Arc::get_mut(&mut snapshot.chunk).unwrap();
+        chunk.set_block(IVec3::new(0, 0, 0), 200, 3);
+        chunk.set_block(IVec3::new(1, 0, 0), 210, 0);
+        chunk.set_block(IVec3::new(2, 0, 0), block::DIRT, 0);
+        chunk.set_block(IVec3::new(3, 0, 0), block::CHEST, 0);
+
+        let mut chest = ChestBlockEntity::default();
+        chest.inv[0] = ItemStack::new_single(3000, 0);
+        chest.inv[1] = ItemStack::new_single(3001, 0);
+        chest.inv[2] = ItemStack::new_single(200, 0);
+        snapshot
+            .block_entities
+            .insert(IVec3::new(3, 0, 0), Box::new(BlockEntity::Chest(chest)));
+
+        assert_eq!(remap.apply(&mut snapshot), 5);
+        assert_eq!(
+            snapshot.chunk.get_block(IVec3::new(0, 0, 0)),
+            (block::STONE, 0)
+        );
+        assert_eq!(
+            snapshot.chunk.get_block(IVec3::new(1, 0, 0)),
+            (block::COBBLESTONE, 0)
+        );
+        assert_eq!(
+            snapshot.chunk.get_block(IVec3::new(2, 0, 0)),
+            (block::DIRT, 0)
+        );
+
+        let BlockEntity::Chest(chest) = &**snapshot.block_entities.values().next().unwrap() else {
+            panic!("expected chest");
+        };
+        assert!(chest.inv[0].is_empty());
+        assert_eq!(chest.inv[1].id, item::DIAMOND);
+        assert_eq!(chest.inv[2].id, block::STONE as u16);
+
+        assert_eq!(IdRemap::parse("block 1").unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn remap_chunk_light_and_block_entities() {
+        let remap = IdRemap::parse("block 54 1").unwrap();
+
+        let mut snapshot = ChunkSnapshot::new(0, 0);
+        let chunk = Arc::get_mut(&mut snapshot.chunk).unwrap();
+        chunk.set_block(IVec3::new(0, 0, 0), block::CHEST, 0);
+        chunk.set_block(IVec3::new(1, 0, 0), block::FURNACE, 0);
+        chunk.set_block(IVec3::new(2, 100, 2), 200, 0);
+        chunk.recompute_all_height();
+        assert_eq!(chunk.get_height(IVec3::new(2, 0, 2)), 101);
+        assert_eq!(chunk.get_sky_light(IVec3::new(2, 50, 2)), 0);
+
+        for (pos, block_entity) in [
+            (
+                IVec3::new(0, 0, 0),
+                BlockEntity::Chest(ChestBlockEntity::default()),
+            ),
+            (
+                IVec3::new(1, 0, 0),
+                BlockEntity::Furnace(FurnaceBlockEntity::default()),
+            ),
+        ] {
+            snapshot.block_entities.insert(pos, Box::new(block_entity));
+        }
+
+        assert_eq!(remap.apply(&mut snapshot), 3);
+        assert_eq!(
+            snapshot.chunk.get_block(IVec3::new(0, 0, 0)),
+            (block::STONE, 0)
+        );
+        assert_eq!(snapshot.chunk.get_height(IVec3::new(2, 0, 2)), 0);
+        assert_eq!(snapshot.chunk.get_sky_light(IVec3::new(2, 50, 2)), 15);
+        assert!(snapshot.light_invalid);
+
+        // The chest remapped to stone lost its block entity, the furnace kept it.
+        assert!(!snapshot.block_entities.contains_key(&IVec3::new(0, 0, 0)));
+        assert!(matches!(
+            snapshot
+                .block_entities
+                .get(&IVec3::new(1, 0, 0))
+                .map(|b| &**b),
+            Some(BlockEntity::Furnace(_))
+        ));
+    }
+}
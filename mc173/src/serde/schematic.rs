@@ -0,0 +1,93 @@
+//! Serialization and deserialization of schematics in the MCEdit format.
+//!
+//! The file is a gzip-compressed NBT compound with the size of the schematic, its blocks
+//! and metadata indexed by Y, then Z, then X, and its block entities with the same
+//! structure as in region files. Entities are ignored.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use glam::IVec3;
+
+use crate::gen::schematic::Schematic;
+
+use super::chunk::block_entity_nbt;
+use super::nbt::{self, Nbt, NbtCompound, NbtCompression, NbtError, NbtParseError};
+
+/// Error while loading a schematic.
+#[derive(thiserror::Error, Debug)]
+pub enum SchematicError {
+    #[error("nbt: {0}")]
+    Nbt(#[from] NbtError),
+    #[error("nbt parse: {0}")]
+    NbtParse(#[from] NbtParseError),
+}
+
+/// Save a schematic to the given file, replacing it.
+pub fn save(path: impl AsRef<Path>, schematic: &Schematic) -> Result<(), NbtError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    nbt::to_compressed_writer(&mut writer, &to_nbt(schematic), NbtCompression::Gzip)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Load a schematic from the given file.
+pub fn load(path: impl AsRef<Path>) -> Result<Schematic, SchematicError> {
+    let reader = BufReader::new(File::open(path).map_err(NbtError::Io)?);
+    let root = nbt::from_detected_reader(reader)?;
+    Ok(from_nbt(&root)?)
+}
+
+pub fn from_nbt(root: &Nbt) -> Result<Schematic, NbtParseError> {
+    let comp = root.parse().as_compound()?;
+
+    let size = IVec3::new(
+        comp.get_short("Width")? as i32,
+        comp.get_short("Height")? as i32,
+        comp.get_short("Length")? as i32,
+    );
+
+    let blocks = comp.get("Blocks")?;
+    let blocks_path = blocks.path().to_string();
+    let blocks = blocks.as_byte_array()?.to_vec();
+    let metadata = comp.get_byte_array("Data")?.to_vec();
+
+    let mut schematic = Schematic::with_blocks(size, blocks, metadata)
+        .ok_or_else(|| NbtParseError::new(blocks_path, "byte array of schematic size"))?;
+
+    if let Ok(list) = comp.get_list("TileEntities") {
+        for item in list.iter() {
+            let (pos, block_entity) = block_entity_nbt::from_nbt(item.as_compound()?)?;
+            schematic.set_block_entity(pos, block_entity);
+        }
+    }
+
+    Ok(schematic)
+}
+
+pub fn to_nbt(schematic: &Schematic) -> Nbt {
+    let mut comp = NbtCompound::new();
+
+    let size = schematic.get_size();
+    comp.insert("Width", size.x as i16);
+    comp.insert("Height", size.y as i16);
+    comp.insert("Length", size.z as i16);
+    comp.insert("Materials", "Alpha");
+    comp.insert("Blocks", schematic.get_blocks().to_vec());
+    comp.insert("Data", schematic.get_metadata().to_vec());
+    comp.insert("Entities", Vec::<Nbt>::new());
+    comp.insert(
+        "TileEntities",
+        schematic
+            .iter_block_entities()
+            .map(|(pos, block_entity)| {
+                let mut comp = NbtCompound::new();
+                block_entity_nbt::to_nbt(&mut comp, pos, block_entity);
+                Nbt::Compound(comp)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    Nbt::Compound(comp)
+}
@@ -5,27 +5,34 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::io;
+use std::fs;
+use std::io::{self, Write};
+use std::ops::AddAssign;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crossbeam_channel::unbounded;
-use crossbeam_channel::TryRecvError;
 use crossbeam_channel::{bounded, select, Receiver, RecvError, Sender};
-use tracing::debug;
+use crossbeam_channel::{RecvTimeoutError, TryRecvError};
+use tracing::{debug, warn};
 
+use crate::biome::Biome;
 use crate::chunk::Chunk;
 use crate::gen::ChunkGenerator;
-use crate::serde::nbt::NbtError;
+use crate::serde::chunk::EntityNbtCache;
 use crate::serde::nbt::NbtParseError;
+use crate::serde::nbt::{Nbt, NbtError};
 use crate::serde::region::{RegionDir, RegionError};
+use crate::serde::remap::IdRemap;
 use crate::world::Dimension;
 use crate::world::{ChunkSnapshot, World};
 
+pub mod fsck;
+
 const POPULATED_NEG_NEG: u8 = 0b0001;
 const POPULATED_POS_NEG: u8 = 0b0010;
 const POPULATED_NEG_POS: u8 = 0b0100;
@@ -61,6 +68,12 @@ struct StorageWorker<G: ChunkGenerator> {
     chunks_populated: HashMap<(i32, i32), u8>,
     /// The region directory to try loading required chunks.
     region_dir: RegionDir,
+    /// Optional ids remapping table applied to loaded chunks.
+    remap: Option<IdRemap>,
+    /// What to do when a stored chunk is corrupted.
+    corrupted_fallback: CorruptedChunkFallback,
+    /// Serialized entities of saved chunks, reused for entities that didn't change.
+    entity_cache: EntityNbtCache,
     /// Request receiver from the handle.
     storage_request_receiver: Receiver<StorageRequest>,
     /// Reply sender to the handle.
@@ -103,6 +116,22 @@ struct Stats {
 impl ChunkStorage {
     /// Create a new chunk storage backed by the given terrain workers count.
     pub fn new<P, G>(region_dir: P, generator: G, terrain_workers: usize) -> Self
+    where
+        P: Into<PathBuf>,
+        G: ChunkGenerator + Sync + Send + 'static,
+    {
+        Self::with_remap(region_dir, generator, terrain_workers, None)
+    }
+
+    /// Create a new chunk storage backed by the given terrain workers count, the block
+    /// and item ids of all loaded chunks are translated through the given remapping
+    /// table, if any.
+    pub fn with_remap<P, G>(
+        region_dir: P,
+        generator: G,
+        terrain_workers: usize,
+        remap: Option<IdRemap>,
+    ) -> Self
     where
         P: Into<PathBuf>,
         G: ChunkGenerator + Sync + Send + 'static,
@@ -153,6 +182,9 @@ impl ChunkStorage {
                     world: World::new(Dimension::Overworld), // Not relevant in worker.
                     chunks_populated: HashMap::new(),
                     region_dir: RegionDir::new(region_dir),
+                    remap,
+                    corrupted_fallback: CorruptedChunkFallback::default(),
+                    entity_cache: EntityNbtCache::default(),
                     storage_request_receiver,
                     storage_reply_sender,
                     terrain_request_sender,
@@ -187,17 +219,20 @@ impl ChunkStorage {
             .expect("worker should not disconnect while this handle exists");
     }
 
+    /// Set what the storage should do when a stored chunk is corrupted and cannot be
+    /// loaded, this applies to all loads requested after this call. By default, the
+    /// error is returned and the stored chunk is left untouched.
+    pub fn set_corrupted_fallback(&mut self, fallback: CorruptedChunkFallback) {
+        self.storage_request_sender
+            .send(StorageRequest::SetCorruptedFallback { fallback })
+            .expect("worker should not disconnect while this handle exists");
+    }
+
     /// Poll without blocking this storage for new reply to requested load and save.
     /// This function returns none if there is not new reply to poll.
     pub fn poll(&mut self) -> Option<ChunkStorageReply> {
         match self.storage_reply_receiver.try_recv() {
-            Ok(reply) => {
-                match reply {
-                    ChunkStorageReply::Load { cx, cz, .. } => self.request_load.remove(&(cx, cz)),
-                    ChunkStorageReply::Save { cx, cz, .. } => self.request_save.remove(&(cx, cz)),
-                };
-                Some(reply)
-            }
+            Ok(reply) => Some(self.handle_reply(reply)),
             Err(TryRecvError::Empty) => None,
             Err(TryRecvError::Disconnected) => {
                 panic!("worker should not disconnect while this handle exists")
@@ -205,6 +240,34 @@ impl ChunkStorage {
         }
     }
 
+    /// Poll this storage for new reply to requested load and save, blocking at most for
+    /// the given timeout. This function returns none if there is no new reply to poll
+    /// before the timeout.
+    pub fn poll_timeout(&mut self, timeout: Duration) -> Option<ChunkStorageReply> {
+        match self.storage_reply_receiver.recv_timeout(timeout) {
+            Ok(reply) => Some(self.handle_reply(reply)),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                panic!("worker should not disconnect while this handle exists")
+            }
+        }
+    }
+
+    /// Internal function to update the pending requests from a polled reply.
+    fn handle_reply(&mut self, reply: ChunkStorageReply) -> ChunkStorageReply {
+        match reply {
+            ChunkStorageReply::Load { cx, cz, .. } => self.request_load.remove(&(cx, cz)),
+            ChunkStorageReply::Save { cx, cz, .. } => self.request_save.remove(&(cx, cz)),
+        };
+        reply
+    }
+
+    /// Return true if the loading of the given chunk has been requested and is pending.
+    #[inline]
+    pub fn is_load_requested(&self, cx: i32, cz: i32) -> bool {
+        self.request_load.contains(&(cx, cz))
+    }
+
     /// Number of requested chunk loads pending.
     #[inline]
     pub fn request_load_count(&self) -> usize {
@@ -238,6 +301,10 @@ impl<G: ChunkGenerator> StorageWorker<G> {
         match request {
             StorageRequest::Load { cx, cz } => self.load_or_gen(cx, cz),
             StorageRequest::Save { snapshot } => self.save(&snapshot),
+            StorageRequest::SetCorruptedFallback { fallback } => {
+                self.corrupted_fallback = fallback;
+                true
+            }
         }
     }
 
@@ -250,9 +317,38 @@ impl<G: ChunkGenerator> StorageWorker<G> {
     /// Internal function to try loading a chunk from region file, if the chunk is not
     /// found, its generation is requested to terrain workers. But if a critical error
     /// is returned by the region file then an error is returned. This avoid overwriting
-    /// the chunk later and ruining a possibly recoverable error.
+    /// the chunk later and ruining a possibly recoverable error, unless a fallback is
+    /// configured for corrupted chunks, see [`CorruptedChunkFallback`].
     fn load_or_gen(&mut self, cx: i32, cz: i32) -> bool {
         match self.try_load(cx, cz) {
+            Err(ChunkLoadError::MissingRegion) => {
+                // The chunk has not been found in region files, generate it.
+                self.request_full(cx, cz);
+                true
+            }
+            Err(err @ ChunkLoadError::Corrupted { .. })
+                if self.corrupted_fallback != CorruptedChunkFallback::Error =>
+            {
+                // The corrupted data is backed up before being overwritten by the
+                // regenerated chunk, if we fail to do so the error is returned.
+                if self.corrupted_fallback == CorruptedChunkFallback::Quarantine {
+                    if let Err(quarantine_err) = self.quarantine(cx, cz) {
+                        warn!("failed to quarantine corrupted chunk {cx}/{cz}: {quarantine_err}");
+                        return self
+                            .storage_reply_sender
+                            .send(ChunkStorageReply::Load {
+                                cx,
+                                cz,
+                                res: Err(err),
+                            })
+                            .is_ok();
+                    }
+                }
+
+                warn!("regenerating corrupted chunk: {err}");
+                self.request_full(cx, cz);
+                true
+            }
             Err(err) => {
                 // Immediately send error, we don't want to load the chunk if there is
                 // an error in the region file, in order to avoid overwriting the error.
@@ -264,7 +360,7 @@ impl<G: ChunkGenerator> StorageWorker<G> {
                     })
                     .is_ok()
             }
-            Ok(Some(snapshot)) => {
+            Ok(snapshot) => {
                 // Immediately send the loaded chunk.
                 self.storage_reply_sender
                     .send(ChunkStorageReply::Load {
@@ -274,45 +370,96 @@ impl<G: ChunkGenerator> StorageWorker<G> {
                     })
                     .is_ok()
             }
-            Ok(None) => {
-                // The chunk has not been found in region files, generate it.
-                self.request_full(cx, cz);
-                true
-            }
         }
     }
 
     /// Try loading a chunk from region file.
-    fn try_load(&mut self, cx: i32, cz: i32) -> Result<Option<ChunkSnapshot>, StorageError> {
+    fn try_load(&mut self, cx: i32, cz: i32) -> Result<ChunkSnapshot, ChunkLoadError> {
         debug!("tried to load chunk: {}/{}", cx, cz);
 
-        // Get the region file but do not create it if not already existing, returning
-        // unsupported if not existing.
+        let corrupted = |cause: StorageError| ChunkLoadError::Corrupted {
+            pos: (cx, cz),
+            cause,
+        };
+
+        // Get the region file but do not create it if not already existing.
         let region = match self.region_dir.ensure_region(cx, cz, false) {
             Ok(region) => region,
             Err(RegionError::Io(err)) if err.kind() == io::ErrorKind::NotFound => {
-                return Ok(None);
+                return Err(ChunkLoadError::MissingRegion);
             }
-            Err(err) => return Err(StorageError::Region(err)),
+            Err(RegionError::Io(err)) => return Err(ChunkLoadError::Io(err)),
+            Err(err) => return Err(corrupted(StorageError::Region(err))),
         };
 
-        // Read the chunk, if it is empty then we return unsupported because we don't
-        // have the chunk but it's not really an error.
+        // Read the chunk, if it is empty then we don't have the chunk but it's not
+        // really an error.
         let reader = match region.read_chunk(cx, cz) {
             Ok(chunk) => chunk,
-            Err(RegionError::EmptyChunk) => return Ok(None),
-            Err(err) => return Err(StorageError::Region(err)),
+            Err(RegionError::EmptyChunk) => return Err(ChunkLoadError::MissingRegion),
+            Err(RegionError::Io(err)) => return Err(ChunkLoadError::Io(err)),
+            Err(err) => return Err(corrupted(StorageError::Region(err))),
         };
 
-        let root_tag = crate::serde::nbt::from_reader(reader)?;
-        let mut snapshot = crate::serde::chunk::from_nbt(&root_tag)?;
+        // Decompression errors and truncated data are also reported as I/O errors.
+        let root_tag = crate::serde::nbt::from_reader(reader).map_err(|err| match err {
+            NbtError::Io(err)
+                if !matches!(
+                    err.kind(),
+                    io::ErrorKind::InvalidData
+                        | io::ErrorKind::InvalidInput
+                        | io::ErrorKind::UnexpectedEof
+                ) =>
+            {
+                ChunkLoadError::Io(err)
+            }
+            err => corrupted(StorageError::Nbt(err)),
+        })?;
+
+        // Chunks saved by later versions use a format that we cannot read.
+        if let Some(version) = get_chunk_version(&root_tag) {
+            return Err(ChunkLoadError::VersionMismatch {
+                pos: (cx, cz),
+                version,
+            });
+        }
+
+        let mut snapshot = match &self.remap {
+            Some(remap) => crate::serde::chunk::from_nbt_remapped(&root_tag, remap),
+            None => crate::serde::chunk::from_nbt(&root_tag),
+        }
+        .map_err(|err| corrupted(StorageError::NbtParse(err)))?;
         let chunk = Arc::get_mut(&mut snapshot.chunk).unwrap();
 
-        // Biomes are not serialized in the chunk NBT, so we need to generate it on each
-        // chunk load because it may be used for natural entity spawn.
-        self.generator.gen_biomes(cx, cz, chunk, &mut self.state);
+        // Biomes are not serialized in Notchian chunk NBT, so we need to generate them if
+        // missing because they are used for natural entity spawn and weather.
+        if chunk.biome.iter().all(|&biome| biome == Biome::Void) {
+            self.generator.gen_biomes(cx, cz, chunk, &mut self.state);
+        }
 
-        Ok(Some(snapshot))
+        Ok(snapshot)
+    }
+
+    /// Copy the raw data of a stored chunk to a sidecar file in the "corrupted"
+    /// subdirectory of the region directory, and return the path of that file.
+    fn quarantine(&mut self, cx: i32, cz: i32) -> Result<PathBuf, RegionError> {
+        let data = self
+            .region_dir
+            .ensure_region(cx, cz, false)?
+            .read_chunk_raw(cx, cz)?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        let dir = self.region_dir.path().join("corrupted");
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("c.{cx}.{cz}.{timestamp}.dat"));
+        fs::write(&path, data)?;
+
+        debug!("quarantined chunk {cx}/{cz} to {}", path.display());
+        Ok(path)
     }
 
     /// Request full generation of a chunk to terrain workers, in order to fully generate
@@ -484,6 +631,11 @@ impl<G: ChunkGenerator> StorageWorker<G> {
                         // remove the chunk from the world.
                         self.chunks_populated.remove(&(current_cx, current_cz));
 
+                        self.world
+                            .get_chunk_mut(current_cx, current_cz)
+                            .expect("chunk should be existing")
+                            .populated = true;
+
                         let snapshot = self
                             .world
                             .remove_chunk_snapshot(current_cx, current_cz)
@@ -498,8 +650,8 @@ impl<G: ChunkGenerator> StorageWorker<G> {
                         if self
                             .storage_reply_sender
                             .send(ChunkStorageReply::Load {
-                                cx,
-                                cz,
+                                cx: current_cx,
+                                cz: current_cz,
                                 res: Ok(snapshot),
                             })
                             .is_err()
@@ -539,28 +691,38 @@ impl<G: ChunkGenerator> StorageWorker<G> {
                     })
                     .is_ok()
             }
-            Ok(()) => self
+            Ok(stats) => self
                 .storage_reply_sender
                 .send(ChunkStorageReply::Save {
                     cx,
                     cz,
-                    res: Ok(()),
+                    res: Ok(stats),
                 })
                 .is_ok(),
         }
     }
 
     /// Save a chunk snapshot and return result about success.
-    fn try_save(&mut self, snapshot: &ChunkSnapshot) -> Result<(), StorageError> {
+    fn try_save(&mut self, snapshot: &ChunkSnapshot) -> Result<SaveStats, StorageError> {
+        let start = Instant::now();
         let (cx, cz) = (snapshot.cx, snapshot.cz);
-        let region = self.region_dir.ensure_region(cx, cz, true)?;
 
+        let mut data = Vec::new();
+        let entities_written =
+            crate::serde::chunk::to_writer_cached(&mut data, snapshot, &mut self.entity_cache)?;
+
+        let region = self.region_dir.ensure_region(cx, cz, true)?;
         let mut writer = region.write_chunk(cx, cz);
-        let root_tag = crate::serde::chunk::to_nbt(snapshot);
-        crate::serde::nbt::to_writer(&mut writer, &root_tag)?;
+        writer.write_all(&data).map_err(RegionError::Io)?;
         writer.flush_chunk()?;
 
-        Ok(())
+        Ok(SaveStats {
+            chunks: 1,
+            entities: snapshot.entities.len(),
+            entities_written,
+            bytes: data.len(),
+            duration: start.elapsed(),
+        })
     }
 }
 
@@ -599,6 +761,24 @@ impl<G: ChunkGenerator> TerrainWorker<G> {
 enum StorageRequest {
     Load { cx: i32, cz: i32 },
     Save { snapshot: ChunkSnapshot },
+    SetCorruptedFallback { fallback: CorruptedChunkFallback },
+}
+
+/// What the storage should do when a stored chunk is corrupted, see
+/// [`ChunkStorage::set_corrupted_fallback`]. Chunks saved by later versions are never
+/// regenerated because their data is not corrupted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CorruptedChunkFallback {
+    /// Return a [`ChunkLoadError::Corrupted`] error and leave the stored chunk untouched.
+    #[default]
+    Error,
+    /// Regenerate the chunk from the generator, the corrupted data is overwritten when
+    /// the regenerated chunk is saved.
+    Regenerate,
+    /// Copy the corrupted data to a sidecar file in the "corrupted" subdirectory of the
+    /// region directory, and then regenerate the chunk. If the data cannot be copied,
+    /// the error is returned like [`Self::Error`].
+    Quarantine,
 }
 
 /// A reply from the storage for a previously requested chunk loading or saving.
@@ -606,15 +786,42 @@ pub enum ChunkStorageReply {
     Load {
         cx: i32,
         cz: i32,
-        res: Result<ChunkSnapshot, StorageError>,
+        res: Result<ChunkSnapshot, ChunkLoadError>,
     },
     Save {
         cx: i32,
         cz: i32,
-        res: Result<(), StorageError>,
+        res: Result<SaveStats, StorageError>,
     },
 }
 
+/// Statistics about saved chunks, returned by the storage for each saved chunk and that
+/// can be summed to get statistics of a whole save.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SaveStats {
+    /// Number of saved chunks.
+    pub chunks: usize,
+    /// Number of entities in the saved chunks.
+    pub entities: usize,
+    /// Number of entities that have been serialized, other entities didn't change since
+    /// the previous save of their chunk and have been reused.
+    pub entities_written: usize,
+    /// Number of bytes of serialized chunk data, before compression.
+    pub bytes: usize,
+    /// Total time spent serializing and writing chunks.
+    pub duration: Duration,
+}
+
+impl AddAssign for SaveStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.chunks += rhs.chunks;
+        self.entities += rhs.entities;
+        self.entities_written += rhs.entities_written;
+        self.bytes += rhs.bytes;
+        self.duration += rhs.duration;
+    }
+}
+
 enum TerrainRequest {
     Load { cx: i32, cz: i32 },
 }
@@ -623,6 +830,60 @@ enum TerrainReply {
     Load { cx: i32, cz: i32, chunk: Arc<Chunk> },
 }
 
+/// Return the data version of a chunk if it has been saved by a version later than
+/// b1.7.3, using a format that is not supported.
+fn get_chunk_version(root_tag: &Nbt) -> Option<i32> {
+    let comp = root_tag.as_compound()?;
+    if let Some(version) = comp.get_int("DataVersion") {
+        return Some(version);
+    }
+
+    // Anvil chunks prior to data versions store blocks in sections.
+    let level = comp.get_compound("Level")?;
+    if level.contains_key("Sections") {
+        Some(0)
+    } else {
+        None
+    }
+}
+
+/// Error returned by the storage when a chunk cannot be loaded.
+#[derive(thiserror::Error, Debug)]
+pub enum ChunkLoadError {
+    /// An I/O error happened while reading the region file, the load can be retried.
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+    /// The stored chunk data is corrupted and cannot be loaded.
+    #[error("chunk {}/{} is corrupted: {cause}", pos.0, pos.1)]
+    Corrupted {
+        /// The chunk position.
+        pos: (i32, i32),
+        /// The error that happened while reading the chunk.
+        #[source]
+        cause: StorageError,
+    },
+    /// The chunk has no data in region files, the storage generates the chunk in such
+    /// case, so this is never returned in a [`ChunkStorageReply`].
+    #[error("missing region data")]
+    MissingRegion,
+    /// The chunk has been saved by a later version, with an unsupported format. The
+    /// version is the data version of the chunk, zero if not known.
+    #[error("chunk {}/{} has an unsupported format from data version {version}", pos.0, pos.1)]
+    VersionMismatch {
+        /// The chunk position.
+        pos: (i32, i32),
+        /// The data version of the chunk.
+        version: i32,
+    },
+}
+
+impl ChunkLoadError {
+    /// Return true if the error is temporary and the load can be retried later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Io(_))
+    }
+}
+
 /// Error type used together with `RegionResult` for every call on region file methods.
 #[derive(thiserror::Error, Debug)]
 pub enum StorageError {
@@ -633,3 +894,113 @@ pub enum StorageError {
     #[error("nbt parse: {0}")]
     NbtParse(#[from] NbtParseError),
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs;
+
+    use super::*;
+    use crate::gen::VoidGenerator;
+    use crate::serde::nbt::NbtCompound;
+
+    #[test]
+    fn load_errors() {
+        let dir = std::env::temp_dir().join(format!("mc173-storage-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        {
+            let mut region_dir = RegionDir::new(&dir);
+            let region = region_dir.ensure_region(0, 0, true).unwrap();
+
+            let mut writer = region.write_chunk(1, 0);
+            writer.write_all(b"garbage").unwrap();
+            writer.flush_chunk().unwrap();
+
+            let mut comp = NbtCompound::new();
+            comp.insert("DataVersion", 3465);
+            let mut writer = region.write_chunk(2, 0);
+            crate::serde::nbt::to_writer(&mut writer, &Nbt::Compound(comp)).unwrap();
+            writer.flush_chunk().unwrap();
+        }
+
+        let mut storage = ChunkStorage::new(&dir, VoidGenerator::new(), 1);
+        storage.request_load(1, 0);
+        storage.request_load(2, 0);
+        storage.request_load(5, 5);
+
+        while storage.request_load_count() != 0 {
+            // Generated chunks are also saved.
+            let reply = storage.poll_timeout(Duration::from_secs(10));
+            let Some(ChunkStorageReply::Load { cx, cz, res }) = reply else {
+                assert!(reply.is_some(), "storage should reply");
+                continue;
+            };
+
+            match (cx, cz) {
+                (1, 0) => assert!(matches!(
+                    res,
+                    Err(ChunkLoadError::Corrupted { pos: (1, 0), .. })
+                )),
+                (2, 0) => assert!(matches!(
+                    res,
+                    Err(ChunkLoadError::VersionMismatch {
+                        pos: (2, 0),
+                        version: 3465
+                    })
+                )),
+                // Missing chunks are generated.
+                (5, 5) => assert!(res.is_ok()),
+                _ => {}
+            }
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn load_corrupted_fallback() {
+        let dir = std::env::temp_dir().join(format!("mc173-quarantine-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        {
+            let mut region_dir = RegionDir::new(&dir);
+            let region = region_dir.ensure_region(0, 0, true).unwrap();
+            let mut writer = region.write_chunk(1, 0);
+            writer.write_all(b"garbage").unwrap();
+            writer.flush_chunk().unwrap();
+        }
+
+        let mut storage = ChunkStorage::new(&dir, VoidGenerator::new(), 1);
+        storage.set_corrupted_fallback(CorruptedChunkFallback::Quarantine);
+        storage.request_load(1, 0);
+
+        while storage.request_load_count() != 0 {
+            let reply = storage.poll_timeout(Duration::from_secs(10));
+            let Some(ChunkStorageReply::Load { cx, cz, res }) = reply else {
+                assert!(reply.is_some(), "storage should reply");
+                continue;
+            };
+            if (cx, cz) == (1, 0) {
+                assert!(res.is_ok(), "corrupted chunk should be regenerated");
+            }
+        }
+
+        let quarantined = fs::read_dir(dir.join("corrupted"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(quarantined.len(), 1);
+        assert!(quarantined[0]
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("c.1.0."));
+        assert_eq!(fs::metadata(&quarantined[0]).unwrap().len(), 4096);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
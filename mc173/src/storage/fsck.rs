@@ -0,0 +1,467 @@
+//! World data validation and repair.
+//!
+//! The [`fsck`] function scans all region files of a region directory and validates
+//! every chunk: the region file structure, the chunk NBT structure, the chunk position,
+//! the height map and the light data. Depending on the options, fixable issues (height
+//! map and light) are repaired in place and chunks that cannot be read are removed from
+//! their region file, so they are regenerated by the storage on next load. Corrupted
+//! region files are renamed with a `.corrupted` extension for the same purpose.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use glam::IVec3;
+
+use crate::block;
+use crate::chunk::{CHUNK_HEIGHT, CHUNK_WIDTH};
+use crate::serde::nbt::{self, Nbt, NbtError, NbtParseError};
+use crate::serde::region::{Region, RegionError};
+use crate::world::ChunkSnapshot;
+
+/// Options of a world data check.
+#[derive(Debug, Clone)]
+pub struct FsckOptions {
+    /// Repair fixable issues in place, such as invalid height maps and light data.
+    pub repair: bool,
+    /// Remove chunks that cannot be read or are invalid, and rename corrupted region
+    /// files, so that they are regenerated on next load.
+    pub regenerate: bool,
+    /// Check that sky light is full above the height map, this should be disabled for
+    /// dimensions without sky.
+    pub check_sky_light: bool,
+}
+
+impl Default for FsckOptions {
+    fn default() -> Self {
+        Self {
+            repair: false,
+            regenerate: false,
+            check_sky_light: true,
+        }
+    }
+}
+
+/// Summary of a world data check.
+#[derive(Debug, Default)]
+pub struct FsckReport {
+    /// Number of region files checked.
+    pub regions: usize,
+    /// Number of chunks checked.
+    pub chunks: usize,
+    /// All issues found.
+    pub issues: Vec<FsckIssue>,
+}
+
+/// An issue found while checking world data.
+#[derive(Debug)]
+pub struct FsckIssue {
+    /// The region coordinates where the issue has been found.
+    pub region: (i32, i32),
+    /// The chunk coordinates where the issue has been found, none if the whole region
+    /// is affected.
+    pub chunk: Option<(i32, i32)>,
+    /// The kind of issue.
+    pub kind: FsckIssueKind,
+    /// The action taken for this issue.
+    pub action: FsckAction,
+}
+
+/// Kind of issue found while checking world data.
+#[derive(Debug)]
+pub enum FsckIssueKind {
+    /// The region file cannot be opened.
+    RegionCorrupted(RegionError),
+    /// The chunk cannot be read from the region file.
+    ChunkUnreadable(RegionError),
+    /// The chunk NBT data cannot be decoded.
+    NbtCorrupted(NbtError),
+    /// The chunk NBT structure is not valid.
+    NbtInvalid(NbtParseError),
+    /// The chunk position saved in the chunk doesn't match its position in the region.
+    ChunkMisplaced { cx: i32, cz: i32 },
+    /// Some columns of the height map are wrong.
+    HeightMap { columns: usize },
+    /// Some columns have a sky light that is not full above the height map.
+    SkyLight { columns: usize },
+    /// Some light-emitting blocks have a block light lower than their emission.
+    BlockLight { blocks: usize },
+}
+
+/// Action taken for an issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsckAction {
+    /// Nothing has been done, either because it's not allowed by the options or
+    /// because the action failed.
+    None,
+    /// The issue has been repaired in place.
+    Repaired,
+    /// The chunk has been removed, or the region file renamed, so it is regenerated
+    /// on next load.
+    Removed,
+}
+
+impl FsckReport {
+    /// Return true if no issue has been found.
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Return the number of issues on which the given action has been taken.
+    pub fn count_action(&self, action: FsckAction) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.action == action)
+            .count()
+    }
+}
+
+impl fmt::Display for FsckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "checked {} regions and {} chunks, {} issues ({} repaired, {} removed)",
+            self.regions,
+            self.chunks,
+            self.issues.len(),
+            self.count_action(FsckAction::Repaired),
+            self.count_action(FsckAction::Removed),
+        )?;
+
+        for issue in &self.issues {
+            let (rx, rz) = issue.region;
+            write!(f, "  region {rx}/{rz}")?;
+            if let Some((cx, cz)) = issue.chunk {
+                write!(f, ", chunk {cx}/{cz}")?;
+            }
+            writeln!(f, ": {} ({:?})", issue.kind, issue.action)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for FsckIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RegionCorrupted(e) => write!(f, "corrupted region: {e}"),
+            Self::ChunkUnreadable(e) => write!(f, "unreadable chunk: {e}"),
+            Self::NbtCorrupted(e) => write!(f, "corrupted nbt: {e}"),
+            Self::NbtInvalid(e) => write!(f, "invalid nbt: {e}"),
+            Self::ChunkMisplaced { cx, cz } => write!(f, "misplaced chunk, saved as {cx}/{cz}"),
+            Self::HeightMap { columns } => write!(f, "invalid height map on {columns} columns"),
+            Self::SkyLight { columns } => write!(f, "invalid sky light on {columns} columns"),
+            Self::BlockLight { blocks } => write!(f, "invalid block light on {blocks} blocks"),
+        }
+    }
+}
+
+/// Check all region files in the given region directory with the given options, an
+/// error is only returned if the directory cannot be listed.
+pub fn fsck(region_dir: impl AsRef<Path>, options: &FsckOptions) -> io::Result<FsckReport> {
+    let region_dir = region_dir.as_ref();
+    let mut report = FsckReport::default();
+
+    let mut regions = Vec::new();
+    for entry in fs::read_dir(region_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        if let Some((rx, rz)) = file_name.to_str().and_then(parse_region_file_name) {
+            regions.push((rx, rz, entry.path()));
+        }
+    }
+
+    regions.sort_by_key(|&(rx, rz, _)| (rx, rz));
+
+    for (rx, rz, path) in regions {
+        report.regions += 1;
+
+        let mut region = match Region::open(&path, false) {
+            Ok(region) => region,
+            Err(e) => {
+                let mut action = FsckAction::None;
+                if options.regenerate {
+                    let mut corrupted_path = path.clone().into_os_string();
+                    corrupted_path.push(".corrupted");
+                    if fs::rename(&path, corrupted_path).is_ok() {
+                        action = FsckAction::Removed;
+                    }
+                }
+
+                report.issues.push(FsckIssue {
+                    region: (rx, rz),
+                    chunk: None,
+                    kind: FsckIssueKind::RegionCorrupted(e),
+                    action,
+                });
+                continue;
+            }
+        };
+
+        for index in 0..1024 {
+            let cx = rx * 32 + (index & 31);
+            let cz = rz * 32 + (index >> 5);
+            if region.contains_chunk(cx, cz) {
+                report.chunks += 1;
+                check_chunk(&mut region, cx, cz, options, &mut report);
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Parse a region file name of the form `r.<rx>.<rz>.mcr`.
+fn parse_region_file_name(name: &str) -> Option<(i32, i32)> {
+    let name = name.strip_prefix("r.")?.strip_suffix(".mcr")?;
+    let (rx, rz) = name.split_once('.')?;
+    Some((rx.parse().ok()?, rz.parse().ok()?))
+}
+
+/// Check a single chunk of a region, pushing its issues to the report.
+fn check_chunk(
+    region: &mut Region<fs::File>,
+    cx: i32,
+    cz: i32,
+    options: &FsckOptions,
+    report: &mut FsckReport,
+) {
+    let region_pos = (cx >> 5, cz >> 5);
+    let mut issues = Vec::new();
+
+    // Read and validate the chunk structure, any failure here means that the chunk
+    // cannot be loaded and should be regenerated.
+    let res = match region.read_chunk(cx, cz) {
+        Ok(reader) => match nbt::from_reader(reader) {
+            Ok(root) => match crate::serde::chunk::from_nbt(&root) {
+                Ok(snapshot) => Ok((root, snapshot)),
+                Err(e) => Err(FsckIssueKind::NbtInvalid(e)),
+            },
+            Err(e) => Err(FsckIssueKind::NbtCorrupted(e)),
+        },
+        Err(e) => Err(FsckIssueKind::ChunkUnreadable(e)),
+    };
+
+    let (mut root, mut snapshot) = match res {
+        Ok(ret) => ret,
+        Err(kind) => {
+            let mut action = FsckAction::None;
+            if options.regenerate && region.remove_chunk(cx, cz).is_ok() {
+                action = FsckAction::Removed;
+            }
+            report.issues.push(FsckIssue {
+                region: region_pos,
+                chunk: Some((cx, cz)),
+                kind,
+                action,
+            });
+            return;
+        }
+    };
+
+    if (snapshot.cx, snapshot.cz) != (cx, cz) {
+        issues.push(FsckIssueKind::ChunkMisplaced {
+            cx: snapshot.cx,
+            cz: snapshot.cz,
+        });
+    }
+
+    let fixed = check_chunk_data(&mut snapshot, options, &mut issues);
+
+    let mut action = FsckAction::None;
+    if options.repair && fixed && write_chunk_data(region, cx, cz, &mut root, &snapshot).is_ok() {
+        action = FsckAction::Repaired;
+    }
+
+    for kind in issues {
+        // A misplaced chunk is never repaired, it's up to the user to decide.
+        let action = match kind {
+            FsckIssueKind::ChunkMisplaced { .. } => FsckAction::None,
+            _ => action,
+        };
+        report.issues.push(FsckIssue {
+            region: region_pos,
+            chunk: Some((cx, cz)),
+            kind,
+            action,
+        });
+    }
+}
+
+/// Check the height map and light data of a chunk, the data is fixed in the snapshot
+/// and true is returned if any fix has been made.
+fn check_chunk_data(
+    snapshot: &mut ChunkSnapshot,
+    options: &FsckOptions,
+    issues: &mut Vec<FsckIssueKind>,
+) -> bool {
+    let chunk = Arc::make_mut(&mut snapshot.chunk);
+
+    let mut height_columns = 0;
+    let mut sky_columns = 0;
+    let mut block_light_blocks = 0;
+
+    for x in 0..CHUNK_WIDTH as i32 {
+        for z in 0..CHUNK_WIDTH as i32 {
+            let column = IVec3::new(x, 0, z);
+
+            // Expected height is the block above the highest light-blocking block.
+            let mut height = CHUNK_HEIGHT as i32;
+            while height > 0 {
+                let (id, _) = chunk.get_block(IVec3::new(x, height - 1, z));
+                if block::material::get_light_opacity(id) != 0 {
+                    break;
+                }
+                height -= 1;
+            }
+
+            if chunk.get_height(column) as i32 != height {
+                height_columns += 1;
+                chunk.set_height(column, height as u8);
+            }
+
+            if options.check_sky_light {
+                let mut invalid = false;
+                for y in height..CHUNK_HEIGHT as i32 {
+                    let pos = IVec3::new(x, y, z);
+                    if chunk.get_sky_light(pos) != 15 {
+                        invalid = true;
+                        chunk.set_sky_light(pos, 15);
+                    }
+                }
+                if invalid {
+                    sky_columns += 1;
+                }
+            }
+
+            for y in 0..CHUNK_HEIGHT as i32 {
+                let pos = IVec3::new(x, y, z);
+                let (id, _) = chunk.get_block(pos);
+                let emission = block::material::get_light_emission(id);
+                if emission > chunk.get_block_light(pos) {
+                    block_light_blocks += 1;
+                    chunk.set_block_light(pos, emission);
+                }
+            }
+        }
+    }
+
+    if height_columns != 0 {
+        issues.push(FsckIssueKind::HeightMap {
+            columns: height_columns,
+        });
+    }
+
+    if sky_columns != 0 {
+        issues.push(FsckIssueKind::SkyLight {
+            columns: sky_columns,
+        });
+    }
+
+    if block_light_blocks != 0 {
+        issues.push(FsckIssueKind::BlockLight {
+            blocks: block_light_blocks,
+        });
+    }
+
+    height_columns != 0 || sky_columns != 0 || block_light_blocks != 0
+}
+
+/// Write back the fixed height map and light data of a chunk. The data is replaced in
+/// the original NBT in order to keep any other data untouched.
+fn write_chunk_data(
+    region: &mut Region<fs::File>,
+    cx: i32,
+    cz: i32,
+    root: &mut Nbt,
+    snapshot: &ChunkSnapshot,
+) -> Result<(), RegionError> {
+    let level = root
+        .as_compound_mut()
+        .and_then(|root| root.get_mut("Level"))
+        .and_then(Nbt::as_compound_mut)
+        .expect("chunk structure has been validated");
+
+    level.insert("HeightMap", snapshot.chunk.height.to_vec());
+    level.insert("SkyLight", snapshot.chunk.sky_light.inner.to_vec());
+    level.insert("BlockLight", snapshot.chunk.block_light.inner.to_vec());
+
+    let mut writer = region.write_chunk(cx, cz);
+    nbt::to_writer(&mut writer, root).map_err(|e| match e {
+        NbtError::Io(e) => RegionError::Io(e),
+        e => RegionError::Io(io::Error::other(e)),
+    })?;
+    writer.flush_chunk()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Write;
+
+    use super::*;
+    use crate::serde::region::RegionDir;
+
+    #[test]
+    fn fsck_repair() {
+        let dir = std::env::temp_dir().join(format!("mc173-fsck-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        {
+            let mut region_dir = RegionDir::new(&dir);
+            let region = region_dir.ensure_region(0, 0, true).unwrap();
+
+            // Valid chunk with a wrong height map on one column.
+            let mut snapshot = ChunkSnapshot::new(0, 0);
+            let chunk = Arc::get_mut(&mut snapshot.chunk).unwrap();
+            chunk.fill_light(IVec3::ZERO, IVec3::new(16, 128, 16), 0, 15);
+            chunk.set_block(IVec3::new(0, 10, 0), block::STONE, 0);
+            let mut writer = region.write_chunk(0, 0);
+            nbt::to_writer(&mut writer, &crate::serde::chunk::to_nbt(&snapshot)).unwrap();
+            writer.flush_chunk().unwrap();
+
+            // Corrupted chunk.
+            let mut writer = region.write_chunk(1, 0);
+            writer.write_all(b"garbage").unwrap();
+            writer.flush_chunk().unwrap();
+        }
+
+        let options = FsckOptions {
+            repair: true,
+            regenerate: true,
+            ..Default::default()
+        };
+
+        let report = fsck(&dir, &options).unwrap();
+        assert_eq!((report.regions, report.chunks), (1, 2));
+        assert_eq!(report.issues.len(), 2);
+        assert!(matches!(
+            report.issues[0],
+            FsckIssue {
+                chunk: Some((0, 0)),
+                kind: FsckIssueKind::HeightMap { columns: 1 },
+                action: FsckAction::Repaired,
+                ..
+            }
+        ));
+        assert!(matches!(
+            report.issues[1],
+            FsckIssue {
+                chunk: Some((1, 0)),
+                kind: FsckIssueKind::NbtCorrupted(_),
+                action: FsckAction::Removed,
+                ..
+            }
+        ));
+
+        let report = fsck(&dir, &options).unwrap();
+        assert_eq!(report.chunks, 1);
+        assert!(report.is_clean(), "{report}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
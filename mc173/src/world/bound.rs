@@ -7,7 +7,7 @@ use glam::{DVec3, IVec3};
 
 use crate::block;
 use crate::block_entity::BlockEntity;
-use crate::geom::{BoundingBox, Face};
+use crate::geom::{BoundingBox, Face, FaceSet};
 
 use super::World;
 
@@ -66,6 +66,46 @@ impl World {
         Some(bb)
     }
 
+    /// Get the horizontal faces where the fence at the given position connects to other
+    /// fences, the set is empty if the block is not a fence.
+    ///
+    /// PARITY: Fence connections are only visual in the Notchian implementation, fences
+    /// are always colliding with a full box of 1.5 block height, so this is not used
+    /// by colliding boxes and is intended for client-side hints or path finding.
+    pub fn get_fence_connections(&self, pos: IVec3) -> FaceSet {
+        if !self.is_block(pos, block::FENCE) {
+            return FaceSet::new();
+        }
+
+        Face::HORIZONTAL
+            .into_iter()
+            .filter(|face| self.is_block(pos + face.delta(), block::FENCE))
+            .collect()
+    }
+
+    /// Iterate the boxes of the fence shape at the given position, a center post with
+    /// arms toward connected fences, all 1.5 block high. See
+    /// [`get_fence_connections`](Self::get_fence_connections).
+    pub fn iter_fence_shape_boxes(&self, pos: IVec3) -> impl Iterator<Item = BoundingBox> {
+        let fence = self.is_block(pos, block::FENCE);
+        let connections = self.get_fence_connections(pos);
+        let offset = pos.as_dvec3();
+
+        let post = BoundingBox::new(0.375, 0.0, 0.375, 0.625, 1.5, 0.625);
+        let arms = Face::HORIZONTAL
+            .into_iter()
+            .filter(move |&face| connections.contains(face))
+            .map(|face| {
+                let arm = face.extrude(0.375, 0.375);
+                BoundingBox::new(arm.min.x, 0.0, arm.min.z, arm.max.x, 1.5, arm.max.z)
+            });
+
+        std::iter::once(post)
+            .filter(move |_| fence)
+            .chain(arms)
+            .map(move |bb| bb + offset)
+    }
+
     /// Get the exclusion box of a block, this function doesn't take the block metadata.
     ///
     /// PARITY: The Notchian implementation is terrible because it uses the colliding box
@@ -75,8 +115,8 @@ impl World {
     pub fn get_block_exclusion_box(&self, pos: IVec3, id: u8) -> Option<BoundingBox> {
         let bb = match id {
             block::BED => Face::NegY.extrude(0.0, 9.0 / 16.0),
-            block::CAKE => Face::NegY.extrude(PIXEL, 0.5),
-            block::CACTUS => Face::NegY.extrude(PIXEL, 1.0),
+            block::CAKE => Face::NegY.extrude(PIXEL, 0.5 - PIXEL),
+            block::CACTUS => Face::NegY.extrude(PIXEL, 1.0 - PIXEL),
             block::SOULSAND => Face::NegY.extrude(0.0, 1.0 - PIXEL_2),
             block::AIR
             | block::LEVER
@@ -130,6 +170,7 @@ impl World {
                 1.0 - PIXEL,
             ),
             block::CACTUS => Face::NegY.extrude(PIXEL, 1.0),
+            block::FARMLAND => Face::NegY.extrude(0.0, 1.0 - PIXEL),
             block::REDSTONE => Face::NegY.extrude(0.0, PIXEL),
             block::WOOD_DOOR | block::IRON_DOOR => {
                 block::door::get_actual_face(metadata).extrude(0.0, PIXEL_3)
@@ -151,22 +192,34 @@ impl World {
             }
             block::PISTON | block::STICKY_PISTON => {
                 if block::piston::is_base_extended(metadata) {
-                    block::piston::get_face(metadata)?.extrude(0.0, 12.0 / 16.0)
+                    block::piston::get_face(metadata)?
+                        .opposite()
+                        .extrude(0.0, 12.0 / 16.0)
                 } else {
                     BoundingBox::CUBE
                 }
             }
             block::PISTON_EXT => block::piston::get_face(metadata)?.extrude(0.0, 0.25),
             block::PISTON_MOVING => return None, // TODO: Use block entity.
-            block::PORTAL => return None,        // TODO: Use surrounding portals to determine
+            block::PORTAL => {
+                // The portal orientation is given by the surrounding portal blocks.
+                // REF: BlockPortal::setBlockBoundsBasedOnState
+                if self.is_block(pos - IVec3::X, block::PORTAL)
+                    || self.is_block(pos + IVec3::X, block::PORTAL)
+                {
+                    BoundingBox::new(0.0, 0.0, 0.375, 1.0, 1.0, 0.625)
+                } else {
+                    BoundingBox::new(0.375, 0.0, 0.0, 0.625, 1.0, 1.0)
+                }
+            }
             block::WOOD_PRESSURE_PLATE | block::STONE_PRESSURE_PLATE => {
                 Face::NegY.extrude(PIXEL, if metadata == 1 { PIXEL / 2.0 } else { PIXEL })
             }
             block::RAIL | block::POWERED_RAIL | block::DETECTOR_RAIL => {
-                // TODO: Use proper metadata functions when implementing rails.
+                let shape = block::rail::get_any_shape(id, metadata);
                 Face::NegY.extrude(
                     0.0,
-                    if (2..=5).contains(&metadata) {
+                    if shape.is_some_and(|shape| shape.is_ascending()) {
                         10.0 / 16.0
                     } else {
                         PIXEL_2
@@ -360,14 +413,16 @@ impl Iterator for BlockCollidingBoxesIter {
 
         let bb = match (index, self.id) {
             (0, block::CACTUS) => {
-                BoundingBox::new(PIXEL, 0.0, PIXEL, 1.0 - PIXEL, 1.0, 1.0 - PIXEL)
+                // Cactus collision is one pixel lower than its overlay, this is what
+                // allows damaging entities standing on it.
+                BoundingBox::new(PIXEL, 0.0, PIXEL, 1.0 - PIXEL, 1.0 - PIXEL, 1.0 - PIXEL)
             }
             (0, block::CAKE) => BoundingBox::new(
                 (1 + metadata * 2) as f64 / 16.0,
                 0.0,
                 PIXEL,
                 1.0 - PIXEL,
-                0.5,
+                0.5 - PIXEL,
                 1.0 - PIXEL,
             ),
             (0, block::FENCE) => BoundingBox::new(0.0, 0.0, 0.0, 1.0, 1.5, 1.0),
@@ -397,6 +452,15 @@ impl Iterator for BlockCollidingBoxesIter {
                     Face::NegY.extrude(0.0, PIXEL_3)
                 }
             }
+            (0, block::PISTON | block::STICKY_PISTON) => {
+                if block::piston::is_base_extended(metadata) {
+                    block::piston::get_face(metadata)?
+                        .opposite()
+                        .extrude(0.0, 12.0 / 16.0)
+                } else {
+                    BoundingBox::CUBE
+                }
+            }
             (0, block::PISTON_MOVING) => return None,
             (0, block::PISTON_EXT) => {
                 // The extension plate first.
@@ -481,3 +545,65 @@ pub struct RayTraceHit {
     /// The face of the block.
     pub face: Face,
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::world::Dimension;
+
+    #[test]
+    fn partial_block_boxes() {
+        let world = World::new(Dimension::Overworld);
+        let pos = IVec3::new(0, 10, 0);
+        let boxes = |id, metadata| {
+            world
+                .iter_block_colliding_boxes(pos, id, metadata)
+                .collect::<Vec<_>>()
+        };
+
+        // Stair facing positive X, with its high part on that side.
+        let stair = boxes(block::WOOD_STAIR, 0);
+        assert_eq!(stair.len(), 2);
+        assert_eq!(stair[1].min.x, 0.5);
+        assert_eq!(stair[1].max.y, 11.0);
+
+        // Snow collides only from 3 layers.
+        assert!(boxes(block::SNOW, 2).is_empty());
+        assert_eq!(boxes(block::SNOW, 3)[0].max.y, 10.5);
+
+        // Extended piston base facing up, its base is at the bottom.
+        let mut metadata = 0;
+        block::piston::set_face(&mut metadata, Face::PosY);
+        block::piston::set_base_extended(&mut metadata, true);
+        let piston = boxes(block::PISTON, metadata);
+        assert_eq!((piston[0].min.y, piston[0].max.y), (10.0, 10.75));
+
+        assert_eq!(boxes(block::CACTUS, 0)[0].max.y, 10.0 + 15.0 / 16.0);
+        assert_eq!(boxes(block::FENCE, 0)[0].max.y, 11.5);
+    }
+
+    #[test]
+    fn fence_connections() {
+        let world = World::builder()
+            .block(IVec3::new(5, 10, 5), block::FENCE, 0)
+            .block(IVec3::new(6, 10, 5), block::FENCE, 0)
+            .block(IVec3::new(5, 10, 4), block::STONE, 0)
+            .build();
+
+        let connections = world.get_fence_connections(IVec3::new(5, 10, 5));
+        assert_eq!(connections.len(), 1);
+        assert!(connections.contains(Face::PosX));
+
+        let shape = world
+            .iter_fence_shape_boxes(IVec3::new(5, 10, 5))
+            .collect::<Vec<_>>();
+        assert_eq!(shape.len(), 2);
+        assert_eq!(shape[1].max.x, 6.0);
+        assert_eq!(shape[1].min.z, 5.375);
+        assert_eq!(
+            world.iter_fence_shape_boxes(IVec3::new(5, 10, 4)).count(),
+            0
+        );
+    }
+}
@@ -6,7 +6,7 @@ use glam::IVec3;
 use crate::block::material::Material;
 use crate::{block, item};
 
-use super::World;
+use super::{BlockEvent, Event, World};
 
 /// Methods related to block breaking.
 impl World {
@@ -14,11 +14,39 @@ impl World {
     /// if the chunk/pos was not valid. It also notifies blocks around, this is basically
     /// a wrapper around [`set_block_notify`](Self::set_block_notify) method.
     pub fn break_block(&mut self, pos: IVec3) -> Option<(u8, u8)> {
+        self.break_block_by(pos, None)
+    }
+
+    /// Same as [`break_block`](Self::break_block), but the block is broken by the given
+    /// entity, which is attached to the pushed break event.
+    pub fn break_block_by(&mut self, pos: IVec3, origin_id: Option<u32>) -> Option<(u8, u8)> {
         let (prev_id, prev_metadata) = self.set_block_notify(pos, block::AIR, 0)?;
+        self.push_block_break(pos, prev_id, prev_metadata, origin_id);
         self.spawn_block_loot(pos, prev_id, prev_metadata, 1.0);
         Some((prev_id, prev_metadata))
     }
 
+    /// Push a block break event at the given position for the given previous block, this
+    /// is ignored if the previous block is air.
+    pub(super) fn push_block_break(
+        &mut self,
+        pos: IVec3,
+        id: u8,
+        metadata: u8,
+        origin_id: Option<u32>,
+    ) {
+        if id != block::AIR {
+            self.push_event(Event::Block {
+                pos,
+                inner: BlockEvent::Break {
+                    id,
+                    metadata,
+                    origin_id,
+                },
+            });
+        }
+    }
+
     /// Get the minimum ticks duration required to break the block given its id.
     pub fn get_break_duration(
         &self,
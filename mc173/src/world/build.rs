@@ -0,0 +1,238 @@
+//! Declarative construction of small worlds, used by unit tests of physics, path
+//! finding, redstone and interactions.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use glam::IVec3;
+
+use crate::chunk::{calc_chunk_pos_unchecked, calc_entity_chunk_pos, Chunk, CHUNK_HEIGHT};
+use crate::entity::Entity;
+
+use super::{Dimension, World};
+
+/// A builder for a world with empty chunks where blocks are placed and entities are
+/// spawned in order, created with [`World::builder`]. Chunks covering all placed
+/// blocks and spawned entities are created, in addition to the chunk at 0/0.
+pub struct WorldBuilder {
+    /// The dimension of the world.
+    dimension: Dimension,
+    /// All the chunks built so far.
+    chunks: HashMap<(i32, i32), Arc<Chunk>>,
+    /// Entities to spawn, with true if they should be set as player entities.
+    entities: Vec<(Box<Entity>, bool)>,
+    /// True if light should be fully propagated when building.
+    light: bool,
+    /// True if the events queue should be enabled after building.
+    events: bool,
+}
+
+impl World {
+    /// Create a builder for a small world, see [`WorldBuilder`].
+    pub fn builder() -> WorldBuilder {
+        WorldBuilder {
+            dimension: Dimension::Overworld,
+            chunks: HashMap::from([((0, 0), Chunk::new())]),
+            entities: Vec::new(),
+            light: false,
+            events: false,
+        }
+    }
+}
+
+impl WorldBuilder {
+    /// Set the dimension of the world, overworld by default.
+    pub fn dimension(mut self, dimension: Dimension) -> Self {
+        self.dimension = dimension;
+        self
+    }
+
+    /// Ensure that all chunks in the given inclusive range are created.
+    pub fn chunks(mut self, min_cx: i32, min_cz: i32, max_cx: i32, max_cz: i32) -> Self {
+        for cx in min_cx..=max_cx {
+            for cz in min_cz..=max_cz {
+                self.chunks.entry((cx, cz)).or_insert_with(Chunk::new);
+            }
+        }
+        self
+    }
+
+    /// Fill all blocks between the two given inclusive positions.
+    pub fn fill(mut self, min: IVec3, max: IVec3, id: u8, metadata: u8) -> Self {
+        let min_pos = min.min(max).max(IVec3::new(i32::MIN, 0, i32::MIN));
+        let max_pos = min
+            .max(max)
+            .min(IVec3::new(i32::MAX, CHUNK_HEIGHT as i32 - 1, i32::MAX));
+        if min_pos.y > max_pos.y {
+            return self;
+        }
+
+        let (min_cx, min_cz) = calc_chunk_pos_unchecked(min_pos);
+        let (max_cx, max_cz) = calc_chunk_pos_unchecked(max_pos);
+        for cx in min_cx..=max_cx {
+            for cz in min_cz..=max_cz {
+                let chunk_min = IVec3::new(cx * 16, 0, cz * 16);
+                let from = min_pos.max(chunk_min);
+                let to = max_pos.min(chunk_min + IVec3::new(15, 127, 15));
+                let chunk = self.chunks.entry((cx, cz)).or_insert_with(Chunk::new);
+                Arc::get_mut(chunk)
+                    .unwrap()
+                    .fill_block(from, to - from + IVec3::ONE, id, metadata);
+            }
+        }
+
+        self
+    }
+
+    /// Set a single block at the given position.
+    pub fn block(self, pos: IVec3, id: u8, metadata: u8) -> Self {
+        self.fill(pos, pos, id, metadata)
+    }
+
+    /// Fill the whole width of all chunks created so far with the given block, from
+    /// the bottom of the world up to the given height (excluded). This should be called
+    /// after the chunk range is known, chunks created later are not filled.
+    pub fn platform(mut self, height: i32, id: u8, metadata: u8) -> Self {
+        let chunks = self.chunks.keys().copied().collect::<Vec<_>>();
+        for (cx, cz) in chunks {
+            self = self.fill(
+                IVec3::new(cx * 16, 0, cz * 16),
+                IVec3::new(cx * 16 + 15, height - 1, cz * 16 + 15),
+                id,
+                metadata,
+            );
+        }
+        self
+    }
+
+    /// Build a vertical wall between two positions at its base, of the given height.
+    /// The wall is one block thick if both positions share the same X or Z.
+    pub fn wall(self, from: IVec3, to: IVec3, height: i32, id: u8, metadata: u8) -> Self {
+        let top = from.y.max(to.y) + height - 1;
+        self.fill(from, IVec3::new(to.x, top, to.z), id, metadata)
+    }
+
+    /// Spawn the given entity after all blocks are placed.
+    pub fn entity(mut self, entity: impl Into<Box<Entity>>) -> Self {
+        let entity = entity.into();
+        let (cx, cz) = calc_entity_chunk_pos(entity.0.pos);
+        self.chunks.entry((cx, cz)).or_insert_with(Chunk::new);
+        self.entities.push((entity, false));
+        self
+    }
+
+    /// Spawn the given entity after all blocks are placed, and set it as a player
+    /// entity.
+    pub fn player(mut self, entity: impl Into<Box<Entity>>) -> Self {
+        self = self.entity(entity);
+        self.entities.last_mut().unwrap().1 = true;
+        self
+    }
+
+    /// Fully propagate block and sky light when building, light is not propagated by
+    /// default and sky light is maximum everywhere.
+    pub fn light(mut self) -> Self {
+        self.light = true;
+        self
+    }
+
+    /// Enable the events queue of the world after building, so that events caused by
+    /// the construction are not present in the queue.
+    pub fn events(mut self) -> Self {
+        self.events = true;
+        self
+    }
+
+    /// Build the world.
+    pub fn build(self) -> World {
+        self.build_with_entities().0
+    }
+
+    /// Build the world and return the ids of the spawned entities, in spawn order.
+    pub fn build_with_entities(self) -> (World, Vec<u32>) {
+        let mut world = World::new(self.dimension);
+
+        for ((cx, cz), mut chunk) in self.chunks {
+            Arc::get_mut(&mut chunk).unwrap().recompute_all_height();
+            world.set_chunk(cx, cz, chunk);
+            if self.light {
+                world.rebuild_light(cx, cz);
+            }
+        }
+
+        while world.get_light_update_count() != 0 {
+            world.tick_light(usize::MAX);
+        }
+
+        let ids = self
+            .entities
+            .into_iter()
+            .map(|(entity, player)| {
+                let id = world.spawn_entity(entity);
+                if player {
+                    world.set_player_entity(id, true);
+                }
+                id
+            })
+            .collect();
+
+        if self.events {
+            world.swap_events(Some(Vec::new()));
+        }
+
+        (world, ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use glam::DVec3;
+
+    use super::*;
+    use crate::block;
+    use crate::entity::Item;
+
+    #[test]
+    fn build_world() {
+        let (world, ids) = World::builder()
+            .chunks(-1, 0, 0, 0)
+            .platform(10, block::STONE, 0)
+            .wall(
+                IVec3::new(-4, 10, 2),
+                IVec3::new(4, 10, 2),
+                3,
+                block::BRICK,
+                0,
+            )
+            .block(IVec3::new(0, 10, 0), block::TORCH, 0)
+            .entity(Item::new_with(|base, _| {
+                base.pos = DVec3::new(20.5, 10.0, 0.5)
+            }))
+            .light()
+            .build_with_entities();
+
+        assert_eq!(
+            world.get_block(IVec3::new(-16, 9, 15)),
+            Some((block::STONE, 0))
+        );
+        assert_eq!(
+            world.get_block(IVec3::new(15, 10, 15)),
+            Some((block::AIR, 0))
+        );
+        assert_eq!(
+            world.get_block(IVec3::new(-4, 12, 2)),
+            Some((block::BRICK, 0))
+        );
+        assert_eq!(world.get_block(IVec3::new(4, 13, 2)), Some((block::AIR, 0)));
+        assert_eq!(world.get_height(IVec3::new(0, 0, 2)), Some(13));
+
+        // The entity's chunk is created, but not filled by the platform.
+        assert!(world.contains_chunk(1, 0));
+        assert_eq!(world.get_block(IVec3::new(20, 9, 0)), Some((block::AIR, 0)));
+        assert!(world.contains_entity(ids[0]));
+
+        assert_eq!(world.get_light(IVec3::new(1, 10, 0)).block, 13);
+        assert_eq!(world.get_light(IVec3::new(0, 8, 0)).sky, 0);
+    }
+}
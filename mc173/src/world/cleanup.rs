@@ -0,0 +1,114 @@
+//! Removal of item entities, either when too old or periodically by a cleanup sweep.
+
+use crate::entity::{BaseKind, Entity};
+
+use super::{Event, World};
+
+/// Default lifetime of item entities, in ticks (5 minutes).
+pub const DEFAULT_ITEM_LIFETIME: u32 = 6000;
+
+/// The time before a cleanup sweep when the [`Event::ItemCleanup`] warning is pushed,
+/// in ticks (30 seconds), no warning is pushed if the interval is shorter.
+pub const ITEM_CLEANUP_WARNING_TIME: u64 = 600;
+
+/// Methods related to the removal of item entities.
+impl World {
+    /// Get the lifetime of item entities, in ticks, items are removed when older.
+    #[inline]
+    pub fn get_item_lifetime(&self) -> u32 {
+        self.item_lifetime
+    }
+
+    /// Set the lifetime of item entities, in ticks, this is 6000 ticks by default.
+    pub fn set_item_lifetime(&mut self, lifetime: u32) {
+        self.item_lifetime = lifetime;
+    }
+
+    /// Get the interval between two cleanup sweeps of all item entities, in ticks.
+    #[inline]
+    pub fn get_item_cleanup_interval(&self) -> Option<u64> {
+        self.item_cleanup_interval
+    }
+
+    /// Set the interval between two cleanup sweeps removing all item entities, in ticks,
+    /// disabled by default. An [`Event::ItemCleanup`] warning is pushed before each
+    /// sweep, see [`ITEM_CLEANUP_WARNING_TIME`].
+    pub fn set_item_cleanup_interval(&mut self, interval: Option<u64>) {
+        self.item_cleanup_interval = interval.filter(|&interval| interval != 0);
+    }
+
+    /// Remove all item entities of the world, returning the number of removed items.
+    pub fn remove_items(&mut self) -> usize {
+        let ids = self
+            .iter_entities()
+            .filter(|(_, Entity(_, kind))| matches!(kind, BaseKind::Item(_)))
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+
+        for &id in &ids {
+            self.remove_entity(id, "item cleanup");
+        }
+
+        ids.len()
+    }
+
+    /// Internal function to run the cleanup sweep if it's time to, or to warn about it.
+    pub(super) fn tick_item_cleanup(&mut self) {
+        let Some(interval) = self.item_cleanup_interval else {
+            return;
+        };
+
+        if self.time.is_multiple_of(interval) {
+            self.remove_items();
+        } else if interval > ITEM_CLEANUP_WARNING_TIME
+            && (self.time + ITEM_CLEANUP_WARNING_TIME).is_multiple_of(interval)
+        {
+            self.push_event(Event::ItemCleanup {
+                delay: ITEM_CLEANUP_WARNING_TIME,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use glam::DVec3;
+
+    use super::*;
+    use crate::entity::Item;
+    use crate::item::{self, ItemStack};
+
+    #[test]
+    fn item_cleanup() {
+        let mut world = World::builder().events().build();
+        world.set_item_lifetime(20);
+        world.set_item_cleanup_interval(Some(1000));
+
+        let spawn_item = |world: &mut World| {
+            world.spawn_entity(Item::new_with(|base, item| {
+                base.pos = DVec3::new(4.5, 10.0, 4.5);
+                item.stack = ItemStack::new_single(item::DIAMOND, 0);
+            }))
+        };
+
+        // Items are removed when older than their lifetime.
+        let id = spawn_item(&mut world);
+        for _ in 0..21 {
+            world.tick();
+        }
+        assert!(!world.contains_entity(id));
+
+        // Items are all removed by the sweep, after a warning.
+        let id = spawn_item(&mut world);
+        world.set_item_lifetime(DEFAULT_ITEM_LIFETIME);
+        while world.get_time() < 1000 {
+            world.tick();
+        }
+        assert!(!world.contains_entity(id));
+        assert!(world
+            .swap_events(None)
+            .unwrap()
+            .contains(&Event::ItemCleanup { delay: 600 }));
+    }
+}
@@ -0,0 +1,112 @@
+//! Swept collision of bounding boxes against blocks, for fast moving entities.
+
+use glam::{DVec3, IVec3};
+
+use crate::geom::{BoundingBox, Face};
+
+use super::World;
+
+/// Methods related to swept collisions.
+impl World {
+    /// Sweep the given bounding box by the given delta and return the first block box
+    /// that is hit on the way. Unlike checking colliding boxes at the final position,
+    /// this cannot tunnel through thin walls when the delta is large, it is intended
+    /// for projectiles and other fast moving entities. Boxes already intersecting the
+    /// given bounding box are ignored, and unloaded chunks are not colliding.
+    pub fn sweep(&self, bb: BoundingBox, delta: DVec3) -> SweepResult {
+        let mut time = 1.0;
+        let mut hit = None;
+
+        for (pos, block, metadata) in self.iter_blocks_in_box(bb.expand(delta)) {
+            for block_bb in self.iter_block_colliding_boxes(pos, block, metadata) {
+                if let Some((block_time, face)) = block_bb.calc_sweep(bb, delta) {
+                    if block_time < time || hit.is_none() {
+                        time = block_time;
+                        hit = Some(SweepHit {
+                            pos,
+                            block,
+                            metadata,
+                            face,
+                        });
+                    }
+                }
+            }
+        }
+
+        SweepResult {
+            delta: delta * time,
+            time,
+            hit,
+        }
+    }
+}
+
+/// Result of a sweep, see [`World::sweep`].
+#[derive(Debug, Clone)]
+pub struct SweepResult {
+    /// The delta that can be applied to the bounding box without colliding.
+    pub delta: DVec3,
+    /// The fraction of the original delta that is applied, in 0..=1.
+    pub time: f64,
+    /// The block hit by the bounding box, if any.
+    pub hit: Option<SweepHit>,
+}
+
+/// A block hit by a sweep.
+#[derive(Debug, Clone)]
+pub struct SweepHit {
+    /// The position of the block.
+    pub pos: IVec3,
+    /// The block.
+    pub block: u8,
+    /// The block metadata.
+    pub metadata: u8,
+    /// The face of the block that has been hit.
+    pub face: Face,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::block;
+
+    #[test]
+    fn sweep_thin_wall() {
+        let world = World::builder()
+            .platform(10, block::STONE, 0)
+            .wall(
+                IVec3::new(8, 10, 0),
+                IVec3::new(8, 10, 15),
+                4,
+                block::OBSIDIAN,
+                0,
+            )
+            .build();
+
+        // A box moving 10 blocks in a single tick would end up past the wall.
+        let bb = BoundingBox::new(2.0, 11.0, 4.0, 2.5, 11.5, 4.5);
+        let delta = DVec3::new(10.0, 0.0, 0.0);
+        assert!(world
+            .iter_blocks_boxes_colliding(bb + delta)
+            .next()
+            .is_none());
+
+        let res = world.sweep(bb, delta);
+        let hit = res.hit.unwrap();
+        assert_eq!(hit.pos, IVec3::new(8, 11, 4));
+        assert_eq!(hit.block, block::OBSIDIAN);
+        assert_eq!(hit.face, Face::NegX);
+        assert_eq!(res.delta, DVec3::new(5.5, 0.0, 0.0));
+
+        // Moving along the wall or the platform doesn't hit.
+        let res = world.sweep(bb, DVec3::new(0.0, 0.0, 10.0));
+        assert!(res.hit.is_none());
+        assert_eq!(res.time, 1.0);
+
+        let bb = BoundingBox::new(2.0, 10.0, 4.0, 2.5, 10.5, 4.5);
+        let res = world.sweep(bb, DVec3::new(4.0, -2.0, 0.0));
+        assert_eq!(res.hit.unwrap().face, Face::PosY);
+        assert_eq!(res.delta, DVec3::ZERO);
+    }
+}
@@ -0,0 +1,281 @@
+//! Dimensions of worlds and their registration.
+//!
+//! Each world is in a single dimension, the [`DimensionInfo`] of a dimension describes
+//! its ambient: sky, day cycle and ambient light. Servers can register the dimensions
+//! they host in a [`DimensionRegistry`], with a chunk generator and a portal target, in
+//! order to offer custom dimensions beyond the overworld and the nether.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::gen::ChunkGenerator;
+use crate::serde::remap::IdRemap;
+use crate::storage::ChunkStorage;
+
+use super::{Light, World};
+
+/// Types of dimensions, used for ambient effects in the world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    /// The overworld dimension with a blue sky and day cycles.
+    Overworld,
+    /// The creepy nether dimension.
+    Nether,
+    /// A custom dimension with its id, it should be neither 0 nor -1.
+    Custom(i8),
+}
+
+impl Dimension {
+    /// Get a dimension from its id.
+    pub fn from_id(id: i8) -> Self {
+        match id {
+            0 => Dimension::Overworld,
+            -1 => Dimension::Nether,
+            id => Dimension::Custom(id),
+        }
+    }
+
+    /// Get the id of this dimension.
+    pub fn id(self) -> i8 {
+        match self {
+            Dimension::Overworld => 0,
+            Dimension::Nether => -1,
+            Dimension::Custom(id) => id,
+        }
+    }
+
+    /// Get the default properties of this dimension, custom dimensions have the
+    /// properties of the overworld by default.
+    pub fn info(self) -> DimensionInfo {
+        match self {
+            Dimension::Overworld => DimensionInfo::OVERWORLD,
+            Dimension::Nether => DimensionInfo::NETHER,
+            Dimension::Custom(id) => DimensionInfo {
+                id,
+                ..DimensionInfo::OVERWORLD
+            },
+        }
+    }
+}
+
+/// Properties of a dimension, consulted for sky light, weather and ambient light, and
+/// sent to clients.
+///
+/// REF: WorldProvider
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DimensionInfo {
+    /// The dimension id, as sent in login and respawn packets.
+    pub id: i8,
+    /// True if the dimension has a sky, this enables weather.
+    pub has_sky: bool,
+    /// If set, the celestial angle is fixed and there is no day cycle.
+    pub fixed_celestial_angle: Option<f32>,
+    /// The minimum brightness of blocks, even in complete darkness.
+    pub ambient_light: f32,
+    /// The height of clouds, only used by clients.
+    pub cloud_height: f32,
+    /// True if players can respawn in this dimension, if not they should respawn in the
+    /// overworld.
+    pub can_respawn: bool,
+    /// True if the dimension is hot, where lava flows faster.
+    pub hot: bool,
+}
+
+impl DimensionInfo {
+    /// Properties of the overworld.
+    pub const OVERWORLD: Self = Self {
+        id: 0,
+        has_sky: true,
+        fixed_celestial_angle: None,
+        ambient_light: 0.05,
+        cloud_height: 108.0,
+        can_respawn: true,
+        hot: false,
+    };
+
+    /// Properties of the nether.
+    ///
+    /// REF: WorldProviderHell
+    pub const NETHER: Self = Self {
+        id: -1,
+        has_sky: false,
+        fixed_celestial_angle: Some(0.5),
+        ambient_light: 0.1,
+        cloud_height: 108.0,
+        can_respawn: false,
+        hot: true,
+    };
+
+    /// Calculate the brightness of a block from its light levels in this dimension.
+    #[inline]
+    pub fn brightness(&self, light: Light) -> f32 {
+        light.brightness_with(self.ambient_light)
+    }
+}
+
+/// A registry of dimensions with their properties, chunk generator and portal target.
+#[derive(Default)]
+pub struct DimensionRegistry {
+    /// All registered dimensions, in registration order.
+    entries: Vec<DimensionEntry>,
+}
+
+/// A dimension registered in a [`DimensionRegistry`].
+pub struct DimensionEntry {
+    /// The registered dimension.
+    dimension: Dimension,
+    /// Properties of the dimension.
+    info: DimensionInfo,
+    /// The dimension where nether portals of this dimension lead to.
+    portal_target: Option<Dimension>,
+    /// Function creating a chunk storage backed by the dimension's chunk generator.
+    storage: Box<dyn Fn(PathBuf, usize, Option<IdRemap>) -> ChunkStorage + Send + Sync>,
+}
+
+impl DimensionRegistry {
+    /// Create a new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a dimension with the given properties, the dimension is identified by
+    /// the id of its properties and it uses the given generator for its chunks. By
+    /// default, the overworld and the nether have each other as portal target, custom
+    /// dimensions have no portal target. None is returned if the id is already taken.
+    pub fn register<G>(&mut self, info: DimensionInfo, generator: G) -> Option<&mut DimensionEntry>
+    where
+        G: ChunkGenerator + Sync + Send + 'static,
+    {
+        let dimension = Dimension::from_id(info.id);
+        if self.get(dimension).is_some() {
+            return None;
+        }
+
+        let generator = Arc::new(generator);
+        self.entries.push(DimensionEntry {
+            dimension,
+            info,
+            portal_target: match dimension {
+                Dimension::Overworld => Some(Dimension::Nether),
+                Dimension::Nether => Some(Dimension::Overworld),
+                Dimension::Custom(_) => None,
+            },
+            storage: Box::new(move |region_dir, terrain_workers, remap| {
+                ChunkStorage::with_remap(region_dir, Arc::clone(&generator), terrain_workers, remap)
+            }),
+        });
+
+        self.entries.last_mut()
+    }
+
+    /// Get a registered dimension.
+    pub fn get(&self, dimension: Dimension) -> Option<&DimensionEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.dimension == dimension)
+    }
+
+    /// Get a registered dimension, mutably.
+    pub fn get_mut(&mut self, dimension: Dimension) -> Option<&mut DimensionEntry> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.dimension == dimension)
+    }
+
+    /// Iterate over all registered dimensions, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &DimensionEntry> + '_ {
+        self.entries.iter()
+    }
+
+    /// Get the dimension where nether portals of the given dimension lead to, only if
+    /// the target dimension is also registered.
+    pub fn get_portal_target(&self, dimension: Dimension) -> Option<Dimension> {
+        self.get(dimension)?
+            .portal_target
+            .filter(|&target| self.get(target).is_some())
+    }
+}
+
+impl DimensionEntry {
+    /// Get the registered dimension.
+    #[inline]
+    pub fn get_dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    /// Get the properties of the registered dimension.
+    #[inline]
+    pub fn get_info(&self) -> &DimensionInfo {
+        &self.info
+    }
+
+    /// Get the dimension where nether portals of this dimension lead to, if any.
+    #[inline]
+    pub fn get_portal_target(&self) -> Option<Dimension> {
+        self.portal_target
+    }
+
+    /// Set the dimension where nether portals of this dimension lead to, none to
+    /// disable portals.
+    pub fn set_portal_target(&mut self, target: Option<Dimension>) -> &mut Self {
+        self.portal_target = target;
+        self
+    }
+
+    /// Create a new empty world of this dimension, with its properties.
+    pub fn new_world(&self) -> World {
+        let mut world = World::new(self.dimension);
+        world.set_dimension_info(self.info);
+        world
+    }
+
+    /// Create a new chunk storage for this dimension, backed by its chunk generator,
+    /// see [`ChunkStorage::with_remap`].
+    pub fn new_storage(
+        &self,
+        region_dir: impl Into<PathBuf>,
+        terrain_workers: usize,
+        remap: Option<IdRemap>,
+    ) -> ChunkStorage {
+        (self.storage)(region_dir.into(), terrain_workers, remap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::gen::OverworldGenerator;
+
+    #[test]
+    fn register_dimensions() {
+        let mut registry = DimensionRegistry::new();
+        assert!(registry
+            .register(DimensionInfo::OVERWORLD, OverworldGenerator::new(0))
+            .is_some());
+        assert!(registry
+            .register(DimensionInfo::OVERWORLD, OverworldGenerator::new(0))
+            .is_none());
+
+        // The nether is not registered, so the overworld has no portal target.
+        assert_eq!(registry.get_portal_target(Dimension::Overworld), None);
+
+        let mirror = DimensionInfo {
+            id: 1,
+            ..DimensionInfo::OVERWORLD
+        };
+        registry
+            .register(mirror, OverworldGenerator::new(1))
+            .unwrap()
+            .set_portal_target(Some(Dimension::Overworld));
+
+        assert_eq!(
+            registry.get_portal_target(Dimension::Custom(1)),
+            Some(Dimension::Overworld)
+        );
+
+        let world = registry.get(Dimension::Custom(1)).unwrap().new_world();
+        assert_eq!(world.get_dimension(), Dimension::Custom(1));
+        assert_eq!(world.get_dimension_info().id, 1);
+    }
+}
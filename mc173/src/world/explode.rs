@@ -1,6 +1,7 @@
 //! Make explosion in world.
 
 use glam::{DVec3, IVec3};
+use indexmap::IndexSet;
 
 use tracing::trace;
 
@@ -8,26 +9,69 @@ use crate::geom::BoundingBox;
 use crate::rand::JavaRandom;
 
 use crate::block;
-use crate::entity::{Entity, Hurt};
+use crate::entity::{Entity, Hurt, Tnt};
 use crate::world::bound::RayTraceKind;
 use crate::world::Event;
 
 use super::World;
 
+/// The default fuse time of primed TNT, in ticks (4 seconds).
+pub const TNT_FUSE_TIME: u32 = 80;
+
 /// Methods related to explosions.
 impl World {
     /// Make an explosion in the world at the given position and size. The explosion can
     /// optionally propagate flames around.
     pub fn explode(&mut self, center: DVec3, radius: f32, set_fire: bool, origin_id: Option<u32>) {
+        self.explode_inner(center, radius, set_fire, true, origin_id);
+    }
+
+    /// Make an explosion caused by a mob, like [`explode`](Self::explode) but blocks are
+    /// only destroyed, and flames only propagated, if mob griefing is enabled, see
+    /// [`is_mob_griefing`](Self::is_mob_griefing).
+    pub fn explode_by_mob(
+        &mut self,
+        center: DVec3,
+        radius: f32,
+        set_fire: bool,
+        origin_id: Option<u32>,
+    ) {
+        let destroy = self.mob_griefing;
+        self.explode_inner(center, radius, set_fire && destroy, destroy, origin_id);
+    }
+
+    /// Spawn a primed TNT entity centered on the given block position, with the given
+    /// fuse time in ticks, the TNT block is not removed by this function. The entity id
+    /// is returned.
+    ///
+    /// REF: EntityTNTPrimed::EntityTNTPrimed
+    pub fn prime_tnt(&mut self, pos: IVec3, fuse_time: u32) -> u32 {
+        let angle = self.rand.next_double() * std::f64::consts::TAU;
+        self.spawn_entity(Tnt::new_with(|base, tnt| {
+            base.pos = pos.as_dvec3() + 0.5;
+            base.vel = DVec3::new(-angle.sin() * 0.02, 0.2, -angle.cos() * 0.02);
+            tnt.fuse_time = fuse_time;
+        }))
+    }
+
+    /// Internal function to make an explosion, optionally without destroying blocks.
+    fn explode_inner(
+        &mut self,
+        center: DVec3,
+        radius: f32,
+        set_fire: bool,
+        destroy: bool,
+        origin_id: Option<u32>,
+    ) {
         /// This is the step to advance each explosion ray.
         const STEP: f32 = 0.3;
 
         trace!("explode, center: {center}, radius: {radius}, set fire: {set_fire}, origin id: {origin_id:?}");
 
         let mut rand = JavaRandom::new_seeded();
-        let mut affected_pos = Vec::new();
+        let mut affected_pos = IndexSet::new();
 
-        // Start by computing each destroyed block.
+        // Start by computing each affected block, the set is ordered like Notchian.
         for dx in 0..16 {
             for dy in 0..16 {
                 for dz in 0..16 {
@@ -53,15 +97,7 @@ impl World {
                             intensity -=
                                 (block::material::get_explosion_resistance(block) + 0.3) * STEP;
                             if intensity > 0.0 {
-                                if set_fire
-                                    && block == block::AIR
-                                    && self.is_block_opaque_cube(block_pos - IVec3::Y)
-                                    && rand.next_int_bounded(3) == 0
-                                {
-                                    self.set_block_notify(block_pos, block::FIRE, 0);
-                                }
-
-                                affected_pos.push((block_pos, block != block::AIR));
+                                affected_pos.insert(block_pos);
                             }
 
                             check_pos += dir;
@@ -88,10 +124,11 @@ impl World {
             let dist_norm = dist as f32 / radius;
 
             if dist_norm <= 1.0 {
-                let dir = delta / dist;
+                let dir = delta.normalize_or_zero();
 
                 // The goal here is to compute how many rays starting from every point in
-                // the entity bounding box we reach the explosion center. The more
+                // the entity bounding box we reach the explosion center. The more rays
+                // reach it, the more the entity is exposed to the explosion.
                 let ray = collided_base.bb.min - center;
                 let step = 1.0 / (collided_base.bb.size() * 2.0 + 1.0);
 
@@ -136,16 +173,204 @@ impl World {
             base.vel += accel;
         }
 
-        // Finally drain the destroyed pos and remove blocks.
-        for (pos, should_destroy) in affected_pos {
-            if should_destroy {
+        // Then remove affected blocks, in reverse order like Notchian.
+        // REF: Explosion::doExplosionB
+        for &pos in affected_pos.iter().rev() {
+            if destroy && !self.is_block_air(pos) {
                 // We can unwrap because these position were previously checked.
                 let (prev_block, prev_metadata) =
                     self.set_block_notify(pos, block::AIR, 0).unwrap();
+                self.push_block_break(pos, prev_block, prev_metadata, None);
                 self.spawn_block_loot(pos, prev_block, prev_metadata, 0.3);
+
+                // TNT blocks are primed with a shorter random fuse, for chain reactions.
+                // REF: BlockTNT::onBlockDestroyedByExplosion
+                if prev_block == block::TNT {
+                    let fuse_time = self.rand.next_int_bounded(TNT_FUSE_TIME as i32 / 4) as u32
+                        + TNT_FUSE_TIME / 8;
+                    self.prime_tnt(pos, fuse_time);
+                }
+            }
+        }
+
+        // Finally propagate flames on affected air blocks, including destroyed ones.
+        if set_fire {
+            for &pos in affected_pos.iter().rev() {
+                if self.is_block_air(pos)
+                    && self.is_block_opaque_cube(pos - IVec3::Y)
+                    && rand.next_int_bounded(3) == 0
+                {
+                    self.set_block_notify(pos, block::FIRE, 0);
+                }
             }
         }
 
         self.push_event(Event::Explode { center, radius });
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::entity::{BaseKind, Creeper, Fireball, Human, Hurt, LivingKind, Pig};
+
+    #[test]
+    fn explosion_exposure() {
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .wall(
+                IVec3::new(10, 10, 0),
+                IVec3::new(10, 10, 15),
+                4,
+                block::OBSIDIAN,
+                0,
+            )
+            .entity(Pig::new_default(DVec3::new(6.5, 10.0, 8.5)))
+            .entity(Pig::new_default(DVec3::new(11.5, 10.0, 8.5)))
+            .build_with_entities();
+
+        world.explode(DVec3::new(8.5, 10.5, 8.5), 4.0, false, None);
+
+        // The exposed pig is damaged and pushed away from the explosion.
+        let Entity(base, _) = world.get_entity(ids[0]).unwrap();
+        assert!(base.hurt[0].damage > 1);
+        assert!(base.vel.x < 0.0);
+
+        // The pig behind the wall only takes the minimum damage.
+        let Entity(base, _) = world.get_entity(ids[1]).unwrap();
+        assert_eq!(base.hurt[0].damage, 1);
+        assert_eq!(base.vel, DVec3::ZERO);
+
+        for z in 0..16 {
+            assert!(world.is_block(IVec3::new(10, 10, z), block::OBSIDIAN));
+        }
+    }
+
+    #[test]
+    fn tnt_chain() {
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .block(IVec3::new(8, 10, 8), block::TNT, 0)
+            .block(IVec3::new(12, 10, 8), block::TNT, 0)
+            .entity(Tnt::new_with(|base, tnt| {
+                base.pos = DVec3::new(10.5, 10.5, 8.5);
+                tnt.fuse_time = 1;
+            }))
+            .build_with_entities();
+
+        world.tick();
+        assert!(world.contains_entity(ids[0]));
+        world.tick();
+        assert!(!world.contains_entity(ids[0]));
+
+        // Both TNT blocks are primed with a shorter fuse.
+        assert!(world.is_block_air(IVec3::new(8, 10, 8)));
+        assert!(world.is_block_air(IVec3::new(12, 10, 8)));
+        let fuse_times = |world: &World| {
+            world
+                .iter_entities()
+                .filter_map(|(_, entity)| match entity {
+                    Entity(_, BaseKind::Tnt(tnt)) => Some(tnt.fuse_time),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let chain_fuse_times = fuse_times(&world);
+        assert_eq!(chain_fuse_times.len(), 2);
+        assert!(chain_fuse_times
+            .iter()
+            .all(|fuse_time| (10..30).contains(fuse_time)));
+
+        // A TNT block is primed when powered.
+        world.set_block_notify(IVec3::new(4, 10, 4), block::TNT, 0);
+        world.set_block_notify(IVec3::new(5, 10, 4), block::REDSTONE_TORCH_LIT, 5);
+        assert!(world.is_block_air(IVec3::new(4, 10, 4)));
+        assert_eq!(fuse_times(&world).len(), 3);
+    }
+
+    #[test]
+    fn fireball_deflect() {
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .wall(
+                IVec3::new(0, 10, 14),
+                IVec3::new(15, 10, 14),
+                4,
+                block::OBSIDIAN,
+                0,
+            )
+            .entity(Fireball::new_with(|base, _, fireball| {
+                base.pos = DVec3::new(8.5, 12.0, 8.5);
+                fireball.accel = DVec3::new(-0.1, 0.0, 0.0);
+            }))
+            .entity(Pig::new_default(DVec3::new(6.5, 10.0, 8.5)))
+            .build_with_entities();
+
+        // The pig is looking toward positive Z, where the fireball is sent back.
+        let Entity(base, _) = world.get_entity_mut(ids[0]).unwrap();
+        base.hurt.push(Hurt {
+            damage: 1,
+            origin_id: Some(ids[1]),
+        });
+
+        world.tick();
+        let Entity(base, _) = world.get_entity(ids[0]).unwrap();
+        assert!(base.vel.z > 0.5);
+        assert!(base.vel.x.abs() < 1e-6);
+
+        // The fireball explodes on the wall, that resists the explosion.
+        for _ in 0..20 {
+            world.tick();
+        }
+        assert!(!world.contains_entity(ids[0]));
+        assert!(world.is_block(IVec3::new(8, 12, 14), block::OBSIDIAN));
+    }
+    #[test]
+    fn creeper_fuse() {
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .entity(Human::new_default(DVec3::new(8.5, 10.0, 8.5)))
+            .entity(Creeper::new_default(DVec3::new(10.5, 10.0, 8.5)))
+            .build_with_entities();
+        world.set_player_entity(ids[0], true);
+
+        let fuse_time = |world: &World| match world.get_entity(ids[1]) {
+            Some(Entity(_, BaseKind::Living(_, LivingKind::Creeper(creeper)))) => {
+                Some((creeper.ignited, creeper.fuse_time))
+            }
+            _ => None,
+        };
+
+        // The creeper swells while the player is close, and shrinks once it is gone.
+        for _ in 0..10 {
+            world.tick();
+        }
+        let (ignited, swell_time) = fuse_time(&world).unwrap();
+        assert!(ignited && swell_time > 0);
+        world
+            .get_entity_mut(ids[0])
+            .unwrap()
+            .teleport(DVec3::new(0.5, 10.0, 0.5));
+        for _ in 0..5 {
+            world.tick();
+        }
+        let (ignited, shrink_time) = fuse_time(&world).unwrap();
+        assert!(!ignited && shrink_time < swell_time);
+
+        // Back near the player, the creeper explodes after its full fuse time.
+        let Entity(creeper_base, _) = world.get_entity(ids[1]).unwrap();
+        let near_pos = creeper_base.pos - DVec3::new(2.0, 0.0, 0.0);
+        world.get_entity_mut(ids[0]).unwrap().teleport(near_pos);
+        world.swap_events(Some(Vec::new()));
+        for _ in 0..Creeper::FUSE_TIME + 10 {
+            world.tick();
+        }
+        assert_eq!(fuse_time(&world), None);
+        assert!(world
+            .swap_events(None)
+            .unwrap()
+            .iter()
+            .any(|event| matches!(event, Event::Explode { .. })));
+    }
+}
@@ -0,0 +1,210 @@
+//! Freezing and thawing of the world runtime state.
+//!
+//! A frozen world contains the complete runtime state of a world: loaded chunks,
+//! entities and block entities in their ticking order, scheduled block ticks, pending
+//! light updates, random generator, time and weather. A thawed world continues exactly
+//! as the frozen one would have, which allows fast restarts without going through the
+//! chunk storage, or taking snapshots of a world for testing.
+//!
+//! Player entities are not frozen, they are expected to be spawned again by the owner,
+//! like when players join the world.
+
+use std::sync::Arc;
+
+use glam::IVec3;
+
+use crate::block_entity::BlockEntity;
+use crate::chunk::Chunk;
+use crate::entity::Entity;
+use crate::rand::JavaRandom;
+
+use super::{
+    BlockTick, BlockTickPriority, BlockTickState, Difficulty, Dimension, LightKind, LightUpdate,
+    Weather, World,
+};
+
+/// The frozen runtime state of a world.
+#[derive(Clone)]
+pub struct WorldFreeze {
+    /// The dimension of the world.
+    pub dimension: Dimension,
+    /// The world time, in ticks.
+    pub time: u64,
+    /// The internal state of the world's random number generator.
+    pub rand_state: i64,
+    /// The seed used for random block ticks.
+    pub random_ticks_seed: i32,
+    /// The countdown before the next cave sound can be played.
+    pub cave_sound_time: u16,
+    /// The current weather.
+    pub weather: Weather,
+    /// The time of the next weather change.
+    pub weather_next_time: u64,
+    /// The sky light subtracted from the sky light level, depending on time and weather.
+    pub sky_light_subtracted: u8,
+    /// The difficulty of the world.
+    pub difficulty: Difficulty,
+    /// True if players are allowed to damage each other.
+    pub pvp: bool,
+    /// The next entity id to be allocated.
+    pub next_entity_id: u32,
+    /// All loaded chunks.
+    pub chunks: Vec<(i32, i32, Arc<Chunk>)>,
+    /// All non-player entities with their id, in ticking order.
+    pub entities: Vec<(u32, Box<Entity>)>,
+    /// All block entities, in ticking order.
+    pub block_entities: Vec<(IVec3, Box<BlockEntity>)>,
+    /// All scheduled block ticks, in scheduling order.
+    pub block_ticks: Vec<FrozenBlockTick>,
+    /// All pending light updates, in order.
+    pub light_updates: Vec<FrozenLightUpdate>,
+}
+
+/// A frozen scheduled block tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrozenBlockTick {
+    /// Position of the block to tick.
+    pub pos: IVec3,
+    /// The expected block id.
+    pub id: u8,
+    /// The world time to tick the block at.
+    pub time: u64,
+    /// Priority among ticks at the same time.
+    pub priority: BlockTickPriority,
+}
+
+/// A frozen pending light update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrozenLightUpdate {
+    /// The light kind to update.
+    pub kind: LightKind,
+    /// Position of the light update.
+    pub pos: IVec3,
+    /// Remaining propagation credit.
+    pub credit: u8,
+}
+
+/// Methods related to world freezing.
+impl World {
+    /// Freeze the complete runtime state of this world, except player entities. This
+    /// should not be called while the world is ticking.
+    pub fn freeze(&self) -> WorldFreeze {
+        let chunks = self
+            .chunks
+            .iter()
+            .filter_map(|(&(cx, cz), chunk_comp)| {
+                chunk_comp
+                    .data
+                    .as_ref()
+                    .map(|chunk| (cx, cz, Arc::clone(chunk)))
+            })
+            .collect();
+
+        let entities = self
+            .entities
+            .iter()
+            .filter(|comp| !self.player_entities_map.contains_key(&comp.id))
+            .filter_map(|comp| Some((comp.id, comp.inner.clone()?)))
+            .collect();
+
+        let block_entities = self
+            .block_entities
+            .iter()
+            .filter_map(|comp| Some((comp.pos, comp.inner.clone()?)))
+            .collect();
+
+        let block_ticks = self
+            .block_ticks
+            .iter()
+            .map(|tick| FrozenBlockTick {
+                pos: tick.state.pos,
+                id: tick.state.id,
+                time: tick.time,
+                priority: tick.priority,
+            })
+            .collect();
+
+        let light_updates = self
+            .light_updates
+            .iter()
+            .map(|update| FrozenLightUpdate {
+                kind: update.kind,
+                pos: update.pos,
+                credit: update.credit,
+            })
+            .collect();
+
+        WorldFreeze {
+            dimension: self.dimension,
+            time: self.time,
+            rand_state: self.rand.get_seed(),
+            random_ticks_seed: self.random_ticks_seed,
+            cave_sound_time: self.cave_sound_time,
+            weather: self.weather,
+            weather_next_time: self.weather_next_time,
+            sky_light_subtracted: self.sky_light_subtracted,
+            difficulty: self.difficulty,
+            pvp: self.pvp,
+            next_entity_id: self.entities_count,
+            chunks,
+            entities,
+            block_entities,
+            block_ticks,
+            light_updates,
+        }
+    }
+
+    /// Create a new world from its frozen state, the world has no events queue.
+    pub fn thaw(freeze: WorldFreeze) -> Self {
+        let mut world = World::new(freeze.dimension);
+
+        world.time = freeze.time;
+        world.rand = JavaRandom::new_raw(freeze.rand_state);
+        world.random_ticks_seed = freeze.random_ticks_seed;
+        world.cave_sound_time = freeze.cave_sound_time;
+        world.weather = freeze.weather;
+        world.weather_next_time = freeze.weather_next_time;
+        world.sky_light_subtracted = freeze.sky_light_subtracted;
+        world.difficulty = freeze.difficulty;
+        world.pvp = freeze.pvp;
+
+        for (cx, cz, chunk) in freeze.chunks {
+            world.set_chunk(cx, cz, chunk);
+        }
+
+        // Entities are spawned in their ticking order, with their original id.
+        for (id, entity) in freeze.entities {
+            world.insert_entity_inner(id, entity);
+        }
+        world.entities_count = freeze.next_entity_id;
+
+        for (pos, block_entity) in freeze.block_entities {
+            world.set_block_entity_inner(pos, block_entity);
+        }
+
+        world.block_ticks_count = freeze.block_ticks.len() as u64;
+        for (uid, tick) in freeze.block_ticks.into_iter().enumerate() {
+            world.insert_block_tick(BlockTick {
+                uid: uid as u64,
+                time: tick.time,
+                priority: tick.priority,
+                state: BlockTickState {
+                    pos: tick.pos,
+                    id: tick.id,
+                },
+            });
+        }
+
+        world.light_updates = freeze
+            .light_updates
+            .into_iter()
+            .map(|update| LightUpdate {
+                kind: update.kind,
+                pos: update.pos,
+                credit: update.credit,
+            })
+            .collect();
+
+        world
+    }
+}
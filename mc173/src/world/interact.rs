@@ -1,15 +1,21 @@
-//! Interaction of players with blocks in the world.
+//! Interaction of players with blocks and entities in the world.
 
-use glam::IVec3;
+use glam::{DVec3, IVec3};
 
-use crate::block;
 use crate::block::material::Material;
 use crate::block_entity::BlockEntity;
+use crate::entity::{BaseKind, Entity, Human, Item, LivingKind};
 use crate::geom::Face;
+use crate::inventory::InventoryHandle;
+use crate::item::ItemStack;
+use crate::{block, item};
 
-use super::{Event, World};
+use super::{EntityEvent, Event, World};
 
-/// Methods related to block interactions when client clicks on a block.
+/// Health of tamed wolves, they can't be healed beyond.
+const TAMED_WOLF_HEALTH: u16 = 20;
+
+/// Methods related to block and entity interactions when client clicks on them.
 impl World {
     /// Interact with a block at given position. This function returns the interaction
     /// result to indicate if the interaction was handled, or if it was
@@ -189,6 +195,218 @@ impl World {
 
         true
     }
+
+    /// Interact with an entity, this is the action of right click on the target entity,
+    /// with the item stack at the given inventory index in hand. This function returns
+    /// true if the interaction was handled.
+    pub fn interact_entity(
+        &mut self,
+        inv: &mut InventoryHandle,
+        index: usize,
+        target_id: u32,
+        entity_id: u32,
+    ) -> bool {
+        match self.get_entity(target_id) {
+            Some(Entity(_, BaseKind::Living(_, LivingKind::Wolf(_)))) => {
+                self.interact_wolf(inv, index, target_id, entity_id)
+            }
+            Some(Entity(_, BaseKind::Living(_, LivingKind::Sheep(_)))) => {
+                self.interact_sheep(inv, index, target_id)
+            }
+            Some(Entity(_, BaseKind::Living(_, LivingKind::Cow(_)))) => {
+                Self::interact_cow(inv, index)
+            }
+            Some(Entity(_, BaseKind::Living(_, LivingKind::Pig(_)))) => {
+                self.interact_pig(inv, index, target_id, entity_id)
+            }
+            _ => false,
+        }
+    }
+
+    /// Interact with a wolf entity, wild wolves can be tamed with bones, tamed wolves can
+    /// be healed with pork chops or told to sit by their owner.
+    ///
+    /// REF: EntityWolf::interact
+    fn interact_wolf(
+        &mut self,
+        inv: &mut InventoryHandle,
+        index: usize,
+        id: u32,
+        entity_id: u32,
+    ) -> bool {
+        let Some(username) = self
+            .get_entity(entity_id)
+            .and_then(|entity| entity.get::<Human>())
+            .map(|human| human.username.clone())
+        else {
+            return false;
+        };
+
+        let Some(Entity(base, BaseKind::Living(living, LivingKind::Wolf(wolf)))) =
+            self.get_entity_mut(id)
+        else {
+            return false;
+        };
+
+        let stack = inv.get(index);
+
+        let Some(owner) = &wolf.owner else {
+            if stack.id != item::BONE || wolf.angry {
+                return false;
+            }
+
+            inv.set(index, stack.inc_damage(1));
+
+            let tamed = base.rand.next_int_bounded(3) == 0;
+            if tamed {
+                wolf.owner = Some(username);
+                wolf.sitting = true;
+                living.path = None;
+                living.attack_target = None;
+                living.health = TAMED_WOLF_HEALTH;
+                self.push_event(Event::Entity {
+                    id,
+                    inner: EntityEvent::Metadata,
+                });
+            }
+
+            self.push_event(Event::Entity {
+                id,
+                inner: EntityEvent::Tame { tamed },
+            });
+            return true;
+        };
+
+        let heal = match stack.id {
+            item::RAW_PORKCHOP => 3,
+            item::COOKED_PORKCHOP => 8,
+            _ => 0,
+        };
+
+        if heal != 0 && living.health < TAMED_WOLF_HEALTH {
+            inv.set(index, stack.inc_damage(1));
+            living.health = (living.health + heal).min(TAMED_WOLF_HEALTH);
+            self.push_event(Event::Entity {
+                id,
+                inner: EntityEvent::Heal,
+            });
+            self.push_event(Event::Entity {
+                id,
+                inner: EntityEvent::Metadata,
+            });
+            return true;
+        }
+
+        if !owner.eq_ignore_ascii_case(&username) {
+            return false;
+        }
+
+        wolf.sitting = !wolf.sitting;
+        living.jumping = false;
+        living.path = None;
+        self.push_event(Event::Entity {
+            id,
+            inner: EntityEvent::Metadata,
+        });
+        true
+    }
+
+    /// Interact with a pig entity, a saddled pig is mounted, or left if already riding
+    /// it, a saddle can be put on a pig without saddle.
+    ///
+    /// REF: EntityPig::interact, ItemSaddle::saddleEntity
+    fn interact_pig(
+        &mut self,
+        inv: &mut InventoryHandle,
+        index: usize,
+        id: u32,
+        entity_id: u32,
+    ) -> bool {
+        let Some(Entity(base, BaseKind::Living(_, LivingKind::Pig(pig)))) = self.get_entity_mut(id)
+        else {
+            return false;
+        };
+
+        if pig.saddle {
+            return match base.rider_id {
+                Some(rider_id) if rider_id == entity_id => self.dismount_entity(entity_id),
+                Some(_) => false,
+                None => self.mount_entity(entity_id, id),
+            };
+        }
+
+        let stack = inv.get(index);
+        if stack.id != item::SADDLE {
+            return false;
+        }
+
+        pig.saddle = true;
+        inv.set(index, stack.inc_damage(1));
+        self.push_event(Event::Entity {
+            id,
+            inner: EntityEvent::Metadata,
+        });
+        true
+    }
+
+    /// Interact with a cow entity, an empty bucket is filled with milk.
+    ///
+    /// REF: EntityCow::interact
+    fn interact_cow(inv: &mut InventoryHandle, index: usize) -> bool {
+        if inv.get(index).id != item::BUCKET {
+            return false;
+        }
+
+        inv.set(index, ItemStack::new_single(item::MILK_BUCKET, 0));
+        true
+    }
+
+    /// Interact with a sheep entity, shearing a sheep drops 1 to 3 wool of its color.
+    ///
+    /// REF: EntitySheep::interact
+    fn interact_sheep(&mut self, inv: &mut InventoryHandle, index: usize, id: u32) -> bool {
+        let stack = inv.get(index);
+        if stack.id != item::SHEARS {
+            return false;
+        }
+
+        let Some(Entity(base, BaseKind::Living(_, LivingKind::Sheep(sheep)))) =
+            self.get_entity_mut(id)
+        else {
+            return false;
+        };
+
+        if sheep.sheared {
+            return false;
+        }
+
+        sheep.sheared = true;
+        let pos = base.pos + DVec3::Y;
+        let wool = ItemStack::new_block(block::WOOL, sheep.color);
+        let count = 1 + base.rand.next_int_bounded(3);
+
+        for _ in 0..count {
+            let entity = Item::new_with(|base, item| {
+                base.persistent = true;
+                base.pos = pos;
+                base.vel.x = self.rand.next_double() * 0.2 - 0.1
+                    + ((self.rand.next_float() - self.rand.next_float()) * 0.1) as f64;
+                base.vel.y = 0.2 + (self.rand.next_float() * 0.05) as f64;
+                base.vel.z = self.rand.next_double() * 0.2 - 0.1
+                    + ((self.rand.next_float() - self.rand.next_float()) * 0.1) as f64;
+                item.stack = wool;
+                item.frozen_time = 10;
+            });
+            self.spawn_entity(entity);
+        }
+
+        inv.set(index, stack.inc_damage(1));
+        self.push_event(Event::Entity {
+            id,
+            inner: EntityEvent::Metadata,
+        });
+        true
+    }
 }
 
 /// The result of an interaction with a block in the world.
@@ -237,3 +455,201 @@ impl From<bool> for Interaction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Build a world with a platform of the given block, a player standing at
+    /// 4.5/10/4.5 and the given entity, returning the world, the player and entity ids.
+    fn build_with_player(ground_id: u8, entity: Box<Entity>) -> (World, u32, u32) {
+        let (world, ids) = World::builder()
+            .platform(10, ground_id, 0)
+            .player(Human::new_default(DVec3::new(4.5, 10.0, 4.5)))
+            .entity(entity)
+            .build_with_entities();
+        (world, ids[0], ids[1])
+    }
+
+    /// Interact with the given target entity as the given player, holding the first
+    /// stack of the given inventory.
+    fn interact(world: &mut World, inv: &mut [ItemStack], target_id: u32, player_id: u32) -> bool {
+        world.interact_entity(&mut InventoryHandle::new(inv), 0, target_id, player_id)
+    }
+
+    #[test]
+    fn wolf_taming() {
+        use crate::entity::{LivingKind, Wolf};
+        use crate::item;
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .player(Human::new_with(|base, _, human| {
+                base.pos = DVec3::new(4.5, 10.0, 4.5);
+                human.username = "Owner".to_string();
+            }))
+            .player(Human::new_with(|base, _, human| {
+                base.pos = DVec3::new(12.5, 10.0, 4.5);
+                human.username = "Other".to_string();
+            }))
+            .entity(Wolf::new_default(DVec3::new(8.5, 10.0, 8.5)))
+            .build_with_entities();
+        let [owner_id, other_id, wolf_id] = ids[..] else {
+            unreachable!()
+        };
+
+        let mut inv = [ItemStack::new_sized(item::BONE, 0, 64)];
+        let interact = |world: &mut World, inv: &mut [ItemStack], player_id: u32| {
+            interact(world, inv, wolf_id, player_id)
+        };
+        let wolf = |world: &World| match world.get_entity(wolf_id) {
+            Some(Entity(_, BaseKind::Living(living, LivingKind::Wolf(wolf)))) => {
+                (living.health, wolf.clone())
+            }
+            _ => panic!("wolf should exists"),
+        };
+
+        // Bones are given until the wolf is tamed, it then sits.
+        while wolf(&world).1.owner.is_none() {
+            assert!(interact(&mut world, &mut inv, owner_id));
+        }
+        let (health, tamed) = wolf(&world);
+        assert_eq!(tamed.owner.as_deref(), Some("Owner"));
+        assert!(tamed.sitting);
+        assert_eq!(health, 20);
+
+        // Only the owner can tell the wolf to stand up.
+        assert!(!interact(&mut world, &mut inv, other_id));
+        assert!(interact(&mut world, &mut inv, owner_id));
+        assert!(!wolf(&world).1.sitting);
+
+        // Any player can heal a tamed wolf with a pork chop.
+        let Some(Entity(_, BaseKind::Living(living, _))) = world.get_entity_mut(wolf_id) else {
+            unreachable!()
+        };
+        living.health = 10;
+        inv[0] = ItemStack::new_single(item::RAW_PORKCHOP, 0);
+        assert!(interact(&mut world, &mut inv, other_id));
+        assert_eq!(wolf(&world).0, 13);
+        assert!(inv[0].is_empty());
+    }
+
+    #[test]
+    fn sheep_shearing() {
+        use crate::entity::{LivingKind, Sheep};
+        use crate::item;
+
+        let (mut world, player_id, sheep_id) = build_with_player(
+            block::GRASS,
+            Sheep::new_with(|base, _, sheep| {
+                base.pos = DVec3::new(8.5, 10.0, 8.5);
+                sheep.color = 14;
+            }),
+        );
+
+        let sheared = |world: &World| match world.get_entity(sheep_id) {
+            Some(Entity(_, BaseKind::Living(_, LivingKind::Sheep(sheep)))) => sheep.sheared,
+            _ => panic!("sheep should exists"),
+        };
+        let wool = |world: &World| {
+            world
+                .iter_entities()
+                .filter_map(|(_, entity)| match entity {
+                    Entity(_, BaseKind::Item(item)) => Some(item.stack),
+                    _ => None,
+                })
+                .inspect(|stack| assert_eq!(*stack, ItemStack::new_block(block::WOOL, 14)))
+                .count()
+        };
+
+        // Only shears can shear the sheep, once.
+        let mut inv = [ItemStack::new_single(item::BONE, 0)];
+        assert!(!interact(&mut world, &mut inv, sheep_id, player_id));
+        inv[0] = ItemStack::new_single(item::SHEARS, 0);
+        assert!(interact(&mut world, &mut inv, sheep_id, player_id));
+        assert!(!interact(&mut world, &mut inv, sheep_id, player_id));
+        assert!(sheared(&world));
+        assert_eq!(inv[0].damage, 1);
+        assert!((1..=3).contains(&wool(&world)));
+
+        // The sheared sheep eventually grazes the grass below it to regrow its wool.
+        for _ in 0..20000 {
+            world.tick();
+            if !sheared(&world) {
+                break;
+            }
+        }
+        assert!(!sheared(&world));
+        let pos = world.get_entity(sheep_id).unwrap().0.pos.floor().as_ivec3();
+        assert!(world.is_block(pos - IVec3::Y, block::DIRT));
+    }
+
+    #[test]
+    fn cow_milking() {
+        use crate::entity::Cow;
+        use crate::item;
+
+        let (mut world, player_id, cow_id) =
+            build_with_player(block::STONE, Cow::new_default(DVec3::new(8.5, 10.0, 8.5)));
+
+        let mut inv = [ItemStack::new_single(item::WATER_BUCKET, 0)];
+        assert!(!interact(&mut world, &mut inv, cow_id, player_id));
+        inv[0] = ItemStack::new_single(item::BUCKET, 0);
+        assert!(interact(&mut world, &mut inv, cow_id, player_id));
+        assert_eq!(inv[0], ItemStack::new_single(item::MILK_BUCKET, 0));
+    }
+
+    #[test]
+    fn pig_riding() {
+        use crate::entity::{Hurt, LivingKind, Pig};
+        use crate::item;
+
+        let (mut world, player_id, pig_id) =
+            build_with_player(block::STONE, Pig::new_default(DVec3::new(8.5, 10.0, 8.5)));
+
+        let mut inv = [ItemStack::new_single(item::SADDLE, 0)];
+        let interact =
+            |world: &mut World, inv: &mut [ItemStack]| interact(world, inv, pig_id, player_id);
+        let vehicle_id = |world: &World| world.get_entity(player_id).unwrap().0.vehicle_id;
+
+        // The saddle is consumed, then the saddled pig can be mounted.
+        assert!(interact(&mut world, &mut inv));
+        assert!(inv[0].is_empty());
+        assert!(matches!(
+            world.get_entity(pig_id),
+            Some(Entity(_, BaseKind::Living(_, LivingKind::Pig(pig)))) if pig.saddle
+        ));
+        assert!(interact(&mut world, &mut inv));
+        assert_eq!(vehicle_id(&world), Some(pig_id));
+
+        // The rider follows the wandering pig.
+        for _ in 0..50 {
+            world.tick();
+        }
+        let pig_pos = world.get_entity(pig_id).unwrap().0.pos;
+        let player_pos = world.get_entity(player_id).unwrap().0.pos;
+        assert_eq!(player_pos, pig_pos + DVec3::new(0.0, 0.9 * 0.75, 0.0));
+
+        // Interacting again leaves the pig.
+        assert!(interact(&mut world, &mut inv));
+        assert_eq!(vehicle_id(&world), None);
+        assert_eq!(world.get_entity(pig_id).unwrap().0.rider_id, None);
+
+        // The rider is dismounted when the pig dies, and the saddle is dropped.
+        assert!(interact(&mut world, &mut inv));
+        world.get_entity_mut(pig_id).unwrap().0.hurt.push(Hurt {
+            damage: 100,
+            origin_id: None,
+        });
+        for _ in 0..30 {
+            world.tick();
+        }
+        assert!(!world.contains_entity(pig_id));
+        assert_eq!(vehicle_id(&world), None);
+        assert!(world.iter_entities().any(|(_, entity)| matches!(
+            entity,
+            Entity(_, BaseKind::Item(item)) if item.stack.id == item::SADDLE
+        )));
+    }
+}
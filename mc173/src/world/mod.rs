@@ -7,23 +7,28 @@ use std::cell::Cell;
 use std::cmp::Ordering;
 use std::hash::Hash;
 use std::iter::FusedIterator;
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::slice;
 use std::sync::Arc;
 
 use glam::{DVec3, IVec3, Vec2};
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 
 use tracing::trace;
 
 use crate::biome::Biome;
 use crate::block_entity::BlockEntity;
 use crate::chunk::{
-    calc_chunk_pos, calc_chunk_pos_unchecked, calc_entity_chunk_pos, Chunk, CHUNK_HEIGHT,
-    CHUNK_WIDTH,
+    calc_chunk_pos, calc_chunk_pos_unchecked, calc_entity_chunk_pos, Chunk, ChunkAccessor,
+    ChunkChange, CHUNK_HEIGHT, CHUNK_WIDTH,
 };
-use crate::entity::{Entity, EntityCategory, EntityKind, LightningBolt};
+use crate::data;
+use crate::entity::{
+    Base, BaseKind, Component, Entity, EntityCategory, EntityKind, Hurt, LightningBolt,
+};
+use crate::gen::ChunkGenerator;
 
 use crate::block;
 use crate::geom::{BoundingBox, Face};
@@ -33,16 +38,35 @@ use crate::rand::JavaRandom;
 // Following modules are order by order of importance, last modules depends on first ones.
 pub mod bound;
 pub mod r#break;
+#[cfg(test)]
+pub mod build;
+pub mod cleanup;
+pub mod collide;
+pub mod dimension;
 pub mod explode;
+pub mod freeze;
 pub mod interact;
 pub mod loot;
 pub mod material;
 pub mod notify;
+pub mod observe;
 pub mod path;
 pub mod place;
 pub mod power;
+pub mod relight;
+pub mod ride;
+pub mod silent;
+pub mod subscribe;
 pub mod tick;
 pub mod r#use;
+pub mod view;
+
+pub use dimension::{Dimension, DimensionInfo};
+pub use view::WorldView;
+
+use observe::ChunkObserver;
+use silent::{BlockChange, BlockChanges};
+use subscribe::Subscription;
 
 // Various thread local vectors that are used to avoid frequent reallocation of
 // temporary vector used in the logic code.
@@ -124,8 +148,20 @@ pub struct World {
     /// borrowing issues, by temporarily taking ownership of events, the caller can get
     /// a mutable reference to that world at the same time.
     events: Option<Vec<Event>>,
+    /// Events subscriptions, each with its own filter and events queue.
+    subscriptions: Vec<Subscription>,
+    /// Total subscriptions count since the world is running, used to give unique ids.
+    subscriptions_count: u32,
+    /// Chunk observers, each with its own region and notifications queue.
+    observers: Vec<ChunkObserver>,
+    /// Total observers count since the world is running, used to give unique ids.
+    observers_count: u32,
+    /// When in a silent changes scope, this contains the aggregated block changes.
+    silent_changes: Option<BlockChanges>,
     /// The dimension
     dimension: Dimension,
+    /// The dimension's properties, initialized from the dimension.
+    dimension_info: DimensionInfo,
     /// The world time, increasing on each tick. This is used for day/night cycle but
     /// also for registering scheduled ticks.
     time: u64,
@@ -149,6 +185,9 @@ pub struct World {
     block_entities: TickVec<BlockEntityComponent>,
     /// Mapping of block entities to they block position.
     block_entities_pos_map: HashMap<IVec3, usize>,
+    /// Positions of block entities that need to be ticked, in tick order, other block
+    /// entities are only modified by interactions and block changes.
+    block_entities_ticking: IndexSet<IVec3>,
     /// Total scheduled ticks count since the world is running.
     block_ticks_count: u64,
     /// Mapping of scheduled ticks in the future.
@@ -156,10 +195,51 @@ pub struct World {
     /// A set of all scheduled tick states, used to avoid ticking twice the same position
     /// and block id.
     block_ticks_states: HashSet<BlockTickState>,
+    /// Number of scheduled ticks in each chunk, only tracked if there is a limit.
+    block_ticks_chunks: HashMap<(i32, i32), usize>,
+    /// Maximum number of scheduled ticks per chunk, new ticks are discarded past it.
+    block_ticks_chunk_limit: Option<usize>,
+    /// When some, scheduled block ticks are not queued but collected here in order to be
+    /// run immediately, see [`World::tick_block_immediate`].
+    block_ticks_immediate: Option<Vec<BlockTickState>>,
+    /// Total number of scheduled ticks that have been discarded because of the limit.
+    block_ticks_discarded: u64,
     /// Queue of pending light updates to be processed.
     light_updates: VecDeque<LightUpdate>,
+    /// Chunks waiting to be lazily relit, in scheduling order.
+    relight_chunks: IndexSet<(i32, i32)>,
+    /// True to schedule a lazy relight of inserted chunks with invalid light data.
+    lazy_relight: bool,
+    /// True if mobs are allowed to modify the world, such as creepers destroying blocks.
+    mob_griefing: bool,
+    /// True if entities spawned by mob spawners are counted in the mob caps.
+    spawner_mobs_capped: bool,
+    /// Maximum distance in chunks from players where chunks are simulated, if limited.
+    simulation_distance: Option<u32>,
+    /// Number of idle ticks before a chunk is frozen, if chunk freezing is enabled.
+    chunk_freeze_delay: Option<u64>,
+    /// Chunks that are not frozen, only maintained if chunk freezing is enabled.
+    active_chunks: IndexSet<(i32, i32)>,
+    /// Total number of times a frozen chunk has been woken up.
+    chunk_wake_count: u64,
+    /// The difficulty of the world.
+    difficulty: Difficulty,
+    /// True if players are allowed to damage each other.
+    pvp: bool,
+    /// The lifetime of item entities, in ticks.
+    item_lifetime: u32,
+    /// The interval between two cleanup sweeps of all item entities, if enabled.
+    item_cleanup_interval: Option<u64>,
+    /// Optional filter overriding the PvP setting, for example depending on the region.
+    pvp_filter: Option<PvpFilter>,
+    /// The function vetoing or altering entity spawns, if any.
+    spawn_filter: Option<SpawnFilter>,
+    /// The function vetoing bulk block changes, if any.
+    build_filter: Option<BuildFilter>,
     /// This is the wrapping seed used by random ticks to compute random block positions.
     random_ticks_seed: i32,
+    /// Countdown before the next cave sound can be played, see [`Event::CaveSound`].
+    cave_sound_time: u16,
     /// The current weather in that world, note that the Notchian server do not work like
     /// this, but rather store two independent state for rain and thunder, but we simplify
     /// the logic in this implementation since it is not strictly needed to be on parity.
@@ -178,7 +258,13 @@ impl World {
     pub fn new(dimension: Dimension) -> Self {
         Self {
             events: None,
+            subscriptions: Vec::new(),
+            subscriptions_count: 0,
+            observers: Vec::new(),
+            observers_count: 0,
+            silent_changes: None,
             dimension,
+            dimension_info: dimension.info(),
             time: 0,
             rand: JavaRandom::new_seeded(),
             chunks: HashMap::new(),
@@ -188,11 +274,32 @@ impl World {
             player_entities_map: IndexMap::new(),
             block_entities: TickVec::new(),
             block_entities_pos_map: HashMap::new(),
+            block_entities_ticking: IndexSet::new(),
             block_ticks_count: 0,
             block_ticks: BTreeSet::new(),
             block_ticks_states: HashSet::new(),
+            block_ticks_chunks: HashMap::new(),
+            block_ticks_chunk_limit: None,
+            block_ticks_immediate: None,
+            block_ticks_discarded: 0,
             light_updates: VecDeque::new(),
+            relight_chunks: IndexSet::new(),
+            lazy_relight: false,
+            mob_griefing: true,
+            spawner_mobs_capped: true,
+            simulation_distance: None,
+            chunk_freeze_delay: None,
+            active_chunks: IndexSet::new(),
+            chunk_wake_count: 0,
+            difficulty: Difficulty::Normal,
+            pvp: true,
+            pvp_filter: None,
+            spawn_filter: None,
+            build_filter: None,
+            item_lifetime: cleanup::DEFAULT_ITEM_LIFETIME,
+            item_cleanup_interval: None,
             random_ticks_seed: JavaRandom::new_seeded().next_int(),
+            cave_sound_time: JavaRandom::new_seeded().next_int_bounded(12000) as u16,
             weather: Weather::Clear,
             weather_next_time: 0,
             sky_light_subtracted: 0,
@@ -209,24 +316,71 @@ impl World {
         mem::replace(&mut self.events, events)
     }
 
-    /// Return true if this world has an internal events queue that enables usage of the
-    /// [`push_event`] method.
+    /// Return true if this world has an internal events queue or any events subscription
+    /// that enables usage of the [`push_event`] method.
     ///
     /// [`push_event`]: Self::push_event
     pub fn has_events(&self) -> bool {
-        self.events.is_some()
+        self.events.is_some() || !self.subscriptions.is_empty()
     }
 
     /// Push an event in this world. This only actually push the event if events are
-    /// enabled. Events queue can be swapped using [`swap_events`](Self::swap_events)
-    /// method.
+    /// enabled or if any subscription accepts it. Events queue can be swapped using
+    /// [`swap_events`](Self::swap_events) method. Block events are not pushed while in
+    /// a [silent scope](Self::with_silent_changes).
     #[inline]
     pub fn push_event(&mut self, event: Event) {
+        if let Event::Entity { id, ref inner } = event {
+            if *inner != EntityEvent::Remove {
+                self.set_entity_dirty(id);
+            }
+        }
+
+        // Any change in a chunk wakes it up if frozen.
+        if let Event::Chunk {
+            cx,
+            cz,
+            inner: ChunkEvent::Dirty,
+        } = event
+        {
+            self.wake_chunk(cx, cz);
+        }
+
+        if self.intercept_silent_event(&event) {
+            return;
+        }
+
+        if let Event::Chunk { cx, cz, ref inner } = event {
+            self.notify_chunk_observers(cx, cz, inner);
+        }
+
+        if !self.subscriptions.is_empty() {
+            let entity = match event {
+                Event::Entity { id, .. } => self
+                    .entities_id_map
+                    .get(&id)
+                    .and_then(|&index| self.entities.get(index))
+                    .map(|comp| (comp.kind, comp.cx, comp.cz)),
+                _ => None,
+            };
+            self.push_subscribed_event(&event, entity);
+        }
+
         if let Some(events) = &mut self.events {
             events.push(event);
         }
     }
 
+    /// Push an event to play the given effect at a block position, effects are not
+    /// pushed while in a [silent scope](Self::with_silent_changes).
+    #[inline]
+    pub fn play_effect(&mut self, pos: IVec3, effect: Effect) {
+        self.push_event(Event::Block {
+            pos,
+            inner: BlockEvent::Effect { effect },
+        });
+    }
+
     /// Get the dimension of this world, this is basically only for sky color on client
     /// and also for celestial angle on the server side for sky light calculation. This
     /// has not direct relation with the actual world generation that is providing this
@@ -235,6 +389,178 @@ impl World {
         self.dimension
     }
 
+    /// Get the properties of this world's dimension, used for sky light, weather and
+    /// ambient light calculations.
+    #[inline]
+    pub fn get_dimension_info(&self) -> &DimensionInfo {
+        &self.dimension_info
+    }
+
+    /// Replace the properties of this world's dimension, this can be used to customize
+    /// the ambient of a world, the sky light is updated on the next tick.
+    pub fn set_dimension_info(&mut self, info: DimensionInfo) {
+        self.dimension_info = info;
+    }
+
+    /// Return true if mobs are allowed to modify the world, this is the case by default.
+    #[inline]
+    pub fn is_mob_griefing(&self) -> bool {
+        self.mob_griefing
+    }
+
+    /// Set if mobs are allowed to modify the world, when disabled creepers and ghast
+    /// fireballs no longer destroy blocks or set fire, and sheared sheep no longer eat
+    /// grass. Entities are still damaged by explosions.
+    pub fn set_mob_griefing(&mut self, mob_griefing: bool) {
+        self.mob_griefing = mob_griefing;
+    }
+
+    /// Return true if entities spawned by mob spawners are counted in the mob caps, this
+    /// is the case by default.
+    #[inline]
+    pub fn is_spawner_mobs_capped(&self) -> bool {
+        self.spawner_mobs_capped
+    }
+
+    /// Set if entities spawned by mob spawners are counted in the mob caps, when disabled
+    /// mob farms built around spawners no longer prevent natural spawning.
+    pub fn set_spawner_mobs_capped(&mut self, capped: bool) {
+        self.spawner_mobs_capped = capped;
+    }
+
+    /// Get the maximum distance in chunks from players where chunks are simulated, if
+    /// limited.
+    #[inline]
+    pub fn get_simulation_distance(&self) -> Option<u32> {
+        self.simulation_distance
+    }
+
+    /// Limit the distance in chunks from players where chunks are simulated, chunks
+    /// further away are still loaded but their random ticks, scheduled ticks and
+    /// entities are frozen until a player comes closer. Scheduled ticks of such chunks
+    /// are postponed, not discarded. This is unlimited by default, every loaded chunk
+    /// being simulated, note that natural spawning is always limited to 8 chunks.
+    pub fn set_simulation_distance(&mut self, distance: Option<u32>) {
+        self.simulation_distance = distance;
+    }
+
+    /// Return true if the chunk at the given position is within the simulation distance
+    /// of a player, always true if the simulation distance is unlimited.
+    pub fn is_chunk_simulated(&self, cx: i32, cz: i32) -> bool {
+        self.simulation_area().contains(cx, cz)
+    }
+
+    /// Internal function to get the area of simulated chunks around players.
+    fn simulation_area(&self) -> SimulationArea {
+        SimulationArea {
+            distance: self.simulation_distance,
+            centers: match self.simulation_distance {
+                Some(_) => self
+                    .player_entities_map
+                    .values()
+                    .map(|&index| self.entities.get(index).unwrap())
+                    .map(|comp| (comp.cx, comp.cz))
+                    .collect(),
+                None => Vec::new(),
+            },
+        }
+    }
+
+    /// Get the current usage of the natural spawning cap of the given entity category,
+    /// a category stops naturally spawning while its count exceeds its maximum, which
+    /// depends on the number of loaded chunks.
+    pub fn get_mob_cap(&self, category: EntityCategory) -> MobCap {
+        MobCap {
+            count: self.count_mob_caps()[category as usize],
+            max: category.natural_spawn_max_world_count() * self.chunks.len() / 256,
+        }
+    }
+
+    /// Count the loaded entities of each category that are accounted in the mob caps.
+    fn count_mob_caps(&self) -> [usize; EntityCategory::ALL.len()] {
+        let mut counts = [0; EntityCategory::ALL.len()];
+        for comp in self.entities.iter() {
+            if comp.loaded {
+                if let Some(entity) = comp.inner.as_deref() {
+                    if !self.spawner_mobs_capped {
+                        if let Entity(_, BaseKind::Living(living, _)) = entity {
+                            if living.from_spawner {
+                                continue;
+                            }
+                        }
+                    }
+                    counts[entity.category() as usize] += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Get the difficulty of this world.
+    #[inline]
+    pub fn get_difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Set the difficulty of this world, on peaceful difficulty hostile entities are no
+    /// longer naturally spawned and the loaded ones are removed on their next tick.
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+    }
+
+    /// Return true if players are allowed to damage each other, this is the case by
+    /// default, note that the [PvP filter](Self::set_pvp_filter) may override it.
+    #[inline]
+    pub fn is_pvp(&self) -> bool {
+        self.pvp
+    }
+
+    /// Set if players are allowed to damage each other, when a player damage is denied
+    /// an [`EntityEvent::PvpDenied`] event is pushed for the victim.
+    pub fn set_pvp(&mut self, pvp: bool) {
+        self.pvp = pvp;
+    }
+
+    /// Set a filter function overriding the PvP setting, for example to allow or deny
+    /// PvP in some regions of the world. The filter is given the attacker entity id, the
+    /// victim position and the world's PvP setting, it returns true to allow the damage.
+    pub fn set_pvp_filter(&mut self, filter: Option<PvpFilter>) {
+        self.pvp_filter = filter;
+    }
+
+    /// Set a filter function checked before spawning entities for a [reason](SpawnReason),
+    /// such as natural spawning or spawners. The filter is given the entity to spawn,
+    /// which it may alter, and the spawn reason, it returns false to cancel the spawn.
+    pub fn set_spawn_filter(&mut self, filter: Option<SpawnFilter>) {
+        self.spawn_filter = filter;
+    }
+
+    /// Set a filter function checked before bulk block changes, such as
+    /// [`fill_blocks`](Self::fill_blocks), for example to protect some regions of the
+    /// world. The filter is given the entity responsible of the change, if any, and the
+    /// block position, it returns false to veto the change.
+    pub fn set_build_filter(&mut self, filter: Option<BuildFilter>) {
+        self.build_filter = filter;
+    }
+
+    /// Return true if the given entity, if any, can change the block at the given
+    /// position, depending on the build filter.
+    pub fn can_build(&self, origin_id: Option<u32>, pos: IVec3) -> bool {
+        match self.build_filter {
+            Some(filter) => filter(self, origin_id, pos),
+            None => true,
+        }
+    }
+
+    /// Return true if the given attacker entity can damage a player at the given
+    /// position, depending on the PvP setting and filter.
+    pub fn can_pvp(&self, attacker_id: u32, victim_pos: DVec3) -> bool {
+        match self.pvp_filter {
+            Some(filter) => filter(self, attacker_id, victim_pos, self.pvp),
+            None => self.pvp,
+        }
+    }
+
     /// Get the world time, in ticks.
     pub fn get_time(&self) -> u64 {
         self.time
@@ -254,13 +580,28 @@ impl World {
     pub fn insert_chunk_snapshot(&mut self, snapshot: ChunkSnapshot) {
         self.set_chunk(snapshot.cx, snapshot.cz, snapshot.chunk);
 
-        for entity in snapshot.entities {
+        if snapshot.light_invalid && self.lazy_relight {
+            self.schedule_chunk_relight(snapshot.cx, snapshot.cz);
+        }
+
+        for (index, entity) in snapshot.entities.into_iter().enumerate() {
             debug_assert_eq!(
                 calc_entity_chunk_pos(entity.0.pos),
                 (snapshot.cx, snapshot.cz),
                 "incoherent entity in chunk snapshot"
             );
-            self.spawn_entity_inner(entity);
+
+            // Entities keep their persisted id, unless it's already used by another
+            // entity, the next id to allocate is kept after all known ids.
+            match snapshot.entities_ids.get(index).copied().flatten() {
+                Some(id) if !self.entities_id_map.contains_key(&id) => {
+                    self.entities_count = self.entities_count.max(id.saturating_add(1));
+                    self.insert_entity_inner(id, entity);
+                }
+                _ => {
+                    self.spawn_entity_inner(entity);
+                }
+            }
         }
 
         for (pos, block_entity) in snapshot.block_entities {
@@ -289,6 +630,19 @@ impl World {
                 // Ignoring entities being updated, silently for now.
                 .filter_map(|&index| self.entities.get(index).unwrap().inner.clone())
                 .collect(),
+            entities_changes: chunk_comp
+                .entities
+                .values()
+                .map(|&index| self.entities.get(index).unwrap())
+                .filter(|comp| comp.inner.is_some())
+                .map(|comp| (comp.id, comp.dirty))
+                .collect(),
+            entities_ids: chunk_comp
+                .entities
+                .iter()
+                .filter(|(_, &index)| self.entities.get(index).unwrap().inner.is_some())
+                .map(|(&id, _)| Some(id))
+                .collect(),
             block_entities: chunk_comp
                 .block_entities
                 .iter()
@@ -301,25 +655,42 @@ impl World {
                         .map(|e| (pos, e))
                 })
                 .collect(),
+            light_invalid: self.relight_chunks.contains(&(cx, cz)),
         })
     }
 
+    /// Same as [`take_chunk_snapshot`] but the entities of the chunk are then no longer
+    /// considered changed, this should be used when the snapshot is saved in order to
+    /// only serialize again the entities that changed since the previous save, see
+    /// [`ChunkSnapshot::entities_changes`].
+    ///
+    /// [`take_chunk_snapshot`]: Self::take_chunk_snapshot
+    pub fn take_chunk_save_snapshot(&mut self, cx: i32, cz: i32) -> Option<ChunkSnapshot> {
+        let snapshot = self.take_chunk_snapshot(cx, cz)?;
+        for &index in self.chunks[&(cx, cz)].entities.values() {
+            self.entities.get_mut(index).unwrap().dirty = false;
+        }
+        Some(snapshot)
+    }
+
     /// Remove a chunk at given chunk coordinates and return a snapshot of it. If there
     /// is no chunk at the coordinates but entities or block entities are present, None
     /// is returned but entities and block entities are removed from the world.
     pub fn remove_chunk_snapshot(&mut self, cx: i32, cz: i32) -> Option<ChunkSnapshot> {
         let chunk_comp = self.chunks.remove(&(cx, cz))?;
+        self.active_chunks.swap_remove(&(cx, cz));
         let mut ret = None;
 
-        let entities = chunk_comp
+        let (entities, entities_ids) = chunk_comp
             .entities
             .keys()
             .filter_map(|&id| {
                 self.remove_entity_inner(id, false, "remove chunk snapshot")
                     .unwrap()
                     .inner
+                    .map(|entity| (entity, Some(id)))
             })
-            .collect();
+            .unzip();
 
         let block_entities = chunk_comp
             .block_entities
@@ -338,7 +709,10 @@ impl World {
                 cz,
                 chunk,
                 entities,
+                entities_changes: Vec::new(),
+                entities_ids,
                 block_entities,
+                light_invalid: self.relight_chunks.shift_remove(&(cx, cz)),
             });
 
             self.push_event(Event::Chunk {
@@ -367,6 +741,12 @@ impl World {
         let chunk_comp = self.chunks.entry((cx, cz)).or_default();
         let was_unloaded = chunk_comp.data.replace(chunk).is_none();
 
+        // New chunks are active until idle.
+        chunk_comp.active_time = self.time;
+        if self.chunk_freeze_delay.is_some() {
+            self.active_chunks.insert((cx, cz));
+        }
+
         if was_unloaded {
             for &index in chunk_comp.entities.values() {
                 self.entities.get_mut(index).unwrap().loaded = true;
@@ -388,6 +768,14 @@ impl World {
         self.chunks.get(&(cx, cz)).is_some_and(|c| c.data.is_some())
     }
 
+    /// Iterate over the coordinates of all chunks present in the world.
+    pub fn iter_chunks(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.chunks
+            .iter()
+            .filter(|(_, c)| c.data.is_some())
+            .map(|(&pos, _)| pos)
+    }
+
     /// Get a reference to a chunk, if existing.
     pub fn get_chunk(&self, cx: i32, cz: i32) -> Option<&Chunk> {
         self.chunks.get(&(cx, cz)).and_then(|c| c.data.as_deref())
@@ -407,6 +795,8 @@ impl World {
         let ret = chunk_comp.data.take();
 
         if ret.is_some() {
+            self.active_chunks.swap_remove(&(cx, cz));
+
             for &index in chunk_comp.entities.values() {
                 self.entities.get_mut(index).unwrap().loaded = false;
             }
@@ -425,6 +815,103 @@ impl World {
         ret
     }
 
+    /// Get the number of idle ticks before a chunk is frozen, if chunk freezing is
+    /// enabled.
+    #[inline]
+    pub fn get_chunk_freeze_delay(&self) -> Option<u64> {
+        self.chunk_freeze_delay
+    }
+
+    /// Enable freezing of chunks that have been idle for the given number of ticks, a
+    /// chunk is idle when it has no entity, no player within 8 chunks and nothing
+    /// changed in it. Frozen chunks are skipped when random ticking, until any block,
+    /// entity, block entity or scheduled tick change wakes them up. Disabled by default.
+    pub fn set_chunk_freeze_delay(&mut self, delay: Option<u64>) {
+        self.chunk_freeze_delay = delay;
+        self.active_chunks.clear();
+        if delay.is_some() {
+            for (&pos, comp) in &mut self.chunks {
+                if comp.data.is_some() {
+                    comp.active_time = self.time;
+                    self.active_chunks.insert(pos);
+                }
+            }
+        }
+    }
+
+    /// Return true if the given chunk is loaded but frozen because idle.
+    pub fn is_chunk_frozen(&self, cx: i32, cz: i32) -> bool {
+        self.chunk_freeze_delay.is_some()
+            && self.contains_chunk(cx, cz)
+            && !self.active_chunks.contains(&(cx, cz))
+    }
+
+    /// Return the number of loaded chunks that are currently frozen.
+    pub fn get_frozen_chunk_count(&self) -> usize {
+        match self.chunk_freeze_delay {
+            Some(_) => self.iter_chunks().count() - self.active_chunks.len(),
+            None => 0,
+        }
+    }
+
+    /// Return the total number of times a frozen chunk has been woken up.
+    #[inline]
+    pub fn get_chunk_wake_count(&self) -> u64 {
+        self.chunk_wake_count
+    }
+
+    /// Internal function to mark a chunk as active, waking it up if frozen.
+    fn wake_chunk(&mut self, cx: i32, cz: i32) {
+        let Some(comp) = self.chunks.get_mut(&(cx, cz)) else {
+            return;
+        };
+
+        comp.active_time = self.time;
+        if self.chunk_freeze_delay.is_some()
+            && comp.data.is_some()
+            && self.active_chunks.insert((cx, cz))
+        {
+            self.chunk_wake_count += 1;
+        }
+    }
+
+    /// Populate an existing chunk with the features of the given generator, this is
+    /// typically used for chunks that have been imported without being populated. Like
+    /// when generating, features are placed with an offset of 8 blocks, so the chunks on
+    /// +X, +Z and +X+Z must also be loaded. Features are placed in a silent scope, see
+    /// [`with_silent_changes`](Self::with_silent_changes), and the aggregated changes
+    /// are returned, none is returned if the chunk was already populated or a chunk is
+    /// missing.
+    pub fn populate_existing<G: ChunkGenerator>(
+        &mut self,
+        cx: i32,
+        cz: i32,
+        generator: &G,
+        state: &mut G::State,
+    ) -> Option<BlockChanges> {
+        if self.get_chunk(cx, cz).is_none_or(|chunk| chunk.populated) {
+            return None;
+        }
+
+        for (dcx, dcz) in [(1, 0), (0, 1), (1, 1)] {
+            if !self.contains_chunk(cx + dcx, cz + dcz) {
+                return None;
+            }
+        }
+
+        let ((), changes) =
+            self.with_silent_changes(|world| generator.gen_features(cx, cz, world, state));
+        self.get_chunk_mut(cx, cz).unwrap().populated = true;
+
+        self.push_event(Event::Chunk {
+            cx,
+            cz,
+            inner: ChunkEvent::Dirty,
+        });
+
+        Some(changes)
+    }
+
     // =================== //
     //        BLOCKS       //
     // =================== //
@@ -451,6 +938,14 @@ impl World {
                 self.schedule_light_update(pos, LightKind::Sky);
             }
 
+            self.record_silent_change(BlockChange {
+                pos,
+                prev_id,
+                prev_metadata,
+                id,
+                metadata,
+            });
+
             self.push_event(Event::Block {
                 pos,
                 inner: BlockEvent::Set {
@@ -491,6 +986,73 @@ impl World {
         Some((prev_id, prev_metadata))
     }
 
+    /// Apply a batch of block changes to the given chunk through a
+    /// [`ChunkAccessor`], with a single chunk lookup. This is equivalent to calling
+    /// [`set_block`](Self::set_block) for each change, but the height map is recomputed
+    /// once per modified column and the chunk is marked dirty once, when the batch ends.
+    /// Like [`set_block`](Self::set_block), blocks are not notified. This returns none if
+    /// the chunk is not loaded.
+    pub fn with_chunk_mut<R>(
+        &mut self,
+        cx: i32,
+        cz: i32,
+        func: impl FnOnce(&mut ChunkAccessor) -> R,
+    ) -> Option<R> {
+        let chunk = self.get_chunk_mut(cx, cz)?;
+        let mut changes = Vec::new();
+        let ret = chunk.apply_inner(Some(&mut changes), func);
+
+        if changes.is_empty() {
+            return Some(ret);
+        }
+
+        let chunk_pos = IVec3::new(cx * CHUNK_WIDTH as i32, 0, cz * CHUNK_WIDTH as i32);
+        for change in changes {
+            let ChunkChange {
+                pos,
+                prev_id,
+                prev_metadata,
+                id,
+                metadata,
+            } = change;
+            let pos = chunk_pos + pos;
+
+            if block::material::get_light_opacity(id) != block::material::get_light_opacity(prev_id)
+                || block::material::get_light_emission(id)
+                    != block::material::get_light_emission(prev_id)
+            {
+                self.schedule_light_update(pos, LightKind::Block);
+                self.schedule_light_update(pos, LightKind::Sky);
+            }
+
+            self.record_silent_change(BlockChange {
+                pos,
+                prev_id,
+                prev_metadata,
+                id,
+                metadata,
+            });
+
+            self.push_event(Event::Block {
+                pos,
+                inner: BlockEvent::Set {
+                    id,
+                    metadata,
+                    prev_id,
+                    prev_metadata,
+                },
+            });
+        }
+
+        self.push_event(Event::Chunk {
+            cx,
+            cz,
+            inner: ChunkEvent::Dirty,
+        });
+
+        Some(ret)
+    }
+
     /// Get block and metadata at given position in the world, if the chunk is not
     /// loaded, none is returned.
     pub fn get_block(&self, pos: IVec3) -> Option<(u8, u8)> {
@@ -539,6 +1101,13 @@ impl World {
         light
     }
 
+    /// Get the brightness of the block at the given position, depending on the light
+    /// levels and the dimension's ambient light.
+    #[inline]
+    pub fn get_brightness(&self, pos: IVec3) -> f32 {
+        self.dimension_info.brightness(self.get_light(pos))
+    }
+
     /// Schedule a light update to be processed in a future tick.
     ///  
     /// See [`tick_light`](Self::tick_light).
@@ -632,6 +1201,15 @@ impl World {
             .checked_add(1)
             .expect("entity count overflow");
 
+        self.insert_entity_inner(id, entity);
+        id
+    }
+
+    /// Internal function to insert an entity with the given id, that should not be
+    /// already used by another entity.
+    fn insert_entity_inner(&mut self, id: u32, entity: Box<Entity>) {
+        debug_assert!(!self.entities_id_map.contains_key(&id), "entity id in use");
+
         let kind = entity.kind();
         trace!("spawn entity #{id} ({:?})", kind);
 
@@ -644,6 +1222,7 @@ impl World {
             cz,
             loaded: chunk_comp.data.is_some(),
             kind,
+            dirty: true,
         });
 
         chunk_comp.entities.insert(id, entity_index);
@@ -658,8 +1237,6 @@ impl World {
             cz,
             inner: ChunkEvent::Dirty,
         });
-
-        id
     }
 
     /// Spawn an entity in this world, this function gives it a unique id and ensure
@@ -673,6 +1250,81 @@ impl World {
         self.spawn_entity_inner(entity.into())
     }
 
+    /// Spawn an entity in this world for the given reason, like [`spawn_entity`], but
+    /// the [spawn filter](Self::set_spawn_filter) is checked before and may veto or
+    /// alter the entity. If spawned, an [`EntityEvent::Spawned`] event is pushed after the
+    /// usual spawn event and the new entity id is returned.
+    ///
+    /// [`spawn_entity`]: Self::spawn_entity
+    pub fn spawn_entity_with_reason(
+        &mut self,
+        entity: impl Into<Box<Entity>>,
+        reason: SpawnReason,
+    ) -> Option<u32> {
+        let mut entity = entity.into();
+        if let Some(filter) = self.spawn_filter {
+            if !filter(self, &mut entity, reason) {
+                return None;
+            }
+        }
+
+        let id = self.spawn_entity_inner(entity);
+        self.push_event(Event::Entity {
+            id,
+            inner: EntityEvent::Spawned { reason },
+        });
+        Some(id)
+    }
+
+    /// Spawn a new entity of the given kind at the given position, its components are
+    /// initialized with default values, and randomized like a natural spawn for some
+    /// kinds, such as the slime size or sheep color. The entity is persistent and living
+    /// entities are artificial, therefore never naturally despawned. The new entity id
+    /// is returned.
+    pub fn spawn_entity_of_kind(&mut self, kind: EntityKind, pos: DVec3) -> u32 {
+        let mut entity = kind.new_default(pos);
+        entity.0.persistent = true;
+        if let Entity(_, BaseKind::Living(living, _)) = &mut *entity {
+            living.artificial = true;
+        }
+
+        entity.init_natural_spawn(self);
+        self.spawn_entity(entity)
+    }
+
+    /// Return the id that will be given to the next spawned entity. This should be
+    /// persisted along with the world, so that ids of saved entities are never given to
+    /// other entities, see [`set_next_entity_id`](Self::set_next_entity_id).
+    #[inline]
+    pub fn get_next_entity_id(&self) -> u32 {
+        self.entities_count
+    }
+
+    /// Set the id that will be given to the next spawned entity, this should be called
+    /// with the persisted id before inserting any chunk. The id is never set below the
+    /// ids already given to entities.
+    pub fn set_next_entity_id(&mut self, id: u32) {
+        self.entities_count = self.entities_count.max(id);
+    }
+
+    /// Set or clear the display name of an entity, see [`Base::name`]. An event is
+    /// pushed if the name has changed, false is returned if the entity is not existing.
+    pub fn set_entity_name(&mut self, id: u32, name: Option<String>) -> bool {
+        let Some(Entity(base, _)) = self.get_entity_mut(id) else {
+            return false;
+        };
+
+        if base.name != name {
+            base.name = name;
+            self.push_event(Event::Entity {
+                id,
+                inner: EntityEvent::Name,
+            });
+        }
+
+        true
+    }
+
     /// Return true if an entity is present from its id.
     pub fn contains_entity(&self, id: u32) -> bool {
         self.entities_id_map.contains_key(&id)
@@ -695,11 +1347,60 @@ impl World {
     /// Get a generic entity from its unique id. This generic entity can later be checked
     /// for being of a particular type. None can be returned if no entity is existing for
     /// this id or if the entity is the current entity being updated.
+    ///
+    /// The entity is considered changed since its last save, see [`is_entity_dirty`].
+    ///
+    /// [`is_entity_dirty`]: Self::is_entity_dirty
     pub fn get_entity_mut(&mut self, id: u32) -> Option<&mut Entity> {
         let index = *self.entities_id_map.get(&id)?;
+        self.set_entity_dirty(id);
         self.entities.get_mut(index).unwrap().inner.as_deref_mut()
     }
 
+    /// Return true if the entity with the given id has changed since the last snapshot
+    /// of its chunk taken for saving, see [`take_chunk_save_snapshot`]. An entity is
+    /// changed when spawned, when mutably accessed from outside of its tick or when
+    /// any event is pushed for it. Entities whose only change is an internal timer
+    /// are not considered changed.
+    ///
+    /// [`take_chunk_save_snapshot`]: Self::take_chunk_save_snapshot
+    pub fn is_entity_dirty(&self, id: u32) -> bool {
+        self.entities_id_map
+            .get(&id)
+            .is_some_and(|&index| self.entities.get(index).unwrap().dirty)
+    }
+
+    /// Internal function to mark an entity as changed since its last save, the chunk
+    /// of the entity is marked dirty if the entity was not already.
+    fn set_entity_dirty(&mut self, id: u32) {
+        let Some(&index) = self.entities_id_map.get(&id) else {
+            return;
+        };
+
+        let comp = self.entities.get_mut(index).unwrap();
+        if !comp.dirty {
+            comp.dirty = true;
+            let (cx, cz) = (comp.cx, comp.cz);
+            self.push_event(Event::Chunk {
+                cx,
+                cz,
+                inner: ChunkEvent::Dirty,
+            });
+        }
+    }
+
+    /// Hurt the entity with the given id, the damage is applied on its next tick, where
+    /// living entities handle their invulnerability time, knock back from the origin
+    /// entity and death, pushing [`EntityEvent::Damage`] and [`EntityEvent::Dead`]
+    /// events. Returns false if the entity doesn't exist.
+    pub fn hurt_entity(&mut self, id: u32, damage: u16, origin_id: Option<u32>) -> bool {
+        let Some(Entity(base, _)) = self.get_entity_mut(id) else {
+            return false;
+        };
+        base.hurt.push(Hurt { damage, origin_id });
+        true
+    }
+
     /// Remove an entity with given id, returning some boxed entity is successful. This
     /// returns true if the entity has been successfully removed removal, the entity's
     /// storage is guaranteed to be freed after return, but the entity footprint in the
@@ -779,10 +1480,16 @@ impl World {
             }
         }
 
-        self.push_event(Event::Entity {
+        // The entity is no longer stored, so we give its kind and chunk to subscriptions.
+        let event = Event::Entity {
             id,
             inner: EntityEvent::Remove,
-        });
+        };
+        self.push_subscribed_event(&event, Some((comp.kind, cx, cz)));
+        if let Some(events) = &mut self.events {
+            events.push(event);
+        }
+
         if has_chunk {
             self.push_event(Event::Chunk {
                 cx,
@@ -836,6 +1543,12 @@ impl World {
     fn set_block_entity_inner(&mut self, pos: IVec3, block_entity: Box<BlockEntity>) {
         trace!("set block entity {pos}");
 
+        if block_entity.needs_tick() {
+            self.block_entities_ticking.insert(pos);
+        } else {
+            self.block_entities_ticking.swap_remove(&pos);
+        }
+
         let (cx, cz) = calc_chunk_pos_unchecked(pos);
         match self.block_entities_pos_map.entry(pos) {
             hash_map::Entry::Occupied(o) => {
@@ -843,9 +1556,6 @@ impl World {
                 // replace it in-place, this avoid all the insertion of cache coherency.
                 let index = *o.into_mut();
                 self.block_entities.get_mut(index).unwrap().inner = Some(block_entity);
-                // We also need to invalid the value to remove it from the current tick
-                // linked list, if any ticking is currently happening.
-                self.block_entities.invalidate(index);
 
                 self.push_event(Event::BlockEntity {
                     pos,
@@ -894,6 +1604,13 @@ impl World {
         self.block_entities.len()
     }
 
+    /// Return the number of block entities in the world that need to be ticked, loaded
+    /// or not, see [`BlockEntity::needs_tick`].
+    #[inline]
+    pub fn get_ticking_block_entity_count(&self) -> usize {
+        self.block_entities_ticking.len()
+    }
+
     /// Get a block entity from its position.
     pub fn get_block_entity(&self, pos: IVec3) -> Option<&BlockEntity> {
         let index = *self.block_entities_pos_map.get(&pos)?;
@@ -928,6 +1645,7 @@ impl World {
         has_chunk: bool,
     ) -> Option<BlockEntityComponent> {
         let index = self.block_entities_pos_map.remove(&pos)?;
+        self.block_entities_ticking.swap_remove(&pos);
         trace!("remove block entity {pos}");
 
         let comp = self.block_entities.remove(index);
@@ -998,21 +1716,50 @@ impl World {
     /// Schedule a tick update to happen at the given position, for the given block id
     /// and with a given delay in ticks. The block tick is not scheduled if a tick was
     /// already scheduled for that exact block id and position.
+    #[inline]
     pub fn schedule_block_tick(&mut self, pos: IVec3, id: u8, delay: u64) {
+        self.schedule_block_tick_with_priority(pos, id, delay, BlockTickPriority::Normal);
+    }
+
+    /// Schedule a tick update like [`schedule_block_tick`](Self::schedule_block_tick),
+    /// but with a given priority: ticks scheduled for the same time are run in priority
+    /// order, and then in scheduling order.
+    ///
+    /// PARITY: The Notchian server has no priority, all builtin blocks schedule their
+    /// ticks with the normal priority.
+    pub fn schedule_block_tick_with_priority(
+        &mut self,
+        pos: IVec3,
+        id: u8,
+        delay: u64,
+        priority: BlockTickPriority,
+    ) {
+        // REF: World::scheduleBlockUpdate, when scheduled updates are immediate.
+        if let Some(immediate) = &mut self.block_ticks_immediate {
+            let min = pos - IVec3::splat(8);
+            let max = pos + IVec3::splat(8);
+            let (min_cx, min_cz) = calc_chunk_pos_unchecked(min);
+            let (max_cx, max_cz) = calc_chunk_pos_unchecked(max);
+            let loaded = (min_cx..=max_cx)
+                .all(|cx| (min_cz..=max_cz).all(|cz| self.chunks.contains_key(&(cx, cz))));
+            if loaded {
+                immediate.push(BlockTickState { pos, id });
+            }
+            return;
+        }
+
         let uid = self.block_ticks_count;
         self.block_ticks_count = self
             .block_ticks_count
             .checked_add(1)
             .expect("scheduled ticks count overflow");
 
-        let state = BlockTickState { pos, id };
-        if self.block_ticks_states.insert(state) {
-            self.block_ticks.insert(BlockTick {
-                time: self.time + delay,
-                state,
-                uid,
-            });
-        }
+        self.insert_block_tick(BlockTick {
+            uid,
+            time: self.time + delay,
+            priority,
+            state: BlockTickState { pos, id },
+        });
     }
 
     /// Return the current number of scheduled block ticks waiting.
@@ -1021,6 +1768,110 @@ impl World {
         self.block_ticks.len()
     }
 
+    /// Limit the number of scheduled block ticks waiting in each chunk, new ticks that
+    /// would exceed this limit are discarded. This avoids pathological growth of the
+    /// scheduled ticks, for example with huge redstone clocks. No limit by default.
+    pub fn set_block_tick_chunk_limit(&mut self, limit: Option<usize>) {
+        self.block_ticks_chunk_limit = limit;
+        self.block_ticks_chunks.clear();
+        if limit.is_some() {
+            for tick in &self.block_ticks {
+                let (cx, cz) = calc_chunk_pos_unchecked(tick.state.pos);
+                *self.block_ticks_chunks.entry((cx, cz)).or_default() += 1;
+            }
+        }
+    }
+
+    /// Get the maximum number of scheduled block ticks waiting in each chunk, if any.
+    #[inline]
+    pub fn get_block_tick_chunk_limit(&self) -> Option<usize> {
+        self.block_ticks_chunk_limit
+    }
+
+    /// Return the total number of scheduled block ticks that have been discarded because
+    /// their chunk reached the limit.
+    #[inline]
+    pub fn get_block_tick_discarded_count(&self) -> u64 {
+        self.block_ticks_discarded
+    }
+
+    /// Internal function to insert a scheduled block tick, if not already scheduled for
+    /// the same position and block id, and if the chunk limit is not reached. Returns
+    /// true if inserted.
+    fn insert_block_tick(&mut self, tick: BlockTick) -> bool {
+        if self.block_ticks_states.contains(&tick.state) {
+            return false;
+        }
+
+        if let Some(limit) = self.block_ticks_chunk_limit {
+            let (cx, cz) = calc_chunk_pos_unchecked(tick.state.pos);
+            let count = self.block_ticks_chunks.entry((cx, cz)).or_default();
+            if *count >= limit {
+                self.block_ticks_discarded += 1;
+                return false;
+            }
+            *count += 1;
+        }
+
+        let (cx, cz) = calc_chunk_pos_unchecked(tick.state.pos);
+        self.wake_chunk(cx, cz);
+
+        self.block_ticks_states.insert(tick.state);
+        self.block_ticks.insert(tick);
+        true
+    }
+
+    /// Internal function to pop the first scheduled block tick, if its time is strictly
+    /// before the given one.
+    fn pop_block_tick(&mut self, before_time: u64) -> Option<BlockTick> {
+        if self.block_ticks.first()?.time >= before_time {
+            return None;
+        }
+
+        let tick = self.block_ticks.pop_first().unwrap();
+        assert!(self.block_ticks_states.remove(&tick.state));
+
+        if self.block_ticks_chunk_limit.is_some() {
+            let (cx, cz) = calc_chunk_pos_unchecked(tick.state.pos);
+            if let hash_map::Entry::Occupied(mut o) = self.block_ticks_chunks.entry((cx, cz)) {
+                *o.get_mut() -= 1;
+                if *o.get() == 0 {
+                    o.remove();
+                }
+            }
+        }
+
+        Some(tick)
+    }
+
+    /// Immediately tick the block at the given position if it has the given id, every
+    /// block tick scheduled while doing so is also run immediately instead of being
+    /// queued, only if all chunks in 8 blocks around are loaded. Ticks already queued
+    /// are left untouched. This is used by feature generators to settle fluids.
+    ///
+    /// REF: WorldGenLiquids::generate, with World::scheduledUpdatesAreImmediate
+    ///
+    /// PARITY: The Notchian server runs the immediate ticks recursively, we run them in
+    ///  scheduling order to avoid deep recursions on long falls.
+    pub fn tick_block_immediate(&mut self, pos: IVec3, id: u8) {
+        debug_assert!(self.block_ticks_immediate.is_none());
+        let mut pending = VecDeque::from([BlockTickState { pos, id }]);
+        self.block_ticks_immediate = Some(Vec::new());
+
+        while let Some(state) = pending.pop_front() {
+            if let Some((block, metadata)) = self.get_block(state.pos) {
+                if block == state.id {
+                    self.tick_block_unchecked(state.pos, block, metadata, false);
+                }
+            }
+            if let Some(immediate) = &mut self.block_ticks_immediate {
+                pending.extend(immediate.drain(..));
+            }
+        }
+
+        self.block_ticks_immediate = None;
+    }
+
     // =================== //
     //      ITERATORS      //
     // =================== //
@@ -1066,6 +1917,22 @@ impl World {
         EntitiesIterMut(self.entities.iter_mut())
     }
 
+    /// Iterate over all entities in the world that have the given component, the base
+    /// of the entity is returned along with the component.
+    /// *This function can't return the current updated entity.*
+    #[inline]
+    pub fn iter_entities_with<C: Component>(&self) -> EntitiesWithIter<'_, C> {
+        EntitiesWithIter(self.iter_entities(), PhantomData)
+    }
+
+    /// Iterate over all entities in the world that have the given component through
+    /// mutable references, the base of the entity is returned along with the component.
+    /// *This function can't return the current updated entity.*
+    #[inline]
+    pub fn iter_entities_with_mut<C: Component>(&mut self) -> EntitiesWithIterMut<'_, C> {
+        EntitiesWithIterMut(self.iter_entities_mut(), PhantomData)
+    }
+
     /// Iterate over all player entities in the world.
     /// *This function can't return the current updated entity.*
     #[inline]
@@ -1169,7 +2036,7 @@ impl World {
     /// Tick the world, this ticks all entities.
     /// TODO: Guard this from being called recursively from tick functions.
     pub fn tick(&mut self) {
-        if self.time % 20 == 0 {
+        if self.time.is_multiple_of(20) {
             // println!("time: {}", self.time);
             // println!("weather: {:?}", self.weather);
             // println!("weather_next_time: {}", self.weather_next_time);
@@ -1180,22 +2047,57 @@ impl World {
         // TODO: Wake up all sleeping player if day time.
 
         self.tick_natural_spawn();
+        self.tick_chunk_freeze();
 
         self.tick_sky_light();
 
         self.time += 1;
 
         self.tick_blocks();
+        self.tick_item_cleanup();
         self.tick_entities();
         self.tick_block_entities();
 
+        self.tick_relight();
         self.tick_light(1000);
     }
 
+    /// Wake up chunks around players and freeze chunks that have been idle for too long.
+    fn tick_chunk_freeze(&mut self) {
+        /// The maximum chebyshev distance of chunks kept active by players.
+        const PLAYER_MAX_DIST: i32 = 8;
+
+        let Some(delay) = self.chunk_freeze_delay else {
+            return;
+        };
+
+        let players_chunks = self
+            .player_entities_map
+            .values()
+            .map(|&index| self.entities.get(index).unwrap())
+            .map(|comp| (comp.cx, comp.cz))
+            .collect::<Vec<_>>();
+
+        for (pcx, pcz) in players_chunks {
+            for cx in pcx - PLAYER_MAX_DIST..=pcx + PLAYER_MAX_DIST {
+                for cz in pcz - PLAYER_MAX_DIST..=pcz + PLAYER_MAX_DIST {
+                    self.wake_chunk(cx, cz);
+                }
+            }
+        }
+
+        let time = self.time;
+        let chunks = &self.chunks;
+        self.active_chunks.retain(|pos| {
+            let comp = &chunks[pos];
+            !comp.entities.is_empty() || time.saturating_sub(comp.active_time) < delay
+        });
+    }
+
     /// Update current weather in the world.
     fn tick_weather(&mut self) {
-        // No weather in the nether.
-        if self.dimension == Dimension::Nether {
+        // No weather in dimensions without sky, like the nether.
+        if !self.dimension_info.has_sky {
             return;
         }
 
@@ -1227,19 +2129,8 @@ impl World {
         /// The minimum distance required from any player entity to spawn.
         const SPAWN_MIN_DIST_SQUARED: f64 = 24.0 * 24.0;
 
-        // Categories of entities to spawn, also used to count how many are currently
-        // loaded in the world. We have 4 slots in this array because there are 4
-        // entity categories.
-        let mut categories_count = [0; EntityCategory::ALL.len()];
-
-        // Count every entity category.
-        for comp in self.entities.iter() {
-            if comp.loaded {
-                if let Some(entity) = comp.inner.as_deref() {
-                    categories_count[entity.category() as usize] += 1;
-                }
-            }
-        }
+        // Count loaded entities of every category against their mob cap.
+        let categories_count = self.count_mob_caps();
 
         // Temporary list of chunks loaded by data and players in range.
         let mut loaded_chunks = LOADED_CHUNKS.take();
@@ -1257,16 +2148,24 @@ impl World {
                     comp.cx.abs_diff(cx) <= CHUNK_MAX_DIST && comp.cz.abs_diff(cz) <= CHUNK_MAX_DIST
                 })
         });
+        let area = self.simulation_area();
+        loaded_chunks.retain(|&(cx, cz)| area.contains(cx, cz));
 
-        for category in EntityCategory::ALL {
-            let max_world_count = category.natural_spawn_max_world_count();
+        // The spawn table is kept for all categories.
+        let tables = data::tables();
 
+        for category in EntityCategory::ALL {
             // Skip the category if it cannot spawn.
-            if max_world_count == 0 {
+            if category.natural_spawn_max_world_count() == 0 {
+                continue;
+            }
+            // Skip hostile entities on peaceful difficulty.
+            if category == EntityCategory::Mob && self.difficulty == Difficulty::Peaceful {
                 continue;
             }
             // Skip the category if it already has enough loaded entities.
-            if categories_count[category as usize] > max_world_count * self.chunks.len() / 256 {
+            let max_count = category.natural_spawn_max_world_count() * self.chunks.len() / 256;
+            if categories_count[category as usize] > max_count {
                 continue;
             }
 
@@ -1276,7 +2175,7 @@ impl World {
                 let chunk_data = chunk.data.as_deref().unwrap();
 
                 let biome = chunk_data.get_biome(IVec3::ZERO);
-                let kinds = biome.natural_entity_kinds(category);
+                let kinds = tables.spawns.get(biome, category);
 
                 // Ignore this chunk is its biome cannot spawn any entity.
                 if kinds.is_empty() {
@@ -1296,13 +2195,13 @@ impl World {
                     continue;
                 }
 
-                let chance_sum = kinds.iter().map(|kind| kind.chance).sum::<u16>();
-                let index = self.rand.next_int_bounded(chance_sum as i32) as u16;
+                let chance_sum = kinds.iter().map(|kind| kind.chance as u32).sum::<u32>();
+                let index = self.rand.next_int_bounded(chance_sum as i32) as u32;
                 let mut chance_acc = 0;
                 let mut kind = kinds[0].kind;
 
                 for test_kind in kinds {
-                    chance_acc += test_kind.chance;
+                    chance_acc += test_kind.chance as u32;
                     if index < chance_acc {
                         kind = test_kind.kind;
                         break;
@@ -1389,7 +2288,13 @@ impl World {
                             continue;
                         }
 
-                        self.spawn_entity(entity);
+                        if self
+                            .spawn_entity_with_reason(entity, SpawnReason::Natural)
+                            .is_none()
+                        {
+                            continue;
+                        }
+
                         spawn_count += 1;
                         if spawn_count >= max_chunk_count {
                             break 'pack;
@@ -1415,9 +2320,9 @@ impl World {
             half_turn -= 1.0;
         }
 
-        let celestial_angle = match self.dimension {
-            Dimension::Nether => 0.5,
-            _ => {
+        let celestial_angle = match self.dimension_info.fixed_celestial_angle {
+            Some(angle) => angle,
+            None => {
                 half_turn
                     + (1.0 - ((half_turn * std::f32::consts::PI).cos() + 1.0) / 2.0 - half_turn)
                         / 3.0
@@ -1439,36 +2344,99 @@ impl World {
     fn tick_blocks(&mut self) {
         debug_assert_eq!(self.block_ticks.len(), self.block_ticks_states.len());
 
-        // Schedule ticks...
-        while let Some(tick) = self.block_ticks.first() {
-            if self.time > tick.time {
-                // This tick should be activated.
-                let tick = self.block_ticks.pop_first().unwrap();
-                assert!(self.block_ticks_states.remove(&tick.state));
-                // Check coherency of the scheduled tick and current block.
-                if let Some((id, metadata)) = self.get_block(tick.state.pos) {
-                    if id == tick.state.id {
-                        self.tick_block_unchecked(tick.state.pos, id, metadata, false);
-                    }
+        let area = self.simulation_area();
+
+        // Schedule ticks, our set is ordered by time first, so we stop when past the
+        // current time. Ticks of chunks that are not simulated are postponed.
+        let mut postponed_ticks = Vec::new();
+        while let Some(tick) = self.pop_block_tick(self.time) {
+            let (cx, cz) = calc_chunk_pos_unchecked(tick.state.pos);
+            if !area.contains(cx, cz) {
+                postponed_ticks.push(tick);
+                continue;
+            }
+            // Check coherency of the scheduled tick and current block.
+            if let Some((id, metadata)) = self.get_block(tick.state.pos) {
+                if id == tick.state.id {
+                    self.tick_block_unchecked(tick.state.pos, id, metadata, false);
                 }
-            } else {
-                // Our set is ordered by time first, so we break when past current time.
-                break;
             }
         }
 
+        for tick in postponed_ticks {
+            self.insert_block_tick(tick);
+        }
+
         // Random ticking...
         let mut pending_random_ticks = RANDOM_TICKS_PENDING.take();
         debug_assert!(pending_random_ticks.is_empty());
 
         // Lightning bolts are rare enough to just use a non cached vector.
         let mut lightning_bolt = Vec::new();
+        // Cave sounds are played at most once every 6000 ticks.
+        let mut cave_sounds = Vec::new();
+        // Snowing positions are also rare enough, at most one per chunk every 16 ticks.
+        let mut snowing = Vec::new();
+
+        // Players positions are only needed when a cave sound can be played.
+        self.cave_sound_time = self.cave_sound_time.saturating_sub(1);
+        let players_pos = if self.cave_sound_time == 0 {
+            self.iter_player_entities()
+                .map(|(_, Entity(base, _))| base.pos)
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        // Random tick only on loaded and simulated chunks, if chunk freezing is enabled
+        // only the active chunks are iterated.
+        let (active_chunks, all_chunks) = match self.chunk_freeze_delay {
+            Some(_) => (Some(&self.active_chunks), None),
+            None => (None, Some(&self.chunks)),
+        };
+        let chunks_pos = active_chunks
+            .into_iter()
+            .flatten()
+            .chain(all_chunks.into_iter().flat_map(|chunks| chunks.keys()));
 
-        // Random tick only on loaded chunks.
-        for (&(cx, cz), chunk) in &mut self.chunks {
-            if let Some(chunk_data) = &chunk.data {
+        for &(cx, cz) in chunks_pos {
+            if !area.contains(cx, cz) {
+                continue;
+            }
+            if let Some(chunk_data) = &self.chunks[&(cx, cz)].data {
                 let chunk_pos = IVec3::new(cx * CHUNK_WIDTH as i32, 0, cz * CHUNK_WIDTH as i32);
 
+                // Try to play a cave sound in a dark air block, if the closest player in
+                // 8 blocks is not too close.
+                // REF: World::updateBlocksAndPlayMusic
+                if self.cave_sound_time == 0 {
+                    self.random_ticks_seed = self
+                        .random_ticks_seed
+                        .wrapping_mul(3)
+                        .wrapping_add(1013904223);
+
+                    let rand = self.random_ticks_seed >> 2;
+                    let pos = IVec3::new(rand & 15, (rand >> 16) & 127, (rand >> 8) & 15);
+
+                    if chunk_data.get_block(pos).0 == block::AIR
+                        && chunk_data.get_block_light(pos) as i32 <= self.rand.next_int_bounded(8)
+                        && chunk_data.get_sky_light(pos) == 0
+                    {
+                        let sound_pos = (chunk_pos + pos).as_dvec3() + 0.5;
+                        let closest_dist = players_pos
+                            .iter()
+                            .map(|player_pos| player_pos.distance_squared(sound_pos))
+                            .filter(|&dist| dist < 8.0 * 8.0)
+                            .min_by(f64::total_cmp);
+
+                        if closest_dist.is_some_and(|dist| dist > 2.0 * 2.0) {
+                            let pitch = 0.8 + self.rand.next_float() * 0.2;
+                            cave_sounds.push((sound_pos, pitch));
+                            self.cave_sound_time = self.rand.next_int_bounded(12000) as u16 + 6000;
+                        }
+                    }
+                }
+
                 // Try to spawn lightning bolt.
                 if self.weather == Weather::Thunder && self.rand.next_int_bounded(100000) == 0 {
                     self.random_ticks_seed = self
@@ -1483,7 +2451,27 @@ impl World {
                     lightning_bolt.push(chunk_pos + pos);
                 }
 
-                // TODO: Random snowing.
+                // Random snowing and water freezing in cold biomes.
+                // PARITY: The Notchian impl search for the top solid or liquid block,
+                //  we use the height map instead.
+                if self.rand.next_int_bounded(16) == 0 {
+                    self.random_ticks_seed = self
+                        .random_ticks_seed
+                        .wrapping_mul(3)
+                        .wrapping_add(1013904223);
+
+                    let rand = self.random_ticks_seed >> 2;
+                    let mut pos = IVec3::new(rand & 15, 0, (rand >> 8) & 15);
+                    pos.y = chunk_data.get_height(pos) as i32;
+
+                    if pos.y > 0
+                        && pos.y < CHUNK_HEIGHT as i32
+                        && chunk_data.get_biome(pos).has_snow()
+                        && chunk_data.get_block_light(pos) < 10
+                    {
+                        snowing.push(chunk_pos + pos);
+                    }
+                }
 
                 // Minecraft run 80 random ticks per tick per chunk.
                 for _ in 0..80 {
@@ -1505,21 +2493,42 @@ impl World {
             self.tick_block_unchecked(pos, id, metadata, true);
         }
 
+        for pos in snowing.drain(..) {
+            let below_pos = pos - IVec3::Y;
+
+            if self.weather != Weather::Clear
+                && self.is_block_air(pos)
+                && self.is_block_opaque_cube(below_pos)
+                && self.is_block_solid(below_pos)
+            {
+                self.set_block_notify(pos, block::SNOW, 0);
+            }
+
+            if let Some((block::WATER_STILL, 0)) = self.get_block(below_pos) {
+                self.set_block_notify(below_pos, block::ICE, 0);
+            }
+        }
+
         for pos in lightning_bolt.drain(..) {
             if self.get_local_weather(pos) == LocalWeather::Rain {
                 self.spawn_entity(LightningBolt::new_default(pos.as_dvec3()));
             }
         }
 
+        for (pos, pitch) in cave_sounds {
+            self.push_event(Event::CaveSound { pos, pitch });
+        }
+
         RANDOM_TICKS_PENDING.set(pending_random_ticks);
     }
 
     /// Internal function to tick all entities.
     fn tick_entities(&mut self) {
+        let area = self.simulation_area();
         self.entities.reset();
 
         while let Some((_, comp)) = self.entities.current_mut() {
-            if !comp.loaded {
+            if !comp.loaded || !area.contains(comp.cx, comp.cz) {
                 self.entities.advance();
                 continue;
             }
@@ -1587,12 +2596,24 @@ impl World {
         }
     }
 
+    /// Tick all loaded block entities that need ticking, block entities set while
+    /// ticking are only ticked on the next tick.
     fn tick_block_entities(&mut self) {
-        self.block_entities.reset();
+        let ticking = self
+            .block_entities_ticking
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+
+        for pos in ticking {
+            // The block entity may have been removed or replaced while ticking.
+            if !self.block_entities_ticking.contains(&pos) {
+                continue;
+            }
 
-        while let Some((_, comp)) = self.block_entities.current_mut() {
+            let index = self.block_entities_pos_map[&pos];
+            let comp = self.block_entities.get_mut(index).unwrap();
             if !comp.loaded {
-                self.block_entities.advance();
                 continue;
             }
 
@@ -1601,23 +2622,28 @@ impl World {
                 .take()
                 .expect("block entity was already being updated");
 
-            let pos = comp.pos;
             block_entity.tick(self, pos);
 
-            // Get the component again and re-insert the block entity.
-            if let Some((_, comp)) = self.block_entities.current_mut() {
-                comp.inner = Some(block_entity);
+            // Get the component again and re-insert the block entity, only if it has not
+            // been removed or replaced while ticking.
+            if let Some(&index) = self.block_entities_pos_map.get(&pos) {
+                let comp = self.block_entities.get_mut(index).unwrap();
+                if comp.inner.is_none() {
+                    comp.inner = Some(block_entity);
+                }
             }
-
-            self.block_entities.advance();
         }
     }
 
-    /// Tick pending light updates for a maximum number of light updates.
+    /// Tick pending light updates for a maximum number of light updates. A single
+    /// [`ChunkEvent::Light`] event is pushed for each chunk where light has changed.
     pub fn tick_light(&mut self, limit: usize) {
         // IMPORTANT NOTE: This algorithm is terrible but works, I've been trying to come
         // with a better one but it has been too complicated so far.
 
+        // Area of changed light for each chunk, min and max included.
+        let mut changed_chunks = IndexMap::<(i32, i32), (IVec3, IVec3)>::new();
+
         for _ in 0..limit {
             let Some(update) = self.light_updates.pop_front() else {
                 break;
@@ -1689,11 +2715,11 @@ impl World {
             }
 
             if changed {
-                self.push_event(Event::Chunk {
-                    cx,
-                    cz,
-                    inner: ChunkEvent::Dirty,
-                });
+                let (min, max) = changed_chunks
+                    .entry((cx, cz))
+                    .or_insert((update.pos, update.pos));
+                *min = min.min(update.pos);
+                *max = max.max(update.pos);
             }
 
             if changed && update.credit >= 1 {
@@ -1712,16 +2738,20 @@ impl World {
                 }
             }
         }
-    }
-}
 
-/// Types of dimensions, used for ambient effects in the world.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Dimension {
-    /// The overworld dimension with a blue sky and day cycles.
-    Overworld,
-    /// The creepy nether dimension.
-    Nether,
+        for ((cx, cz), (min, max)) in changed_chunks {
+            self.push_event(Event::Chunk {
+                cx,
+                cz,
+                inner: ChunkEvent::Dirty,
+            });
+            self.push_event(Event::Chunk {
+                cx,
+                cz,
+                inner: ChunkEvent::Light { min, max },
+            });
+        }
+    }
 }
 
 /// Type of weather currently in the world.
@@ -1735,6 +2765,51 @@ pub enum Weather {
     Thunder,
 }
 
+/// Difficulty of a world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Difficulty {
+    /// No hostile entities, players regenerate their health.
+    Peaceful = 0,
+    /// Damage dealt by hostile entities to players is reduced.
+    Easy = 1,
+    /// Damage dealt by hostile entities is not scaled.
+    #[default]
+    Normal = 2,
+    /// Damage dealt by hostile entities to players is increased.
+    Hard = 3,
+}
+
+impl Difficulty {
+    /// Get a difficulty from its numeric id, none if invalid.
+    pub fn from_id(id: u8) -> Option<Self> {
+        Some(match id {
+            0 => Self::Peaceful,
+            1 => Self::Easy,
+            2 => Self::Normal,
+            3 => Self::Hard,
+            _ => return None,
+        })
+    }
+
+    /// Get the numeric id of this difficulty.
+    #[inline]
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+
+    /// Scale the damage dealt by a hostile entity to a player depending on difficulty.
+    ///
+    /// REF: EntityPlayer::attackEntityFrom
+    pub fn scale_player_damage(self, damage: u16) -> u16 {
+        match self {
+            Self::Peaceful => 0,
+            Self::Easy => damage / 3 + 1,
+            Self::Normal => damage,
+            Self::Hard => damage * 3 / 2,
+        }
+    }
+}
+
 /// Type of weather at a specific position.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LocalWeather {
@@ -1770,18 +2845,23 @@ impl Light {
         u8::max(self.block, self.sky_real)
     }
 
-    /// Calculate the block brightness from its light levels.
+    /// Calculate the block brightness from its light levels, with the overworld ambient
+    /// light, see [`DimensionInfo::brightness`] for other dimensions.
     #[inline]
     pub fn brightness(self) -> f32 {
-        // TODO: In nether, OFFSET is 0.1
-        const OFFSET: f32 = 0.05;
+        self.brightness_with(DimensionInfo::OVERWORLD.ambient_light)
+    }
+
+    /// Calculate the block brightness from its light levels and the given ambient light.
+    #[inline]
+    pub fn brightness_with(self, ambient_light: f32) -> f32 {
         let base = 1.0 - self.max_real() as f32 / 15.0;
-        (1.0 - base) * (base * 3.0 + 1.0) * (1.0 - OFFSET) + OFFSET
+        (1.0 - base) * (base * 3.0 + 1.0) * (1.0 - ambient_light) + ambient_light
     }
 }
 
 /// Different kind of lights in the word.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LightKind {
     /// Block light level, the light spread in all directions and blocks have a minimum
     /// opacity of 1 in all directions, each block has its own light emission.
@@ -1838,6 +2918,32 @@ pub enum Event {
         /// Radius of the explosion around center.
         radius: f32,
     },
+    /// A lightning bolt entity has struck at the given position, this is pushed once
+    /// when the bolt is first ticked. Lightning bolts are not tracked like other
+    /// entities because they are only a visual effect for the client.
+    Lightning {
+        /// The unique id of the lightning bolt entity.
+        id: u32,
+        /// The position of the strike.
+        pos: DVec3,
+    },
+    /// All item entities will be removed by a cleanup sweep after the given delay, see
+    /// [`World::set_item_cleanup_interval`].
+    ItemCleanup {
+        /// The delay before the sweep, in ticks.
+        delay: u64,
+    },
+    /// Play the ambient cave sound at the given position, this is played randomly in
+    /// dark air blocks near players.
+    ///
+    /// PARITY: The Notchian client plays cave sounds by itself, just like the music, no
+    /// packet exists in this version to play them from the server.
+    CaveSound {
+        /// Center of the air block where the sound is played.
+        pos: DVec3,
+        /// The pitch of the sound, between 0.8 and 1.0.
+        pitch: f32,
+    },
     /// An event to debug and spawn block break particles at the given position.
     DebugParticle {
         /// The block position to spawn particles at.
@@ -1861,12 +2967,20 @@ pub enum BlockEvent {
         /// Previous block metadata.
         prev_metadata: u8,
     },
-    /// Play the block activation sound at given position and id/metadata.
-    Sound {
-        /// Current id of the block.
+    /// A block has been destroyed, its break sound and particles should be played.
+    Break {
+        /// The broken block id.
         id: u8,
-        /// Current metadata of the block.
+        /// The broken block metadata.
         metadata: u8,
+        /// The entity that broke the block, if any. Its client already plays the break
+        /// effect by itself.
+        origin_id: Option<u32>,
+    },
+    /// Play an effect at the given position.
+    Effect {
+        /// The effect to play.
+        effect: Effect,
     },
     /// A piston has been extended or retracted at the given position.
     Piston {
@@ -1884,11 +2998,48 @@ pub enum BlockEvent {
     },
 }
 
+/// A sound or particle effect played at a block position, these are the auxiliary
+/// effects known by the Notchian client.
+/// REF: World::playAuxSFX
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// The click sound, played by a dispenser when dispensing.
+    Click,
+    /// The higher pitched click sound, played by an empty dispenser.
+    ClickHigh,
+    /// The bow sound, played by a dispenser when shooting an arrow.
+    Bow,
+    /// The sound of a door or trapdoor opening or closing.
+    Door,
+    /// The fizz sound, played when something is burned, such as an item in lava.
+    Fizz,
+    /// Start playing the record with the given item id, or stop the current record if
+    /// zero, this is used by jukeboxes.
+    Record(u32),
+    /// Smoke particles, spawned toward the given horizontal face or centered if none.
+    Smoke(Option<Face>),
+    /// The break sound and particles of the given block id.
+    BlockBreak(u8),
+}
+
 /// An event with an entity.
 #[derive(Debug, Clone, PartialEq)]
 pub enum EntityEvent {
     /// The entity has been spawned. The initial chunk position is given.
     Spawn,
+    /// The entity has been spawned for the given reason and accepted by the spawn filter,
+    /// this is pushed just after the [`EntityEvent::Spawn`] event, only for entities
+    /// spawned with [`World::spawn_entity_with_reason`].
+    Spawned {
+        /// The reason of the spawn.
+        reason: SpawnReason,
+    },
+    /// The entity has mounted the given vehicle entity, or left its vehicle if none,
+    /// see [`World::mount_entity`].
+    Ride {
+        /// The id of the vehicle entity, if riding.
+        vehicle_id: Option<u32>,
+    },
     /// The entity has been removed. The last chunk position is given.
     Remove,
     /// The entity changed its position.
@@ -1904,12 +3055,45 @@ pub enum EntityEvent {
         /// The id of the picked up entity.
         target_id: u32,
     },
+    /// The projectile entity has hit a block or an entity, given if any, and is about to
+    /// be removed.
+    Impact {
+        /// The id of the hit entity, if any.
+        target_id: Option<u32>,
+    },
+    /// A fish has bitten the fishing bobber entity, which can now be reeled in to catch
+    /// a fish, the splash sound and particles should be played by frontend.
+    Bite,
     /// The entity is damaged and the damage animation should be played by frontend.
     Damage,
     /// The entity is dead and the dead animation should be played by frontend.
     Dead,
     /// Some unspecified entity metadata has changed.
     Metadata,
+    /// The display name of the entity has changed, see [`World::set_entity_name`].
+    Name,
+    /// The entity has been healed, its health has increased.
+    Heal,
+    /// The armor of the player entity has been damaged by a hurt, some pieces may have
+    /// been broken, see [`Human::armor`](crate::entity::Human::armor).
+    Armor,
+    /// A player has tried to tame the entity, the hearts particles should be played by
+    /// frontend if it has been tamed, or the smoke particles if not.
+    Tame {
+        /// True if the entity has been tamed.
+        tamed: bool,
+    },
+    /// The player entity has not been damaged by another player because PvP is denied,
+    /// see [`World::set_pvp`].
+    PvpDenied {
+        /// The entity id of the attacker.
+        attacker_id: u32,
+    },
+    /// The entity plays its ambient sound, see [`sound`](crate::entity::sound).
+    AmbientSound {
+        /// The name of the sound.
+        sound: &'static str,
+    },
 }
 
 /// An event with a block entity.
@@ -1966,6 +3150,11 @@ pub enum ChunkEvent {
     /// Any chunk component (block, light, entity, block entity) has been modified in the
     /// chunk so it's marked dirty.
     Dirty,
+    /// The light data of the chunk has been modified in the given area, min and max
+    /// included, in world coordinates. Light changes are merged into a single event per
+    /// chunk each time light updates are ticked, and are always accompanied by a
+    /// [`Dirty`](Self::Dirty) event. Chunk observers don't receive this event.
+    Light { min: IVec3, max: IVec3 },
 }
 
 /// A snapshot contains all of the content within a chunk, block, light, height map,
@@ -1984,9 +3173,21 @@ pub struct ChunkSnapshot {
     /// The entities in that chunk, note that entities are not guaranteed to have a
     /// position that is within chunk boundaries.
     pub entities: Vec<Box<Entity>>,
+    /// For each entity, in the same order, its id in the world and true if it has
+    /// changed since the previous snapshot taken for saving, see
+    /// [`World::take_chunk_save_snapshot`]. This is empty when unknown, in which case
+    /// all entities should be considered changed.
+    pub entities_changes: Vec<(u32, bool)>,
+    /// For each entity, in the same order, its persisted id in the world if known. When
+    /// inserted in a world, entities keep their id if not already used, this is empty
+    /// when no entity id is known, in which case new ids are allocated.
+    pub entities_ids: Vec<Option<u32>>,
     /// Block entities in that chunk, all block entities are mapped to their absolute
     /// coordinates in the world.
     pub block_entities: HashMap<IVec3, Box<BlockEntity>>,
+    /// True if the light data of the chunk is missing or suspect, the chunk should be
+    /// relit, see [`World::rebuild_light`].
+    pub light_invalid: bool,
 }
 
 impl ChunkSnapshot {
@@ -1997,7 +3198,10 @@ impl ChunkSnapshot {
             cz,
             chunk: Chunk::new(),
             entities: Vec::new(),
+            entities_changes: Vec::new(),
+            entities_ids: Vec::new(),
             block_entities: HashMap::new(),
+            light_invalid: false,
         }
     }
 }
@@ -2033,6 +3237,8 @@ struct ChunkComponent {
     entities: IndexMap<u32, usize>,
     /// Block entities belonging to this chunk.
     block_entities: HashMap<IVec3, usize>,
+    /// The last time something happened in this chunk, used to freeze idle chunks.
+    active_time: u64,
 }
 
 /// Internal type for storing a world entity and keep track of its current chunk.
@@ -2052,6 +3258,9 @@ struct EntityComponent {
     /// This field describes the initial entity kind of the entity when spawned, it should
     /// not be changed afterward by ticking functions.
     kind: EntityKind,
+    /// True when the entity has changed since the last snapshot of its chunk taken for
+    /// saving.
+    dirty: bool,
 }
 
 /// Internal type for storing a world block entity.
@@ -2078,21 +3287,36 @@ struct BlockTickState {
 }
 
 /// A block tick scheduled in the future, it's associated to a world time in a tree map.
-/// This structure is ordered by time and then by position, this allows to have multiple
-/// block update at the same time but for different positions.
+/// This structure is ordered by time, then by priority and then by unique id, this allows
+/// to have multiple block update at the same time but for different positions.
 #[derive(Clone, Eq)]
 struct BlockTick {
     /// This tick unique id within the world.
     uid: u64,
     /// The time to tick the block.
     time: u64,
+    /// Priority of the tick among ticks scheduled at the same time.
+    priority: BlockTickPriority,
     /// State of that scheduled tick.
     state: BlockTickState,
 }
 
+/// Priority of a scheduled block tick, ticks scheduled at the same time are run from
+/// the highest to the lowest priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum BlockTickPriority {
+    /// Run before other ticks, like redstone components that must settle first.
+    High,
+    /// The default priority, used by all builtin blocks.
+    #[default]
+    Normal,
+    /// Run after other ticks, like fluids that should see the settled blocks.
+    Low,
+}
+
 impl PartialEq for BlockTick {
     fn eq(&self, other: &Self) -> bool {
-        self.uid == other.uid && self.time == other.time
+        self.uid == other.uid && self.time == other.time && self.priority == other.priority
     }
 }
 
@@ -2104,7 +3328,10 @@ impl PartialOrd for BlockTick {
 
 impl Ord for BlockTick {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.time.cmp(&other.time).then(self.uid.cmp(&other.uid))
+        self.time
+            .cmp(&other.time)
+            .then(self.priority.cmp(&other.priority))
+            .then(self.uid.cmp(&other.uid))
     }
 }
 
@@ -2607,6 +3834,101 @@ impl<'a> Iterator for EntitiesIterMut<'a> {
     }
 }
 
+/// An iterator over all entities in the world that have a given component.
+pub struct EntitiesWithIter<'a, C>(EntitiesIter<'a>, PhantomData<&'a C>);
+
+impl<C: Component> FusedIterator for EntitiesWithIter<'_, C> {}
+impl<'a, C: Component + 'a> Iterator for EntitiesWithIter<'a, C> {
+    type Item = (u32, &'a Base, &'a C);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .find_map(|(id, Entity(base, base_kind))| Some((id, base, C::from_kind(base_kind)?)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.0.size_hint().1)
+    }
+}
+
+/// An iterator over all entities in the world that have a given component through
+/// mutable references.
+pub struct EntitiesWithIterMut<'a, C>(EntitiesIterMut<'a>, PhantomData<&'a mut C>);
+
+impl<C: Component> FusedIterator for EntitiesWithIterMut<'_, C> {}
+impl<'a, C: Component + 'a> Iterator for EntitiesWithIterMut<'a, C> {
+    type Item = (u32, &'a mut Base, &'a mut C);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.find_map(|(id, Entity(base, base_kind))| {
+            Some((id, base, C::from_kind_mut(base_kind)?))
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.0.size_hint().1)
+    }
+}
+
+/// A function overriding the PvP setting of a world, see [`World::set_pvp_filter`].
+pub type PvpFilter = fn(&World, u32, DVec3, bool) -> bool;
+
+/// A function vetoing bulk block changes, see [`World::set_build_filter`].
+pub type BuildFilter = fn(&World, Option<u32>, IVec3) -> bool;
+
+/// A function vetoing or altering entity spawns, see [`World::set_spawn_filter`].
+pub type SpawnFilter = fn(&World, &mut Entity, SpawnReason) -> bool;
+
+/// The reason of an entity spawn that is checked by the spawn filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpawnReason {
+    /// The entity spawns naturally near players.
+    Natural,
+    /// The entity is spawned by a mob spawner.
+    Spawner,
+    /// The chicken hatched from a thrown egg.
+    Egg,
+    /// The slime is split from a dead bigger slime.
+    Split,
+    /// The pig zombie is transformed from a pig struck by lightning.
+    Lightning,
+}
+
+/// The area of chunks that are simulated, around the chunks of player entities.
+struct SimulationArea {
+    /// The simulation distance, every chunk is simulated if unlimited.
+    distance: Option<u32>,
+    /// Chunk positions of player entities.
+    centers: Vec<(i32, i32)>,
+}
+
+impl SimulationArea {
+    /// Return true if the given chunk is simulated.
+    fn contains(&self, cx: i32, cz: i32) -> bool {
+        match self.distance {
+            Some(distance) => self
+                .centers
+                .iter()
+                .any(|&(ccx, ccz)| ccx.abs_diff(cx) <= distance && ccz.abs_diff(cz) <= distance),
+            None => true,
+        }
+    }
+}
+
+/// The usage of the natural spawning cap of an entity category, see
+/// [`World::get_mob_cap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MobCap {
+    /// Number of loaded entities counted in the cap.
+    pub count: usize,
+    /// Maximum count before the category stops naturally spawning.
+    pub max: usize,
+}
+
 // TODO: we are currently using type alias because the logic is exactly the same and it's
 // a pain to implement, maybe just use a wrapper in the future.
 /// An iterator of player entities in the world.
@@ -2893,6 +4215,592 @@ mod tests {
         assert_eq!(ChunkRange::new(0, 0, -1, -1).collect::<Vec<_>>(), []);
     }
 
+    #[test]
+    fn spawn_entity_of_kind() {
+        let mut world = World::new(Dimension::Overworld);
+        for kind in EntityKind::ALL {
+            assert_eq!(EntityKind::from_name(kind.name()), Some(kind));
+            let id = world.spawn_entity_of_kind(kind, DVec3::new(0.5, 64.0, 0.5));
+            assert_eq!(world.get_entity(id).unwrap().kind(), kind);
+        }
+        assert_eq!(
+            EntityKind::from_name("PIG_ZOMBIE"),
+            Some(EntityKind::PigZombie)
+        );
+    }
+
+    #[test]
+    fn cave_sound() {
+        use crate::chunk::ChunkNibbleArray3;
+        use crate::entity::Human;
+
+        let player_pos = DVec3::new(8.0, 64.0, 8.0);
+        let mut world = World::builder()
+            .player(Human::new_default(player_pos))
+            .events()
+            .build();
+        world.get_chunk_mut(0, 0).unwrap().sky_light = ChunkNibbleArray3::new(0);
+
+        world.cave_sound_time = 1;
+        let mut sounds = Vec::new();
+        for _ in 0..2000 {
+            world.tick_blocks();
+            sounds.extend(
+                world
+                    .swap_events(Some(Vec::new()))
+                    .unwrap()
+                    .into_iter()
+                    .filter_map(|event| match event {
+                        Event::CaveSound { pos, .. } => Some(pos),
+                        _ => None,
+                    }),
+            );
+        }
+
+        // Only one sound is played before the countdown is reset.
+        let [pos] = sounds[..] else {
+            panic!("expected a single cave sound: {sounds:?}");
+        };
+        let dist = pos.distance_squared(player_pos);
+        assert!(dist > 4.0 && dist < 64.0);
+        assert!(world.cave_sound_time >= 6000 - 2000);
+    }
+
+    #[test]
+    fn light_event() {
+        let mut world = World::new(Dimension::Overworld);
+        world.set_chunk(0, 0, Chunk::new());
+        world.tick_light(usize::MAX);
+
+        let pos = IVec3::new(8, 10, 8);
+        world.set_block(pos, block::TORCH, 0);
+        world.swap_events(Some(Vec::new()));
+        world.tick_light(usize::MAX);
+
+        // Light changes are merged in a single event.
+        let lights = world
+            .swap_events(None)
+            .unwrap()
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Chunk {
+                    inner: ChunkEvent::Light { min, max },
+                    ..
+                } => Some((min, max)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let [(min, max)] = lights[..] else {
+            panic!("expected a single light event: {lights:?}");
+        };
+        assert!(min.cmple(pos).all() && max.cmpge(pos).all());
+        assert!(min.x < pos.x && max.x > pos.x);
+    }
+
+    #[test]
+    fn chunk_unload() {
+        use crate::entity::Item;
+
+        let mut world = World::new(Dimension::Overworld);
+        world.set_chunk(0, 0, Chunk::new());
+        world.set_chunk(1, 0, Chunk::new());
+        let id = world.spawn_entity(Item::new_with(|base, _| {
+            base.pos = DVec3::new(20.0, 10.0, 4.0);
+        }));
+
+        world.swap_events(Some(Vec::new()));
+        let snapshot = world.remove_chunk_snapshot(1, 0).unwrap();
+        assert_eq!(snapshot.entities.len(), 1);
+        assert!(!world.contains_entity(id));
+        assert_eq!(world.iter_chunks().collect::<Vec<_>>(), [(0, 0)]);
+
+        let events = world.swap_events(Some(Vec::new())).unwrap();
+        assert!(events.contains(&Event::Chunk {
+            cx: 1,
+            cz: 0,
+            inner: ChunkEvent::Remove
+        }));
+        assert!(events.contains(&Event::Entity {
+            id,
+            inner: EntityEvent::Remove
+        }));
+
+        // Entities are restored with the chunk.
+        world.insert_chunk_snapshot(snapshot);
+        assert_eq!(world.get_entity_count(), 1);
+    }
+
+    #[test]
+    fn entity_save_changes() {
+        use crate::entity::Item;
+        use crate::serde::chunk::{to_writer_cached, EntityNbtCache};
+
+        let (mut world, ids) = World::builder()
+            .entity(Item::new_default(DVec3::new(4.5, 10.0, 4.5)))
+            .entity(Item::new_default(DVec3::new(8.5, 10.0, 8.5)))
+            .events()
+            .build_with_entities();
+        let [item_id, other_id] = ids[..] else {
+            unreachable!()
+        };
+
+        let mut cache = EntityNbtCache::default();
+        let mut data = Vec::new();
+        let snapshot = world.take_chunk_save_snapshot(0, 0).unwrap();
+        assert!(snapshot.entities_changes.iter().all(|&(_, dirty)| dirty));
+        assert_eq!(
+            to_writer_cached(&mut data, &snapshot, &mut cache).unwrap(),
+            2
+        );
+        assert!(!world.is_entity_dirty(item_id));
+
+        // Mutating the entity marks it and its chunk dirty.
+        world.get_entity_mut(item_id).unwrap().0.pos.y = 11.0;
+        assert!(world.is_entity_dirty(item_id));
+        assert!(!world.is_entity_dirty(other_id));
+        assert!(world
+            .swap_events(Some(Vec::new()))
+            .unwrap()
+            .contains(&Event::Chunk {
+                cx: 0,
+                cz: 0,
+                inner: ChunkEvent::Dirty
+            }));
+
+        // Only the changed entity is serialized again, with the same final data.
+        let snapshot = world.take_chunk_save_snapshot(0, 0).unwrap();
+        let mut cached_data = Vec::new();
+        assert_eq!(
+            to_writer_cached(&mut cached_data, &snapshot, &mut cache).unwrap(),
+            1
+        );
+        let mut full_data = Vec::new();
+        crate::serde::nbt::to_writer(&mut full_data, &crate::serde::chunk::to_nbt(&snapshot))
+            .unwrap();
+        assert_eq!(cached_data, full_data);
+
+        // Unknown changes serialize all entities.
+        let snapshot = world.remove_chunk_snapshot(0, 0).unwrap();
+        assert_eq!(
+            to_writer_cached(&mut data, &snapshot, &mut cache).unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn entity_id_persistence() {
+        use crate::entity::{Arrow, Item};
+        use crate::serde::chunk;
+
+        let (mut world, ids) = World::builder()
+            .entity(Item::new_default(DVec3::new(4.5, 10.0, 4.5)))
+            .entity(Item::new_default(DVec3::new(8.5, 10.0, 8.5)))
+            .build_with_entities();
+        let arrow_id = world.spawn_entity(Arrow::new_with(|base, projectile, _| {
+            base.pos = DVec3::new(6.5, 10.0, 6.5);
+            projectile.owner_id = Some(ids[0]);
+        }));
+        world.remove_entity(ids[1], "test");
+        assert_eq!(world.get_next_entity_id(), 3);
+
+        // Saved entities keep their id and references in a world restarted with the
+        // persisted next entity id.
+        let snapshot = world.remove_chunk_snapshot(0, 0).unwrap();
+        let snapshot = chunk::from_nbt(&chunk::to_nbt(&snapshot)).unwrap();
+
+        let mut world = World::new(Dimension::Overworld);
+        world.set_next_entity_id(3);
+        let player_id = world.spawn_entity(Item::new_default(DVec3::new(40.5, 10.0, 4.5)));
+        assert_eq!(player_id, 3);
+        world.insert_chunk_snapshot(snapshot.clone());
+
+        assert!(world.contains_entity(ids[0]));
+        assert!(!world.contains_entity(ids[1]));
+        let Some(Entity(_, BaseKind::Projectile(projectile, _))) = world.get_entity(arrow_id)
+        else {
+            panic!("arrow should keep its id");
+        };
+        assert_eq!(projectile.owner_id, Some(ids[0]));
+        assert_eq!(world.get_next_entity_id(), 4);
+
+        // Without the persisted id, loaded ids are still never given again, and ids
+        // already in use are replaced.
+        let mut world = World::new(Dimension::Overworld);
+        let other_id = world.spawn_entity(Item::new_default(DVec3::new(40.5, 10.0, 4.5)));
+        assert_eq!(other_id, ids[0]);
+        world.insert_chunk_snapshot(snapshot);
+        assert!(world.contains_entity(arrow_id));
+        assert_eq!(world.get_entity_count(), 3);
+        assert!(world.get_next_entity_id() > arrow_id);
+    }
+
+    #[test]
+    fn entity_name() {
+        use crate::entity::Pig;
+        use crate::serde::chunk;
+
+        let (mut world, ids) = World::builder()
+            .entity(Pig::new_default(DVec3::new(4.5, 10.0, 4.5)))
+            .build_with_entities();
+        world.swap_events(Some(Vec::new()));
+
+        assert!(world.set_entity_name(ids[0], Some("Bacon".to_string())));
+        assert!(world.set_entity_name(ids[0], Some("Bacon".to_string())));
+        let events = world.swap_events(None).unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            Event::Entity {
+                inner: EntityEvent::Name,
+                ..
+            }
+        ));
+
+        let snapshot = world.remove_chunk_snapshot(0, 0).unwrap();
+        let snapshot = chunk::from_nbt(&chunk::to_nbt(&snapshot)).unwrap();
+        world.insert_chunk_snapshot(snapshot);
+        let Entity(base, _) = world.get_entity(ids[0]).unwrap();
+        assert_eq!(base.name.as_deref(), Some("Bacon"));
+        assert!(!world.set_entity_name(ids[0] + 1, None));
+    }
+
+    #[test]
+    fn fluid_flow() {
+        use crate::block::fluid::calc_flow;
+        use crate::entity::Item;
+
+        let (mut world, ids) = World::builder()
+            .platform(10, block::STONE, 0)
+            .block(IVec3::new(4, 10, 8), block::WATER_STILL, 0)
+            .block(IVec3::new(5, 10, 8), block::WATER_MOVING, 1)
+            .block(IVec3::new(6, 10, 8), block::WATER_MOVING, 2)
+            .block(IVec3::new(10, 11, 8), block::WATER_MOVING, 8)
+            .block(IVec3::new(11, 11, 8), block::STONE, 0)
+            .entity(Item::new_default(DVec3::new(5.5, 10.1, 8.5)))
+            .build_with_entities();
+
+        // Water flows away from its source, and falling water is pulled down by walls.
+        assert_eq!(calc_flow(&world, IVec3::new(4, 10, 8)), DVec3::X);
+        assert_eq!(calc_flow(&world, IVec3::new(5, 10, 8)), DVec3::X);
+        assert_eq!(calc_flow(&world, IVec3::new(10, 11, 8)), DVec3::NEG_Y);
+        assert_eq!(calc_flow(&world, IVec3::new(8, 9, 8)), DVec3::ZERO);
+
+        // Items drift downstream.
+        world.tick();
+        let Entity(base, _) = world.get_entity(ids[0]).unwrap();
+        assert!(base.vel.x > 0.0);
+    }
+
+    #[test]
+    fn portal_and_obsidian() {
+        let mut world = World::builder()
+            .platform(10, block::STONE, 0)
+            .fill(
+                IVec3::new(4, 10, 8),
+                IVec3::new(7, 14, 8),
+                block::OBSIDIAN,
+                0,
+            )
+            .fill(IVec3::new(5, 11, 8), IVec3::new(6, 13, 8), block::AIR, 0)
+            .block(IVec3::new(10, 10, 4), block::WATER_STILL, 0)
+            .block(IVec3::new(10, 10, 6), block::WATER_STILL, 0)
+            .build();
+
+        // Lighting a fire inside the frame creates the portal.
+        world.set_block_notify(IVec3::new(5, 11, 8), block::FIRE, 0);
+        for y in 11..14 {
+            assert!(world.is_block(IVec3::new(5, y, 8), block::PORTAL));
+            assert!(world.is_block(IVec3::new(6, y, 8), block::PORTAL));
+        }
+
+        // The portal is oriented along its frame.
+        let bb = world
+            .get_block_overlay_box(IVec3::new(5, 12, 8), block::PORTAL, 0)
+            .unwrap();
+        assert_eq!(bb.size_x(), 1.0);
+        assert_eq!(bb.size_z(), 0.25);
+
+        // Breaking the frame collapses the whole portal.
+        world.set_block_notify(IVec3::new(7, 12, 8), block::AIR, 0);
+        for y in 11..14 {
+            assert!(!world.is_block(IVec3::new(5, y, 8), block::PORTAL));
+            assert!(!world.is_block(IVec3::new(6, y, 8), block::PORTAL));
+        }
+
+        // Lava source hardens to obsidian next to water, flowing lava to cobblestone.
+        world.set_block_notify(IVec3::new(11, 10, 4), block::LAVA_MOVING, 0);
+        world.set_block_notify(IVec3::new(11, 10, 6), block::LAVA_MOVING, 2);
+        assert!(world.is_block(IVec3::new(11, 10, 4), block::OBSIDIAN));
+        assert!(world.is_block(IVec3::new(11, 10, 6), block::COBBLESTONE));
+    }
+
+    #[test]
+    fn chunk_batch_access() {
+        let mut world = World::new(Dimension::Overworld);
+        world.set_chunk(1, 0, Chunk::new());
+        world.swap_events(Some(Vec::new()));
+        assert!(world.with_chunk_mut(0, 0, |_| ()).is_none());
+
+        let prev = world.with_chunk_mut(1, 0, |chunk| {
+            for y in 0..10 {
+                chunk.set_block(IVec3::new(3, y, 4), block::STONE, 0);
+            }
+            chunk.set_block(IVec3::new(5, 20, 6), block::GLOWSTONE, 0);
+            chunk.set_block(IVec3::new(5, 20, 6), block::GLOWSTONE, 0)
+        });
+        assert_eq!(prev, Some((block::GLOWSTONE, 0)));
+
+        // Blocks are set in world coordinates, and heights are recomputed.
+        assert_eq!(
+            world.get_block(IVec3::new(19, 9, 4)),
+            Some((block::STONE, 0))
+        );
+        assert_eq!(world.get_height(IVec3::new(19, 0, 4)), Some(10));
+        assert_eq!(world.get_height(IVec3::new(21, 0, 6)), Some(21));
+        assert_eq!(world.get_light_update_count(), 22);
+
+        let events = world.swap_events(None).unwrap();
+        assert_eq!(events.len(), 12);
+        assert!(matches!(
+            events[10],
+            Event::Block {
+                pos: IVec3 { x: 21, y: 20, z: 6 },
+                inner: BlockEvent::Set {
+                    id: block::GLOWSTONE,
+                    prev_id: block::AIR,
+                    ..
+                }
+            }
+        ));
+        assert!(matches!(
+            events[11],
+            Event::Chunk {
+                cx: 1,
+                cz: 0,
+                inner: ChunkEvent::Dirty
+            }
+        ));
+    }
+
+    #[test]
+    fn block_break_events() {
+        let mut world = World::builder().platform(10, block::STONE, 0).build();
+        world.swap_events(Some(Vec::new()));
+
+        // Breaking air is not a break.
+        world.break_block_by(IVec3::new(2, 9, 2), Some(7));
+        world.break_block(IVec3::new(2, 9, 2));
+        world.break_block(IVec3::new(2, 10, 2));
+        let events = world.swap_events(Some(Vec::new())).unwrap();
+        let breaks = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Block {
+                    pos,
+                    inner: BlockEvent::Break { id, origin_id, .. },
+                } => Some((*pos, *id, *origin_id)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(breaks, [(IVec3::new(2, 9, 2), block::STONE, Some(7))]);
+
+        // Each block destroyed by an explosion is a break.
+        world.explode(DVec3::new(8.5, 10.5, 8.5), 3.0, false, None);
+        let events = world.swap_events(None).unwrap();
+        let removed = events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    Event::Block {
+                        inner: BlockEvent::Set { id: block::AIR, .. },
+                        ..
+                    }
+                )
+            })
+            .count();
+        let breaks = events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    Event::Block {
+                        inner: BlockEvent::Break {
+                            id: block::STONE,
+                            origin_id: None,
+                            ..
+                        },
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert!(removed > 0);
+        assert_eq!(breaks, removed);
+    }
+
+    #[test]
+    fn block_entity_ticking() {
+        use crate::block_entity::chest::ChestBlockEntity;
+        use crate::block_entity::furnace::FurnaceBlockEntity;
+
+        let mut world = World::builder().build();
+        let furnace_pos = IVec3::new(2, 10, 2);
+        let mut furnace = FurnaceBlockEntity::default();
+        furnace.burn_remaining_ticks = 10;
+        world.set_block_entity(furnace_pos, BlockEntity::Furnace(furnace));
+        world.set_block_entity(
+            IVec3::new(4, 10, 4),
+            BlockEntity::Chest(ChestBlockEntity::default()),
+        );
+        assert_eq!(world.get_block_entity_count(), 2);
+        assert_eq!(world.get_ticking_block_entity_count(), 1);
+
+        world.tick();
+        let Some(BlockEntity::Furnace(furnace)) = world.get_block_entity(furnace_pos) else {
+            panic!("furnace should be present");
+        };
+        assert_eq!(furnace.burn_remaining_ticks, 9);
+
+        // Replacing the furnace unregisters it.
+        world.set_block_entity(furnace_pos, BlockEntity::Chest(ChestBlockEntity::default()));
+        assert_eq!(world.get_ticking_block_entity_count(), 0);
+        world.tick();
+        assert!(matches!(
+            world.get_block_entity(furnace_pos),
+            Some(BlockEntity::Chest(_))
+        ));
+    }
+
+    #[test]
+    fn spawn_filter() {
+        use crate::entity::{Creeper, Zombie};
+
+        let mut world = World::builder().platform(10, block::STONE, 0).build();
+        world.set_spawn_filter(Some(|_, entity, reason| {
+            if entity.kind() == EntityKind::Creeper {
+                return false;
+            }
+            entity.0.name = Some(format!("{reason:?}"));
+            true
+        }));
+        world.swap_events(Some(Vec::new()));
+
+        let pos = DVec3::new(8.5, 10.0, 8.5);
+        let creeper =
+            world.spawn_entity_with_reason(Creeper::new_default(pos), SpawnReason::Natural);
+        assert_eq!(creeper, None);
+        assert_eq!(world.get_entity_count(), 0);
+
+        let zombie_id = world
+            .spawn_entity_with_reason(Zombie::new_default(pos), SpawnReason::Spawner)
+            .expect("zombie should be spawned");
+        assert_eq!(
+            world.get_entity(zombie_id).unwrap().0.name.as_deref(),
+            Some("Spawner")
+        );
+
+        let events = world.swap_events(None).unwrap();
+        assert!(events.iter().any(|event| matches!(event, Event::Entity {
+            id,
+            inner: EntityEvent::Spawned { reason: SpawnReason::Spawner },
+        } if *id == zombie_id)));
+
+        // Spawns without reason are never filtered.
+        world.spawn_entity(Creeper::new_default(pos));
+        assert_eq!(world.get_entity_count(), 2);
+    }
+
+    #[test]
+    fn mob_cap() {
+        use crate::entity::{Pig, Zombie};
+
+        let mut world = World::builder().platform(10, block::STONE, 0).build();
+        let pos = DVec3::new(8.5, 10.0, 8.5);
+        world.spawn_entity(Zombie::new_default(pos));
+        world.spawn_entity(Pig::new_default(pos));
+        let mut zombie = Zombie::new_default(pos);
+        if let Entity(_, BaseKind::Living(living, _)) = &mut *zombie {
+            living.from_spawner = true;
+        }
+        world.spawn_entity(zombie);
+
+        assert_eq!(world.get_mob_cap(EntityCategory::Mob).count, 2);
+        assert_eq!(world.get_mob_cap(EntityCategory::Animal).count, 1);
+        assert_eq!(world.get_mob_cap(EntityCategory::WaterAnimal).count, 0);
+
+        world.set_spawner_mobs_capped(false);
+        assert_eq!(world.get_mob_cap(EntityCategory::Mob).count, 1);
+        assert_eq!(world.get_mob_cap(EntityCategory::Animal).count, 1);
+    }
+
+    #[test]
+    fn simulation_distance() {
+        use crate::entity::{Human, Item};
+
+        let mut world = World::new(Dimension::Overworld);
+        for cx in 0..4 {
+            world.set_chunk(cx, 0, Chunk::new());
+        }
+        let player_id = world.spawn_entity(Human::new_default(DVec3::new(8.0, 10.0, 8.0)));
+        world.set_player_entity(player_id, true);
+        let item_id = world.spawn_entity(Item::new_default(DVec3::new(56.0, 10.0, 8.0)));
+        world.set_block(IVec3::new(56, 20, 8), block::SAND, 0);
+        world.schedule_block_tick(IVec3::new(56, 20, 8), block::SAND, 1);
+
+        world.set_simulation_distance(Some(2));
+        assert!(world.is_chunk_simulated(2, 0));
+        assert!(!world.is_chunk_simulated(3, 0));
+
+        for _ in 0..5 {
+            world.tick();
+        }
+        assert_eq!(world.get_entity(item_id).unwrap().0.pos.y, 10.0);
+        assert!(world.is_block(IVec3::new(56, 20, 8), block::SAND));
+        assert_eq!(world.get_block_tick_count(), 1);
+
+        world.set_simulation_distance(None);
+        world.tick();
+        assert!(world.get_entity(item_id).unwrap().0.pos.y < 10.0);
+        assert!(world.is_block_air(IVec3::new(56, 20, 8)));
+    }
+
+    #[test]
+    fn chunk_freeze() {
+        use crate::entity::{Human, Pig};
+
+        let mut world = World::new(Dimension::Overworld);
+        for cx in 0..20 {
+            world.set_chunk(cx, 0, Chunk::new());
+        }
+        world.set_chunk_freeze_delay(Some(10));
+        let player_id = world.spawn_entity(Human::new_default(DVec3::new(8.0, 10.0, 8.0)));
+        world.set_player_entity(player_id, true);
+
+        for _ in 0..11 {
+            world.tick();
+        }
+        // Chunks within 8 chunks of the player are kept active.
+        assert_eq!(world.get_frozen_chunk_count(), 11);
+        assert!(!world.is_chunk_frozen(8, 0));
+        assert!(world.is_chunk_frozen(9, 0));
+
+        world.set_block(IVec3::new(9 * 16, 10, 0), block::STONE, 0);
+        world.spawn_entity(Pig::new_default(DVec3::new(12.0 * 16.0, 10.0, 8.0)));
+        assert!(!world.is_chunk_frozen(9, 0));
+        assert!(!world.is_chunk_frozen(12, 0));
+        assert_eq!(world.get_chunk_wake_count(), 2);
+
+        for _ in 0..11 {
+            world.tick();
+        }
+        // The chunk with an entity is never frozen.
+        assert!(world.is_chunk_frozen(9, 0));
+        assert!(!world.is_chunk_frozen(12, 0));
+        assert_eq!(world.get_frozen_chunk_count(), 10);
+    }
+
     #[test]
     fn tick_vec() {
         // We want to extensively test this data structure since it is highly critical
@@ -2947,4 +4855,47 @@ mod tests {
         v.advance();
         assert_eq!(v.current(), None);
     }
+
+    #[test]
+    fn block_tick_queue() {
+        let mut world = World::new(Dimension::Overworld);
+        let pos = |x| IVec3::new(x, 64, 0);
+
+        // Identical ticks are deduplicated.
+        world.schedule_block_tick(pos(0), block::WATER_MOVING, 5);
+        world.schedule_block_tick(pos(0), block::WATER_MOVING, 2);
+        world.schedule_block_tick(pos(0), block::LAVA_MOVING, 5);
+        assert_eq!(world.get_block_tick_count(), 2);
+
+        // Ticks at the same time are ordered by priority.
+        world.schedule_block_tick_with_priority(
+            pos(1),
+            block::REPEATER,
+            5,
+            BlockTickPriority::High,
+        );
+        world.schedule_block_tick_with_priority(pos(2), block::SAND, 5, BlockTickPriority::Low);
+        let order = world
+            .block_ticks
+            .iter()
+            .map(|tick| tick.state.pos.x)
+            .collect::<Vec<_>>();
+        assert_eq!(order, [1, 0, 0, 2]);
+
+        // Ticks past the chunk limit are discarded.
+        world.set_block_tick_chunk_limit(Some(5));
+        world.schedule_block_tick(pos(3), block::SAND, 1);
+        world.schedule_block_tick(pos(4), block::SAND, 1);
+        world.schedule_block_tick(pos(-1), block::SAND, 1);
+        assert_eq!(world.get_block_tick_count(), 6);
+        assert_eq!(world.get_block_tick_discarded_count(), 1);
+
+        // Running ticks frees the chunk.
+        for _ in 0..6 {
+            world.tick();
+        }
+        assert_eq!(world.get_block_tick_count(), 0);
+        world.schedule_block_tick(pos(4), block::SAND, 1);
+        assert_eq!(world.get_block_tick_count(), 1);
+    }
 }
@@ -11,7 +11,8 @@ use crate::block_entity::piston::PistonBlockEntity;
 use crate::block_entity::BlockEntity;
 use crate::geom::{Face, FaceSet};
 
-use super::{BlockEvent, Event, World};
+use super::explode::TNT_FUSE_TIME;
+use super::{BlockEvent, Effect, Event, World};
 
 /// Methods related to block self and neighbor notifications.
 impl World {
@@ -37,6 +38,11 @@ impl World {
         metadata: u8,
         origin_id: u8,
     ) {
+        // Neighbor notifications are suppressed in silent scopes.
+        if self.is_silent() {
+            return;
+        }
+
         match id {
             block::REDSTONE if origin_id != block::REDSTONE => self.notify_redstone(pos),
             block::REPEATER | block::REPEATER_LIT => self.notify_repeater(pos, id, metadata),
@@ -55,6 +61,7 @@ impl World {
             block::WHEAT => self.notify_flower(pos, &[block::FARMLAND]),
             block::RED_MUSHROOM | block::BROWN_MUSHROOM => self.notify_mushroom(pos),
             block::CACTUS => self.notify_cactus(pos),
+            block::SUGAR_CANES => self.notify_sugar_canes(pos),
             block::SAND | block::GRAVEL => self.schedule_block_tick(pos, id, 3),
             block::FIRE => {
                 self.notify_fire(pos);
@@ -62,6 +69,9 @@ impl World {
             block::PISTON | block::STICKY_PISTON => self.notify_piston(pos, id, metadata),
             block::PISTON_EXT => self.notify_piston_ext(pos, metadata, origin_id),
             block::NOTE_BLOCK => self.notify_note_block(pos, origin_id),
+            block::FARMLAND => self.notify_farmland(pos),
+            block::TNT if is_redstone_block(origin_id) => self.notify_tnt(pos),
+            block::PORTAL => self.notify_portal(pos),
             _ => {}
         }
     }
@@ -107,12 +117,21 @@ impl World {
             block::JUKEBOX if to_id != block::JUKEBOX => {
                 self.remove_block_entity(pos);
             }
+            // Mark leaves around to check for decay.
+            block::LOG if to_id != block::LOG => self.notify_leaves_decay(pos, 4),
+            block::LEAVES if to_id != block::LEAVES => self.notify_leaves_decay(pos, 1),
             _ => {}
         }
 
         match to_id {
             block::WATER_MOVING => self.schedule_block_tick(pos, to_id, 5),
-            block::LAVA_MOVING => self.schedule_block_tick(pos, to_id, 30),
+            block::LAVA_MOVING => {
+                // Lava may directly harden if placed next to water.
+                self.notify_fluid(pos, to_id, to_metadata);
+                if self.is_block(pos, to_id) {
+                    self.schedule_block_tick(pos, to_id, 30);
+                }
+            }
             block::REDSTONE => self.notify_redstone(pos),
             block::REPEATER | block::REPEATER_LIT => {
                 self.notify_repeater(pos, to_id, from_metadata)
@@ -124,50 +143,134 @@ impl World {
             block::CACTUS => self.notify_cactus(pos),
             block::FIRE => self.notify_fire_place(pos),
             block::PISTON | block::STICKY_PISTON => self.notify_piston(pos, to_id, to_metadata),
+            block::TNT => self.notify_tnt(pos),
             _ => {}
         }
     }
 
     /// Notification of a moving fluid block.
+    ///
+    /// REF: BlockFluid::checkForHarden
     fn notify_fluid(&mut self, pos: IVec3, id: u8, metadata: u8) {
-        // If the fluid block is lava, check if we make cobblestone or lava.
+        // If the fluid block is lava, check if we make cobblestone or obsidian when there
+        // is at least one water block on the sides or above.
         if id == block::LAVA_MOVING {
-            let distance = block::fluid::get_distance(metadata);
-            for face in Face::HORIZONTAL {
-                if let Some((block::WATER_MOVING | block::WATER_STILL, _)) =
-                    self.get_block(pos + face.delta())
-                {
-                    // If there is at least one water block around.
-                    if distance == 0 {
-                        self.set_block_notify(pos, block::OBSIDIAN, 0);
-                    } else if distance <= 4 {
-                        self.set_block_notify(pos, block::COBBLESTONE, 0);
-                    }
+            let water_around = [Face::NegZ, Face::PosZ, Face::NegX, Face::PosX, Face::PosY]
+                .into_iter()
+                .any(|face| {
+                    matches!(
+                        self.get_block(pos + face.delta()),
+                        Some((block::WATER_MOVING | block::WATER_STILL, _))
+                    )
+                });
+
+            // NOTE: The raw metadata is used, so falling lava never hardens.
+            if water_around {
+                if block::fluid::is_source(metadata) {
+                    self.set_block_notify(pos, block::OBSIDIAN, 0);
+                } else if metadata <= 4 {
+                    self.set_block_notify(pos, block::COBBLESTONE, 0);
                 }
             }
         }
     }
 
     /// Notification of a still fluid block.
+    ///
+    /// REF: BlockStationary::onNeighborBlockChange
     fn notify_fluid_still(&mut self, pos: IVec3, id: u8, metadata: u8) {
         // Subtract 1 from id to go from still to moving.
         let moving_id = id - 1;
 
         self.notify_fluid(pos, moving_id, metadata);
-        self.set_block_self_notify(pos, moving_id, metadata);
+
+        // The fluid may have hardened, in which case it should not be set moving.
+        if self.is_block(pos, id) {
+            self.set_block_self_notify(pos, moving_id, metadata);
+        }
+    }
+
+    /// Notification of a portal block, it is removed if its obsidian frame is no longer
+    /// complete. Removed portal blocks notify their neighbors, so the whole portal
+    /// collapses.
+    ///
+    /// REF: BlockPortal::onNeighborBlockChange
+    fn notify_portal(&mut self, pos: IVec3) {
+        let along_x = self.is_block(pos - IVec3::X, block::PORTAL)
+            || self.is_block(pos + IVec3::X, block::PORTAL);
+        let along_z = self.is_block(pos - IVec3::Z, block::PORTAL)
+            || self.is_block(pos + IVec3::Z, block::PORTAL);
+        let axis = if along_x { IVec3::X } else { IVec3::Z };
+
+        // Find the bottom of the portal column, that should be on obsidian.
+        let mut bottom = pos;
+        while self.is_block(bottom - IVec3::Y, block::PORTAL) {
+            bottom.y -= 1;
+        }
+
+        let mut valid = self.is_block(bottom - IVec3::Y, block::OBSIDIAN);
+        if valid {
+            // The column should be exactly 3 blocks high, with obsidian on top.
+            let mut height = 1;
+            while height < 4 && self.is_block(bottom + IVec3::Y * height, block::PORTAL) {
+                height += 1;
+            }
+            valid = height == 3 && self.is_block(bottom + IVec3::Y * height, block::OBSIDIAN);
+        }
+
+        // The portal should be on a single axis, with obsidian on one side and another
+        // portal block on the other side.
+        if valid {
+            valid = !(along_x && along_z)
+                && ((self.is_block(pos + axis, block::OBSIDIAN)
+                    && self.is_block(pos - axis, block::PORTAL))
+                    || (self.is_block(pos - axis, block::OBSIDIAN)
+                        && self.is_block(pos + axis, block::PORTAL)));
+        }
+
+        if !valid {
+            self.set_block_notify(pos, block::AIR, 0);
+        }
     }
 
     /// Notification of standard flower subclasses.
     fn notify_flower(&mut self, pos: IVec3, stay_blocks: &[u8]) {
         if self.get_light(pos).max() >= 8 {
             let (below_id, _) = self.get_block(pos - IVec3::Y).unwrap_or((0, 0));
-            if stay_blocks.iter().any(|&id| id == below_id) {
+            if stay_blocks.contains(&below_id) {
                 return;
             }
         }
         self.break_block(pos);
     }
 
+    /// Notification of a log or leaves removal, all leaves in the given radius are marked
+    /// to check for decay on their next random tick.
+    ///
+    /// REF: BlockLeaves::onBlockRemoval, BlockLog::onBlockRemoval
+    fn notify_leaves_decay(&mut self, pos: IVec3, radius: i32) {
+        for x in pos.x - radius..=pos.x + radius {
+            for y in pos.y - radius..=pos.y + radius {
+                for z in pos.z - radius..=pos.z + radius {
+                    let leaves_pos = IVec3::new(x, y, z);
+                    if let Some((block::LEAVES, mut metadata)) = self.get_block(leaves_pos) {
+                        if !block::leaves::is_check_decay(metadata) {
+                            block::leaves::set_check_decay(&mut metadata, true);
+                            self.set_block(leaves_pos, block::LEAVES, metadata);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Notification of a farmland block, it reverts to dirt if a solid block is above.
+    fn notify_farmland(&mut self, pos: IVec3) {
+        if self.is_block_solid(pos + IVec3::Y) {
+            self.set_block_notify(pos, block::DIRT, 0);
+        }
+    }
+
     /// Notification of a mushroom block.
     fn notify_mushroom(&mut self, pos: IVec3) {
         if self.get_light(pos).max() >= 13 || !self.is_block_opaque_cube(pos - IVec3::Y) {
@@ -175,18 +278,16 @@ impl World {
         }
     }
 
-    /// Notification of a cactus block. The block is broken if
+    /// Notification of a cactus block. The block is broken if it can no longer stay.
     fn notify_cactus(&mut self, pos: IVec3) {
-        for face in Face::HORIZONTAL {
-            if self.is_block_solid(pos + face.delta()) {
-                self.break_block(pos);
-                return;
-            }
+        if !self.can_place_cactus(pos) {
+            self.break_block(pos);
         }
-        if !matches!(
-            self.get_block(pos - IVec3::Y),
-            Some((block::CACTUS | block::SAND, _))
-        ) {
+    }
+
+    /// Notification of a sugar canes block. The block is broken if it can no longer stay.
+    fn notify_sugar_canes(&mut self, pos: IVec3) {
+        if !self.can_place_sugar_canes(pos) {
             self.break_block(pos);
         }
     }
@@ -261,11 +362,12 @@ impl World {
                 }
             }
 
-            // If portal layout is valid, create it.
+            // If portal layout is valid, create it. Neighbors are not notified, else the
+            // portal blocks would break each other while being partially created.
             if valid {
                 for dxz in 0..2 {
                     for dy in 0..3 {
-                        self.set_block_notify(
+                        self.set_block_self_notify(
                             pos + factor * IVec3::new(dxz, dy, dxz),
                             block::PORTAL,
                             0,
@@ -306,6 +408,16 @@ impl World {
         }
     }
 
+    /// Notification of a TNT block, it is removed and primed if powered by redstone.
+    ///
+    /// REF: BlockTNT::onNeighborBlockChange
+    fn notify_tnt(&mut self, pos: IVec3) {
+        if self.has_passive_power(pos) {
+            self.set_block_notify(pos, block::AIR, 0);
+            self.prime_tnt(pos, TNT_FUSE_TIME);
+        }
+    }
+
     /// Notification of a trapdoor, breaking it if no longer on its wall, or updating its
     /// state depending on redstone signal.
     fn notify_trapdoor(&mut self, pos: IVec3, mut metadata: u8, origin_id: u8) {
@@ -319,13 +431,7 @@ impl World {
                 if open != powered {
                     block::trapdoor::set_open(&mut metadata, powered);
                     self.set_block_notify(pos, block::TRAPDOOR, metadata);
-                    self.push_event(Event::Block {
-                        pos,
-                        inner: BlockEvent::Sound {
-                            id: block::TRAPDOOR,
-                            metadata,
-                        },
-                    });
+                    self.play_effect(pos, Effect::Door);
                 }
             }
         }
@@ -392,10 +498,7 @@ impl World {
                         self.notify_block(pos + face.delta() + IVec3::Y, id);
                     }
 
-                    self.push_event(Event::Block {
-                        pos,
-                        inner: BlockEvent::Sound { id, metadata },
-                    });
+                    self.play_effect(pos, Effect::Door);
                 }
             }
         }
@@ -450,7 +553,8 @@ impl World {
 
                 // Break the last position (do not use self.break_block to avoid recurse).
                 if let Some((prev_id, prev_metadata)) = self.set_block(check_pos, block::AIR, 0) {
-                    self.spawn_block_loot(pos, prev_id, prev_metadata, 1.0);
+                    self.push_block_break(check_pos, prev_id, prev_metadata, None);
+                    self.spawn_block_loot(check_pos, prev_id, prev_metadata, 1.0);
                 }
 
                 // Now we initialize the block entities.
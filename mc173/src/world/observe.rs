@@ -0,0 +1,209 @@
+//! Chunk observers for regions of interest.
+//!
+//! Consumers that need to know which chunks are available in a region, like chunk
+//! streamers or map renderers, can register an observer instead of polling the chunk
+//! map. An observer receives load, unload and dirty notifications for chunks in its
+//! region, dirty notifications are only kept once per chunk until taken.
+
+use std::collections::HashSet;
+use std::mem;
+
+use super::{ChunkEvent, World};
+
+/// A handle to a chunk observer in a world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverHandle(u32);
+
+/// A rectangular region of chunks, min and max included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChunkRegion {
+    /// Minimum chunk X coordinate.
+    pub min_cx: i32,
+    /// Minimum chunk Z coordinate.
+    pub min_cz: i32,
+    /// Maximum chunk X coordinate.
+    pub max_cx: i32,
+    /// Maximum chunk Z coordinate.
+    pub max_cz: i32,
+}
+
+impl ChunkRegion {
+    /// Create a new region from its min and max chunk coordinates, included.
+    #[inline]
+    pub fn new(min_cx: i32, min_cz: i32, max_cx: i32, max_cz: i32) -> Self {
+        Self {
+            min_cx,
+            min_cz,
+            max_cx,
+            max_cz,
+        }
+    }
+
+    /// Create a new square region of all chunks in the given radius around a center
+    /// chunk.
+    #[inline]
+    pub fn around(cx: i32, cz: i32, radius: u32) -> Self {
+        let radius = radius as i32;
+        Self::new(cx - radius, cz - radius, cx + radius, cz + radius)
+    }
+
+    /// Return true if the given chunk is in this region.
+    #[inline]
+    pub fn contains(&self, cx: i32, cz: i32) -> bool {
+        cx >= self.min_cx && cx <= self.max_cx && cz >= self.min_cz && cz <= self.max_cz
+    }
+}
+
+/// Internal chunk observer state.
+#[derive(Debug, Clone)]
+pub(super) struct ChunkObserver {
+    /// Unique id of the observer.
+    id: u32,
+    /// The observed region.
+    region: ChunkRegion,
+    /// The queue of chunk notifications since last taken.
+    notifications: Vec<(i32, i32, ChunkEvent)>,
+    /// Chunks with a pending dirty notification, used to avoid duplicates.
+    dirty: HashSet<(i32, i32)>,
+}
+
+impl ChunkObserver {
+    /// Push a notification to this observer if in its region.
+    fn push(&mut self, cx: i32, cz: i32, event: &ChunkEvent) {
+        if !self.region.contains(cx, cz) || matches!(event, ChunkEvent::Light { .. }) {
+            return;
+        }
+
+        if *event == ChunkEvent::Dirty && !self.dirty.insert((cx, cz)) {
+            return;
+        }
+
+        self.notifications.push((cx, cz, event.clone()));
+    }
+}
+
+/// Methods related to chunk observers.
+impl World {
+    /// Register a new chunk observer for the given region, the observer immediately
+    /// receives a [`ChunkEvent::Set`] notification for each chunk already loaded in
+    /// this region.
+    pub fn observe_chunks(&mut self, region: ChunkRegion) -> ObserverHandle {
+        let id = self.observers_count;
+        self.observers_count = self
+            .observers_count
+            .checked_add(1)
+            .expect("observers count overflow");
+
+        // The observer starts with an empty region so all loaded chunks are entering.
+        let mut observer = ChunkObserver {
+            id,
+            region: ChunkRegion::new(0, 0, -1, -1),
+            notifications: Vec::new(),
+            dirty: HashSet::new(),
+        };
+
+        self.notify_entering_chunks(&mut observer, region);
+        self.observers.push(observer);
+        ObserverHandle(id)
+    }
+
+    /// Change the region of an existing observer, it receives a [`ChunkEvent::Set`]
+    /// notification for each loaded chunk that was not in its previous region. Chunks
+    /// leaving the region are not notified. Returns false if the observer is not
+    /// existing.
+    pub fn set_observed_region(&mut self, handle: ObserverHandle, region: ChunkRegion) -> bool {
+        let Some(index) = self.observers.iter().position(|obs| obs.id == handle.0) else {
+            return false;
+        };
+
+        let mut observer = self.observers.swap_remove(index);
+        if observer.region != region {
+            self.notify_entering_chunks(&mut observer, region);
+        }
+        self.observers.push(observer);
+        true
+    }
+
+    /// Remove a chunk observer, returning false if it was not existing.
+    pub fn unobserve_chunks(&mut self, handle: ObserverHandle) -> bool {
+        let Some(index) = self.observers.iter().position(|obs| obs.id == handle.0) else {
+            return false;
+        };
+
+        self.observers.swap_remove(index);
+        true
+    }
+
+    /// Take all chunk notifications of the given observer since the last call, in the
+    /// order they happened, returning none if the observer is not existing.
+    pub fn take_observed_chunks(
+        &mut self,
+        handle: ObserverHandle,
+    ) -> Option<Vec<(i32, i32, ChunkEvent)>> {
+        let observer = self.observers.iter_mut().find(|obs| obs.id == handle.0)?;
+        observer.dirty.clear();
+        Some(mem::take(&mut observer.notifications))
+    }
+
+    /// Internal function to notify all observers of a chunk event.
+    pub(super) fn notify_chunk_observers(&mut self, cx: i32, cz: i32, event: &ChunkEvent) {
+        for observer in &mut self.observers {
+            observer.push(cx, cz, event);
+        }
+    }
+
+    /// Internal function to change the region of an observer while notifying it of
+    /// the loaded chunks entering its region.
+    fn notify_entering_chunks(&self, observer: &mut ChunkObserver, region: ChunkRegion) {
+        // Iterate over loaded chunks instead of the region, which may be huge.
+        let prev_region = mem::replace(&mut observer.region, region);
+        for (&(cx, cz), chunk_comp) in &self.chunks {
+            if chunk_comp.data.is_some() && !prev_region.contains(cx, cz) {
+                observer.push(cx, cz, &ChunkEvent::Set);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use glam::IVec3;
+
+    use super::*;
+    use crate::block;
+    use crate::chunk::Chunk;
+    use crate::world::Dimension;
+
+    #[test]
+    fn observe_chunks() {
+        let mut world = World::new(Dimension::Overworld);
+        world.set_chunk(0, 0, Chunk::new());
+
+        let handle = world.observe_chunks(ChunkRegion::around(0, 0, 1));
+        world.set_chunk(1, 1, Chunk::new());
+        world.set_chunk(2, 0, Chunk::new());
+        world.set_block(IVec3::new(1, 10, 1), block::STONE, 0);
+        world.set_block(IVec3::new(2, 10, 1), block::STONE, 0);
+        world.remove_chunk(1, 1);
+
+        assert_eq!(
+            world.take_observed_chunks(handle).unwrap(),
+            [
+                (0, 0, ChunkEvent::Set),
+                (1, 1, ChunkEvent::Set),
+                (0, 0, ChunkEvent::Dirty),
+                (1, 1, ChunkEvent::Remove),
+            ]
+        );
+
+        assert!(world.set_observed_region(handle, ChunkRegion::around(1, 0, 1)));
+        assert_eq!(
+            world.take_observed_chunks(handle).unwrap(),
+            [(2, 0, ChunkEvent::Set)]
+        );
+
+        assert!(world.unobserve_chunks(handle));
+        assert!(world.take_observed_chunks(handle).is_none());
+    }
+}
@@ -23,8 +23,9 @@ impl World {
         to: IVec3,
         entity_size: IVec3,
         dist: f32,
+        options: PathOptions,
     ) -> Option<Vec<IVec3>> {
-        PathFinder::new(self).find_path(from, to, entity_size, dist)
+        PathFinder::new(self, options).find_path(from, to, entity_size, dist)
     }
 
     /// A specialization or [`find_path`](Self::find_path) to find a path of a moving
@@ -35,6 +36,7 @@ impl World {
         from: BoundingBox,
         to: DVec3,
         dist: f32,
+        options: PathOptions,
     ) -> Option<Vec<IVec3>> {
         // println!("== find_path_from_bounding_box: from {from}, to {to}, dist {dist}");
 
@@ -49,16 +51,28 @@ impl World {
             .floor()
             .as_ivec3();
 
-        self.find_path(from, to, size.add(1.0).floor().as_ivec3(), dist)
+        self.find_path(from, to, size.add(1.0).floor().as_ivec3(), dist, options)
     }
 }
 
+/// Abilities of the entity that should go through the path, the default options are
+/// the ones of all Notchian mobs, that can neither open doors nor swim.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PathOptions {
+    /// True if closed wooden doors can be opened and are therefore passable.
+    pub open_doors: bool,
+    /// True if water blocks are passable.
+    pub swim: bool,
+}
+
 /// A path finder on a world.
 struct PathFinder<'a> {
     /// Back-reference to the world.
     world: &'a World,
     /// The size of the entity (or whatever you want) that should go through the path.
     entity_size: IVec3,
+    /// Abilities of the entity that should go through the path.
+    options: PathOptions,
     /// All points allocated by the path finder.
     points: Vec<PathPoint>,
     /// Mapping of points from their block position.
@@ -89,10 +103,11 @@ struct PathPoint {
 }
 
 impl<'a> PathFinder<'a> {
-    pub fn new(world: &'a World) -> Self {
+    pub fn new(world: &'a World, options: PathOptions) -> Self {
         Self {
             world,
             entity_size: IVec3::ONE,
+            options,
             points: Vec::new(),
             points_map: HashMap::new(),
             pending: Vec::new(),
@@ -126,11 +141,13 @@ impl<'a> PathFinder<'a> {
         for (_, block, metadata) in self.world.iter_blocks_in(pos, pos + self.entity_size) {
             match block {
                 block::AIR => {}
+                block::WOOD_DOOR if self.options.open_doors => {}
                 block::IRON_DOOR | block::WOOD_DOOR => {
                     if !block::door::is_open(metadata) {
                         return PathClearance::Blocked;
                     }
                 }
+                block::FIRE => return PathClearance::Hazard,
                 _ => match block::material::get_material(block) {
                     Material::Water => return PathClearance::Water,
                     Material::Lava => return PathClearance::Lava,
@@ -146,15 +163,33 @@ impl<'a> PathFinder<'a> {
         PathClearance::Clear
     }
 
+    /// Return true if the given clearance can be walked (or swam) through.
+    fn is_passable(&self, clearance: PathClearance) -> bool {
+        match clearance {
+            PathClearance::Clear => true,
+            PathClearance::Water => self.options.swim,
+            _ => false,
+        }
+    }
+
+    /// Return true if an entity should not stand on the given ground block, fences
+    /// cannot be jumped over because of their 1.5 block height and cactus is harmful.
+    fn is_unsafe_ground(&self, pos: IVec3) -> bool {
+        matches!(
+            self.world.get_block(pos),
+            Some((block::FENCE | block::CACTUS, _))
+        )
+    }
+
     /// Find a safe point to path find to in above or below the given position.
     fn find_safe_point(&mut self, mut pos: IVec3, clear: bool) -> Option<usize> {
         let mut ret = None;
 
-        if self.check_clearance(pos) == PathClearance::Clear {
+        if self.is_passable(self.check_clearance(pos)) {
             ret = Some(self.ensure_point(pos).0);
         }
 
-        if ret.is_none() && clear && self.check_clearance(pos + IVec3::Y) == PathClearance::Clear {
+        if ret.is_none() && clear && self.is_passable(self.check_clearance(pos + IVec3::Y)) {
             pos.y += 1;
             ret = Some(self.ensure_point(pos).0);
         }
@@ -167,8 +202,18 @@ impl<'a> PathFinder<'a> {
 
                 match self.check_clearance(pos) {
                     PathClearance::Clear => {}
-                    PathClearance::Lava => return None,
-                    _ => break,
+                    PathClearance::Water if self.options.swim => {
+                        // Swimming entities stay at the surface.
+                        *point_index = self.ensure_point(pos).0;
+                        return ret;
+                    }
+                    PathClearance::Lava | PathClearance::Hazard => return None,
+                    _ => {
+                        if self.is_unsafe_ground(pos) {
+                            return None;
+                        }
+                        break;
+                    }
                 }
 
                 // NOTE: Updating height here is important, because if we get block/water
@@ -345,4 +390,45 @@ enum PathClearance {
     Blocked,
     Water,
     Lava,
+    /// Harmful block that is not solid, such as fire.
+    Hazard,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn path_special_blocks() {
+        // A wall of fences with a closed wooden door in the middle.
+        let mut world = World::builder()
+            .platform(10, block::STONE, 0)
+            .wall(
+                IVec3::new(8, 10, 0),
+                IVec3::new(8, 10, 15),
+                1,
+                block::FENCE,
+                0,
+            )
+            .block(IVec3::new(8, 10, 8), block::WOOD_DOOR, 0)
+            .block(IVec3::new(8, 11, 8), block::WOOD_DOOR, 8)
+            .build();
+
+        let from = IVec3::new(4, 10, 8);
+        let to = IVec3::new(12, 10, 8);
+        let size = IVec3::new(1, 2, 1);
+
+        // Fences can't be walked over, and the door is closed.
+        let path = world.find_path(from, to, size, 16.0, PathOptions::default());
+        assert!(path.is_none_or(|path| *path.last().unwrap() != to));
+
+        let options = PathOptions {
+            open_doors: true,
+            ..Default::default()
+        };
+        let path = world.find_path(from, to, size, 16.0, options).unwrap();
+        assert_eq!(*path.last().unwrap(), to);
+        assert!(path.contains(&IVec3::new(8, 10, 8)));
+    }
 }
@@ -71,7 +71,9 @@ impl World {
         base && self.is_block_replaceable(pos)
     }
 
-    fn can_place_cactus(&mut self, pos: IVec3) -> bool {
+    /// Return true if a cactus can be placed at the given position, this is also used to
+    /// check if a cactus can stay.
+    pub(super) fn can_place_cactus(&mut self, pos: IVec3) -> bool {
         for face in Face::HORIZONTAL {
             if self.is_block_solid(pos + face.delta()) {
                 return false;
@@ -83,18 +85,19 @@ impl World {
         )
     }
 
-    fn can_place_sugar_canes(&mut self, pos: IVec3) -> bool {
+    /// Return true if sugar canes can be placed at the given position, this is also used
+    /// to check if sugar canes can stay.
+    pub(super) fn can_place_sugar_canes(&mut self, pos: IVec3) -> bool {
         let below_pos = pos - IVec3::Y;
-        if let Some((block::SUGAR_CANES | block::GRASS | block::DIRT, _)) =
-            self.get_block(below_pos)
-        {
-            for face in Face::HORIZONTAL {
-                if self.get_block_material(below_pos + face.delta()) == Material::Water {
-                    return true;
-                }
-            }
+        match self.get_block(below_pos) {
+            Some((block::SUGAR_CANES, _)) => return true,
+            Some((block::GRASS | block::DIRT, _)) => {}
+            _ => return false,
         }
-        false
+
+        Face::HORIZONTAL
+            .into_iter()
+            .any(|face| self.get_block_material(below_pos + face.delta()) == Material::Water)
     }
 
     fn can_place_chest(&mut self, pos: IVec3) -> bool {
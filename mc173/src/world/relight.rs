@@ -0,0 +1,192 @@
+//! Rebuilding of chunks' light data.
+//!
+//! Light data stored in chunks may be missing or wrong, typically for chunks imported
+//! from converters or older tools, such chunks render black on clients and natural
+//! spawning is wrong. This module allows rebuilding the light of a chunk from its
+//! blocks, immediately or lazily where chunks are relit one by one over ticks.
+
+use glam::IVec3;
+
+use crate::block;
+use crate::chunk::{CHUNK_HEIGHT, CHUNK_WIDTH};
+use crate::geom::Face;
+
+use super::{ChunkEvent, Event, LightKind, World};
+
+/// Maximum number of pending light updates for a lazy relight to happen on a tick.
+const RELIGHT_MAX_PENDING_UPDATES: usize = 4096;
+
+/// Methods related to light rebuilding.
+impl World {
+    /// Rebuild all light data of the given chunk from its blocks. Block light and sky
+    /// light are reset and light updates are scheduled to propagate them, including from
+    /// and to loaded neighbor chunks. Returns false if the chunk is not loaded.
+    pub fn rebuild_light(&mut self, cx: i32, cz: i32) -> bool {
+        let Some(chunk) = self.get_chunk_mut(cx, cz) else {
+            return false;
+        };
+
+        chunk.reset_light();
+
+        let chunk_pos = IVec3::new(cx * CHUNK_WIDTH as i32, 0, cz * CHUNK_WIDTH as i32);
+        let mut updates = Vec::new();
+
+        for x in 0..CHUNK_WIDTH as i32 {
+            for z in 0..CHUNK_WIDTH as i32 {
+                let column_pos = chunk_pos + IVec3::new(x, 0, z);
+                let height = chunk.get_height(column_pos) as i32;
+
+                // Block light is propagated from each emitting block.
+                for y in 0..CHUNK_HEIGHT as i32 {
+                    let pos = IVec3::new(column_pos.x, y, column_pos.z);
+                    let (id, _) = chunk.get_block(pos);
+                    if block::material::get_light_emission(id) != 0 {
+                        updates.push((LightKind::Block, pos));
+                    }
+                }
+
+                // Sky light is spread horizontally below the height of this column,
+                // down to the lowest height of neighbor columns in this chunk.
+                let mut min_height = height;
+                for face in Face::HORIZONTAL {
+                    let face_pos = column_pos + face.delta();
+                    if (face_pos.x >> 4, face_pos.z >> 4) == (cx, cz) {
+                        min_height = min_height.min(chunk.get_height(face_pos) as i32);
+                    }
+                }
+
+                for y in min_height..height {
+                    updates.push((LightKind::Sky, IVec3::new(column_pos.x, y, column_pos.z)));
+                }
+            }
+        }
+
+        // Positions on the chunk border are updated if the neighbor block has light to
+        // propagate, or if the neighbor column is lower.
+        for face in Face::HORIZONTAL {
+            for i in 0..CHUNK_WIDTH as i32 {
+                let column_pos = chunk_pos
+                    + match face {
+                        Face::NegX => IVec3::new(0, 0, i),
+                        Face::PosX => IVec3::new(15, 0, i),
+                        Face::NegZ => IVec3::new(i, 0, 0),
+                        _ => IVec3::new(i, 0, 15),
+                    };
+
+                let face_column_pos = column_pos + face.delta();
+                let (face_cx, face_cz) = (face_column_pos.x >> 4, face_column_pos.z >> 4);
+                let Some(face_chunk) = self.get_chunk(face_cx, face_cz) else {
+                    continue;
+                };
+
+                for y in 0..CHUNK_HEIGHT as i32 {
+                    let face_pos = IVec3::new(face_column_pos.x, y, face_column_pos.z);
+                    if face_chunk.get_block_light(face_pos) > 1 {
+                        updates.push((LightKind::Block, IVec3::new(column_pos.x, y, column_pos.z)));
+                    }
+                    if face_chunk.get_sky_light(face_pos) > 1 {
+                        updates.push((LightKind::Sky, IVec3::new(column_pos.x, y, column_pos.z)));
+                    }
+                }
+            }
+        }
+
+        for (kind, pos) in updates {
+            self.schedule_light_update(pos, kind);
+        }
+
+        self.push_event(Event::Chunk {
+            cx,
+            cz,
+            inner: ChunkEvent::Dirty,
+        });
+        self.push_event(Event::Chunk {
+            cx,
+            cz,
+            inner: ChunkEvent::Light {
+                min: chunk_pos,
+                max: chunk_pos + IVec3::new(15, CHUNK_HEIGHT as i32 - 1, 15),
+            },
+        });
+
+        true
+    }
+
+    /// Schedule the given chunk to be lazily relit, see
+    /// [`rebuild_light`](Self::rebuild_light). Chunks are relit one by one on each tick
+    /// when few light updates are pending, a chunk that is no longer loaded is ignored.
+    pub fn schedule_chunk_relight(&mut self, cx: i32, cz: i32) {
+        self.relight_chunks.insert((cx, cz));
+    }
+
+    /// Return the number of chunks waiting to be lazily relit.
+    #[inline]
+    pub fn get_chunk_relight_count(&self) -> usize {
+        self.relight_chunks.len()
+    }
+
+    /// Set if chunks inserted from snapshots with invalid light data should be scheduled
+    /// for lazy relight, see [`ChunkSnapshot::light_invalid`](super::ChunkSnapshot::light_invalid).
+    /// Disabled by default.
+    pub fn set_lazy_relight(&mut self, enabled: bool) {
+        self.lazy_relight = enabled;
+    }
+
+    /// Return true if chunks with invalid light data are lazily relit when inserted.
+    #[inline]
+    pub fn is_lazy_relight(&self) -> bool {
+        self.lazy_relight
+    }
+
+    /// Internal function to relight the next scheduled chunk, if not too many light
+    /// updates are pending.
+    pub(super) fn tick_relight(&mut self) {
+        if self.light_updates.len() > RELIGHT_MAX_PENDING_UPDATES {
+            return;
+        }
+
+        while let Some((cx, cz)) = self.relight_chunks.shift_remove_index(0) {
+            if self.rebuild_light(cx, cz) {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::chunk::{Chunk, ChunkNibbleArray3};
+    use crate::world::Dimension;
+
+    #[test]
+    fn rebuild_light() {
+        let mut world = World::new(Dimension::Overworld);
+        let mut chunk = Chunk::new();
+        let chunk_mut = Arc::get_mut(&mut chunk).unwrap();
+        chunk_mut.fill_block(IVec3::ZERO, IVec3::new(16, 64, 16), block::STONE, 0);
+        // A roof above a torch, with a hole on the side.
+        chunk_mut.fill_block(IVec3::new(4, 70, 4), IVec3::new(5, 1, 5), block::STONE, 0);
+        chunk_mut.set_block(IVec3::new(6, 64, 6), block::TORCH, 0);
+        // Light data is zeroed, like imported chunks.
+        chunk_mut.sky_light = ChunkNibbleArray3::new(0);
+        world.set_chunk(0, 0, chunk);
+
+        assert!(world.rebuild_light(0, 0));
+        assert!(!world.rebuild_light(1, 0));
+        while world.get_light_update_count() != 0 {
+            world.tick_light(1000);
+        }
+
+        let light = |x, y, z| world.get_light(IVec3::new(x, y, z));
+        assert_eq!(light(0, 100, 0).sky, 15);
+        assert_eq!(light(6, 64, 6).block, 14);
+        assert_eq!(light(7, 64, 6).block, 13);
+        // Below the roof, sky light comes from the sides.
+        assert_eq!(light(6, 69, 6).sky, 12);
+        assert_eq!(light(6, 63, 6).sky, 0);
+    }
+}
@@ -0,0 +1,96 @@
+//! Entities riding other entities, such as players riding saddled pigs.
+
+use glam::DVec3;
+
+use crate::entity::Entity;
+
+use super::{EntityEvent, Event, World};
+
+/// Methods related to entities riding vehicle entities.
+impl World {
+    /// Make an entity ride a vehicle entity, the rider leaves its previous vehicle if
+    /// any. The rider then follows its vehicle when the vehicle is ticked. This returns
+    /// false if any of the entity is not existing, if the rider is the vehicle itself or
+    /// if the vehicle is already ridden by another entity.
+    ///
+    /// REF: Entity::mountEntity
+    pub fn mount_entity(&mut self, rider_id: u32, vehicle_id: u32) -> bool {
+        if rider_id == vehicle_id || !self.contains_entity(rider_id) {
+            return false;
+        }
+
+        let Some(Entity(vehicle_base, _)) = self.get_entity_mut(vehicle_id) else {
+            return false;
+        };
+
+        if vehicle_base.rider_id.is_some_and(|id| id != rider_id) {
+            return false;
+        }
+
+        vehicle_base.rider_id = Some(rider_id);
+        let pos = vehicle_base.pos + DVec3::new(0.0, vehicle_base.bb.size_y() * 0.75, 0.0);
+
+        let rider = self.get_entity_mut(rider_id).unwrap();
+        let prev_vehicle_id = rider.0.vehicle_id.replace(vehicle_id);
+        rider.teleport(pos);
+
+        if let Some(prev_vehicle_id) = prev_vehicle_id.filter(|&id| id != vehicle_id) {
+            if let Some(Entity(prev_vehicle_base, _)) = self.get_entity_mut(prev_vehicle_id) {
+                prev_vehicle_base.rider_id = None;
+            }
+        }
+
+        self.push_event(Event::Entity {
+            id: rider_id,
+            inner: EntityEvent::Ride {
+                vehicle_id: Some(vehicle_id),
+            },
+        });
+        self.push_event(Event::Entity {
+            id: rider_id,
+            inner: EntityEvent::Position { pos },
+        });
+        true
+    }
+
+    /// Make an entity leave its vehicle, the rider is placed on top of the vehicle. This
+    /// returns false if the entity is not existing or is not riding.
+    ///
+    /// REF: Entity::mountEntity
+    pub fn dismount_entity(&mut self, rider_id: u32) -> bool {
+        let Some(Entity(rider_base, _)) = self.get_entity_mut(rider_id) else {
+            return false;
+        };
+
+        let Some(vehicle_id) = rider_base.vehicle_id.take() else {
+            return false;
+        };
+
+        let mut pos = None;
+        if let Some(Entity(vehicle_base, _)) = self.get_entity_mut(vehicle_id) {
+            if vehicle_base.rider_id == Some(rider_id) {
+                vehicle_base.rider_id = None;
+            }
+            pos = Some(DVec3::new(
+                vehicle_base.pos.x,
+                vehicle_base.bb.max.y,
+                vehicle_base.pos.z,
+            ));
+        }
+
+        self.push_event(Event::Entity {
+            id: rider_id,
+            inner: EntityEvent::Ride { vehicle_id: None },
+        });
+
+        if let Some(pos) = pos {
+            self.get_entity_mut(rider_id).unwrap().teleport(pos);
+            self.push_event(Event::Entity {
+                id: rider_id,
+                inner: EntityEvent::Position { pos },
+            });
+        }
+
+        true
+    }
+}
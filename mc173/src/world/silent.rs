@@ -0,0 +1,359 @@
+//! Silent block changes scopes.
+//!
+//! Some operations change a lot of blocks at once, like generating a structure at
+//! runtime, pasting a schematic or filling a region. When done with the usual methods,
+//! each block change notifies its neighbors and pushes its own events, which may storm
+//! the block ticks scheduler and the network. Silent scopes instead aggregate all the
+//! changes into a single change set that is returned to the caller.
+
+use std::collections::{hash_map, HashMap, HashSet};
+use std::mem;
+
+use glam::IVec3;
+
+use crate::geom::Face;
+
+use super::{BlockEvent, ChunkEvent, Event, World};
+
+/// A single block change in a change set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockChange {
+    /// Position of the changed block.
+    pub pos: IVec3,
+    /// Block id before the first change.
+    pub prev_id: u8,
+    /// Block metadata before the first change.
+    pub prev_metadata: u8,
+    /// Block id after the last change.
+    pub id: u8,
+    /// Block metadata after the last change.
+    pub metadata: u8,
+}
+
+/// An aggregated set of block changes, each position appears only once with the block
+/// before the first change and after the last change.
+#[derive(Debug, Clone, Default)]
+pub struct BlockChanges {
+    /// All changed blocks, mapped to their position.
+    blocks: HashMap<IVec3, BlockChange>,
+    /// All chunks that have been marked dirty.
+    chunks: HashSet<(i32, i32)>,
+}
+
+impl BlockChanges {
+    /// Return true if no block has been changed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Return the number of changed blocks.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Get the change at the given block position, if any.
+    #[inline]
+    pub fn get(&self, pos: IVec3) -> Option<&BlockChange> {
+        self.blocks.get(&pos)
+    }
+
+    /// Iterate over all block changes, in no particular order. Note that a block may
+    /// have been changed and then restored to its previous state.
+    #[inline]
+    pub fn iter(&self) -> hash_map::Values<'_, IVec3, BlockChange> {
+        self.blocks.values()
+    }
+
+    /// Iterate over all chunks that have been modified.
+    #[inline]
+    pub fn iter_chunks(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.chunks.iter().copied()
+    }
+
+    /// Internal function to record a block change.
+    fn insert(&mut self, change: BlockChange) {
+        match self.blocks.entry(change.pos) {
+            hash_map::Entry::Occupied(o) => {
+                let prev = o.into_mut();
+                prev.id = change.id;
+                prev.metadata = change.metadata;
+            }
+            hash_map::Entry::Vacant(v) => {
+                v.insert(change);
+            }
+        }
+    }
+
+    /// Internal function to merge the changes of an inner scope into this one.
+    fn merge(&mut self, other: BlockChanges) {
+        for change in other.blocks.into_values() {
+            self.insert(change);
+        }
+        self.chunks.extend(other.chunks);
+    }
+}
+
+/// Methods related to silent changes scopes.
+impl World {
+    /// Run the given function in a silent scope where blocks are not notified of their
+    /// neighbor changes, and where block set events are not pushed. Instead, all block
+    /// changes are aggregated and returned with the function's result. Other block
+    /// events (like sounds, pistons or note blocks), entity and block entity events are
+    /// still pushed as usual, and a single dirty event is pushed for each modified chunk
+    /// when leaving the outermost scope.
+    ///
+    /// Scopes can be nested, in such case the changes of the inner scope are also
+    /// aggregated in the outer one.
+    pub fn with_silent_changes<R>(
+        &mut self,
+        func: impl FnOnce(&mut Self) -> R,
+    ) -> (R, BlockChanges) {
+        let outer = self.silent_changes.replace(BlockChanges::default());
+        let ret = func(self);
+        let changes = mem::replace(&mut self.silent_changes, outer)
+            .expect("silent changes should be present");
+
+        if let Some(outer) = &mut self.silent_changes {
+            outer.merge(changes.clone());
+        } else {
+            for &(cx, cz) in &changes.chunks {
+                self.push_event(Event::Chunk {
+                    cx,
+                    cz,
+                    inner: ChunkEvent::Dirty,
+                });
+            }
+        }
+
+        (ret, changes)
+    }
+
+    /// Fill the cuboid between the two given positions, both inclusive, with the given
+    /// block, the origin entity is the one responsible of the fill, if any. Blocks are
+    /// changed in a silent scope, see [`with_silent_changes`](Self::with_silent_changes),
+    /// and are notified once all blocks have been changed, along with blocks around the
+    /// cuboid. Positions in unloaded chunks or vetoed by the
+    /// [build filter](Self::set_build_filter) are ignored, the aggregated changes are
+    /// returned.
+    pub fn fill_blocks(
+        &mut self,
+        from: IVec3,
+        to: IVec3,
+        id: u8,
+        metadata: u8,
+        origin_id: Option<u32>,
+    ) -> BlockChanges {
+        let min = from.min(to);
+        let max = from.max(to);
+
+        let ((), changes) = self.with_silent_changes(|world| {
+            for y in min.y..=max.y {
+                for z in min.z..=max.z {
+                    for x in min.x..=max.x {
+                        let pos = IVec3::new(x, y, z);
+                        if world.can_build(origin_id, pos) {
+                            world.set_block_notify(pos, id, metadata);
+                        }
+                    }
+                }
+            }
+        });
+
+        // Notifications are batched, changed blocks are notified of their own change, so
+        // that sand falls and fluids flow, and blocks around the cuboid are notified.
+        for change in changes.iter() {
+            self.notify_block(change.pos, change.id);
+            for face in Face::ALL {
+                let face_pos = change.pos + face.delta();
+                if face_pos.cmplt(min).any() || face_pos.cmpgt(max).any() {
+                    self.notify_block(face_pos, change.id);
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Return true if the world is currently in a silent changes scope.
+    #[inline]
+    pub fn is_silent(&self) -> bool {
+        self.silent_changes.is_some()
+    }
+
+    /// Internal function to intercept an event in a silent scope, returning true if the
+    /// event has been intercepted and should not be pushed.
+    pub(super) fn intercept_silent_event(&mut self, event: &Event) -> bool {
+        let Some(changes) = &mut self.silent_changes else {
+            return false;
+        };
+
+        match *event {
+            Event::Block {
+                inner: BlockEvent::Set { .. },
+                ..
+            } => true,
+            Event::Chunk {
+                cx,
+                cz,
+                inner: ChunkEvent::Dirty,
+            } => {
+                changes.chunks.insert((cx, cz));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Internal function to record a block change in the current silent scope, if any.
+    pub(super) fn record_silent_change(&mut self, change: BlockChange) {
+        if let Some(changes) = &mut self.silent_changes {
+            changes.insert(change);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::block;
+    use crate::chunk::Chunk;
+    use crate::world::Dimension;
+
+    #[test]
+    fn silent_changes() {
+        let mut world = World::new(Dimension::Overworld);
+        world.set_chunk(0, 0, Chunk::new());
+        world.swap_events(Some(Vec::new()));
+
+        let ((), changes) = world.with_silent_changes(|world| {
+            world.set_block_notify(IVec3::new(1, 10, 1), block::STONE, 0);
+            world.set_block_notify(IVec3::new(1, 10, 1), block::DIRT, 0);
+            world.set_block_notify(IVec3::new(2, 10, 1), block::WATER_MOVING, 0);
+            world.push_event(Event::Block {
+                pos: IVec3::new(3, 10, 1),
+                inner: BlockEvent::NoteBlock {
+                    instrument: 0,
+                    note: 0,
+                },
+            });
+        });
+
+        assert_eq!(changes.len(), 2);
+        let change = changes.get(IVec3::new(1, 10, 1)).unwrap();
+        assert_eq!((change.prev_id, change.id), (block::AIR, block::DIRT));
+        assert_eq!(changes.iter_chunks().collect::<Vec<_>>(), [(0, 0)]);
+
+        // Only the note block and the aggregated dirty events are expected.
+        let events = world.swap_events(None).unwrap();
+        assert!(matches!(
+            events[..],
+            [
+                Event::Block {
+                    inner: BlockEvent::NoteBlock { .. },
+                    ..
+                },
+                Event::Chunk {
+                    cx: 0,
+                    cz: 0,
+                    inner: ChunkEvent::Dirty
+                }
+            ]
+        ));
+        assert!(!world.is_silent());
+    }
+
+    #[test]
+    fn fill_blocks() {
+        let mut world = World::new(Dimension::Overworld);
+        world.set_chunk(0, 0, Chunk::new());
+        world.set_block(IVec3::new(4, 9, 4), block::STONE, 0);
+        world.set_block(IVec3::new(4, 13, 4), block::SAND, 0);
+
+        let changes = world.fill_blocks(
+            IVec3::new(5, 12, 5),
+            IVec3::new(3, 10, 3),
+            block::AIR,
+            0,
+            None,
+        );
+        assert_eq!(changes.len(), 0);
+
+        let changes = world.fill_blocks(
+            IVec3::new(5, 12, 5),
+            IVec3::new(3, 10, 3),
+            block::DIRT,
+            0,
+            None,
+        );
+        assert_eq!(changes.len(), 27);
+        assert_eq!(
+            world.get_block(IVec3::new(3, 10, 5)),
+            Some((block::DIRT, 0))
+        );
+
+        // The sand above the cuboid is notified and schedules its fall check.
+        assert_eq!(world.get_block_tick_count(), 1);
+    }
+
+    #[test]
+    fn fill_blocks_falling() {
+        let mut world = World::builder().platform(10, block::STONE, 0).build();
+        world.set_build_filter(Some(|_, _, pos| pos.x != 5));
+
+        let changes = world.fill_blocks(
+            IVec3::new(3, 15, 3),
+            IVec3::new(5, 15, 3),
+            block::SAND,
+            0,
+            None,
+        );
+        assert_eq!(changes.len(), 2);
+        assert!(world.is_block_air(IVec3::new(5, 15, 3)));
+
+        // Filled sand is notified of its own change and falls to the ground.
+        for _ in 0..40 {
+            world.tick();
+        }
+        assert!(world.is_block_air(IVec3::new(3, 15, 3)));
+        assert!(world.is_block(IVec3::new(3, 10, 3), block::SAND));
+        assert!(world.is_block(IVec3::new(4, 10, 3), block::SAND));
+    }
+
+    #[test]
+    fn paste_schematic() {
+        use crate::block_entity::chest::ChestBlockEntity;
+        use crate::block_entity::BlockEntity;
+        use crate::gen::schematic::Schematic;
+
+        let mut schematic = Schematic::new(IVec3::new(2, 2, 2));
+        schematic.set_block(IVec3::new(0, 0, 0), block::STONE, 0);
+        schematic.set_block(IVec3::new(1, 1, 1), block::CHEST, 0);
+        schematic.set_block_entity(
+            IVec3::new(1, 1, 1),
+            BlockEntity::Chest(ChestBlockEntity::default()),
+        );
+
+        let mut world = World::new(Dimension::Overworld);
+        world.set_chunk(0, 0, Chunk::new());
+        world.set_block(IVec3::new(5, 10, 4), block::DIRT, 0);
+        world.swap_events(Some(Vec::new()));
+
+        let changes = schematic.paste(IVec3::new(4, 10, 4), &mut world);
+        assert_eq!(changes.len(), 2);
+        assert!(world.is_block(IVec3::new(4, 10, 4), block::STONE));
+        // Air blocks of the schematic are not pasted.
+        assert!(world.is_block(IVec3::new(5, 10, 4), block::DIRT));
+        assert!(matches!(
+            world.get_block_entity(IVec3::new(5, 11, 5)),
+            Some(BlockEntity::Chest(_))
+        ));
+
+        let events = world.swap_events(None).unwrap();
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, Event::Block { .. })));
+    }
+}
@@ -0,0 +1,224 @@
+//! Event subscriptions with filtering.
+//!
+//! In addition to the main events queue that is swapped in and out of the world, any
+//! number of consumers can subscribe to events with a filter. Each subscription has its
+//! own queue that only receives the events matching its filter, so consumers don't steal
+//! events from each others.
+
+use std::mem;
+
+use crate::entity::EntityKind;
+
+use super::{calc_chunk_pos_unchecked, calc_entity_chunk_pos, Event, World};
+
+/// A handle to an events subscription in a world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventSubscription(u32);
+
+/// A filter for events subscriptions, by default all events are accepted. When some
+/// criteria are set, an event is accepted only if it satisfies all of them, and events
+/// that cannot be checked against a criterion are rejected, for example a block event
+/// is rejected if the filter requires an entity id.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    /// Only accept entity events for this entity id.
+    entity_id: Option<u32>,
+    /// Only accept entity events for entities of this kind.
+    entity_kind: Option<EntityKind>,
+    /// Only accept events located in this chunk region, min and max included.
+    region: Option<((i32, i32), (i32, i32))>,
+}
+
+impl EventFilter {
+    /// Create a new filter accepting all events.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only accept entity events for the given entity id.
+    #[inline]
+    pub fn with_entity_id(mut self, id: u32) -> Self {
+        self.entity_id = Some(id);
+        self
+    }
+
+    /// Only accept entity events for entities of the given kind.
+    #[inline]
+    pub fn with_entity_kind(mut self, kind: EntityKind) -> Self {
+        self.entity_kind = Some(kind);
+        self
+    }
+
+    /// Only accept events located in the given chunk region, min and max included.
+    /// Entity events are located in the chunk where the entity is cached, events
+    /// without location, like weather changes, are rejected.
+    #[inline]
+    pub fn with_region(mut self, min_cx: i32, min_cz: i32, max_cx: i32, max_cz: i32) -> Self {
+        self.region = Some(((min_cx, min_cz), (max_cx, max_cz)));
+        self
+    }
+
+    /// Check if the given event is accepted by this filter, the entity kind and chunk
+    /// is given if the event is an entity event and the entity is known.
+    fn accept(&self, event: &Event, entity: Option<(EntityKind, i32, i32)>) -> bool {
+        if self.entity_id.is_some() || self.entity_kind.is_some() {
+            let Event::Entity { id, .. } = *event else {
+                return false;
+            };
+
+            if self.entity_id.is_some_and(|filter_id| filter_id != id) {
+                return false;
+            }
+
+            if let Some(filter_kind) = self.entity_kind {
+                if !entity.is_some_and(|(kind, _, _)| kind == filter_kind) {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(((min_cx, min_cz), (max_cx, max_cz))) = self.region {
+            let chunk = match *event {
+                Event::Block { pos, .. } | Event::BlockEntity { pos, .. } => {
+                    Some(calc_chunk_pos_unchecked(pos))
+                }
+                Event::Entity { .. } => entity.map(|(_, cx, cz)| (cx, cz)),
+                Event::Chunk { cx, cz, .. } => Some((cx, cz)),
+                Event::Explode { center, .. }
+                | Event::CaveSound { pos: center, .. }
+                | Event::Lightning { pos: center, .. } => Some(calc_entity_chunk_pos(center)),
+                Event::DebugParticle { pos, .. } => Some(calc_chunk_pos_unchecked(pos)),
+                Event::Weather { .. } | Event::ItemCleanup { .. } => None,
+            };
+
+            let Some((cx, cz)) = chunk else {
+                return false;
+            };
+
+            if cx < min_cx || cx > max_cx || cz < min_cz || cz > max_cz {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Internal subscription state.
+#[derive(Debug, Clone)]
+pub(super) struct Subscription {
+    /// Unique id of the subscription.
+    id: u32,
+    /// The filter of accepted events.
+    filter: EventFilter,
+    /// The queue of accepted events since last taken.
+    events: Vec<Event>,
+}
+
+/// Methods related to events subscriptions.
+impl World {
+    /// Subscribe to events matching the given filter, the returned handle can be used to
+    /// take the events accepted since the last call, and to unsubscribe.
+    pub fn subscribe_events(&mut self, filter: EventFilter) -> EventSubscription {
+        let id = self.subscriptions_count;
+        self.subscriptions_count = self
+            .subscriptions_count
+            .checked_add(1)
+            .expect("subscriptions count overflow");
+
+        self.subscriptions.push(Subscription {
+            id,
+            filter,
+            events: Vec::new(),
+        });
+
+        EventSubscription(id)
+    }
+
+    /// Remove a subscription, its pending events are returned if it was existing.
+    pub fn unsubscribe_events(&mut self, subscription: EventSubscription) -> Option<Vec<Event>> {
+        let index = self
+            .subscriptions
+            .iter()
+            .position(|sub| sub.id == subscription.0)?;
+        Some(self.subscriptions.swap_remove(index).events)
+    }
+
+    /// Take all events accepted by the given subscription since the last call, returning
+    /// none if the subscription is not existing.
+    pub fn take_subscribed_events(
+        &mut self,
+        subscription: EventSubscription,
+    ) -> Option<Vec<Event>> {
+        self.subscriptions
+            .iter_mut()
+            .find(|sub| sub.id == subscription.0)
+            .map(|sub| mem::take(&mut sub.events))
+    }
+
+    /// Internal function to push an event to all subscriptions accepting it, the entity
+    /// kind and chunk must be given if known for entity events.
+    pub(super) fn push_subscribed_event(
+        &mut self,
+        event: &Event,
+        entity: Option<(EntityKind, i32, i32)>,
+    ) {
+        for sub in &mut self.subscriptions {
+            if sub.filter.accept(event, entity) {
+                sub.events.push(event.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use glam::IVec3;
+
+    use super::*;
+    use crate::world::{BlockEvent, Effect, EntityEvent, Weather};
+
+    #[test]
+    fn filter_accept() {
+        let entity_event = Event::Entity {
+            id: 3,
+            inner: EntityEvent::Spawn,
+        };
+        let block_event = Event::Block {
+            pos: IVec3::new(20, 64, -5),
+            inner: BlockEvent::Effect {
+                effect: Effect::Fizz,
+            },
+        };
+        let weather_event = Event::Weather {
+            prev: Weather::Clear,
+            new: Weather::Rain,
+        };
+        let zombie = Some((EntityKind::Zombie, 0, 0));
+
+        let all = EventFilter::new();
+        assert!(all.accept(&entity_event, zombie));
+        assert!(all.accept(&block_event, None));
+        assert!(all.accept(&weather_event, None));
+
+        let by_id = EventFilter::new().with_entity_id(3);
+        assert!(by_id.accept(&entity_event, zombie));
+        assert!(!by_id.accept(&block_event, None));
+        assert!(!EventFilter::new()
+            .with_entity_id(4)
+            .accept(&entity_event, zombie));
+
+        let by_kind = EventFilter::new().with_entity_kind(EntityKind::Zombie);
+        assert!(by_kind.accept(&entity_event, zombie));
+        assert!(!by_kind.accept(&entity_event, Some((EntityKind::Pig, 0, 0))));
+        assert!(!by_kind.accept(&entity_event, None));
+
+        let by_region = EventFilter::new().with_region(0, -1, 1, 0);
+        assert!(by_region.accept(&entity_event, zombie));
+        assert!(by_region.accept(&block_event, None));
+        assert!(!by_region.accept(&weather_event, None));
+        assert!(!by_region.accept(&entity_event, Some((EntityKind::Zombie, 2, 0))));
+    }
+}
@@ -1,18 +1,20 @@
 //! Block ticking functions.
 
-use glam::{DVec3, IVec3};
+use std::collections::{HashSet, VecDeque};
+
+use glam::{DVec3, IVec3, Vec3Swizzles};
 
 use tracing::warn;
 
 use crate::block::material::Material;
 use crate::block::sapling::TreeKind;
 use crate::block_entity::BlockEntity;
-use crate::entity::{FallingBlock, Item};
+use crate::entity::{Base, Egg, FallingBlock, Item, Snowball};
 use crate::gen::tree::TreeGenerator;
 use crate::geom::{Face, FaceSet};
 use crate::{block, item};
 
-use super::{BlockEntityEvent, BlockEntityStorage, Dimension, Event, LocalWeather, World};
+use super::{BlockEntityEvent, BlockEntityStorage, Effect, Event, LocalWeather, World};
 
 /// Methods related to block scheduled ticking and random ticking.
 impl World {
@@ -38,7 +40,7 @@ impl World {
             block::CAKE => {} // Seems unused in MC
             block::WHEAT => self.tick_wheat(pos, metadata),
             block::DETECTOR_RAIL => {}
-            block::FARMLAND => {}
+            block::FARMLAND => self.tick_farmland(pos, metadata),
             block::FIRE => self.tick_fire(pos, metadata),
             // PARITY: Notchian client check if flowers can stay, we intentionally don't
             // respect that to allow glitched plants to stay.
@@ -47,9 +49,9 @@ impl World {
             block::RED_MUSHROOM | block::BROWN_MUSHROOM => self.tick_mushroom(pos, id),
             block::SAPLING => self.tick_sapling(pos, metadata),
             block::SAND | block::GRAVEL if !random => self.tick_falling_block(pos, id),
-            block::GRASS => {}  // Spread
-            block::ICE => {}    // Melt
-            block::LEAVES => {} // Decay
+            block::GRASS => self.tick_grass(pos),
+            block::ICE => {} // Melt
+            block::LEAVES => self.tick_leaves(pos, metadata),
             block::WOOD_PRESSURE_PLATE | block::STONE_PRESSURE_PLATE => {} // Weird, why random tick for redstone?
             block::PUMPKIN | block::PUMPKIN_LIT => {}                      // Seems unused
             block::REDSTONE_ORE_LIT => self.tick_redstone_ore_lit(pos),
@@ -135,10 +137,27 @@ impl World {
 
             if dispense_stack.id == item::ARROW {
                 warn!("TODO: shot arrow from dispenser");
-            } else if dispense_stack.id == item::EGG {
-                warn!("TODO: shot egg from dispenser");
-            } else if dispense_stack.id == item::SNOWBALL {
-                warn!("TODO: shot snowball from dispenser");
+            } else if dispense_stack.id == item::EGG || dispense_stack.id == item::SNOWBALL {
+                let init = |throw_base: &mut Base| {
+                    throw_base.pos = origin_pos;
+
+                    let dir = face.delta().as_dvec3() + DVec3::Y * 0.1;
+                    throw_base.vel = dir.normalize();
+                    throw_base.vel += throw_base.rand.next_gaussian_vec() * 0.0075 * 6.0;
+                    throw_base.vel *= 1.1;
+
+                    throw_base.look.x = f64::atan2(throw_base.vel.x, throw_base.vel.z) as f32;
+                    throw_base.look.y =
+                        f64::atan2(throw_base.vel.y, throw_base.vel.xz().length()) as f32;
+                };
+
+                if dispense_stack.id == item::EGG {
+                    self.spawn_entity(Egg::new_with(|throw_base, _, _| init(throw_base)));
+                } else {
+                    self.spawn_entity(Snowball::new_with(|throw_base, _, _| init(throw_base)));
+                }
+
+                self.play_effect(pos, Effect::Bow);
             } else {
                 let entity = Item::new_with(|base, item| {
                     base.persistent = true;
@@ -160,8 +179,19 @@ impl World {
         }
     }
 
-    /// Tick a cactus.
+    /// Tick a cactus or sugar canes, the block is broken if it can no longer stay, else
+    /// it grows until it reaches a height of 3 blocks.
     fn tick_cactus_or_sugar_canes(&mut self, pos: IVec3, id: u8, metadata: u8) {
+        let can_stay = match id {
+            block::CACTUS => self.can_place_cactus(pos),
+            _ => self.can_place_sugar_canes(pos),
+        };
+
+        if !can_stay {
+            self.break_block(pos);
+            return;
+        }
+
         // If the block above is air, count how many cactus block are below.
         if self.is_block_air(pos + IVec3::Y) {
             for dy in 1.. {
@@ -239,6 +269,31 @@ impl World {
         }
     }
 
+    /// Tick a farmland block, the farmland is hydrated if water is found around or if it
+    /// is raining, else it slowly dries out and finally reverts to dirt if nothing is
+    /// planted on it. The hydration level is stored in metadata, 0 is dry.
+    ///
+    /// REF: BlockFarmland::updateTick
+    fn tick_farmland(&mut self, pos: IVec3, metadata: u8) {
+        if self.rand.next_int_bounded(5) != 0 {
+            return;
+        }
+
+        let water_nearby = self
+            .iter_blocks_in(pos - IVec3::new(4, 0, 4), pos + IVec3::new(5, 2, 5))
+            .any(|(_, id, _)| block::material::get_material(id) == Material::Water);
+
+        if water_nearby || self.get_local_weather(pos + IVec3::Y) == LocalWeather::Rain {
+            if metadata != 7 {
+                self.set_block_notify(pos, block::FARMLAND, 7);
+            }
+        } else if metadata > 0 {
+            self.set_block_notify(pos, block::FARMLAND, metadata - 1);
+        } else if !self.is_block(pos + IVec3::Y, block::WHEAT) {
+            self.set_block_notify(pos, block::DIRT, 0);
+        }
+    }
+
     /// Tick a fire and try spreading it.
     fn tick_fire(&mut self, pos: IVec3, metadata: u8) {
         // Cache each block id on each face to avoid multiple query to world.
@@ -360,6 +415,87 @@ impl World {
         }
     }
 
+    /// Tick a grass block, the grass dies to dirt if the block above is too opaque and
+    /// not enough light is received, or spread to a random dirt block around.
+    ///
+    /// REF: BlockGrass::updateTick
+    fn tick_grass(&mut self, pos: IVec3) {
+        let above_pos = pos + IVec3::Y;
+        let (above_id, _) = self.get_block(above_pos).unwrap_or_default();
+        let light = self.get_light(above_pos).max_real();
+
+        if light < 4 && block::material::get_light_opacity(above_id) > 2 {
+            if self.rand.next_int_bounded(4) == 0 {
+                self.set_block_notify(pos, block::DIRT, 0);
+            }
+        } else if light >= 9 {
+            let target_pos = pos
+                + IVec3 {
+                    x: self.rand.next_int_bounded(3) - 1,
+                    y: self.rand.next_int_bounded(5) - 3,
+                    z: self.rand.next_int_bounded(3) - 1,
+                };
+
+            if self.is_block(target_pos, block::DIRT) {
+                let target_above_pos = target_pos + IVec3::Y;
+                let (target_above_id, _) = self.get_block(target_above_pos).unwrap_or_default();
+                if self.get_light(target_above_pos).max_real() >= 4
+                    && block::material::get_light_opacity(target_above_id) <= 2
+                {
+                    self.set_block_notify(target_pos, block::GRASS, 0);
+                }
+            }
+        }
+    }
+
+    /// Tick a leaves block, if the leaves has been marked to check for decay, search for
+    /// a log connected through leaves and break the leaves if none is found.
+    ///
+    /// REF: BlockLeaves::updateTick
+    fn tick_leaves(&mut self, pos: IVec3, mut metadata: u8) {
+        /// Maximum distance of a log from the leaves, through other leaves.
+        const MAX_DISTANCE: u8 = 4;
+
+        if !block::leaves::is_check_decay(metadata) {
+            return;
+        }
+
+        // Breadth-first search from the leaves to find a log through other leaves. We
+        // abort the search if a chunk is not loaded, this prevents decaying leaves of
+        // trees that are across unloaded chunks.
+        let mut visited = HashSet::from([pos]);
+        let mut queue = VecDeque::from([(pos, 0u8)]);
+        let mut log_found = false;
+
+        'search: while let Some((current_pos, distance)) = queue.pop_front() {
+            for face in Face::ALL {
+                let face_pos = current_pos + face.delta();
+                if !visited.insert(face_pos) {
+                    continue;
+                }
+
+                match self.get_block(face_pos) {
+                    None | Some((block::LOG, _)) => {
+                        log_found = true;
+                        break 'search;
+                    }
+                    Some((block::LEAVES, _)) if distance + 1 < MAX_DISTANCE => {
+                        queue.push_back((face_pos, distance + 1));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if log_found {
+            block::leaves::set_check_decay(&mut metadata, false);
+            self.set_block(pos, block::LEAVES, metadata);
+        } else {
+            // PARITY: Leaves only drop saplings in this version, no apples.
+            self.break_block(pos);
+        }
+    }
+
     /// Tick a mushroom to try spreading it.
     fn tick_mushroom(&mut self, pos: IVec3, id: u8) {
         if self.rand.next_int_bounded(100) == 0 {
@@ -426,7 +562,7 @@ impl World {
 
         // Default distance to decrement on each block unit.
         let dist_drop = match flowing_id {
-            block::LAVA_MOVING if self.get_dimension() != Dimension::Nether => 2,
+            block::LAVA_MOVING if !self.get_dimension_info().hot => 2,
             _ => 1,
         };
 
@@ -454,18 +590,16 @@ impl World {
                 }
             }
 
-            let mut new_metadata = shortest_dist + dist_drop;
-            if new_metadata > 7 {
-                // Just mark that the metadata is invalid, fluid should disappear.
-                new_metadata = 0xFF;
-            }
+            // No metadata if the fluid is too far from any source, it should disappear.
+            let mut new_metadata = block::fluid::from_distance(shortest_dist + dist_drop);
 
             // If the top block on top is the same fluid, this become a falling state fluid.
             if let Some((above_id, above_metadata)) = self.get_block(pos + IVec3::Y) {
                 if above_id == flowing_id || above_id == still_id {
                     // Copy the above metadata but force falling state.
-                    new_metadata = above_metadata;
-                    block::fluid::set_falling(&mut new_metadata, true);
+                    let mut above_metadata = above_metadata;
+                    block::fluid::set_falling(&mut above_metadata, true);
+                    new_metadata = Some(above_metadata);
                 }
             }
 
@@ -476,32 +610,31 @@ impl World {
                     || ((below_id == flowing_id || below_id == still_id)
                         && block::fluid::is_source(below_metadata)))
             {
-                block::fluid::set_source(&mut new_metadata);
+                block::fluid::set_source(new_metadata.get_or_insert(0));
             }
 
             // TODO: Weird lava stuff.
 
-            if new_metadata != metadata {
-                metadata = new_metadata;
-                if new_metadata == 0xFF {
+            match new_metadata {
+                // The block is removed, don't propagate it.
+                None => {
                     self.set_block_notify(pos, block::AIR, 0);
-                } else {
+                    return;
+                }
+                Some(new_metadata) if new_metadata != metadata => {
+                    metadata = new_metadata;
                     self.set_block_notify(pos, flowing_id, new_metadata);
                 }
-            } else {
-                // Metadata is the same, set still.
-                self.set_block(pos, still_id, metadata);
+                Some(_) => {
+                    // Metadata is the same, set still.
+                    self.set_block(pos, still_id, metadata);
+                }
             }
         } else {
             // Moving source is systematically set to still source.
             self.set_block(pos, still_id, metadata);
         }
 
-        // The block has been removed, don't propagate it.
-        if metadata == 0xFF {
-            return;
-        }
-
         // Check if we can flow below.
         let blocked_below = block::material::is_fluid_proof(below_id);
 
@@ -516,9 +649,9 @@ impl World {
 
             // FIXME: Dist drop is always 1 if source block
             let new_dist = block::fluid::get_actual_distance(metadata) + dist_drop;
-            if new_dist > 7 {
+            let Some(new_metadata) = block::fluid::from_distance(new_dist) else {
                 return;
-            }
+            };
 
             for face in Face::HORIZONTAL {
                 if flow_faces.contains(face) {
@@ -529,7 +662,7 @@ impl World {
                         {
                             // TODO: Break only for water.
                             self.break_block(face_pos);
-                            self.set_block_notify(face_pos, flowing_id, new_dist);
+                            self.set_block_notify(face_pos, flowing_id, new_metadata);
                         }
                     }
                 }
@@ -6,9 +6,10 @@ use crate::block;
 use crate::block::sapling::TreeKind;
 use crate::block_entity::BlockEntity;
 use crate::entity::{
-    Arrow, BaseKind, Bobber, Entity, EntityKind, Item, Painting, PaintingArt, ProjectileKind,
-    Snowball, Tnt,
+    Arrow, Base, BaseKind, Bobber, Egg, Entity, EntityKind, Item, Painting, PaintingArt,
+    Projectile, ProjectileKind, Snowball,
 };
+use crate::gen::plant::BoneMealGenerator;
 use crate::gen::tree::TreeGenerator;
 use crate::geom::Face;
 use crate::inventory::InventoryHandle;
@@ -16,6 +17,7 @@ use crate::item::{self, ItemStack};
 use crate::util::default as def;
 
 use super::bound::RayTraceKind;
+use super::explode::TNT_FUSE_TIME;
 use super::World;
 
 /// Methods related to item usage in the world.
@@ -79,7 +81,7 @@ impl World {
                 self.use_bucket_stack(inv, index, entity_id)
             }
             item::BOW => self.use_bow_stack(inv, index, entity_id),
-            item::SNOWBALL => self.use_snowball_stack(inv, index, entity_id),
+            item::SNOWBALL | item::EGG => self.use_throwable_stack(inv, index, entity_id),
             item::FISHING_ROD => self.use_fishing_rod_stack(inv, index, entity_id),
             _ => (),
         }
@@ -300,6 +302,9 @@ impl World {
 
             gen.generate_from_sapling(self, pos);
             true
+        } else if block == block::GRASS {
+            BoneMealGenerator::new().generate_from_bone_meal(self, pos);
+            true
         } else {
             false
         }
@@ -307,11 +312,8 @@ impl World {
 
     fn use_flint_and_steel(&mut self, pos: IVec3, face: Face) -> bool {
         if self.is_block(pos, block::TNT) {
-            self.spawn_entity(Tnt::new_with(|new_base, new_tnt| {
-                new_base.pos = pos.as_dvec3() + 0.5;
-                new_tnt.fuse_time = 80;
-            }));
             self.set_block_notify(pos, block::AIR, 0);
+            self.prime_tnt(pos, TNT_FUSE_TIME);
         } else {
             let fire_pos = pos + face.delta();
             if self.is_block_air(fire_pos) {
@@ -499,13 +501,18 @@ impl World {
         self.spawn_entity(arrow);
     }
 
-    fn use_snowball_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32) {
+    /// Throw a snowball or an egg.
+    ///
+    /// REF:
+    /// - ItemSnowball::onItemRightClick
+    /// - ItemEgg::onItemRightClick
+    fn use_throwable_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32) {
         let stack = inv.get(index);
         inv.set(index, stack.inc_damage(1));
 
         let Entity(base, _) = self.get_entity(entity_id).unwrap();
 
-        let snowball = Snowball::new_with(|throw_base, throw_projectile, _| {
+        let init = |throw_base: &mut Base, throw_projectile: &mut Projectile| {
             throw_base.pos = base.pos;
             throw_base.pos.y += base.eye_height as f64 - 0.1;
             throw_base.look = base.look;
@@ -526,9 +533,15 @@ impl World {
             throw_base.vel *= 1.5;
 
             throw_projectile.owner_id = Some(entity_id);
-        });
+        };
+
+        let projectile = if stack.id == item::EGG {
+            Egg::new_with(|throw_base, throw_projectile, _| init(throw_base, throw_projectile))
+        } else {
+            Snowball::new_with(|throw_base, throw_projectile, _| init(throw_base, throw_projectile))
+        };
 
-        self.spawn_entity(snowball);
+        self.spawn_entity(projectile);
     }
 
     fn use_fishing_rod_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32) {
@@ -548,18 +561,21 @@ impl World {
             )) = self.get_entity(bobber_id)
             {
                 let bobber_pos = bobber_base.pos;
+                let bobber_attached_id = bobber.attached_id;
+                let bobber_catch_time = bobber.catch_time;
+                let bobber_in_block = bobber_projectile.state.is_some();
 
                 let bobber_delta = base_pos - bobber_pos;
                 let bobber_dist = bobber_delta.length();
                 let mut bobber_accel = bobber_delta * 0.1;
                 bobber_accel.y += bobber_dist.sqrt() * 0.08;
 
-                if let Some(attached_id) = bobber.attached_id {
+                if let Some(attached_id) = bobber_attached_id {
                     if let Some(Entity(attached_base, _)) = self.get_entity_mut(attached_id) {
                         attached_base.vel += bobber_accel;
                         item_damage = 3;
                     }
-                } else if bobber.catch_time > 0 {
+                } else if bobber_catch_time > 0 {
                     self.spawn_entity(Item::new_with(|item_base, item| {
                         item_base.persistent = true;
                         item_base.pos = bobber_pos;
@@ -568,7 +584,10 @@ impl World {
                     }));
 
                     item_damage = 1;
-                } else if bobber_projectile.state.is_some() {
+                }
+
+                // A bobber stuck in a block damages the rod more, whatever it caught.
+                if bobber_in_block {
                     item_damage = 2;
                 }
             }
@@ -0,0 +1,176 @@
+//! Read-only snapshot of the world's chunks that can be sent to other threads.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use glam::IVec3;
+
+use crate::biome::Biome;
+use crate::chunk::{calc_chunk_pos, calc_chunk_pos_unchecked, Chunk};
+
+use super::{Dimension, Light, World};
+
+/// A read-only view of some chunks of a world, as they were when the view was created.
+/// Chunks are shared with the world through reference counting, so creating a view is
+/// cheap and the world is not blocked: a chunk is only copied by the world if it gets
+/// modified while a view still references it.
+///
+/// This type is [`Send`] and [`Sync`] and is intended for read-only work on other
+/// threads, such as map rendering, analytics or path finding precomputation. Entities
+/// and block entities are not part of the view.
+#[derive(Clone)]
+pub struct WorldView {
+    /// The dimension of the world.
+    dimension: Dimension,
+    /// The world time when the view was created.
+    time: u64,
+    /// The sky light subtracted from the chunk sky light when the view was created.
+    sky_light_subtracted: u8,
+    /// All the chunks of the view.
+    chunks: HashMap<(i32, i32), Arc<Chunk>>,
+}
+
+/// Methods related to world views.
+impl World {
+    /// Create a view of all chunks currently loaded in the world.
+    pub fn view(&self) -> WorldView {
+        self.view_filtered(|_, _| true)
+    }
+
+    /// Create a view of chunks currently loaded in the given inclusive chunk range.
+    pub fn view_range(&self, min_cx: i32, min_cz: i32, max_cx: i32, max_cz: i32) -> WorldView {
+        self.view_filtered(|cx, cz| {
+            (min_cx..=max_cx).contains(&cx) && (min_cz..=max_cz).contains(&cz)
+        })
+    }
+
+    fn view_filtered(&self, mut filter: impl FnMut(i32, i32) -> bool) -> WorldView {
+        WorldView {
+            dimension: self.dimension,
+            time: self.time,
+            sky_light_subtracted: self.sky_light_subtracted,
+            chunks: self
+                .chunks
+                .iter()
+                .filter(|(&(cx, cz), _)| filter(cx, cz))
+                .filter_map(|(&pos, chunk_comp)| {
+                    chunk_comp
+                        .data
+                        .as_ref()
+                        .map(|chunk| (pos, Arc::clone(chunk)))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl WorldView {
+    /// Get the dimension of the world.
+    #[inline]
+    pub fn get_dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    /// Get the world time when the view was created, in ticks.
+    #[inline]
+    pub fn get_time(&self) -> u64 {
+        self.time
+    }
+
+    /// Return true if a given chunk is present in the view.
+    pub fn contains_chunk(&self, cx: i32, cz: i32) -> bool {
+        self.chunks.contains_key(&(cx, cz))
+    }
+
+    /// Iterate over the coordinates of all chunks present in the view.
+    pub fn iter_chunks(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.chunks.keys().copied()
+    }
+
+    /// Get a reference to a chunk, if present in the view.
+    pub fn get_chunk(&self, cx: i32, cz: i32) -> Option<&Chunk> {
+        self.chunks.get(&(cx, cz)).map(|chunk| &**chunk)
+    }
+
+    /// Get block and metadata at given position, see [`World::get_block`].
+    pub fn get_block(&self, pos: IVec3) -> Option<(u8, u8)> {
+        let (cx, cz) = calc_chunk_pos(pos)?;
+        let chunk = self.get_chunk(cx, cz)?;
+        Some(chunk.get_block(pos))
+    }
+
+    /// Get saved height of a chunk column, see [`World::get_height`].
+    pub fn get_height(&self, pos: IVec3) -> Option<i32> {
+        let (cx, cz) = calc_chunk_pos_unchecked(pos);
+        let chunk = self.get_chunk(cx, cz)?;
+        Some(chunk.get_height(pos) as i32)
+    }
+
+    /// Get light level at the given position, see [`World::get_light`].
+    pub fn get_light(&self, mut pos: IVec3) -> Light {
+        if pos.y > 127 {
+            pos.y = 127;
+        }
+
+        let mut light = Light {
+            block: 0,
+            sky: 15,
+            sky_real: 0,
+        };
+
+        if let Some((cx, cz)) = calc_chunk_pos(pos) {
+            if let Some(chunk) = self.get_chunk(cx, cz) {
+                light.block = chunk.get_block_light(pos);
+                light.sky = chunk.get_sky_light(pos);
+            }
+        }
+
+        light.sky_real = light.sky.saturating_sub(self.sky_light_subtracted);
+        light
+    }
+
+    /// Get the biome at some position, see [`World::get_biome`].
+    pub fn get_biome(&self, pos: IVec3) -> Option<Biome> {
+        let (cx, cz) = calc_chunk_pos_unchecked(pos);
+        let chunk = self.get_chunk(cx, cz)?;
+        Some(chunk.get_biome(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::block;
+
+    #[test]
+    fn view_snapshot() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let mut world = World::builder()
+            .chunks(0, 0, 1, 0)
+            .block(IVec3::new(4, 10, 4), block::STONE, 0)
+            .build();
+        let view = world.view_range(0, 0, 0, 0);
+        assert_send_sync(&view);
+        assert!(!view.contains_chunk(1, 0));
+
+        // Changes to the world are not visible from the view.
+        world.set_block(IVec3::new(4, 10, 4), block::DIRT, 0);
+        let view = std::thread::spawn(move || {
+            assert_eq!(
+                view.get_block(IVec3::new(4, 10, 4)),
+                Some((block::STONE, 0))
+            );
+            view
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(view.get_height(IVec3::new(4, 0, 4)), Some(11));
+        assert_eq!(
+            world.view().get_block(IVec3::new(4, 10, 4)),
+            Some((block::DIRT, 0))
+        );
+    }
+}